@@ -0,0 +1,55 @@
+//! Demonstrates why `registered_voters`/`votes_cast` are `HashSet`s: a
+//! `contains` check against a 100k-voter election is O(1) either way, but
+//! a `Vec` pays for a scan that gets longer as the election grows. Run
+//! with `cargo bench --bench membership_lookup`.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use voting_state_machine::{UserId, VotingState};
+
+const VOTER_COUNT: usize = 100_000;
+
+fn voter_id(i: usize) -> UserId {
+    format!("voter-{i}")
+}
+
+fn populated_state() -> VotingState {
+    let mut state = VotingState::new("admin".to_string());
+    for i in 0..VOTER_COUNT {
+        state.register_voter(voter_id(i)).unwrap();
+    }
+    state
+}
+
+fn populated_vec() -> Vec<UserId> {
+    (0..VOTER_COUNT).map(voter_id).collect()
+}
+
+fn bench_membership_lookup(c: &mut Criterion) {
+    let state = populated_state();
+    let voters: Vec<UserId> = populated_vec();
+    // The worst case for a linear scan, and a fair comparison for a
+    // `HashSet`, which does no better or worse depending on where a
+    // member sits.
+    let last_voter = voter_id(VOTER_COUNT - 1);
+
+    let mut group = c.benchmark_group("membership_lookup");
+    group.bench_function("hash_set_contains", |b| {
+        b.iter(|| state.registered_voters.contains(black_box(&last_voter)))
+    });
+    group.bench_function("vec_contains", |b| b.iter(|| voters.contains(black_box(&last_voter))));
+    group.finish();
+}
+
+fn bench_hash_set_baseline(c: &mut Criterion) {
+    // A `HashSet` built directly, without `VotingState` in the way, so the
+    // comparison above isn't just measuring `VotingState`'s overhead.
+    let set: HashSet<UserId> = (0..VOTER_COUNT).map(voter_id).collect();
+    let last_voter = voter_id(VOTER_COUNT - 1);
+    c.bench_function("hash_set_contains_baseline", |b| b.iter(|| set.contains(black_box(&last_voter))));
+}
+
+criterion_group!(benches, bench_membership_lookup, bench_hash_set_baseline);
+criterion_main!(benches);
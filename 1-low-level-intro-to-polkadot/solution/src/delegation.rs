@@ -0,0 +1,154 @@
+//! Optional liquid-democracy layer: a registered voter can hand their
+//! vote to another voter instead of casting it themselves, and when
+//! that delegate votes, every vote handed to them (directly or through
+//! a chain of delegations) counts alongside their own.
+//!
+//! ## Scope
+//!
+//! [`DelegationGraph`] tracks who delegated to whom independently of
+//! [`VotingState`] - the same reason [`crate::tally`]'s ranked ballots
+//! and [`crate::threshold_encryption`]'s ciphertexts live outside it:
+//! `VotingState` has no notion of delegated weight, so this module
+//! layers one on top instead of changing what `cast_vote` means for
+//! every other caller. [`cast_delegated_vote`] is the bridge between the
+//! two - it still calls [`VotingState::cast_vote`] for the caller's own
+//! ballot, then folds every delegator's weight into the same option by
+//! writing `vote_counts`/`votes_by_option`/`votes_cast` directly, which
+//! only works because `VotingState`'s fields are `pub` for exactly this
+//! kind of composition.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{Timestamp, UserId, VoteError, VoteOption, VotingState};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DelegationError {
+    /// `delegate_vote(from, to)` was called with `from == to`.
+    SelfDelegation,
+    /// `to` already (transitively) delegates back to `from` - following
+    /// the new delegation would never reach a voter who actually votes.
+    Cycle,
+    /// `revoke` was called for a voter with no delegation to revoke.
+    NoSuchDelegation,
+    /// `revoke` was called after the delegate already cast a vote with
+    /// this voter's weight folded into it.
+    AlreadyVoted,
+}
+
+/// Who delegates to whom, one delegation per voter. A voter delegating
+/// replaces any delegation they already had, the same way registering
+/// twice is rejected elsewhere in this crate rather than allowed to
+/// silently overwrite - see [`Self::delegate_vote`].
+#[derive(Debug, Default)]
+pub struct DelegationGraph {
+    delegations: BTreeMap<UserId, UserId>,
+    /// Delegators whose weight has already been folded into a vote,
+    /// kept separately from `VotingState::votes_cast` so [`Self::revoke`]
+    /// can still tell a delegation that's been spent from one that
+    /// hasn't, after [`cast_delegated_vote`] has also marked them voted
+    /// in `VotingState` itself.
+    spent: HashSet<UserId>,
+}
+
+impl DelegationGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Who `voter` delegates to directly, if anyone.
+    pub fn delegate_of(&self, voter: &UserId) -> Option<&UserId> {
+        self.delegations.get(voter)
+    }
+
+    /// Follows `voter`'s delegation chain to whoever it ends at - the
+    /// one who actually casts a vote with `voter`'s weight included.
+    /// A voter who hasn't delegated resolves to themselves.
+    pub fn final_delegate<'a>(&'a self, voter: &'a UserId) -> &'a UserId {
+        let mut current = voter;
+        let mut steps = 0;
+        while let Some(next) = self.delegations.get(current) {
+            current = next;
+            steps += 1;
+            if steps > self.delegations.len() {
+                // `delegate_vote` never lets a cycle form, so this is
+                // unreachable outside a bug in this module - bail out
+                // rather than loop forever if one ever does.
+                break;
+            }
+        }
+        current
+    }
+
+    /// Records that `from` delegates their vote to `to`, rejecting a
+    /// self-delegation or one that would close a cycle (so every chain
+    /// still ends at a voter who votes, not back where it started).
+    pub fn delegate_vote(&mut self, from: UserId, to: UserId) -> Result<(), DelegationError> {
+        if from == to {
+            return Err(DelegationError::SelfDelegation);
+        }
+        if self.final_delegate(&to) == &from {
+            return Err(DelegationError::Cycle);
+        }
+        self.spent.remove(&from);
+        self.delegations.insert(from, to);
+        Ok(())
+    }
+
+    /// Undoes `from`'s delegation, as long as it hasn't already been
+    /// spent by [`cast_delegated_vote`] folding `from`'s weight into a
+    /// vote.
+    pub fn revoke(&mut self, from: &UserId) -> Result<(), DelegationError> {
+        if self.spent.contains(from) {
+            return Err(DelegationError::AlreadyVoted);
+        }
+        if self.delegations.remove(from).is_none() {
+            return Err(DelegationError::NoSuchDelegation);
+        }
+        Ok(())
+    }
+
+    /// Every registered voter (other than `voter` itself) whose
+    /// delegation chain resolves to `voter` and hasn't already voted -
+    /// the weight [`cast_delegated_vote`] folds in when `voter` votes.
+    fn delegators_of<'a>(&'a self, voter: &'a UserId, state: &'a VotingState) -> Vec<&'a UserId> {
+        state
+            .registered_voters
+            .iter()
+            .filter(|delegator| *delegator != voter)
+            .filter(|delegator| self.final_delegate(delegator) == voter)
+            .filter(|delegator| !state.votes_cast.contains(*delegator))
+            .collect()
+    }
+}
+
+/// Casts `caller_id`'s own vote via [`VotingState::cast_vote`], then
+/// folds in the weight of every registered voter who delegated to them
+/// (directly or through a chain) and hasn't voted themselves - crediting
+/// `vote_option` with one vote per delegator in `votes_by_option` and
+/// `vote_counts`, and marking each delegator as having voted in
+/// `votes_cast` so they can't also cast their own ballot afterwards.
+/// Returns the total weight counted: one for `caller_id`, plus one per
+/// delegator.
+pub fn cast_delegated_vote(
+    graph: &mut DelegationGraph,
+    state: &mut VotingState,
+    caller_id: UserId,
+    vote_option: VoteOption,
+    now: Timestamp,
+) -> Result<u64, VoteError> {
+    let delegators: Vec<UserId> = graph.delegators_of(&caller_id, state).into_iter().cloned().collect();
+
+    state.cast_vote(caller_id, vote_option.clone(), now)?;
+
+    let voters_for_option = state.votes_by_option.entry(vote_option.clone()).or_default();
+    for delegator in &delegators {
+        voters_for_option.push(delegator.clone());
+    }
+    *state.vote_counts.get_mut(&vote_option).unwrap() += delegators.len() as u64;
+    for delegator in &delegators {
+        state.votes_cast.insert(delegator.clone());
+        graph.spent.insert(delegator.clone());
+    }
+
+    Ok(1 + delegators.len() as u64)
+}
@@ -0,0 +1,154 @@
+//! Optional event-sourced wrapper around the direct-call API: a
+//! [`Command`] is the fallible request ("register this voter"), an
+//! [`Event`] is the fact it produced once accepted ("this voter was
+//! registered") - the same extrinsic/event split a FRAME pallet makes.
+//! [`apply`] turns the one into the other by running the matching
+//! [`VotingState`] method, and [`replay`] rebuilds a state from nothing
+//! but the events it produced, which is what makes an event stream (and
+//! not the mutated state itself) safe to use as the durable record.
+//!
+//! A rejected command never reaches this module's [`Event`] side - same
+//! as a failed extrinsic, which doesn't emit the event its success case
+//! would have. [`replay`] only ever sees the commands that succeeded, so
+//! it reproduces exactly the state those successes left behind; it
+//! doesn't (and can't) reproduce incidental bookkeeping a rejected call
+//! left too, like [`VotingState::invalid_vote_attempts`] or an
+//! [`crate::AuditEntry`] for that rejection.
+
+use crate::{Action, Quorum, TieBreakPolicy, Timestamp, UserId, VoteError, VoteOption, VotingState};
+
+/// A caller-issued request to mutate a [`VotingState`]. Each variant
+/// carries exactly the arguments the matching [`VotingState`] method (or
+/// [`crate::transition`], for [`Command::Transition`]) takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    RegisterVoter { caller_id: UserId },
+    CastVote { caller_id: UserId, vote_option: VoteOption, now: Timestamp },
+    AddOption { caller_id: UserId, option: VoteOption },
+    RemoveOption { caller_id: UserId, option: VoteOption },
+    SetQuorum { caller_id: UserId, quorum: Quorum },
+    SetTieBreakPolicy { caller_id: UserId, policy: TieBreakPolicy },
+    Transition { caller_id: UserId, action: Action, now: Timestamp },
+    PauseVoting { caller_id: UserId, now: Timestamp },
+    ResumeVoting { caller_id: UserId, now: Timestamp },
+    SetMaxExtension { caller_id: UserId, max_extension: Timestamp },
+    ExtendVoting { caller_id: UserId, extra_secs: Timestamp, now: Timestamp },
+}
+
+/// The fact a [`Command`] produced once [`apply`] accepted it - the
+/// input [`replay`] rebuilds a [`VotingState`] from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    VoterRegistered { caller_id: UserId },
+    VoteCast { caller_id: UserId, vote_option: VoteOption, at: Timestamp },
+    OptionAdded { caller_id: UserId, option: VoteOption },
+    OptionRemoved { caller_id: UserId, option: VoteOption },
+    QuorumSet { caller_id: UserId, quorum: Quorum },
+    TieBreakPolicySet { caller_id: UserId, policy: TieBreakPolicy },
+    Transitioned { caller_id: UserId, action: Action, at: Timestamp },
+    VotingPaused { caller_id: UserId, at: Timestamp },
+    VotingResumed { caller_id: UserId, at: Timestamp },
+    MaxExtensionSet { caller_id: UserId, max_extension: Timestamp },
+    VotingExtended { caller_id: UserId, extra_secs: Timestamp, at: Timestamp },
+}
+
+/// Runs `command` against `state` exactly as calling the matching
+/// [`VotingState`] method directly would, and on success returns the
+/// [`Event`] it produced. A rejected command returns the same
+/// [`VoteError`] the direct call would have and produces no event.
+pub fn apply(state: &mut VotingState, command: Command) -> Result<Vec<Event>, VoteError> {
+    match command {
+        Command::RegisterVoter { caller_id } => {
+            state.register_voter(caller_id.clone())?;
+            Ok(vec![Event::VoterRegistered { caller_id }])
+        }
+        Command::CastVote { caller_id, vote_option, now } => {
+            state.cast_vote(caller_id.clone(), vote_option.clone(), now)?;
+            Ok(vec![Event::VoteCast { caller_id, vote_option, at: now }])
+        }
+        Command::AddOption { caller_id, option } => {
+            state.add_option(&caller_id, option.clone())?;
+            Ok(vec![Event::OptionAdded { caller_id, option }])
+        }
+        Command::RemoveOption { caller_id, option } => {
+            state.remove_option(&caller_id, &option)?;
+            Ok(vec![Event::OptionRemoved { caller_id, option }])
+        }
+        Command::SetQuorum { caller_id, quorum } => {
+            state.set_quorum(&caller_id, quorum)?;
+            Ok(vec![Event::QuorumSet { caller_id, quorum }])
+        }
+        Command::SetTieBreakPolicy { caller_id, policy } => {
+            state.set_tie_break_policy(&caller_id, policy.clone())?;
+            Ok(vec![Event::TieBreakPolicySet { caller_id, policy }])
+        }
+        Command::Transition { caller_id, action, now } => {
+            crate::transition(state, caller_id.clone(), action.clone(), now)?;
+            Ok(vec![Event::Transitioned { caller_id, action, at: now }])
+        }
+        Command::PauseVoting { caller_id, now } => {
+            state.pause_voting(&caller_id, now)?;
+            Ok(vec![Event::VotingPaused { caller_id, at: now }])
+        }
+        Command::ResumeVoting { caller_id, now } => {
+            state.resume_voting(&caller_id, now)?;
+            Ok(vec![Event::VotingResumed { caller_id, at: now }])
+        }
+        Command::SetMaxExtension { caller_id, max_extension } => {
+            state.set_max_extension(&caller_id, max_extension)?;
+            Ok(vec![Event::MaxExtensionSet { caller_id, max_extension }])
+        }
+        Command::ExtendVoting { caller_id, extra_secs, now } => {
+            state.extend_voting(&caller_id, extra_secs, now)?;
+            Ok(vec![Event::VotingExtended { caller_id, extra_secs, at: now }])
+        }
+    }
+}
+
+/// Rebuilds a [`VotingState`] from nothing but `events`, by starting
+/// from [`VotingState::new`] and re-running each event's [`Command`]
+/// counterpart in order. Deterministic: the same `admin_id` and events
+/// always produce the same state, which is what lets a test build a
+/// state once, [`apply`] a few more commands, and assert `replay`
+/// reaches the same place.
+pub fn replay(admin_id: UserId, events: &[Event]) -> Result<VotingState, VoteError> {
+    let mut state = VotingState::new(admin_id);
+    for event in events {
+        match event.clone() {
+            Event::VoterRegistered { caller_id } => {
+                state.register_voter(caller_id)?;
+            }
+            Event::VoteCast { caller_id, vote_option, at } => {
+                state.cast_vote(caller_id, vote_option, at)?;
+            }
+            Event::OptionAdded { caller_id, option } => {
+                state.add_option(&caller_id, option)?;
+            }
+            Event::OptionRemoved { caller_id, option } => {
+                state.remove_option(&caller_id, &option)?;
+            }
+            Event::QuorumSet { caller_id, quorum } => {
+                state.set_quorum(&caller_id, quorum)?;
+            }
+            Event::TieBreakPolicySet { caller_id, policy } => {
+                state.set_tie_break_policy(&caller_id, policy)?;
+            }
+            Event::Transitioned { caller_id, action, at } => {
+                crate::transition(&mut state, caller_id, action, at)?;
+            }
+            Event::VotingPaused { caller_id, at } => {
+                state.pause_voting(&caller_id, at)?;
+            }
+            Event::VotingResumed { caller_id, at } => {
+                state.resume_voting(&caller_id, at)?;
+            }
+            Event::MaxExtensionSet { caller_id, max_extension } => {
+                state.set_max_extension(&caller_id, max_extension)?;
+            }
+            Event::VotingExtended { caller_id, extra_secs, at } => {
+                state.extend_voting(&caller_id, extra_secs, at)?;
+            }
+        }
+    }
+    Ok(state)
+}
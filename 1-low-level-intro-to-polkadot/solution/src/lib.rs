@@ -0,0 +1,1235 @@
+//! Solution for Task 1: a low-level voting state machine, with phase
+//! transitions driven by a declarative table instead of a hand-written
+//! `match`. Adding a new guarded phase transition means adding a row to
+//! [`TRANSITIONS`], not touching the dispatch logic in [`transition`].
+
+use std::collections::{BTreeMap, HashSet};
+
+pub mod messages;
+#[cfg(feature = "ranked-tally")]
+pub mod tally;
+#[cfg(feature = "tally-proof")]
+pub mod tally_proof;
+#[cfg(feature = "threshold-encryption")]
+pub mod threshold_encryption;
+#[cfg(feature = "vote-delegation")]
+pub mod delegation;
+#[cfg(feature = "multi-poll")]
+pub mod poll_manager;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+#[cfg(feature = "weight-accounting")]
+pub mod weight;
+#[cfg(feature = "serde")]
+pub mod persistence;
+#[cfg(feature = "scale")]
+pub mod scale;
+#[cfg(feature = "event-sourcing")]
+pub mod events;
+
+pub type UserId = String;
+pub type VoteOption = String;
+/// Block number or timestamp unit; the caller decides which.
+pub type Timestamp = u64;
+
+/// Minimal time source abstraction. `transition`/`cast_vote` above take a
+/// raw `Timestamp` directly since this state machine only ever needs "the
+/// current time" at a single call site and neither function nor
+/// `VotingState` itself ever reads the real clock - a test already gets
+/// determinism for free by passing whatever `now` it likes, no fake clock
+/// needed. `Clock` exists for tasks built on this one (the Task 12 staking
+/// library) that thread a time source through more machinery than one
+/// function call and want a fake for deterministic tests instead of
+/// reading the real clock.
+pub trait Clock {
+    fn now(&self) -> Timestamp;
+}
+
+/// A `Clock` fixed to a single value: the obvious fake for deterministic
+/// tests. Advance it by mutating `.0` instead of sleeping. `test-utils`
+/// re-exports this as `MockClock` for callers that want the name to say
+/// "this is a test fixture" rather than "this is fixed".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// The real `Clock`: seconds since the Unix epoch, read from the system
+/// clock. The obvious default for a caller that holds a `Clock` instead
+/// of being handed `now` directly (see [`Clock`]'s doc comment) and isn't
+/// a test.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system clock should be after the Unix epoch")
+            .as_secs()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum VotingPhase {
+    RegistrationOpen,
+    VotingActive,
+    ResultsFinalized,
+    /// Entered via [`VotingState::pause_voting`], left via
+    /// [`VotingState::resume_voting`] - not a [`TRANSITIONS`] row, since
+    /// unlike every other phase change it always returns to exactly the
+    /// phase it left (`VotingActive`) rather than moving forward.
+    Paused,
+}
+
+/// A caller-triggered request to move the state machine between phases.
+/// `register_voter` and `cast_vote` are *not* modeled here because they
+/// don't change `current_voting_state` - they're plain methods on
+/// [`VotingState`] guarded by the current phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    StartRegistration,
+    StartVoting { duration: Timestamp },
+    /// `override_quorum: true` lets the admin finalize results even if
+    /// turnout falls short of [`VotingState::quorum`] - see
+    /// [`VoteError::QuorumNotMet`].
+    EndVoting { override_quorum: bool },
+    ResetApplication,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ActionKind {
+    StartRegistration,
+    StartVoting,
+    EndVoting,
+    ResetApplication,
+}
+
+impl Action {
+    fn kind(&self) -> ActionKind {
+        match self {
+            Action::StartRegistration => ActionKind::StartRegistration,
+            Action::StartVoting { .. } => ActionKind::StartVoting,
+            Action::EndVoting { .. } => ActionKind::EndVoting,
+            Action::ResetApplication => ActionKind::ResetApplication,
+        }
+    }
+
+    /// The [`AuditEntry::action`] label [`transition_notified`] records
+    /// for this `Action` - snake_case like [`VoteError::key`], since both
+    /// exist to be matched on by something outside this crate rather than
+    /// read as prose.
+    fn audit_name(&self) -> &'static str {
+        match self {
+            Action::StartRegistration => "start_registration",
+            Action::StartVoting { .. } => "start_voting",
+            Action::EndVoting { .. } => "end_voting",
+            Action::ResetApplication => "reset_application",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum VoteError {
+    NotAdmin,
+    /// No row in [`TRANSITIONS`] matches `(current phase, action)`.
+    InvalidTransition,
+    InvalidDuration,
+    VotingWindowNotElapsed,
+    AlreadyRegistered,
+    NotRegistered,
+    AlreadyVoted,
+    VotingNotActive,
+    InvalidOption,
+    OutsideVotingWindow,
+    InvalidSignature,
+    InvalidUserId,
+    NotAPerson,
+    /// [`VotingStateBuilder::build`] was asked for an empty admin id.
+    EmptyAdminId,
+    /// [`VotingStateBuilder::build`] was given the same option twice, or
+    /// [`VotingState::add_option`] was given one already in `vote_counts`.
+    DuplicateOption,
+    /// [`VotingState::add_option`]/[`VotingState::remove_option`] were
+    /// called outside [`VotingPhase::RegistrationOpen`].
+    VotingAlreadyStarted,
+    /// [`VotingState::remove_option`] was asked to remove an option that
+    /// already has votes recorded against it.
+    OptionHasVotes,
+    /// [`poll_manager::PollManager`](crate::poll_manager::PollManager) was
+    /// asked for a [`poll_manager::PollId`](crate::poll_manager::PollId)
+    /// it has no poll under.
+    PollNotFound,
+    /// [`poll_manager::PollManager::create_poll`](crate::poll_manager::PollManager::create_poll)
+    /// was given a [`poll_manager::PollId`](crate::poll_manager::PollId)
+    /// that's already in use.
+    PollAlreadyExists,
+    /// [`Action::EndVoting`] was rejected because turnout hasn't met
+    /// [`VotingState::quorum`] and `override_quorum` wasn't set.
+    QuorumNotMet,
+    /// [`VotingState::winner`] found two or more options tied for the
+    /// top vote count and [`VotingState::tie_break_policy`] is
+    /// [`TieBreakPolicy::Error`] (or [`TieBreakPolicy::AdminDecides`]
+    /// without a valid `admin_choice`).
+    TieUnresolved,
+    /// [`VotingState::extend_voting`] was asked for more than
+    /// [`VotingState::max_extension`] allows, counting every extension
+    /// already granted this election.
+    ExtensionLimitExceeded,
+}
+
+impl VoteError {
+    /// A stable, locale-independent identity for this error - what a
+    /// CLI or REST layer should match on or log, since (unlike the
+    /// `Debug` string, which is free to change for readability) it's
+    /// part of the interface. See the [`messages`](crate::messages)
+    /// module for rendering it to a person instead.
+    pub fn key(&self) -> &'static str {
+        match self {
+            VoteError::NotAdmin => "not_admin",
+            VoteError::InvalidTransition => "invalid_transition",
+            VoteError::InvalidDuration => "invalid_duration",
+            VoteError::VotingWindowNotElapsed => "voting_window_not_elapsed",
+            VoteError::AlreadyRegistered => "already_registered",
+            VoteError::NotRegistered => "not_registered",
+            VoteError::AlreadyVoted => "already_voted",
+            VoteError::VotingNotActive => "voting_not_active",
+            VoteError::InvalidOption => "invalid_option",
+            VoteError::OutsideVotingWindow => "outside_voting_window",
+            VoteError::InvalidSignature => "invalid_signature",
+            VoteError::InvalidUserId => "invalid_user_id",
+            VoteError::NotAPerson => "not_a_person",
+            VoteError::EmptyAdminId => "empty_admin_id",
+            VoteError::DuplicateOption => "duplicate_option",
+            VoteError::VotingAlreadyStarted => "voting_already_started",
+            VoteError::OptionHasVotes => "option_has_votes",
+            VoteError::PollNotFound => "poll_not_found",
+            VoteError::PollAlreadyExists => "poll_already_exists",
+            VoteError::QuorumNotMet => "quorum_not_met",
+            VoteError::TieUnresolved => "tie_unresolved",
+            VoteError::ExtensionLimitExceeded => "extension_limit_exceeded",
+        }
+    }
+}
+
+/// Optional input validation for [`VotingState::register_voter_checked`] -
+/// lets a deployment restrict `UserId`s to some address format (e.g. the
+/// SS58 codec in Task 19) without the core state machine depending on it.
+pub trait UserIdValidator {
+    fn validate(&self, user_id: &UserId) -> bool;
+}
+
+/// Proves a caller is a distinct person, not one of several accounts
+/// controlled by the same entity, before [`VotingState::register_voter_attested`]
+/// lets them register. A deployment plugs in whatever sybil-resistance
+/// strategy it trusts - a trusted attestor's signature, a reputation
+/// score, proof of a unique hardware key - without the core state
+/// machine caring which; `proof` is opaque bytes the provider alone
+/// interprets.
+pub trait PersonhoodProvider {
+    fn is_person(&self, caller_id: &UserId, proof: &[u8]) -> bool;
+}
+
+/// A [`PersonhoodProvider`] that accepts everyone - the default for
+/// deployments (and every other task's tests) that don't need sybil
+/// resistance. Not meant for a real election.
+pub struct AlwaysPerson;
+
+impl PersonhoodProvider for AlwaysPerson {
+    fn is_person(&self, _caller_id: &UserId, _proof: &[u8]) -> bool {
+        true
+    }
+}
+
+/// The exact bytes a [`PersonhoodProvider`]'s signed-attestation
+/// implementation asks a trusted attestor to have signed. The caller id
+/// alone is enough context - attesting that someone is a distinct
+/// person doesn't depend on what they're registering for.
+pub fn personhood_attestation_message(caller_id: &UserId) -> Vec<u8> {
+    caller_id.clone().into_bytes()
+}
+
+/// A [`PersonhoodProvider`] that accepts `caller_id` when `proof`
+/// verifies, via `verifier`, as `attestor_id`'s signature over
+/// [`personhood_attestation_message`] - the trusted third party vouching
+/// that this caller is a distinct person.
+pub struct AttestedPersonhood<'a, V> {
+    pub attestor_id: &'a UserId,
+    pub verifier: &'a V,
+}
+
+impl<V: SignatureVerifier> PersonhoodProvider for AttestedPersonhood<'_, V> {
+    fn is_person(&self, caller_id: &UserId, proof: &[u8]) -> bool {
+        self.verifier.verify(self.attestor_id, &personhood_attestation_message(caller_id), proof)
+    }
+}
+
+/// Checks that a vote was signed by the voter claiming to cast it.
+/// [`VotingState::cast_vote_signed`] runs this before delegating to
+/// [`VotingState::cast_vote`], so the rest of the state machine stays
+/// ignorant of which signature scheme, if any, a particular deployment
+/// uses. The Task 18 key-generation library is the real implementation;
+/// tests here use a fake that just checks the signature equals the
+/// message.
+pub trait SignatureVerifier {
+    fn verify(&self, signer: &UserId, message: &[u8], signature: &[u8]) -> bool;
+}
+
+/// Hook point for reacting to election lifecycle events - registration
+/// opening, voting starting, results being finalized - without
+/// [`transition`] itself knowing what a caller wants to do about them
+/// (post to Slack, log to a file, nothing at all). Every method has a
+/// no-op default, so an operator who only cares about one event doesn't
+/// have to implement the other two. See [`transition_notified`] for the
+/// version of `transition` that calls these hooks, and the `webhook`
+/// module (behind the `webhook` feature) for an HTTP implementation.
+pub trait Notifier {
+    fn registration_open(&self, _state: &VotingState) {}
+    fn voting_started(&self, _state: &VotingState) {}
+    fn finalized(&self, _state: &VotingState) {}
+}
+
+/// The implicit [`Notifier`] for every caller that doesn't care about
+/// lifecycle events - including plain [`transition`], which is just
+/// [`transition_notified`] with this as the notifier.
+pub struct NoopNotifier;
+
+impl Notifier for NoopNotifier {}
+
+/// The exact bytes a [`SignatureVerifier`] is asked to check a signature
+/// against for a given vote - kept in one place so signers and verifiers
+/// always agree on what a "vote" message is.
+pub fn signed_vote_message(caller_id: &UserId, vote_option: &VoteOption) -> Vec<u8> {
+    let mut message = caller_id.clone().into_bytes();
+    message.push(0);
+    message.extend(vote_option.clone().into_bytes());
+    message
+}
+
+/// One option's count in a [`VotingState::public_results`] report.
+///
+/// Carries the threshold it was suppressed against rather than a bare
+/// flag, so a caller rendering `Suppressed` can say "<5" instead of just
+/// "<k" - `k` has already happened by the time anything sees this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultCount {
+    Exact(u64),
+    Suppressed(u64),
+}
+
+impl std::fmt::Display for ResultCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultCount::Exact(count) => write!(f, "{count}"),
+            ResultCount::Suppressed(k) => write!(f, "<{k}"),
+        }
+    }
+}
+
+/// A turnout requirement [`VotingState::set_quorum`] can gate
+/// [`VotingPhase::ResultsFinalized`] on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum Quorum {
+    /// At least this many votes must have been cast.
+    Absolute(u64),
+    /// At least this percentage (0-100) of `registered_voters` must have
+    /// voted.
+    Percentage(u8),
+}
+
+impl Quorum {
+    fn is_met(&self, votes_cast: u64, registered_voters: u64) -> bool {
+        match self {
+            Quorum::Absolute(minimum) => votes_cast >= *minimum,
+            Quorum::Percentage(percent) => votes_cast * 100 >= registered_voters * u64::from(*percent),
+        }
+    }
+}
+
+/// How [`VotingState::winner`] settles a tie between two or more options
+/// that end with the same top vote count, configured via
+/// [`VotingState::set_tie_break_policy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub enum TieBreakPolicy {
+    /// A tie is reported as [`VoteError::TieUnresolved`] rather than
+    /// picked automatically.
+    Error,
+    /// Whichever tied option was added to the poll earliest (see
+    /// `option_order`) wins.
+    FirstRegisteredOption,
+    /// The admin picks among the tied options, passed as `admin_choice`
+    /// to [`VotingState::winner`].
+    AdminDecides,
+    /// One of the tied options is picked pseudorandomly - deterministic
+    /// for a given seed, so a tie-break can be reproduced or audited.
+    RandomWithSeed(u64),
+}
+
+/// One row of [`VotingState::audit_log`]: who did what, when (if the call
+/// had a timestamp to give), and whether it succeeded. Recorded for both
+/// outcomes - a rejected `cast_vote` is as much a fact about the election
+/// as an accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scale", derive(codec::Encode, codec::Decode, scale_info::TypeInfo))]
+pub struct AuditEntry {
+    pub caller_id: UserId,
+    /// The method that produced this entry, e.g. `"cast_vote"` -
+    /// `register_voter`/`cast_vote` aren't [`Action`]s (see its doc
+    /// comment), so a plain name is the only label that covers both those
+    /// and the phase transitions [`transition_notified`] drives.
+    pub action: String,
+    /// `None` for calls that don't take a `now` (`register_voter`,
+    /// `add_option`, and the other registration-time admin setters) -
+    /// there's genuinely no timestamp to report for those rather than a
+    /// made-up one.
+    pub timestamp: Option<Timestamp>,
+    /// `None` if `action` succeeded, `Some` with exactly the
+    /// [`VoteError`] the caller itself got back otherwise - a `Result`
+    /// would say the same thing, but `toml` (used by the `serde`
+    /// feature's [`persistence`](crate::persistence) module) can't
+    /// serialize `Result<(), _>`'s unit `Ok` case.
+    pub rejected: Option<VoteError>,
+}
+
+/// The answer to "how did the election turn out", as returned by
+/// [`VotingState::get_results`] once [`VotingPhase::ResultsFinalized`] is
+/// reached - so a caller doesn't have to re-derive percentages and
+/// turnout from `vote_counts` and `registered_voters` themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectionResults {
+    /// `None` if [`VotingState::winner`] couldn't settle a tie with the
+    /// `admin_choice` [`VotingState::get_results`] was given.
+    pub winner: Option<VoteOption>,
+    pub counts: BTreeMap<VoteOption, u64>,
+    /// Each option's share of `votes_cast`, out of 100. `0.0` for every
+    /// option if nobody voted, rather than dividing by zero.
+    pub percentages: BTreeMap<VoteOption, f64>,
+    /// `votes_cast.len() / registered_voters.len()`, out of 100. `0.0` if
+    /// nobody was registered, rather than dividing by zero.
+    pub turnout: f64,
+    pub invalid_vote_attempts: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Clone, serde::Serialize, serde::Deserialize))]
+pub struct VotingState {
+    pub current_voting_state: VotingPhase,
+    /// A `HashSet` rather than a `Vec`: `register_voter`/`cast_vote` only
+    /// ever ask "is this caller in here?", and that check runs on every
+    /// registration and every vote - a `Vec` scan made both linear in the
+    /// number of voters. Order was never part of the contract (voter
+    /// order, when it matters at all, is `votes_by_option`'s job), so
+    /// nothing is given up by not keeping one.
+    pub registered_voters: HashSet<UserId>,
+    /// See `registered_voters` above - same O(1)-membership reasoning,
+    /// and the same non-issue for order: `votes_by_option` is still what
+    /// anything that cares about vote order reads.
+    pub votes_cast: HashSet<UserId>,
+    pub vote_counts: BTreeMap<VoteOption, u64>,
+    /// Secondary index: every voter who picked a given option, in the
+    /// order they voted. Kept alongside `vote_counts` - one is the tally,
+    /// the other is the index a recount or per-option audit needs
+    /// instead of scanning `votes_cast` against every ballot. See
+    /// [`Self::voters_for`] for the paginated read of this.
+    pub votes_by_option: BTreeMap<VoteOption, Vec<UserId>>,
+    pub voting_start_time: Timestamp,
+    pub voting_end_time: Timestamp,
+    pub admin_id: UserId,
+    /// Turnout requirement [`Action::EndVoting`] must meet before
+    /// finalizing results, configured via [`Self::set_quorum`]. `None`
+    /// (the default) means no quorum is required.
+    pub quorum: Option<Quorum>,
+    /// Every option in the order it was added - `vote_counts` is a
+    /// `BTreeMap` and so reports options alphabetically, which loses the
+    /// one thing [`TieBreakPolicy::FirstRegisteredOption`] needs to
+    /// break a tie. Kept in step with `vote_counts` by
+    /// [`Self::add_vote_option`]/[`Self::add_option`]/[`Self::remove_option`].
+    pub option_order: Vec<VoteOption>,
+    /// How [`Self::winner`] settles a tie, configured via
+    /// [`Self::set_tie_break_policy`]. Defaults to
+    /// [`TieBreakPolicy::Error`], so a tie is never resolved silently
+    /// unless an election explicitly opts into a policy that does.
+    pub tie_break_policy: TieBreakPolicy,
+    /// How many times [`Self::cast_vote`]/[`Self::cast_vote_signed`] have
+    /// been rejected, for any reason. Surfaced through
+    /// [`Self::get_results`] as a rough signal of confusion or abuse
+    /// (a wrong option name, a double-vote attempt) that the raw tally
+    /// alone wouldn't show.
+    pub invalid_vote_attempts: u64,
+    /// Every action taken against this election, successful or not, in
+    /// the order it happened - see [`Self::audit_log`]/
+    /// [`Self::export_audit_log`] for reading it back.
+    pub audit_log: Vec<AuditEntry>,
+    /// When [`Self::pause_voting`] paused this election, so
+    /// [`Self::resume_voting`] can push `voting_end_time` out by however
+    /// long the pause lasted. `None` except while
+    /// [`VotingPhase::Paused`].
+    pub paused_at: Option<Timestamp>,
+    /// Ceiling on how much [`Self::extend_voting`] may push
+    /// `voting_end_time` out in total, configured via
+    /// [`Self::set_max_extension`]. `None` (the default) means no limit.
+    pub max_extension: Option<Timestamp>,
+    /// Total seconds [`Self::extend_voting`] has already granted this
+    /// election, checked against `max_extension` on every further call.
+    pub total_extension: Timestamp,
+}
+
+impl VotingState {
+    pub fn new(admin_id: UserId) -> Self {
+        Self {
+            current_voting_state: VotingPhase::RegistrationOpen,
+            registered_voters: HashSet::new(),
+            votes_cast: HashSet::new(),
+            vote_counts: BTreeMap::new(),
+            votes_by_option: BTreeMap::new(),
+            voting_start_time: 0,
+            voting_end_time: 0,
+            admin_id,
+            quorum: None,
+            option_order: Vec::new(),
+            tie_break_policy: TieBreakPolicy::Error,
+            invalid_vote_attempts: 0,
+            audit_log: Vec::new(),
+            paused_at: None,
+            max_extension: None,
+            total_extension: 0,
+        }
+    }
+
+    fn is_admin(&self, caller_id: &UserId) -> bool {
+        caller_id == &self.admin_id
+    }
+
+    /// Appends one [`AuditEntry`] to [`Self::audit_log`]. `outcome`
+    /// mirrors exactly what `action` itself is about to return to its
+    /// caller, so the log never disagrees with what actually happened.
+    fn record_audit(
+        &mut self,
+        caller_id: UserId,
+        action: &'static str,
+        timestamp: Option<Timestamp>,
+        outcome: Result<(), VoteError>,
+    ) {
+        self.audit_log.push(AuditEntry {
+            caller_id,
+            action: action.to_string(),
+            timestamp,
+            rejected: outcome.err(),
+        });
+    }
+
+    fn clear_voter_data(&mut self) {
+        self.registered_voters.clear();
+        self.votes_cast.clear();
+        self.vote_counts.clear();
+        self.votes_by_option.clear();
+        // Without this, re-adding the same options after a reset finds
+        // `vote_counts` empty (just cleared above) and pushes a second
+        // copy of each one onto `option_order` - see `add_vote_option`'s
+        // `vote_counts.insert(..).is_none()` guard, which only stops a
+        // duplicate push when the option is still present here too.
+        self.option_order.clear();
+    }
+
+    pub fn register_voter(&mut self, caller_id: UserId) -> Result<(), VoteError> {
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("register_voter::invalid_transition", [
+                "Registration is only open during one particular phase.",
+                "Check `current_voting_state` before touching `registered_voters`.",
+                "Return `VoteError::InvalidTransition` unless `current_voting_state == VotingPhase::RegistrationOpen`.",
+            ]);
+            self.record_audit(caller_id, "register_voter", None, Err(VoteError::InvalidTransition));
+            return Err(VoteError::InvalidTransition);
+        }
+        if self.registered_voters.contains(&caller_id) {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("register_voter::already_registered", [
+                "A voter shouldn't be able to register twice.",
+                "Check `registered_voters` for `caller_id` before pushing it.",
+                "Return `VoteError::AlreadyRegistered` when `registered_voters.contains(&caller_id)`.",
+            ]);
+            self.record_audit(caller_id, "register_voter", None, Err(VoteError::AlreadyRegistered));
+            return Err(VoteError::AlreadyRegistered);
+        }
+        self.registered_voters.insert(caller_id.clone());
+        self.record_audit(caller_id, "register_voter", None, Ok(()));
+        Ok(())
+    }
+
+    pub fn cast_vote(
+        &mut self,
+        caller_id: UserId,
+        vote_option: VoteOption,
+        now: Timestamp,
+    ) -> Result<(), VoteError> {
+        if self.current_voting_state != VotingPhase::VotingActive {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("cast_vote::voting_not_active", [
+                "Votes can only be cast during one particular phase.",
+                "Check `current_voting_state` before looking at the caller or the tally.",
+                "Return `VoteError::VotingNotActive` unless `current_voting_state == VotingPhase::VotingActive`.",
+            ]);
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote", Some(now), Err(VoteError::VotingNotActive));
+            return Err(VoteError::VotingNotActive);
+        }
+        if !self.registered_voters.contains(&caller_id) {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("cast_vote::not_registered", [
+                "Check whether the caller is in the registered-voters list.",
+                "`cast_vote` should reject unregistered callers before touching the tally.",
+                "Return `VoteError::NotRegistered` when `registered_voters` doesn't contain `caller_id`.",
+            ]);
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote", Some(now), Err(VoteError::NotRegistered));
+            return Err(VoteError::NotRegistered);
+        }
+        if self.votes_cast.contains(&caller_id) {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("cast_vote::already_voted", [
+                "A voter shouldn't be able to cast a second vote.",
+                "Check `votes_cast` for `caller_id` before recording this one.",
+                "Return `VoteError::AlreadyVoted` when `votes_cast.contains(&caller_id)`.",
+            ]);
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote", Some(now), Err(VoteError::AlreadyVoted));
+            return Err(VoteError::AlreadyVoted);
+        }
+        if now < self.voting_start_time || now > self.voting_end_time {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("cast_vote::outside_voting_window", [
+                "There's a start and end time for voting, not just a phase.",
+                "Compare `now` against `voting_start_time` and `voting_end_time`.",
+                "Return `VoteError::OutsideVotingWindow` when `now < voting_start_time || now > voting_end_time`.",
+            ]);
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote", Some(now), Err(VoteError::OutsideVotingWindow));
+            return Err(VoteError::OutsideVotingWindow);
+        }
+        if !self.vote_counts.contains_key(&vote_option) {
+            #[cfg(feature = "hints")]
+            hints::hint_on_failure!("cast_vote::invalid_option", [
+                "Not every string is a valid vote option.",
+                "Check `vote_counts` for `vote_option` before counting the vote.",
+                "Return `VoteError::InvalidOption` when `vote_counts` doesn't contain a key for `vote_option`.",
+            ]);
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote", Some(now), Err(VoteError::InvalidOption));
+            return Err(VoteError::InvalidOption);
+        }
+        self.votes_by_option.entry(vote_option.clone()).or_default().push(caller_id.clone());
+        self.record_audit(caller_id.clone(), "cast_vote", Some(now), Ok(()));
+        self.votes_cast.insert(caller_id);
+        *self.vote_counts.get_mut(&vote_option).unwrap() += 1;
+        Ok(())
+    }
+
+    /// Same as [`Self::register_voter`], but first rejects `caller_id`
+    /// with [`VoteError::InvalidUserId`] if `validator` doesn't accept it.
+    pub fn register_voter_checked<V: UserIdValidator>(
+        &mut self,
+        caller_id: UserId,
+        validator: &V,
+    ) -> Result<(), VoteError> {
+        if !validator.validate(&caller_id) {
+            self.record_audit(caller_id, "register_voter_checked", None, Err(VoteError::InvalidUserId));
+            return Err(VoteError::InvalidUserId);
+        }
+        self.register_voter(caller_id)
+    }
+
+    /// Same as [`Self::register_voter`], but first rejects `caller_id`
+    /// with [`VoteError::NotAPerson`] if `provider` can't verify `proof`
+    /// as evidence they're a distinct person.
+    pub fn register_voter_attested<P: PersonhoodProvider>(
+        &mut self,
+        caller_id: UserId,
+        proof: &[u8],
+        provider: &P,
+    ) -> Result<(), VoteError> {
+        if !provider.is_person(&caller_id, proof) {
+            self.record_audit(caller_id, "register_voter_attested", None, Err(VoteError::NotAPerson));
+            return Err(VoteError::NotAPerson);
+        }
+        self.register_voter(caller_id)
+    }
+
+    /// Same as [`Self::cast_vote`], but first checks `signature` against
+    /// [`signed_vote_message`] via `verifier` and rejects the vote with
+    /// [`VoteError::InvalidSignature`] if it doesn't check out. Callers who
+    /// don't need signed votes keep using `cast_vote` directly.
+    pub fn cast_vote_signed<V: SignatureVerifier>(
+        &mut self,
+        caller_id: UserId,
+        vote_option: VoteOption,
+        now: Timestamp,
+        signature: &[u8],
+        verifier: &V,
+    ) -> Result<(), VoteError> {
+        let message = signed_vote_message(&caller_id, &vote_option);
+        if !verifier.verify(&caller_id, &message, signature) {
+            self.invalid_vote_attempts += 1;
+            self.record_audit(caller_id, "cast_vote_signed", Some(now), Err(VoteError::InvalidSignature));
+            return Err(VoteError::InvalidSignature);
+        }
+        self.cast_vote(caller_id, vote_option, now)
+    }
+
+    /// Registers `option` as a valid choice while registration is open.
+    /// `cast_vote` rejects any option that hasn't been added this way.
+    pub fn add_vote_option(&mut self, option: VoteOption) {
+        if self.vote_counts.insert(option.clone(), 0).is_none() {
+            self.option_order.push(option);
+        }
+    }
+
+    /// The admin-gated, validating counterpart to [`Self::add_vote_option`]:
+    /// rejects a caller who isn't `admin_id`, a duplicate option, or being
+    /// called once voting has started, instead of silently accepting all
+    /// three the way the plain setup call does.
+    pub fn add_option(&mut self, caller_id: &UserId, option: VoteOption) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "add_option", None, Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            self.record_audit(caller_id.clone(), "add_option", None, Err(VoteError::VotingAlreadyStarted));
+            return Err(VoteError::VotingAlreadyStarted);
+        }
+        if self.vote_counts.contains_key(&option) {
+            self.record_audit(caller_id.clone(), "add_option", None, Err(VoteError::DuplicateOption));
+            return Err(VoteError::DuplicateOption);
+        }
+        self.vote_counts.insert(option.clone(), 0);
+        self.option_order.push(option);
+        self.record_audit(caller_id.clone(), "add_option", None, Ok(()));
+        Ok(())
+    }
+
+    /// Removes `option`, as long as it's `admin_id` asking, voting hasn't
+    /// started yet, and nobody has voted for it - removing an option with
+    /// votes already recorded against it would silently drop ballots.
+    pub fn remove_option(&mut self, caller_id: &UserId, option: &VoteOption) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "remove_option", None, Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            self.record_audit(caller_id.clone(), "remove_option", None, Err(VoteError::VotingAlreadyStarted));
+            return Err(VoteError::VotingAlreadyStarted);
+        }
+        let result = match self.vote_counts.get(option) {
+            None => Err(VoteError::InvalidOption),
+            Some(&count) if count > 0 => Err(VoteError::OptionHasVotes),
+            Some(_) => {
+                self.vote_counts.remove(option);
+                self.votes_by_option.remove(option);
+                self.option_order.retain(|registered| registered != option);
+                Ok(())
+            }
+        };
+        self.record_audit(caller_id.clone(), "remove_option", None, result.clone());
+        result
+    }
+
+    /// Every option presently open for voting, in the same order
+    /// `vote_counts` itself reports them in. A read, so unlike
+    /// [`Self::add_option`]/[`Self::remove_option`] it isn't admin-gated.
+    pub fn list_options(&self) -> Vec<VoteOption> {
+        self.vote_counts.keys().cloned().collect()
+    }
+
+    /// Sets the turnout [`Quorum`] that [`Action::EndVoting`] must meet
+    /// (unless `override_quorum` is set) before results can be
+    /// finalized. Like [`Self::add_option`], admin-gated and only
+    /// allowed before voting starts, so turnout requirements can't be
+    /// tightened or loosened once ballots are already being cast.
+    pub fn set_quorum(&mut self, caller_id: &UserId, quorum: Quorum) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "set_quorum", None, Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            self.record_audit(caller_id.clone(), "set_quorum", None, Err(VoteError::VotingAlreadyStarted));
+            return Err(VoteError::VotingAlreadyStarted);
+        }
+        self.quorum = Some(quorum);
+        self.record_audit(caller_id.clone(), "set_quorum", None, Ok(()));
+        Ok(())
+    }
+
+    /// Sets the [`TieBreakPolicy`] [`Self::winner`] applies when two or
+    /// more options end up tied for the top vote count. Like
+    /// [`Self::set_quorum`], admin-gated and only allowed before voting
+    /// starts, so the tie-break rule can't be changed after ballots are
+    /// already being cast.
+    pub fn set_tie_break_policy(&mut self, caller_id: &UserId, policy: TieBreakPolicy) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "set_tie_break_policy", None, Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            self.record_audit(caller_id.clone(), "set_tie_break_policy", None, Err(VoteError::VotingAlreadyStarted));
+            return Err(VoteError::VotingAlreadyStarted);
+        }
+        self.tie_break_policy = policy;
+        self.record_audit(caller_id.clone(), "set_tie_break_policy", None, Ok(()));
+        Ok(())
+    }
+
+    /// Admin-only: moves an in-progress election to [`VotingPhase::Paused`],
+    /// where [`Self::cast_vote`] is rejected the same way it already is
+    /// outside [`VotingPhase::VotingActive`]. Not a [`TRANSITIONS`] row -
+    /// see [`VotingPhase::Paused`]'s doc comment - so a mismatched phase
+    /// reports [`VoteError::InvalidTransition`] the same way a rejected
+    /// table-driven transition would.
+    pub fn pause_voting(&mut self, caller_id: &UserId, now: Timestamp) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "pause_voting", Some(now), Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::VotingActive {
+            self.record_audit(caller_id.clone(), "pause_voting", Some(now), Err(VoteError::InvalidTransition));
+            return Err(VoteError::InvalidTransition);
+        }
+        self.current_voting_state = VotingPhase::Paused;
+        self.paused_at = Some(now);
+        self.record_audit(caller_id.clone(), "pause_voting", Some(now), Ok(()));
+        Ok(())
+    }
+
+    /// Admin-only counterpart to [`Self::pause_voting`]: returns to
+    /// [`VotingPhase::VotingActive`] and pushes `voting_end_time` out by
+    /// however long the election was paused, so a pause never costs
+    /// voters any of the window they were promised.
+    pub fn resume_voting(&mut self, caller_id: &UserId, now: Timestamp) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "resume_voting", Some(now), Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        let Some(paused_at) = self.paused_at else {
+            self.record_audit(caller_id.clone(), "resume_voting", Some(now), Err(VoteError::InvalidTransition));
+            return Err(VoteError::InvalidTransition);
+        };
+        self.voting_end_time += now.saturating_sub(paused_at);
+        self.current_voting_state = VotingPhase::VotingActive;
+        self.paused_at = None;
+        self.record_audit(caller_id.clone(), "resume_voting", Some(now), Ok(()));
+        Ok(())
+    }
+
+    /// Caps how many total seconds [`Self::extend_voting`] may grant
+    /// across the election. Like [`Self::set_quorum`], admin-gated and
+    /// only allowed before voting starts.
+    pub fn set_max_extension(&mut self, caller_id: &UserId, max_extension: Timestamp) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "set_max_extension", None, Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::RegistrationOpen {
+            self.record_audit(caller_id.clone(), "set_max_extension", None, Err(VoteError::VotingAlreadyStarted));
+            return Err(VoteError::VotingAlreadyStarted);
+        }
+        self.max_extension = Some(max_extension);
+        self.record_audit(caller_id.clone(), "set_max_extension", None, Ok(()));
+        Ok(())
+    }
+
+    /// Admin-only: pushes `voting_end_time` out by `extra_secs` while
+    /// voting is active, rejecting the extension if it would push
+    /// [`Self::total_extension`] past [`Self::max_extension`] (when one's
+    /// configured via [`Self::set_max_extension`]). Every call, accepted
+    /// or not, lands in [`Self::audit_log`].
+    pub fn extend_voting(&mut self, caller_id: &UserId, extra_secs: Timestamp, now: Timestamp) -> Result<(), VoteError> {
+        if !self.is_admin(caller_id) {
+            self.record_audit(caller_id.clone(), "extend_voting", Some(now), Err(VoteError::NotAdmin));
+            return Err(VoteError::NotAdmin);
+        }
+        if self.current_voting_state != VotingPhase::VotingActive {
+            self.record_audit(caller_id.clone(), "extend_voting", Some(now), Err(VoteError::VotingNotActive));
+            return Err(VoteError::VotingNotActive);
+        }
+        let extended = self.total_extension.saturating_add(extra_secs);
+        if let Some(max_extension) = self.max_extension {
+            if extended > max_extension {
+                self.record_audit(caller_id.clone(), "extend_voting", Some(now), Err(VoteError::ExtensionLimitExceeded));
+                return Err(VoteError::ExtensionLimitExceeded);
+            }
+        }
+        self.voting_end_time += extra_secs;
+        self.total_extension = extended;
+        self.record_audit(caller_id.clone(), "extend_voting", Some(now), Ok(()));
+        Ok(())
+    }
+
+    /// The option (or, under [`TieBreakPolicy::AdminDecides`], one of the
+    /// tied options `admin_choice` names) with the most votes.
+    /// `admin_choice` is ignored unless `tie_break_policy` is
+    /// [`TieBreakPolicy::AdminDecides`] and there actually is a tie.
+    /// Returns [`VoteError::InvalidOption`] if there are no options at
+    /// all, and [`VoteError::TieUnresolved`] if the tie can't be settled
+    /// with what was given.
+    pub fn winner(&self, admin_choice: Option<&VoteOption>) -> Result<VoteOption, VoteError> {
+        let top = match self.vote_counts.values().max() {
+            Some(&top) => top,
+            None => return Err(VoteError::InvalidOption),
+        };
+        let tied: Vec<&VoteOption> =
+            self.option_order.iter().filter(|option| self.vote_counts.get(*option) == Some(&top)).collect();
+        match tied.as_slice() {
+            [only] => Ok((*only).clone()),
+            _ => match &self.tie_break_policy {
+                TieBreakPolicy::Error => Err(VoteError::TieUnresolved),
+                TieBreakPolicy::FirstRegisteredOption => Ok(tied[0].clone()),
+                TieBreakPolicy::AdminDecides => match admin_choice {
+                    Some(choice) if tied.contains(&choice) => Ok(choice.clone()),
+                    _ => Err(VoteError::TieUnresolved),
+                },
+                TieBreakPolicy::RandomWithSeed(seed) => Ok(tied[*seed as usize % tied.len()].clone()),
+            },
+        }
+    }
+
+    /// `vote_counts`, but with any option below `k` votes reported as
+    /// [`ResultCount::Suppressed`] instead of its exact count - so
+    /// publishing results from a tiny or lopsided election doesn't
+    /// expose who voted for an option only a handful of people picked.
+    /// `vote_counts` itself is untouched; the admin (or anything else
+    /// with direct access to `VotingState`) can still read exact counts
+    /// there regardless of `k`.
+    pub fn public_results(&self, k: u64) -> BTreeMap<VoteOption, ResultCount> {
+        self.vote_counts
+            .iter()
+            .map(|(option, &count)| {
+                let reported = if count < k { ResultCount::Suppressed(k) } else { ResultCount::Exact(count) };
+                (option.clone(), reported)
+            })
+            .collect()
+    }
+
+    /// A page of `votes_by_option[option]`, skipping the first `offset`
+    /// voters and returning up to `limit` of the rest in the order they
+    /// voted - a recount or per-option audit's way of paging through
+    /// every ballot for one option without loading them all into memory
+    /// at once, or rescanning `votes_cast` against every other ballot to
+    /// find them. An out-of-range `offset` returns an empty slice rather
+    /// than panicking.
+    ///
+    /// This hands back exactly who voted for `option`: skip calling it
+    /// in a deployment that wants to keep that private, and reach for
+    /// [`Self::public_results`] instead, which only ever reports counts.
+    pub fn voters_for(&self, option: &VoteOption, offset: usize, limit: usize) -> &[UserId] {
+        let voters = self.votes_by_option.get(option).map(Vec::as_slice).unwrap_or(&[]);
+        let start = offset.min(voters.len());
+        let end = start.saturating_add(limit).min(voters.len());
+        &voters[start..end]
+    }
+
+    /// The full [`ElectionResults`] report - winner, per-option counts
+    /// and percentages, turnout, and how many vote attempts were
+    /// rejected - once results are in. `admin_choice` is forwarded
+    /// straight to [`Self::winner`], and ignored the same way it is
+    /// there unless it's actually needed to settle a tie.
+    ///
+    /// Returns [`VoteError::VotingNotActive`] before
+    /// [`VotingPhase::ResultsFinalized`] is reached - the same error the
+    /// `ranked-tally` feature's own end-of-voting tally uses for the same
+    /// reason: results aren't final yet, whatever phase got here first.
+    pub fn get_results(&self, admin_choice: Option<&VoteOption>) -> Result<ElectionResults, VoteError> {
+        if self.current_voting_state != VotingPhase::ResultsFinalized {
+            return Err(VoteError::VotingNotActive);
+        }
+        let total_votes: u64 = self.vote_counts.values().sum();
+        let percentages = self
+            .vote_counts
+            .iter()
+            .map(|(option, &count)| {
+                let percentage =
+                    if total_votes == 0 { 0.0 } else { count as f64 / total_votes as f64 * 100.0 };
+                (option.clone(), percentage)
+            })
+            .collect();
+        let turnout = if self.registered_voters.is_empty() {
+            0.0
+        } else {
+            self.votes_cast.len() as f64 / self.registered_voters.len() as f64 * 100.0
+        };
+        Ok(ElectionResults {
+            winner: self.winner(admin_choice).ok(),
+            counts: self.vote_counts.clone(),
+            percentages,
+            turnout,
+            invalid_vote_attempts: self.invalid_vote_attempts,
+        })
+    }
+
+    /// The full audit trail recorded so far, in the order it happened.
+    /// Available at any phase, unlike [`Self::export_audit_log`] - a
+    /// caller debugging a rejected call mid-election shouldn't have to
+    /// wait for results to see why.
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit_log
+    }
+
+    /// The subset of [`Self::audit_log`] performed by `caller_id`, in the
+    /// order it happened.
+    pub fn audit_log_for(&self, caller_id: &UserId) -> Vec<&AuditEntry> {
+        self.audit_log.iter().filter(|entry| &entry.caller_id == caller_id).collect()
+    }
+
+    /// The full audit trail, once results are in - the same
+    /// [`VoteError::VotingNotActive`]-before-finalization rule as
+    /// [`Self::get_results`], so a demo of "verify this election" can't
+    /// read it out from under an election that's still in progress.
+    pub fn export_audit_log(&self) -> Result<&[AuditEntry], VoteError> {
+        if self.current_voting_state != VotingPhase::ResultsFinalized {
+            return Err(VoteError::VotingNotActive);
+        }
+        Ok(&self.audit_log)
+    }
+}
+
+/// Builds a [`VotingState`] with validation, for callers that want to
+/// configure an election in one place and be told about a mistake (a
+/// duplicate option, a blank admin id) before it's live, rather than
+/// discovering it through some later `register_voter`/`cast_vote` call
+/// that fails for a confusing reason. `VotingState::new` plus direct
+/// method calls is still fine for the common case of building the state
+/// up one step at a time; this is for assembling it from configuration
+/// gathered all at once.
+#[derive(Default)]
+pub struct VotingStateBuilder {
+    admin_id: Option<UserId>,
+    options: Vec<VoteOption>,
+    voters: Vec<UserId>,
+    voting_active_from: Option<(Timestamp, Timestamp)>,
+}
+
+impl VotingStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_admin(mut self, admin_id: UserId) -> Self {
+        self.admin_id = Some(admin_id);
+        self
+    }
+
+    pub fn with_option(mut self, option: VoteOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn with_voter(mut self, voter: UserId) -> Self {
+        self.voters.push(voter);
+        self
+    }
+
+    /// Drives the built state through `Action::StartVoting { duration }`
+    /// at time `at`, landing it in `VotingPhase::VotingActive` instead of
+    /// the default `RegistrationOpen`.
+    pub fn voting_active(mut self, duration: Timestamp, at: Timestamp) -> Self {
+        self.voting_active_from = Some((duration, at));
+        self
+    }
+
+    /// Validates the configuration and assembles it into a [`VotingState`]
+    /// via the same `register_voter`/`add_vote_option`/`transition` calls
+    /// a caller driving the state machine by hand would make, so a built
+    /// state can never diverge from one built step by step.
+    pub fn build(self) -> Result<VotingState, VoteError> {
+        let admin_id = self.admin_id.ok_or(VoteError::EmptyAdminId)?;
+        if admin_id.is_empty() {
+            return Err(VoteError::EmptyAdminId);
+        }
+
+        let mut seen_options = HashSet::new();
+        for option in &self.options {
+            if !seen_options.insert(option) {
+                return Err(VoteError::DuplicateOption);
+            }
+        }
+
+        let mut state = VotingState::new(admin_id.clone());
+        for option in self.options {
+            state.add_vote_option(option);
+        }
+        for voter in self.voters {
+            state.register_voter(voter)?;
+        }
+        if let Some((duration, at)) = self.voting_active_from {
+            transition(&mut state, admin_id, Action::StartVoting { duration }, at)?;
+        }
+
+        Ok(state)
+    }
+}
+
+/// One row of the transition table: `from = None` matches any current
+/// phase, letting a single row (e.g. `reset_application`) apply everywhere.
+struct TransitionRule {
+    from: Option<VotingPhase>,
+    action: ActionKind,
+    guard: fn(&VotingState, &UserId, &Action) -> Result<(), VoteError>,
+    to: VotingPhase,
+}
+
+fn guard_admin_only(state: &VotingState, caller_id: &UserId, _action: &Action) -> Result<(), VoteError> {
+    if !state.is_admin(caller_id) {
+        return Err(VoteError::NotAdmin);
+    }
+    Ok(())
+}
+
+fn guard_start_voting(state: &VotingState, caller_id: &UserId, action: &Action) -> Result<(), VoteError> {
+    guard_admin_only(state, caller_id, action)?;
+    match action {
+        Action::StartVoting { duration } if *duration == 0 => Err(VoteError::InvalidDuration),
+        _ => Ok(()),
+    }
+}
+
+/// Guards [`Action::EndVoting`]: enforces [`VotingState::quorum`] unless
+/// `override_quorum` is set. The actual "has voting_end_time passed?"
+/// check needs the current time, which `transition` doesn't thread
+/// through the guard signature (it's only known by the caller) -
+/// `transition_notified` re-checks that itself once this guard passes.
+fn guard_end_voting(state: &VotingState, _caller_id: &UserId, action: &Action) -> Result<(), VoteError> {
+    if let Action::EndVoting { override_quorum: false } = action {
+        if let Some(quorum) = state.quorum {
+            if !quorum.is_met(state.votes_cast.len() as u64, state.registered_voters.len() as u64) {
+                return Err(VoteError::QuorumNotMet);
+            }
+        }
+    }
+    Ok(())
+}
+
+const TRANSITIONS: &[TransitionRule] = &[
+    TransitionRule {
+        from: Some(VotingPhase::VotingActive),
+        action: ActionKind::StartRegistration,
+        guard: guard_admin_only,
+        to: VotingPhase::RegistrationOpen,
+    },
+    TransitionRule {
+        from: Some(VotingPhase::ResultsFinalized),
+        action: ActionKind::StartRegistration,
+        guard: guard_admin_only,
+        to: VotingPhase::RegistrationOpen,
+    },
+    TransitionRule {
+        from: Some(VotingPhase::RegistrationOpen),
+        action: ActionKind::StartVoting,
+        guard: guard_start_voting,
+        to: VotingPhase::VotingActive,
+    },
+    TransitionRule {
+        from: Some(VotingPhase::VotingActive),
+        action: ActionKind::EndVoting,
+        guard: guard_end_voting,
+        to: VotingPhase::ResultsFinalized,
+    },
+    TransitionRule {
+        from: None,
+        action: ActionKind::ResetApplication,
+        guard: guard_admin_only,
+        to: VotingPhase::RegistrationOpen,
+    },
+];
+
+/// Looks up the row matching `(state.current_voting_state, action)`, runs
+/// its guard, then applies the action's side effects and commits `to`.
+/// Shorthand for [`transition_notified`] with a [`NoopNotifier`], for
+/// the common case (most tests, any caller that doesn't want lifecycle
+/// hooks) where there's nothing to notify.
+pub fn transition(
+    state: &mut VotingState,
+    caller_id: UserId,
+    action: Action,
+    now: Timestamp,
+) -> Result<(), VoteError> {
+    transition_notified(state, caller_id, action, now, &NoopNotifier)
+}
+
+/// Same as [`transition`], but calls the matching hook on `notifier`
+/// once the transition commits: [`Notifier::registration_open`] when
+/// the new phase is [`VotingPhase::RegistrationOpen`],
+/// [`Notifier::voting_started`] for [`VotingPhase::VotingActive`], and
+/// [`Notifier::finalized`] for [`VotingPhase::ResultsFinalized`]. Not
+/// called at all if `action` fails - a rejected transition is nothing
+/// to notify anyone about.
+pub fn transition_notified<N: Notifier>(
+    state: &mut VotingState,
+    caller_id: UserId,
+    action: Action,
+    now: Timestamp,
+    notifier: &N,
+) -> Result<(), VoteError> {
+    let audit_name = action.audit_name();
+    let result = apply_transition(state, &caller_id, action, now, notifier);
+    state.record_audit(caller_id, audit_name, Some(now), result.clone());
+    result
+}
+
+/// The body of [`transition_notified`], split out so it can run to
+/// completion (including every early `?` return) before that function
+/// records one [`AuditEntry`] with the result - `caller_id` is only
+/// borrowed here since `transition_notified` still needs it afterward.
+fn apply_transition<N: Notifier>(
+    state: &mut VotingState,
+    caller_id: &UserId,
+    action: Action,
+    now: Timestamp,
+    notifier: &N,
+) -> Result<(), VoteError> {
+    let rule = TRANSITIONS
+        .iter()
+        .find(|rule| {
+            let phase_matches = match rule.from {
+                Some(phase) => phase == state.current_voting_state,
+                None => true,
+            };
+            phase_matches && rule.action == action.kind()
+        })
+        .ok_or(VoteError::InvalidTransition)?;
+
+    (rule.guard)(state, caller_id, &action)?;
+
+    match &action {
+        Action::StartRegistration | Action::ResetApplication => state.clear_voter_data(),
+        Action::StartVoting { duration } => {
+            state.voting_start_time = now;
+            state.voting_end_time = now + duration;
+        }
+        Action::EndVoting { .. } => {
+            if now < state.voting_end_time {
+                return Err(VoteError::VotingWindowNotElapsed);
+            }
+        }
+    }
+
+    state.current_voting_state = rule.to;
+    match state.current_voting_state {
+        VotingPhase::RegistrationOpen => notifier.registration_open(state),
+        VotingPhase::VotingActive => notifier.voting_started(state),
+        VotingPhase::ResultsFinalized => notifier.finalized(state),
+        VotingPhase::Paused => unreachable!("no TRANSITIONS row ever targets VotingPhase::Paused"),
+    }
+    Ok(())
+}
+
@@ -0,0 +1,96 @@
+//! Renders a [`VoteError`] as human-readable text, separately from the
+//! error's own identity. [`VoteError::key`] is what a CLI or REST layer
+//! should match on or log, since it's stable across phrasing changes;
+//! [`message`] is what it shows a person, and only this module needs to
+//! know what languages that's available in.
+//!
+//! Starts with English and Swahili, the two languages this study
+//! group's workshops run in. Adding a locale means extending [`Locale`]
+//! and this module's match - the match is exhaustive over
+//! `(&VoteError, Locale)`, so the compiler points at every message
+//! still missing.
+//!
+//! No `VoteError` variant currently carries data to interpolate into a
+//! message - if one grows a parameter (e.g. a deadline on
+//! [`VoteError::OutsideVotingWindow`](crate::VoteError::OutsideVotingWindow)),
+//! `message` returning `String` instead of `&'static str` is the
+//! natural extension point; there's no parameter to thread through yet.
+
+use crate::VoteError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Sw,
+}
+
+/// Renders `error` in `locale`.
+pub fn message(error: &VoteError, locale: Locale) -> &'static str {
+    match (error, locale) {
+        (VoteError::NotAdmin, Locale::En) => "Only the election admin can do this.",
+        (VoteError::NotAdmin, Locale::Sw) => "Ni msimamizi wa uchaguzi pekee anayeweza kufanya hivi.",
+
+        (VoteError::InvalidTransition, Locale::En) => "That action isn't allowed from the current phase.",
+        (VoteError::InvalidTransition, Locale::Sw) => "Kitendo hicho hakiruhusiwi katika awamu ya sasa.",
+
+        (VoteError::InvalidDuration, Locale::En) => "Voting duration must be greater than zero.",
+        (VoteError::InvalidDuration, Locale::Sw) => "Muda wa kupiga kura lazima uwe zaidi ya sifuri.",
+
+        (VoteError::VotingWindowNotElapsed, Locale::En) => "Voting hasn't ended yet.",
+        (VoteError::VotingWindowNotElapsed, Locale::Sw) => "Muda wa kupiga kura bado haujaisha.",
+
+        (VoteError::AlreadyRegistered, Locale::En) => "This voter is already registered.",
+        (VoteError::AlreadyRegistered, Locale::Sw) => "Mpiga kura huyu tayari amesajiliwa.",
+
+        (VoteError::NotRegistered, Locale::En) => "This voter isn't registered.",
+        (VoteError::NotRegistered, Locale::Sw) => "Mpiga kura huyu hajasajiliwa.",
+
+        (VoteError::AlreadyVoted, Locale::En) => "This voter has already voted.",
+        (VoteError::AlreadyVoted, Locale::Sw) => "Mpiga kura huyu tayari amepiga kura.",
+
+        (VoteError::VotingNotActive, Locale::En) => "Voting isn't open right now.",
+        (VoteError::VotingNotActive, Locale::Sw) => "Kupiga kura hakujafunguliwa kwa sasa.",
+
+        (VoteError::InvalidOption, Locale::En) => "That isn't a valid vote option.",
+        (VoteError::InvalidOption, Locale::Sw) => "Hicho si chaguo sahihi la kupigia kura.",
+
+        (VoteError::OutsideVotingWindow, Locale::En) => "That's outside the voting window.",
+        (VoteError::OutsideVotingWindow, Locale::Sw) => "Hiyo iko nje ya muda wa kupiga kura.",
+
+        (VoteError::InvalidSignature, Locale::En) => "The vote's signature doesn't check out.",
+        (VoteError::InvalidSignature, Locale::Sw) => "Sahihi ya kura hailingani.",
+
+        (VoteError::InvalidUserId, Locale::En) => "That user id isn't valid.",
+        (VoteError::InvalidUserId, Locale::Sw) => "Kitambulisho hicho cha mtumiaji si sahihi.",
+
+        (VoteError::NotAPerson, Locale::En) => "Couldn't verify you're a distinct person.",
+        (VoteError::NotAPerson, Locale::Sw) => "Imeshindwa kuthibitisha kuwa wewe ni mtu binafsi.",
+
+        (VoteError::EmptyAdminId, Locale::En) => "The election needs an admin id.",
+        (VoteError::EmptyAdminId, Locale::Sw) => "Uchaguzi unahitaji kitambulisho cha msimamizi.",
+
+        (VoteError::DuplicateOption, Locale::En) => "That vote option was already added.",
+        (VoteError::DuplicateOption, Locale::Sw) => "Chaguo hilo la kupigia kura tayari limeongezwa.",
+
+        (VoteError::VotingAlreadyStarted, Locale::En) => "Vote options can't be changed once voting has started.",
+        (VoteError::VotingAlreadyStarted, Locale::Sw) => "Chaguo za kura haziwezi kubadilishwa baada ya kupiga kura kuanza.",
+
+        (VoteError::OptionHasVotes, Locale::En) => "That option already has votes and can't be removed.",
+        (VoteError::OptionHasVotes, Locale::Sw) => "Chaguo hilo tayari lina kura na haliwezi kuondolewa.",
+
+        (VoteError::PollNotFound, Locale::En) => "No poll exists with that id.",
+        (VoteError::PollNotFound, Locale::Sw) => "Hakuna kura iliyopo yenye kitambulisho hicho.",
+
+        (VoteError::PollAlreadyExists, Locale::En) => "A poll with that id already exists.",
+        (VoteError::PollAlreadyExists, Locale::Sw) => "Kura yenye kitambulisho hicho tayari ipo.",
+
+        (VoteError::QuorumNotMet, Locale::En) => "Turnout hasn't met the required quorum.",
+        (VoteError::QuorumNotMet, Locale::Sw) => "Idadi ya wapiga kura haijafikia kiwango cha chini kinachohitajika.",
+
+        (VoteError::TieUnresolved, Locale::En) => "The vote ended in a tie that couldn't be resolved.",
+        (VoteError::TieUnresolved, Locale::Sw) => "Matokeo ya kura yamefungana na hayakuweza kutatuliwa.",
+
+        (VoteError::ExtensionLimitExceeded, Locale::En) => "That extension would exceed the maximum allowed for this election.",
+        (VoteError::ExtensionLimitExceeded, Locale::Sw) => "Nyongeza hiyo ingezidi kiwango cha juu kinachoruhusiwa kwa uchaguzi huu.",
+    }
+}
@@ -0,0 +1,58 @@
+//! Snapshotting a [`VotingState`] to a string and back, so a workshop
+//! demo that gets killed mid-election doesn't lose everything - the
+//! `serde` derives on [`VotingState`] and the enums it's built from
+//! (gated behind this same feature) make that mechanical; this module is
+//! just the versioned envelope around them, so a snapshot written by an
+//! older build of this crate fails loudly with [`SnapshotError`] instead
+//! of silently decoding into a state nobody actually configured.
+
+use serde::{Deserialize, Serialize};
+
+use crate::VotingState;
+
+/// Bumped whenever [`VotingState`]'s shape changes in a way that would
+/// make an older snapshot decode into something wrong rather than fail
+/// outright - a renamed field, say, that `toml` would just leave at its
+/// default instead of erroring on.
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    schema_version: u32,
+    state: VotingState,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The snapshot's `schema_version` doesn't match [`SCHEMA_VERSION`] -
+    /// carries both so a caller can say which snapshot from which build.
+    UnsupportedSchemaVersion { found: u32, expected: u32 },
+    /// The snapshot couldn't be parsed as the envelope at all - not a
+    /// version mismatch, just not a snapshot this crate ever wrote.
+    Malformed(String),
+}
+
+impl VotingState {
+    /// Serializes `self` into a versioned snapshot string that
+    /// [`VotingState::from_snapshot`] can restore later, in this or a
+    /// future process.
+    pub fn save_snapshot(&self) -> String {
+        let snapshot = Snapshot { schema_version: SCHEMA_VERSION, state: self.clone() };
+        toml::to_string(&snapshot).expect("VotingState always serializes")
+    }
+
+    /// The inverse of [`Self::save_snapshot`]. Rejects a snapshot from an
+    /// incompatible schema version, or one that isn't a snapshot this
+    /// crate wrote at all, instead of restoring a state nobody actually
+    /// configured.
+    pub fn from_snapshot(raw: &str) -> Result<Self, SnapshotError> {
+        let snapshot: Snapshot = toml::from_str(raw).map_err(|error| SnapshotError::Malformed(error.to_string()))?;
+        if snapshot.schema_version != SCHEMA_VERSION {
+            return Err(SnapshotError::UnsupportedSchemaVersion {
+                found: snapshot.schema_version,
+                expected: SCHEMA_VERSION,
+            });
+        }
+        Ok(snapshot.state)
+    }
+}
@@ -0,0 +1,103 @@
+//! Optional multi-poll layer: [`VotingState`] itself is one election,
+//! started by one admin and tracked by nobody else - this module is for
+//! a deployment that wants to host many of them side by side, each at
+//! its own phase with its own admin.
+//!
+//! ## Scope
+//!
+//! [`PollManager`] is a thin multiplexer: a [`BTreeMap`] from
+//! [`PollId`] to [`VotingState`], with "per-poll admin" coming for free
+//! from `VotingState::admin_id` already being per-instance. Its
+//! lifecycle methods (`register_voter`, `cast_vote`, `transition`) just
+//! look up the poll and forward to the matching [`VotingState`]/
+//! [`crate::transition`] call, reusing [`VoteError`] rather than
+//! wrapping it in a poll-specific error type - [`VoteError::PollNotFound`]
+//! and [`VoteError::PollAlreadyExists`] are the only two cases a lookup
+//! can fail that a single-poll caller never sees.
+
+use std::collections::BTreeMap;
+
+use crate::{Action, Timestamp, UserId, VoteError, VoteOption, VotingPhase, VotingState};
+
+pub type PollId = String;
+
+/// Many independent elections, each its own [`VotingState`] keyed by
+/// [`PollId`].
+#[derive(Debug, Default)]
+pub struct PollManager {
+    polls: BTreeMap<PollId, VotingState>,
+}
+
+impl PollManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new poll under `poll_id`, admin'd by `admin_id`.
+    pub fn create_poll(&mut self, poll_id: PollId, admin_id: UserId) -> Result<(), VoteError> {
+        if self.polls.contains_key(&poll_id) {
+            return Err(VoteError::PollAlreadyExists);
+        }
+        self.polls.insert(poll_id, VotingState::new(admin_id));
+        Ok(())
+    }
+
+    /// Read-only access to one poll's state, for callers who want to
+    /// call a query method (e.g. [`VotingState::public_results`])
+    /// directly instead of going through a forwarding method here.
+    pub fn poll(&self, poll_id: &PollId) -> Result<&VotingState, VoteError> {
+        self.polls.get(poll_id).ok_or(VoteError::PollNotFound)
+    }
+
+    fn poll_mut(&mut self, poll_id: &PollId) -> Result<&mut VotingState, VoteError> {
+        self.polls.get_mut(poll_id).ok_or(VoteError::PollNotFound)
+    }
+
+    /// Forwards to [`VotingState::add_vote_option`] for the poll at
+    /// `poll_id`.
+    pub fn add_vote_option(&mut self, poll_id: &PollId, option: VoteOption) -> Result<(), VoteError> {
+        self.poll_mut(poll_id)?.add_vote_option(option);
+        Ok(())
+    }
+
+    /// Forwards to [`VotingState::register_voter`] for the poll at
+    /// `poll_id`.
+    pub fn register_voter(&mut self, poll_id: &PollId, caller_id: UserId) -> Result<(), VoteError> {
+        self.poll_mut(poll_id)?.register_voter(caller_id)
+    }
+
+    /// Forwards to [`VotingState::cast_vote`] for the poll at `poll_id`.
+    pub fn cast_vote(
+        &mut self,
+        poll_id: &PollId,
+        caller_id: UserId,
+        vote_option: VoteOption,
+        now: Timestamp,
+    ) -> Result<(), VoteError> {
+        self.poll_mut(poll_id)?.cast_vote(caller_id, vote_option, now)
+    }
+
+    /// Forwards to [`crate::transition`] for the poll at `poll_id`.
+    pub fn transition(&mut self, poll_id: &PollId, caller_id: UserId, action: Action, now: Timestamp) -> Result<(), VoteError> {
+        crate::transition(self.poll_mut(poll_id)?, caller_id, action, now)
+    }
+
+    fn polls_in_phase(&self, phase: VotingPhase) -> Vec<&PollId> {
+        self.polls.iter().filter(|(_, state)| state.current_voting_state == phase).map(|(poll_id, _)| poll_id).collect()
+    }
+
+    /// Every poll still in [`VotingPhase::RegistrationOpen`].
+    pub fn open_polls(&self) -> Vec<&PollId> {
+        self.polls_in_phase(VotingPhase::RegistrationOpen)
+    }
+
+    /// Every poll in [`VotingPhase::VotingActive`].
+    pub fn active_polls(&self) -> Vec<&PollId> {
+        self.polls_in_phase(VotingPhase::VotingActive)
+    }
+
+    /// Every poll in [`VotingPhase::ResultsFinalized`].
+    pub fn finalized_polls(&self) -> Vec<&PollId> {
+        self.polls_in_phase(VotingPhase::ResultsFinalized)
+    }
+}
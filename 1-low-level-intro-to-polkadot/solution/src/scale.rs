@@ -0,0 +1,120 @@
+//! `parity-scale-codec`/`scale-info` support for [`VotingState`], so the
+//! same type can be reused as pallet or contract storage instead of a
+//! caller hand-rolling its own encoding. [`VotingPhase`], [`Quorum`],
+//! [`TieBreakPolicy`], and [`VoteError`] derive `Encode`/`Decode`/
+//! `TypeInfo` directly - every field they carry is already
+//! codec-compatible.
+//!
+//! [`VotingState`] itself can't: `registered_voters`/`votes_cast` are
+//! `HashSet`s, and neither `parity-scale-codec` nor `scale-info` has an
+//! impl for those (no encoding order of their own - the same reason the
+//! `scale-codec-exercise` crate sorts them into a `Vec` before encoding
+//! by hand). `VotingStateWire` is that same sorted-`Vec` representation,
+//! derived instead of hand-written, and [`VotingState`]'s own
+//! `Encode`/`Decode`/`TypeInfo` impls below just delegate to it.
+
+use std::collections::{BTreeMap, HashSet};
+
+use codec::{Decode, Encode, Input};
+use scale_info::TypeInfo;
+
+use crate::{AuditEntry, Quorum, TieBreakPolicy, UserId, VoteOption, VotingPhase, VotingState};
+
+/// The wire shape [`VotingState`] actually encodes as - `pub` only
+/// because [`TypeInfo::Identity`] has to name a type at least as visible
+/// as the impl it's on. Build one from `&VotingState` via `From` if you
+/// need to inspect the wire representation directly; everything else
+/// should just call `.encode()`/`::decode()` on `VotingState` itself.
+#[derive(Encode, Decode, TypeInfo)]
+pub struct VotingStateWire {
+    current_voting_state: VotingPhase,
+    registered_voters: Vec<UserId>,
+    votes_cast: Vec<UserId>,
+    vote_counts: BTreeMap<VoteOption, u64>,
+    votes_by_option: BTreeMap<VoteOption, Vec<UserId>>,
+    voting_start_time: u64,
+    voting_end_time: u64,
+    admin_id: UserId,
+    quorum: Option<Quorum>,
+    option_order: Vec<VoteOption>,
+    tie_break_policy: TieBreakPolicy,
+    invalid_vote_attempts: u64,
+    audit_log: Vec<AuditEntry>,
+    paused_at: Option<u64>,
+    max_extension: Option<u64>,
+    total_extension: u64,
+}
+
+impl From<&VotingState> for VotingStateWire {
+    fn from(state: &VotingState) -> Self {
+        let mut registered_voters: Vec<UserId> = state.registered_voters.iter().cloned().collect();
+        registered_voters.sort();
+        let mut votes_cast: Vec<UserId> = state.votes_cast.iter().cloned().collect();
+        votes_cast.sort();
+        Self {
+            current_voting_state: state.current_voting_state,
+            registered_voters,
+            votes_cast,
+            vote_counts: state.vote_counts.clone(),
+            votes_by_option: state.votes_by_option.clone(),
+            voting_start_time: state.voting_start_time,
+            voting_end_time: state.voting_end_time,
+            admin_id: state.admin_id.clone(),
+            quorum: state.quorum,
+            option_order: state.option_order.clone(),
+            tie_break_policy: state.tie_break_policy.clone(),
+            invalid_vote_attempts: state.invalid_vote_attempts,
+            audit_log: state.audit_log.clone(),
+            paused_at: state.paused_at,
+            max_extension: state.max_extension,
+            total_extension: state.total_extension,
+        }
+    }
+}
+
+impl From<VotingStateWire> for VotingState {
+    fn from(wire: VotingStateWire) -> Self {
+        Self {
+            current_voting_state: wire.current_voting_state,
+            registered_voters: HashSet::from_iter(wire.registered_voters),
+            votes_cast: HashSet::from_iter(wire.votes_cast),
+            vote_counts: wire.vote_counts,
+            votes_by_option: wire.votes_by_option,
+            voting_start_time: wire.voting_start_time,
+            voting_end_time: wire.voting_end_time,
+            admin_id: wire.admin_id,
+            quorum: wire.quorum,
+            option_order: wire.option_order,
+            tie_break_policy: wire.tie_break_policy,
+            invalid_vote_attempts: wire.invalid_vote_attempts,
+            audit_log: wire.audit_log,
+            paused_at: wire.paused_at,
+            max_extension: wire.max_extension,
+            total_extension: wire.total_extension,
+        }
+    }
+}
+
+impl Encode for VotingState {
+    fn size_hint(&self) -> usize {
+        VotingStateWire::from(self).size_hint()
+    }
+
+    fn encode_to<T: codec::Output + ?Sized>(&self, dest: &mut T) {
+        VotingStateWire::from(self).encode_to(dest)
+    }
+}
+
+impl Decode for VotingState {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        VotingStateWire::decode(input).map(Into::into)
+    }
+}
+
+impl TypeInfo for VotingState {
+    type Identity = VotingStateWire;
+
+    fn type_info() -> scale_info::Type {
+        VotingStateWire::type_info()
+    }
+}
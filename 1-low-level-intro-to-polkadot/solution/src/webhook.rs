@@ -0,0 +1,49 @@
+//! An HTTP [`Notifier`] implementation: posts a small JSON payload to a
+//! configured webhook URL for each lifecycle event - the shape Slack's
+//! and Matrix's "incoming webhook" endpoints expect a plain POST for.
+//! Behind the `webhook` feature so the default build - every test, the
+//! scenario runner - doesn't pull in an HTTP client it doesn't need.
+
+use serde::Serialize;
+
+use crate::{Notifier, VotingState};
+
+/// Posts to `webhook_url` on every lifecycle event. Failures are
+/// logged to stderr and otherwise swallowed - a missed Slack message
+/// shouldn't fail an election.
+pub struct WebhookNotifier {
+    webhook_url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self { webhook_url: webhook_url.into() }
+    }
+
+    fn post(&self, event: &str, state: &VotingState) {
+        let payload = Payload { event, phase: format!("{:?}", state.current_voting_state) };
+        if let Err(err) = ureq::post(&self.webhook_url).send_json(payload) {
+            eprintln!("webhook notification for {event} failed: {err}");
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: &'a str,
+    phase: String,
+}
+
+impl Notifier for WebhookNotifier {
+    fn registration_open(&self, state: &VotingState) {
+        self.post("registration_open", state);
+    }
+
+    fn voting_started(&self, state: &VotingState) {
+        self.post("voting_started", state);
+    }
+
+    fn finalized(&self, state: &VotingState) {
+        self.post("finalized", state);
+    }
+}
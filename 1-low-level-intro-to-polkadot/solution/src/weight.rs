@@ -0,0 +1,149 @@
+//! Optional per-operation weight accounting: measures how much storage
+//! each call against a [`VotingState`] touches - reads, writes, and
+//! bytes of input - and accumulates it, so a learner can see how a
+//! data-structure choice changes "weight" before Task 43 turns that
+//! into a priced gas model, or a real FRAME pallet turns it into
+//! `#[pallet::weight]`.
+//!
+//! Unlike the Task 43 `contract-gas-metering-simulation` solution, whose
+//! `GasMeteredVoting` owns a `VotingState` and charges calls against a
+//! caller's balance to enforce affordability, [`WeightMeter`] here is a
+//! pure observer: it wraps calls against a `VotingState` you still own
+//! and records what they cost without ever rejecting one. The read/write
+//! counts for each operation are the same ones Task 43's `CostModel`
+//! prices, since both are describing the same underlying storage access.
+
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+use crate::{Action, Timestamp, UserId, VoteError, VoteOption, VotingState};
+
+/// The storage footprint of one operation: how many storage slots it
+/// read, how many it wrote, and how many bytes of input it carried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Weight {
+    pub reads: u32,
+    pub writes: u32,
+    pub bytes: u32,
+}
+
+impl Weight {
+    fn new(reads: u32, writes: u32, bytes: u32) -> Self {
+        Self { reads, writes, bytes }
+    }
+}
+
+impl AddAssign for Weight {
+    fn add_assign(&mut self, other: Self) {
+        self.reads += other.reads;
+        self.writes += other.writes;
+        self.bytes += other.bytes;
+    }
+}
+
+/// One recorded call: which operation ran and what it cost, in the
+/// order [`WeightMeter`] observed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightedCall {
+    pub operation: &'static str,
+    pub weight: Weight,
+}
+
+/// Records every metered call made through it. Meant to live for one
+/// election - construct a fresh one alongside a fresh [`VotingState`]
+/// and [`Self::total`] is that election's weight so far.
+#[derive(Debug, Default)]
+pub struct WeightMeter {
+    calls: Vec<WeightedCall>,
+}
+
+impl WeightMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&mut self, operation: &'static str, weight: Weight) {
+        self.calls.push(WeightedCall { operation, weight });
+    }
+
+    /// Every call recorded so far, in the order it happened.
+    pub fn calls(&self) -> &[WeightedCall] {
+        &self.calls
+    }
+
+    /// The summed weight of every call recorded so far.
+    pub fn total(&self) -> Weight {
+        let mut total = Weight::default();
+        for call in &self.calls {
+            total += call.weight;
+        }
+        total
+    }
+
+    /// The summed weight of every call recorded so far, grouped by
+    /// operation - which part of the voting machine is doing the most
+    /// work.
+    pub fn by_operation(&self) -> BTreeMap<&'static str, Weight> {
+        let mut totals: BTreeMap<&'static str, Weight> = BTreeMap::new();
+        for call in &self.calls {
+            *totals.entry(call.operation).or_default() += call.weight;
+        }
+        totals
+    }
+
+    /// Meters a call to [`VotingState::register_voter`]. Weight is
+    /// recorded the same whether the call succeeds or is rejected - a
+    /// real chain charges weight regardless of dispatch outcome too.
+    pub fn register_voter(&mut self, state: &mut VotingState, caller_id: UserId) -> Result<(), VoteError> {
+        let bytes = caller_id.len() as u32;
+        let result = state.register_voter(caller_id);
+        self.record("register_voter", Weight::new(1, 1, bytes));
+        result
+    }
+
+    /// Meters a call to [`VotingState::cast_vote`].
+    pub fn cast_vote(
+        &mut self,
+        state: &mut VotingState,
+        caller_id: UserId,
+        vote_option: VoteOption,
+        now: Timestamp,
+    ) -> Result<(), VoteError> {
+        let bytes = (caller_id.len() + vote_option.len()) as u32;
+        let result = state.cast_vote(caller_id, vote_option, now);
+        self.record("cast_vote", Weight::new(3, 2, bytes));
+        result
+    }
+
+    /// Meters a call to [`VotingState::add_vote_option`].
+    pub fn add_vote_option(&mut self, state: &mut VotingState, option: VoteOption) {
+        let bytes = option.len() as u32;
+        state.add_vote_option(option);
+        self.record("add_vote_option", Weight::new(1, 1, bytes));
+    }
+
+    /// Meters a call to [`crate::transition`], priced the same way
+    /// Task 43's `GasMeteredVoting::transition` prices it.
+    pub fn transition(
+        &mut self,
+        state: &mut VotingState,
+        caller_id: UserId,
+        action: Action,
+        now: Timestamp,
+    ) -> Result<(), VoteError> {
+        let (reads, writes, bytes) = match &action {
+            Action::StartRegistration | Action::ResetApplication => (1, 3, 0),
+            Action::StartVoting { .. } => (1, 2, 16),
+            Action::EndVoting { .. } => (2, 1, 0),
+        };
+        let operation = match &action {
+            Action::StartRegistration => "start_registration",
+            Action::StartVoting { .. } => "start_voting",
+            Action::EndVoting { .. } => "end_voting",
+            Action::ResetApplication => "reset_application",
+        };
+        let result = crate::transition(state, caller_id, action, now);
+        self.record(operation, Weight::new(reads, writes, bytes));
+        result
+    }
+}
@@ -0,0 +1,125 @@
+//! Integration tests for the `vote-delegation` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::delegation::{cast_delegated_vote, DelegationError, DelegationGraph};
+use voting_state_machine::{Action, VoteError, VotingState};
+
+fn alice() -> String {
+    "alice".to_string()
+}
+fn bob() -> String {
+    "bob".to_string()
+}
+fn carol() -> String {
+    "carol".to_string()
+}
+fn admin() -> String {
+    "admin".to_string()
+}
+
+fn active_state() -> VotingState {
+    let mut state = VotingState::new(admin());
+    state.add_vote_option("A".to_string());
+    for voter in [alice(), bob(), carol()] {
+        state.register_voter(voter).unwrap();
+    }
+    voting_state_machine::transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    state
+}
+
+#[test]
+fn delegate_vote_rejects_delegating_to_yourself() {
+    let mut graph = DelegationGraph::new();
+    assert_eq!(graph.delegate_vote(alice(), alice()), Err(DelegationError::SelfDelegation));
+}
+
+#[test]
+fn delegate_vote_rejects_a_cycle() {
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+    graph.delegate_vote(bob(), carol()).unwrap();
+    assert_eq!(graph.delegate_vote(carol(), alice()), Err(DelegationError::Cycle));
+}
+
+#[test]
+fn final_delegate_follows_a_chain_to_the_end() {
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+    graph.delegate_vote(bob(), carol()).unwrap();
+    assert_eq!(graph.final_delegate(&alice()), &carol());
+}
+
+#[test]
+fn revoke_undoes_a_delegation() {
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+    graph.revoke(&alice()).unwrap();
+    assert_eq!(graph.delegate_of(&alice()), None);
+}
+
+#[test]
+fn revoke_rejects_a_voter_with_no_delegation() {
+    let mut graph = DelegationGraph::new();
+    assert_eq!(graph.revoke(&alice()), Err(DelegationError::NoSuchDelegation));
+}
+
+#[test]
+fn revoke_rejects_a_delegation_already_voted_with() {
+    let mut state = active_state();
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+
+    cast_delegated_vote(&mut graph, &mut state, bob(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(graph.revoke(&alice()), Err(DelegationError::AlreadyVoted));
+}
+
+#[test]
+fn cast_delegated_vote_counts_the_delegates_own_ballot_with_no_delegators() {
+    let mut state = active_state();
+    let mut graph = DelegationGraph::new();
+
+    let weight = cast_delegated_vote(&mut graph, &mut state, alice(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(weight, 1);
+    assert_eq!(state.vote_counts[&"A".to_string()], 1);
+}
+
+#[test]
+fn cast_delegated_vote_folds_in_a_direct_delegation() {
+    let mut state = active_state();
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+
+    let weight = cast_delegated_vote(&mut graph, &mut state, bob(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(weight, 2);
+    assert_eq!(state.vote_counts[&"A".to_string()], 2);
+    assert!(state.votes_cast.contains(&alice()));
+}
+
+#[test]
+fn cast_delegated_vote_folds_in_a_transitive_chain() {
+    let mut state = active_state();
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+    graph.delegate_vote(bob(), carol()).unwrap();
+
+    let weight = cast_delegated_vote(&mut graph, &mut state, carol(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(weight, 3);
+    assert_eq!(state.vote_counts[&"A".to_string()], 3);
+}
+
+#[test]
+fn delegator_cannot_cast_their_own_vote_after_their_delegate_voted() {
+    let mut state = active_state();
+    let mut graph = DelegationGraph::new();
+    graph.delegate_vote(alice(), bob()).unwrap();
+
+    cast_delegated_vote(&mut graph, &mut state, bob(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(state.cast_vote(alice(), "A".to_string(), 1), Err(VoteError::AlreadyVoted));
+}
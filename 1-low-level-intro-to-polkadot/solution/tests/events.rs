@@ -0,0 +1,116 @@
+//! Integration tests for the `event-sourcing` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::events::{apply, replay, Command, Event};
+use voting_state_machine::{Action, VoteError, VotingState};
+
+#[test]
+fn apply_register_voter_returns_the_matching_event() {
+    let mut state = VotingState::new("admin".to_string());
+
+    let events = apply(&mut state, Command::RegisterVoter { caller_id: "alice".to_string() }).unwrap();
+
+    assert_eq!(events, vec![Event::VoterRegistered { caller_id: "alice".to_string() }]);
+    assert!(state.registered_voters.contains("alice"));
+}
+
+#[test]
+fn apply_propagates_the_underlying_methods_error_and_emits_no_event() {
+    let mut state = VotingState::new("admin".to_string());
+    apply(&mut state, Command::RegisterVoter { caller_id: "alice".to_string() }).unwrap();
+
+    let result = apply(&mut state, Command::RegisterVoter { caller_id: "alice".to_string() });
+
+    assert_eq!(result, Err(VoteError::AlreadyRegistered));
+}
+
+#[test]
+fn replay_rebuilds_the_same_state_an_equivalent_command_sequence_produced() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut events = Vec::new();
+
+    events.extend(apply(&mut state, Command::AddOption { caller_id: "admin".to_string(), option: "A".to_string() }).unwrap());
+    events.extend(apply(&mut state, Command::RegisterVoter { caller_id: "alice".to_string() }).unwrap());
+    events.extend(
+        apply(
+            &mut state,
+            Command::Transition { caller_id: "admin".to_string(), action: Action::StartVoting { duration: 60 }, now: 0 },
+        )
+        .unwrap(),
+    );
+    events.extend(apply(&mut state, Command::CastVote { caller_id: "alice".to_string(), vote_option: "A".to_string(), now: 1 }).unwrap());
+
+    let replayed = replay("admin".to_string(), &events).unwrap();
+
+    assert_eq!(replayed.current_voting_state, state.current_voting_state);
+    assert_eq!(replayed.registered_voters, state.registered_voters);
+    assert_eq!(replayed.vote_counts, state.vote_counts);
+}
+
+#[test]
+fn replay_of_an_empty_event_stream_is_a_fresh_state() {
+    let replayed = replay("admin".to_string(), &[]).unwrap();
+
+    assert_eq!(replayed, VotingState::new("admin".to_string()));
+}
+
+#[test]
+fn replay_never_sees_a_rejected_commands_effects() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut events = apply(&mut state, Command::RegisterVoter { caller_id: "alice".to_string() }).unwrap();
+    // Rejected - no event, so `invalid_vote_attempts` never shows up on replay.
+    assert!(apply(&mut state, Command::CastVote { caller_id: "alice".to_string(), vote_option: "A".to_string(), now: 0 }).is_err());
+    events.extend(apply(&mut state, Command::AddOption { caller_id: "admin".to_string(), option: "A".to_string() }).unwrap());
+
+    let replayed = replay("admin".to_string(), &events).unwrap();
+
+    assert_eq!(replayed.invalid_vote_attempts, 0);
+    assert_eq!(state.invalid_vote_attempts, 1);
+}
+
+#[test]
+fn replay_reconstructs_a_pause_and_resume() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut events = apply(&mut state, Command::AddOption { caller_id: "admin".to_string(), option: "A".to_string() }).unwrap();
+    events.extend(
+        apply(
+            &mut state,
+            Command::Transition { caller_id: "admin".to_string(), action: Action::StartVoting { duration: 60 }, now: 0 },
+        )
+        .unwrap(),
+    );
+    events.extend(apply(&mut state, Command::PauseVoting { caller_id: "admin".to_string(), now: 3 }).unwrap());
+    events.extend(apply(&mut state, Command::ResumeVoting { caller_id: "admin".to_string(), now: 7 }).unwrap());
+
+    let replayed = replay("admin".to_string(), &events).unwrap();
+
+    assert_eq!(replayed.current_voting_state, state.current_voting_state);
+    assert_eq!(replayed.voting_end_time, state.voting_end_time);
+}
+
+#[test]
+fn replay_reconstructs_a_voting_extension() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut events = apply(&mut state, Command::AddOption { caller_id: "admin".to_string(), option: "A".to_string() }).unwrap();
+    events.extend(apply(&mut state, Command::SetMaxExtension { caller_id: "admin".to_string(), max_extension: 10 }).unwrap());
+    events.extend(
+        apply(
+            &mut state,
+            Command::Transition { caller_id: "admin".to_string(), action: Action::StartVoting { duration: 60 }, now: 0 },
+        )
+        .unwrap(),
+    );
+    events.extend(apply(&mut state, Command::ExtendVoting { caller_id: "admin".to_string(), extra_secs: 5, now: 1 }).unwrap());
+
+    let mut replayed = replay("admin".to_string(), &events).unwrap();
+
+    assert_eq!(replayed.voting_end_time, state.voting_end_time);
+    assert_eq!(replayed.total_extension, state.total_extension);
+    // The rebuilt state enforces the same max_extension going forward.
+    assert_eq!(
+        apply(&mut replayed, Command::ExtendVoting { caller_id: "admin".to_string(), extra_secs: 10, now: 2 }),
+        Err(VoteError::ExtensionLimitExceeded)
+    );
+}
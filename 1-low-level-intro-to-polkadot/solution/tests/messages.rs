@@ -0,0 +1,20 @@
+//! Integration tests for the `messages` module. Coverage of every
+//! `(VoteError, Locale)` pair is already enforced at compile time by
+//! `messages::message`'s exhaustive match; these just check the two
+//! properties that match alone doesn't guarantee.
+
+use voting_state_machine::messages::{message, Locale};
+use voting_state_machine::VoteError;
+
+#[test]
+fn key_is_stable_and_locale_independent() {
+    assert_eq!(VoteError::NotRegistered.key(), "not_registered");
+    assert_eq!(VoteError::NotRegistered.key(), VoteError::NotRegistered.key());
+}
+
+#[test]
+fn message_differs_by_locale() {
+    let en = message(&VoteError::AlreadyVoted, Locale::En);
+    let sw = message(&VoteError::AlreadyVoted, Locale::Sw);
+    assert_ne!(en, sw);
+}
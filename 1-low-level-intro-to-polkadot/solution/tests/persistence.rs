@@ -0,0 +1,46 @@
+//! Integration tests for the `serde` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! type that isn't derived yet.
+
+use voting_state_machine::{transition, Action, VotingState};
+
+use test_utils::{admin, alice, VotingStateBuilder};
+
+#[test]
+fn a_snapshot_round_trips_back_to_an_equal_state() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.cast_vote(alice(), "A".to_string(), 0).unwrap();
+
+    let snapshot = state.save_snapshot();
+    let restored = VotingState::from_snapshot(&snapshot).unwrap();
+
+    assert_eq!(state, restored);
+}
+
+#[test]
+fn a_restored_state_keeps_working_as_the_original_would_have() {
+    let state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    let mut restored = VotingState::from_snapshot(&state.save_snapshot()).unwrap();
+
+    transition(&mut restored, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+    assert_eq!(restored.get_results(None).unwrap().invalid_vote_attempts, 0);
+}
+
+#[test]
+fn from_snapshot_rejects_a_string_that_isnt_a_snapshot() {
+    let error = VotingState::from_snapshot("not a snapshot").unwrap_err();
+    assert!(matches!(error, voting_state_machine::persistence::SnapshotError::Malformed(_)));
+}
+
+#[test]
+fn from_snapshot_rejects_a_mismatched_schema_version() {
+    let state = VotingStateBuilder::new().build();
+    let snapshot = state.save_snapshot().replacen("schema_version = 1", "schema_version = 999", 1);
+
+    let error = VotingState::from_snapshot(&snapshot).unwrap_err();
+    assert!(matches!(
+        error,
+        voting_state_machine::persistence::SnapshotError::UnsupportedSchemaVersion { found: 999, expected: 1 }
+    ));
+}
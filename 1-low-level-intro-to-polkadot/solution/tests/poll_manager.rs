@@ -0,0 +1,77 @@
+//! Integration tests for the `multi-poll` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::poll_manager::PollManager;
+use voting_state_machine::{Action, VoteError};
+
+fn admin_a() -> String {
+    "admin-a".to_string()
+}
+fn admin_b() -> String {
+    "admin-b".to_string()
+}
+
+#[test]
+fn create_poll_rejects_a_poll_id_already_in_use() {
+    let mut manager = PollManager::new();
+    manager.create_poll("poll-1".to_string(), admin_a()).unwrap();
+    assert_eq!(manager.create_poll("poll-1".to_string(), admin_b()), Err(VoteError::PollAlreadyExists));
+}
+
+#[test]
+fn lifecycle_methods_reject_an_unknown_poll_id() {
+    let mut manager = PollManager::new();
+    assert_eq!(manager.register_voter(&"no-such-poll".to_string(), "alice".to_string()), Err(VoteError::PollNotFound));
+    assert_eq!(manager.poll(&"no-such-poll".to_string()).err(), Some(VoteError::PollNotFound));
+}
+
+#[test]
+fn each_poll_has_its_own_admin_and_state() {
+    let mut manager = PollManager::new();
+    manager.create_poll("poll-a".to_string(), admin_a()).unwrap();
+    manager.create_poll("poll-b".to_string(), admin_b()).unwrap();
+
+    manager.register_voter(&"poll-a".to_string(), "alice".to_string()).unwrap();
+    assert_eq!(manager.register_voter(&"poll-b".to_string(), "alice".to_string()), Ok(()));
+
+    assert_eq!(manager.poll(&"poll-a".to_string()).unwrap().admin_id, admin_a());
+    assert_eq!(manager.poll(&"poll-b".to_string()).unwrap().admin_id, admin_b());
+
+    // admin-a is only admin of poll-a, so it can't drive poll-b's transitions.
+    assert_eq!(
+        manager.transition(&"poll-b".to_string(), admin_a(), Action::StartVoting { duration: 10 }, 0),
+        Err(VoteError::NotAdmin)
+    );
+}
+
+#[test]
+fn open_active_and_finalized_polls_are_listed_separately() {
+    let mut manager = PollManager::new();
+    manager.create_poll("still-open".to_string(), admin_a()).unwrap();
+    manager.create_poll("now-active".to_string(), admin_a()).unwrap();
+    manager.create_poll("now-finalized".to_string(), admin_a()).unwrap();
+
+    manager.transition(&"now-active".to_string(), admin_a(), Action::StartVoting { duration: 10 }, 0).unwrap();
+
+    manager.transition(&"now-finalized".to_string(), admin_a(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    manager.transition(&"now-finalized".to_string(), admin_a(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    assert_eq!(manager.open_polls(), vec![&"still-open".to_string()]);
+    assert_eq!(manager.active_polls(), vec![&"now-active".to_string()]);
+    assert_eq!(manager.finalized_polls(), vec![&"now-finalized".to_string()]);
+}
+
+#[test]
+fn cast_vote_is_forwarded_to_the_right_poll() {
+    let mut manager = PollManager::new();
+    manager.create_poll("poll-1".to_string(), admin_a()).unwrap();
+    manager.add_vote_option(&"poll-1".to_string(), "A".to_string()).unwrap();
+    manager.register_voter(&"poll-1".to_string(), "alice".to_string()).unwrap();
+    manager.transition(&"poll-1".to_string(), admin_a(), Action::StartVoting { duration: 10 }, 0).unwrap();
+
+    manager.cast_vote(&"poll-1".to_string(), "alice".to_string(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(manager.poll(&"poll-1".to_string()).unwrap().vote_counts[&"A".to_string()], 1);
+}
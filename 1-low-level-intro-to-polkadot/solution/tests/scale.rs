@@ -0,0 +1,26 @@
+//! Integration tests for the `scale` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! type that hasn't derived `Encode`/`Decode` yet.
+
+use codec::{Decode, Encode};
+
+use test_utils::{alice, VotingStateBuilder};
+use voting_state_machine::VoteError;
+
+#[test]
+fn a_voting_state_round_trips_through_scale_encoding() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.cast_vote(alice(), "A".to_string(), 0).unwrap();
+
+    let encoded = state.encode();
+    let decoded = voting_state_machine::VotingState::decode(&mut &encoded[..]).unwrap();
+
+    assert_eq!(state, decoded);
+}
+
+#[test]
+fn a_vote_error_round_trips_through_scale_encoding() {
+    let encoded = VoteError::QuorumNotMet.encode();
+    assert_eq!(VoteError::decode(&mut &encoded[..]).unwrap(), VoteError::QuorumNotMet);
+}
@@ -0,0 +1,22 @@
+//! A golden-file companion to `tests/voting_state_machine.rs`'s
+//! individual field assertions: one canonical scenario, rendered as a
+//! whole report and diffed against a committed snapshot, so a change to
+//! the report format or the scenario's outcome shows up as an obvious
+//! diff instead of several separately-updated `assert_eq!` calls.
+
+use test_utils::{alice, bob, render_report, VotingStateBuilder};
+
+#[test]
+fn two_voter_scenario_matches_snapshot() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("yes")
+        .with_candidate("no")
+        .with_voter(alice())
+        .with_voter(bob())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "yes".to_string(), 1).unwrap();
+    state.cast_vote(bob(), "no".to_string(), 2).unwrap();
+
+    snapshot::assert_snapshot!("two_voter_scenario", render_report(&state));
+}
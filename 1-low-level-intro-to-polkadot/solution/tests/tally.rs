@@ -0,0 +1,216 @@
+//! Integration tests for the `ranked-tally` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::tally::{
+    borda_count, condorcet_winner, instant_runoff, tally, tally_at_end_of_voting, CondorcetResult,
+    InstantRunoffOutcome, RankedBallot, RankedBallotBox, TallyMethod, TallyOutcome,
+};
+use voting_state_machine::{transition, Action, VoteError};
+
+use test_utils::{admin, alice, bob, VotingStateBuilder};
+
+fn ballot(voter: &str, ranking: &[&str]) -> RankedBallot {
+    RankedBallot { voter: voter.to_string(), ranking: ranking.iter().map(|s| s.to_string()).collect() }
+}
+
+fn candidates(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn condorcet_finds_a_pairwise_winner_even_when_it_isnt_the_most_first_choices() {
+    let candidates = candidates(&["A", "B", "C"]);
+    let ballots = vec![
+        ballot("alice", &["A", "C", "B"]),
+        ballot("bob", &["A", "C", "B"]),
+        ballot("carol", &["B", "C", "A"]),
+        ballot("dave", &["B", "C", "A"]),
+        ballot("erin", &["B", "C", "A"]),
+        ballot("frank", &["C", "B", "A"]),
+        ballot("grace", &["C", "B", "A"]),
+    ];
+
+    // B has the most first-choice votes (3, vs 2 each for A and C), but C
+    // beats both A and B head-to-head.
+    assert_eq!(condorcet_winner(&candidates, &ballots), CondorcetResult::Winner("C".to_string()));
+}
+
+#[test]
+fn condorcet_reports_a_cycle_when_no_candidate_beats_every_other() {
+    let candidates = candidates(&["A", "B", "C"]);
+    let ballots =
+        vec![ballot("alice", &["A", "B", "C"]), ballot("bob", &["B", "C", "A"]), ballot("carol", &["C", "A", "B"])];
+
+    assert_eq!(condorcet_winner(&candidates, &ballots), CondorcetResult::Cycle(candidates));
+}
+
+#[test]
+fn borda_count_scores_first_place_highest_and_last_place_zero() {
+    let candidates = candidates(&["A", "B", "C"]);
+    let ballots = vec![ballot("alice", &["A", "B", "C"]), ballot("bob", &["B", "A", "C"])];
+
+    // alice: A=2 B=1 C=0. bob: B=2 A=1 C=0. Totals: A=3 B=3 C=0.
+    assert_eq!(
+        borda_count(&candidates, &ballots),
+        vec![("A".to_string(), 3), ("B".to_string(), 3), ("C".to_string(), 0)]
+    );
+}
+
+#[test]
+fn borda_and_condorcet_can_disagree_on_the_same_ballots() {
+    let candidates = candidates(&["A", "B", "C"]);
+    let ballots = vec![
+        ballot("alice", &["B", "C", "A"]),
+        ballot("bob", &["B", "C", "A"]),
+        ballot("carol", &["B", "C", "A"]),
+        ballot("dave", &["C", "A", "B"]),
+        ballot("erin", &["C", "A", "B"]),
+    ];
+
+    assert_eq!(
+        tally(TallyMethod::Condorcet, &candidates, &ballots),
+        TallyOutcome::Condorcet(CondorcetResult::Winner("B".to_string()))
+    );
+
+    // C edges out B on Borda points even though B is the Condorcet winner.
+    let TallyOutcome::Borda(scores) = tally(TallyMethod::Borda, &candidates, &ballots) else {
+        panic!("expected a Borda outcome");
+    };
+    assert_eq!(scores[0].0, "C");
+}
+
+#[test]
+fn a_ballot_missing_a_candidate_doesnt_count_toward_either_pairwise_comparison() {
+    let candidates = candidates(&["A", "B"]);
+    let ballots = vec![ballot("alice", &["A"])];
+
+    assert_eq!(condorcet_winner(&candidates, &ballots), CondorcetResult::Cycle(candidates));
+}
+
+#[test]
+fn instant_runoff_declares_an_immediate_majority_winner_in_one_round() {
+    let candidates = candidates(&["A", "B"]);
+    let ballots = vec![ballot("alice", &["A", "B"]), ballot("bob", &["A", "B"]), ballot("carol", &["B", "A"])];
+
+    let InstantRunoffOutcome::Winner { winner, rounds } = instant_runoff(&candidates, &ballots) else {
+        panic!("expected a winner");
+    };
+    assert_eq!(winner, "A");
+    assert_eq!(rounds.len(), 1);
+}
+
+#[test]
+fn instant_runoff_eliminates_the_weakest_candidate_each_round_until_a_majority_emerges() {
+    let candidates = candidates(&["A", "B", "C"]);
+    let ballots = vec![
+        ballot("alice", &["A", "B", "C"]),
+        ballot("bob", &["A", "B", "C"]),
+        ballot("carol", &["B", "C", "A"]),
+        ballot("dave", &["B", "C", "A"]),
+        ballot("erin", &["C", "B", "A"]),
+    ];
+
+    // Round 1: A=2, B=2, C=1 - no majority, C is eliminated.
+    // Round 2: C's ballot falls through to B, giving A=2, B=3 - B wins.
+    let InstantRunoffOutcome::Winner { winner, rounds } = instant_runoff(&candidates, &ballots) else {
+        panic!("expected a winner");
+    };
+    assert_eq!(winner, "B");
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].eliminated, Some("C".to_string()));
+    assert_eq!(rounds[1].eliminated, None);
+    assert_eq!(rounds[1].counts[&"B".to_string()], 3);
+}
+
+#[test]
+fn instant_runoff_ties_for_last_are_broken_by_candidate_order() {
+    let candidates = candidates(&["A", "B", "C"]);
+    // Round 1: A=1, B=2, C=1 - A and C are tied for fewest first
+    // choices; A comes first in `candidates`, so A is the one
+    // eliminated, and alice's ballot falls through to her next
+    // preference, B.
+    let ballots = vec![
+        ballot("alice", &["A", "B", "C"]),
+        ballot("bob", &["B", "C", "A"]),
+        ballot("carol", &["B", "A", "C"]),
+        ballot("dave", &["C", "A", "B"]),
+    ];
+
+    let InstantRunoffOutcome::Winner { winner, rounds } = instant_runoff(&candidates, &ballots) else {
+        panic!("expected a winner");
+    };
+    assert_eq!(winner, "B");
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].eliminated, Some("A".to_string()));
+}
+
+#[test]
+fn instant_runoff_reports_no_majority_with_zero_ballots() {
+    let candidates = candidates(&["A", "B"]);
+    let InstantRunoffOutcome::NoMajority { rounds } = instant_runoff(&candidates, &[]) else {
+        panic!("expected no majority with no ballots cast");
+    };
+    // With zero ballots cast every round ties at zero, so candidates keep
+    // getting eliminated in order until only one is left.
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].eliminated, Some("A".to_string()));
+    assert_eq!(rounds[1].eliminated, None);
+}
+
+#[test]
+fn cast_ranked_vote_rejects_an_unregistered_caller() {
+    let state = VotingStateBuilder::new().voting_started(10, 0).build();
+    let mut ballots = RankedBallotBox::new();
+    assert_eq!(
+        ballots.cast_ranked_vote(&state, "mallory".to_string(), vec!["A".to_string()]),
+        Err(VoteError::NotRegistered)
+    );
+}
+
+#[test]
+fn cast_ranked_vote_rejects_a_second_ballot_from_the_same_voter() {
+    let state = VotingStateBuilder::new().with_voter(alice()).voting_started(10, 0).build();
+    let mut ballots = RankedBallotBox::new();
+    ballots.cast_ranked_vote(&state, alice(), vec!["A".to_string()]).unwrap();
+    assert_eq!(
+        ballots.cast_ranked_vote(&state, alice(), vec!["B".to_string()]),
+        Err(VoteError::AlreadyVoted)
+    );
+}
+
+#[test]
+fn cast_ranked_vote_rejects_a_caller_before_voting_is_active() {
+    let state = VotingStateBuilder::new().with_voter(alice()).build();
+    let mut ballots = RankedBallotBox::new();
+    assert_eq!(
+        ballots.cast_ranked_vote(&state, alice(), vec!["A".to_string()]),
+        Err(VoteError::VotingNotActive)
+    );
+}
+
+#[test]
+fn tally_at_end_of_voting_rejects_a_read_before_results_are_finalized() {
+    let state = VotingStateBuilder::new().voting_started(10, 0).build();
+    let ballots = RankedBallotBox::new();
+    assert_eq!(
+        tally_at_end_of_voting(&state, &candidates(&["A", "B"]), &ballots),
+        Err(VoteError::VotingNotActive)
+    );
+}
+
+#[test]
+fn tally_at_end_of_voting_runs_instant_runoff_over_cast_ranked_ballots() {
+    let mut state = VotingStateBuilder::new().with_voter(alice()).with_voter(bob()).voting_started(10, 0).build();
+    let mut ballots = RankedBallotBox::new();
+    ballots.cast_ranked_vote(&state, alice(), vec!["A".to_string(), "B".to_string()]).unwrap();
+    ballots.cast_ranked_vote(&state, bob(), vec!["A".to_string(), "B".to_string()]).unwrap();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    let outcome = tally_at_end_of_voting(&state, &candidates(&["A", "B"]), &ballots).unwrap();
+    let InstantRunoffOutcome::Winner { winner, .. } = outcome else {
+        panic!("expected a winner");
+    };
+    assert_eq!(winner, "A");
+}
@@ -0,0 +1,59 @@
+//! Integration tests for the `tally-proof` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::tally_proof::{commit, prove_tally, verify_tally, Ballot, TallyProofError};
+
+fn ballot(caller_id: &str, vote_option: &str, salt: u8) -> Ballot {
+    Ballot { caller_id: caller_id.to_string(), vote_option: vote_option.to_string(), salt: [salt; 32] }
+}
+
+#[test]
+fn prove_tally_rejects_an_empty_ballot_set() {
+    assert_eq!(prove_tally(&[]), Err(TallyProofError::NoBallots));
+}
+
+#[test]
+fn prove_tally_counts_each_option() {
+    let ballots = vec![ballot("alice", "A", 1), ballot("bob", "A", 2), ballot("carol", "B", 3)];
+    let proof = prove_tally(&ballots).unwrap();
+    assert_eq!(proof.tally[&"A".to_string()], 2);
+    assert_eq!(proof.tally[&"B".to_string()], 1);
+}
+
+#[test]
+fn verify_tally_accepts_a_proof_against_the_same_commitments() {
+    let ballots = vec![ballot("alice", "A", 1), ballot("bob", "A", 2), ballot("carol", "B", 3)];
+    let proof = prove_tally(&ballots).unwrap();
+    let commitments: Vec<_> = ballots.iter().map(commit).collect();
+    assert!(verify_tally(&proof, &commitments));
+}
+
+#[test]
+fn verify_tally_rejects_a_commitment_set_that_does_not_match_the_root() {
+    let ballots = vec![ballot("alice", "A", 1), ballot("bob", "A", 2)];
+    let proof = prove_tally(&ballots).unwrap();
+
+    let tampered = vec![commit(&ballot("alice", "A", 1)), commit(&ballot("bob", "B", 2))];
+    assert!(!verify_tally(&proof, &tampered));
+}
+
+#[test]
+fn verify_tally_rejects_a_tally_that_does_not_account_for_every_commitment() {
+    let ballots = vec![ballot("alice", "A", 1), ballot("bob", "A", 2)];
+    let mut proof = prove_tally(&ballots).unwrap();
+    proof.tally.insert("A".to_string(), 1);
+
+    let commitments: Vec<_> = ballots.iter().map(commit).collect();
+    assert!(!verify_tally(&proof, &commitments));
+}
+
+#[test]
+fn commit_is_deterministic_and_sensitive_to_every_field() {
+    let a = ballot("alice", "A", 1);
+    assert_eq!(commit(&a), commit(&a));
+    assert_ne!(commit(&a), commit(&ballot("alice", "A", 2)));
+    assert_ne!(commit(&a), commit(&ballot("alice", "B", 1)));
+    assert_ne!(commit(&a), commit(&ballot("bob", "A", 1)));
+}
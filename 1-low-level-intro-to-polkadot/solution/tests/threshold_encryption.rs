@@ -0,0 +1,91 @@
+//! Integration tests for the `threshold-encryption` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::threshold_encryption::{
+    combine_ciphertexts, combine_partial_decryptions, encrypt, generate_shares, partial_decrypt,
+    public_key_from_secret, DecryptionError,
+};
+
+const MAX_TALLY: u64 = 50;
+
+#[test]
+fn a_single_ballot_round_trips_through_encryption_and_threshold_decryption() {
+    let secret = 77;
+    let pk = public_key_from_secret(secret);
+    let shares = generate_shares(secret, &[19, 5], &[1, 2, 3, 4, 5]);
+
+    let ciphertext = encrypt(&pk, 1, 41);
+
+    let partials: Vec<_> = shares.iter().take(3).map(|share| partial_decrypt(share, &ciphertext)).collect();
+    let tally = combine_partial_decryptions(3, &partials, &ciphertext, MAX_TALLY).unwrap();
+    assert_eq!(tally, 1);
+}
+
+#[test]
+fn many_ballots_tally_homomorphically_before_any_decryption() {
+    let secret = 101;
+    let pk = public_key_from_secret(secret);
+    let shares = generate_shares(secret, &[60, 12, 200], &[1, 2, 3, 4, 5]);
+
+    let ballots = [
+        encrypt(&pk, 1, 7),
+        encrypt(&pk, 0, 88),
+        encrypt(&pk, 1, 150),
+        encrypt(&pk, 1, 3),
+        encrypt(&pk, 0, 222),
+    ];
+    let combined = combine_ciphertexts(&ballots);
+
+    let partials: Vec<_> = shares.iter().take(4).map(|share| partial_decrypt(share, &combined)).collect();
+    let tally = combine_partial_decryptions(4, &partials, &combined, MAX_TALLY).unwrap();
+    assert_eq!(tally, 3);
+}
+
+#[test]
+fn any_qualifying_subset_of_trustees_recovers_the_same_tally() {
+    let secret = 9;
+    let pk = public_key_from_secret(secret);
+    let shares = generate_shares(secret, &[150, 77], &[1, 2, 3, 4, 5]);
+    let ciphertext = combine_ciphertexts(&[encrypt(&pk, 1, 10), encrypt(&pk, 1, 20), encrypt(&pk, 1, 30)]);
+
+    let all_partials: Vec<_> = shares.iter().map(|share| partial_decrypt(share, &ciphertext)).collect();
+
+    let first_three = &all_partials[0..3];
+    let last_three = &all_partials[2..5];
+    assert_eq!(
+        combine_partial_decryptions(3, first_three, &ciphertext, MAX_TALLY).unwrap(),
+        combine_partial_decryptions(3, last_three, &ciphertext, MAX_TALLY).unwrap(),
+    );
+}
+
+#[test]
+fn fewer_than_the_threshold_partial_decryptions_are_rejected() {
+    let secret = 42;
+    let pk = public_key_from_secret(secret);
+    let shares = generate_shares(secret, &[15, 30], &[1, 2, 3, 4, 5]);
+    let ciphertext = encrypt(&pk, 2, 5);
+
+    let partials: Vec<_> = shares.iter().take(2).map(|share| partial_decrypt(share, &ciphertext)).collect();
+
+    assert_eq!(
+        combine_partial_decryptions(3, &partials, &ciphertext, MAX_TALLY),
+        Err(DecryptionError::NotEnoughShares { required: 3, provided: 2 })
+    );
+}
+
+#[test]
+fn a_tally_outside_the_searched_range_is_reported_as_unresolved() {
+    let secret = 8;
+    let pk = public_key_from_secret(secret);
+    let shares = generate_shares(secret, &[4], &[1, 2]);
+    let ciphertext = encrypt(&pk, 99, 3);
+
+    let partials: Vec<_> = shares.iter().map(|share| partial_decrypt(share, &ciphertext)).collect();
+
+    assert_eq!(
+        combine_partial_decryptions(2, &partials, &ciphertext, 10),
+        Err(DecryptionError::NoMatchingTally { searched_up_to: 10 })
+    );
+}
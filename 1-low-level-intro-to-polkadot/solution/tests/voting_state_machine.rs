@@ -0,0 +1,775 @@
+//! Integration tests for the Task 1 voting state machine, migrated here
+//! (instead of a `#[cfg(test)] mod tests` in `src/lib.rs`) so they can
+//! depend on the `test-utils` fixtures crate: a unit-test module would
+//! link two separate copies of this very crate (once as the lib under
+//! test, once indirectly through `test-utils`), so their types wouldn't
+//! unify. An integration test only ever links the one, already-built
+//! library crate, which `test-utils` depends on too.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use voting_state_machine::{
+    personhood_attestation_message, signed_vote_message, transition, transition_notified, Action, AlwaysPerson,
+    AttestedPersonhood, Clock, ElectionResults, FixedClock, Notifier, Quorum, ResultCount, SignatureVerifier,
+    SystemClock, TieBreakPolicy, UserId, UserIdValidator, VoteError, VotingPhase,
+    VotingStateBuilder as ValidatingBuilder,
+};
+
+use test_utils::{admin, alice, assert_phase, assert_vote_count, bob, VotingStateBuilder};
+
+#[test]
+fn register_voter_closes_once_voting_starts() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.register_voter(alice()), Err(VoteError::InvalidTransition));
+}
+
+#[test]
+fn start_voting_rejects_zero_duration() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        transition(&mut state, admin(), Action::StartVoting { duration: 0 }, 0),
+        Err(VoteError::InvalidDuration)
+    );
+}
+
+#[test]
+fn end_voting_requires_window_to_elapse() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(
+        transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 5),
+        Err(VoteError::VotingWindowNotElapsed)
+    );
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+    assert_phase(&state, VotingPhase::ResultsFinalized);
+}
+
+#[test]
+fn only_admin_can_drive_transitions() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        transition(&mut state, "mallory".to_string(), Action::StartVoting { duration: 10 }, 0),
+        Err(VoteError::NotAdmin)
+    );
+}
+
+#[test]
+fn reset_application_works_from_any_phase() {
+    let mut state = VotingStateBuilder::new().with_voter(alice()).build();
+    transition(&mut state, admin(), Action::ResetApplication, 0).unwrap();
+    assert_phase(&state, VotingPhase::RegistrationOpen);
+    assert!(state.registered_voters.is_empty());
+}
+
+#[test]
+fn reset_application_clears_option_order_so_re_adding_options_does_not_duplicate_them() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).build();
+    transition(&mut state, admin(), Action::ResetApplication, 0).unwrap();
+    state.add_option(&admin(), "A".to_string()).unwrap();
+
+    assert_eq!(state.option_order, vec!["A".to_string()]);
+    assert_eq!(state.winner(None), Ok("A".to_string()));
+}
+
+#[test]
+fn register_and_vote_end_to_end() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    assert_vote_count(&state, "A", 1);
+    assert_eq!(state.cast_vote(alice(), "A".to_string(), 2), Err(VoteError::AlreadyVoted));
+}
+
+struct OnlyAlice;
+
+impl UserIdValidator for OnlyAlice {
+    fn validate(&self, user_id: &UserId) -> bool {
+        user_id == "alice"
+    }
+}
+
+#[test]
+fn register_voter_checked_rejects_a_user_id_the_validator_refuses() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        state.register_voter_checked("mallory".to_string(), &OnlyAlice),
+        Err(VoteError::InvalidUserId)
+    );
+    assert!(state.registered_voters.is_empty());
+}
+
+#[test]
+fn register_voter_checked_accepts_a_user_id_the_validator_allows() {
+    let mut state = VotingStateBuilder::new().build();
+    state.register_voter_checked(alice(), &OnlyAlice).unwrap();
+    assert_eq!(state.registered_voters, HashSet::from([alice()]));
+}
+
+#[test]
+fn register_voter_attested_with_always_person_behaves_like_plain_register() {
+    let mut state = VotingStateBuilder::new().build();
+    state.register_voter_attested(alice(), b"", &AlwaysPerson).unwrap();
+    assert_eq!(state.registered_voters, HashSet::from([alice()]));
+}
+
+struct FakeVerifier;
+
+impl SignatureVerifier for FakeVerifier {
+    fn verify(&self, _signer: &UserId, message: &[u8], signature: &[u8]) -> bool {
+        message == signature
+    }
+}
+
+#[test]
+fn register_voter_attested_rejects_a_bad_proof() {
+    let mut state = VotingStateBuilder::new().build();
+    let provider = AttestedPersonhood { attestor_id: &admin(), verifier: &FakeVerifier };
+
+    assert_eq!(
+        state.register_voter_attested(alice(), b"not the right proof", &provider),
+        Err(VoteError::NotAPerson)
+    );
+    assert!(state.registered_voters.is_empty());
+}
+
+#[test]
+fn register_voter_attested_accepts_a_valid_proof() {
+    let mut state = VotingStateBuilder::new().build();
+    let provider = AttestedPersonhood { attestor_id: &admin(), verifier: &FakeVerifier };
+
+    let proof = personhood_attestation_message(&alice());
+    state.register_voter_attested(alice(), &proof, &provider).unwrap();
+    assert_eq!(state.registered_voters, HashSet::from([alice()]));
+}
+
+#[test]
+fn cast_vote_signed_rejects_a_bad_signature() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .voting_started(10, 0)
+        .build();
+
+    assert_eq!(
+        state.cast_vote_signed(alice(), "A".to_string(), 1, b"not the right message", &FakeVerifier),
+        Err(VoteError::InvalidSignature)
+    );
+    assert!(state.votes_cast.is_empty());
+}
+
+#[test]
+fn cast_vote_signed_accepts_a_valid_signature() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .voting_started(10, 0)
+        .build();
+
+    let signature = signed_vote_message(&alice(), &"A".to_string());
+    state
+        .cast_vote_signed(alice(), "A".to_string(), 1, &signature, &FakeVerifier)
+        .unwrap();
+    assert_vote_count(&state, "A", 1);
+}
+
+#[derive(Default)]
+struct RecordingNotifier {
+    events: RefCell<Vec<&'static str>>,
+}
+
+impl Notifier for RecordingNotifier {
+    fn registration_open(&self, _state: &voting_state_machine::VotingState) {
+        self.events.borrow_mut().push("registration_open");
+    }
+
+    fn voting_started(&self, _state: &voting_state_machine::VotingState) {
+        self.events.borrow_mut().push("voting_started");
+    }
+
+    fn finalized(&self, _state: &voting_state_machine::VotingState) {
+        self.events.borrow_mut().push("finalized");
+    }
+}
+
+#[test]
+fn transition_notified_fires_the_hook_matching_the_new_phase() {
+    let mut state = VotingStateBuilder::new().build();
+    let notifier = RecordingNotifier::default();
+
+    transition_notified(&mut state, admin(), Action::StartVoting { duration: 10 }, 0, &notifier).unwrap();
+    transition_notified(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10, &notifier).unwrap();
+    transition_notified(&mut state, admin(), Action::StartRegistration, 10, &notifier).unwrap();
+
+    assert_eq!(*notifier.events.borrow(), vec!["voting_started", "finalized", "registration_open"]);
+}
+
+#[test]
+fn transition_notified_stays_quiet_on_a_rejected_transition() {
+    let mut state = VotingStateBuilder::new().build();
+    let notifier = RecordingNotifier::default();
+
+    assert_eq!(
+        transition_notified(&mut state, "mallory".to_string(), Action::StartVoting { duration: 10 }, 0, &notifier),
+        Err(VoteError::NotAdmin)
+    );
+    assert!(notifier.events.borrow().is_empty());
+}
+
+#[test]
+fn public_results_suppresses_counts_below_the_threshold() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_candidate("B")
+        .with_voter(alice())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+
+    let results = state.public_results(2);
+    assert_eq!(results[&"A".to_string()], ResultCount::Suppressed(2));
+    assert_eq!(results[&"B".to_string()], ResultCount::Suppressed(2));
+}
+
+#[test]
+fn public_results_reports_exact_counts_once_they_reach_the_threshold() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    state.cast_vote(bob(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(state.public_results(2)[&"A".to_string()], ResultCount::Exact(2));
+}
+
+#[test]
+fn public_results_leaves_vote_counts_itself_untouched() {
+    let state = VotingStateBuilder::new().with_candidate("A").voting_started(10, 0).build();
+    state.public_results(5);
+    assert_eq!(state.vote_counts[&"A".to_string()], 0);
+}
+
+#[test]
+fn add_option_rejects_a_non_admin_caller() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        state.add_option(&"mallory".to_string(), "A".to_string()),
+        Err(VoteError::NotAdmin)
+    );
+}
+
+#[test]
+fn add_option_rejects_a_duplicate() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").build();
+    assert_eq!(
+        state.add_option(&admin(), "A".to_string()),
+        Err(VoteError::DuplicateOption)
+    );
+}
+
+#[test]
+fn add_option_rejects_once_voting_has_started() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").voting_started(10, 0).build();
+    assert_eq!(
+        state.add_option(&admin(), "B".to_string()),
+        Err(VoteError::VotingAlreadyStarted)
+    );
+}
+
+#[test]
+fn add_option_then_list_options_reports_it() {
+    let mut state = VotingStateBuilder::new().build();
+    state.add_option(&admin(), "A".to_string()).unwrap();
+    assert_eq!(state.list_options(), vec!["A".to_string()]);
+}
+
+#[test]
+fn remove_option_rejects_an_unknown_option() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        state.remove_option(&admin(), &"A".to_string()),
+        Err(VoteError::InvalidOption)
+    );
+}
+
+#[test]
+fn remove_option_rejects_an_option_with_votes() {
+    // Casting a vote requires `VotingPhase::VotingActive`, which
+    // `remove_option` already refuses with `VotingAlreadyStarted` - so
+    // reaching its vote-count check means setting up a count directly,
+    // the one way left to exercise it as a guard in its own right.
+    let mut state = VotingStateBuilder::new().with_candidate("A").build();
+    *state.vote_counts.get_mut("A").unwrap() = 1;
+
+    assert_eq!(
+        state.remove_option(&admin(), &"A".to_string()),
+        Err(VoteError::OptionHasVotes)
+    );
+}
+
+#[test]
+fn remove_option_drops_an_unvoted_option() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").build();
+    state.remove_option(&admin(), &"A".to_string()).unwrap();
+    assert!(state.list_options().is_empty());
+}
+
+#[test]
+fn voters_for_lists_voters_in_the_order_they_voted() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_candidate("B")
+        .with_voter(alice())
+        .with_voter(bob())
+        .with_voter("carol".to_string())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(bob(), "A".to_string(), 1).unwrap();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    state.cast_vote("carol".to_string(), "B".to_string(), 1).unwrap();
+
+    assert_eq!(state.voters_for(&"A".to_string(), 0, 10), &[bob(), alice()]);
+    assert_eq!(state.voters_for(&"B".to_string(), 0, 10), &["carol".to_string()]);
+}
+
+#[test]
+fn voters_for_paginates_with_offset_and_limit() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .with_voter("carol".to_string())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    state.cast_vote(bob(), "A".to_string(), 1).unwrap();
+    state.cast_vote("carol".to_string(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(state.voters_for(&"A".to_string(), 1, 1), &[bob()]);
+    assert_eq!(state.voters_for(&"A".to_string(), 2, 10), &["carol".to_string()]);
+    assert_eq!(state.voters_for(&"A".to_string(), 10, 10), &[] as &[UserId]);
+}
+
+#[test]
+fn voters_for_an_option_no_one_voted_for_is_empty() {
+    let state = VotingStateBuilder::new().with_candidate("A").voting_started(10, 0).build();
+    assert_eq!(state.voters_for(&"A".to_string(), 0, 10), &[] as &[UserId]);
+}
+
+#[test]
+fn fixed_clock_reports_whatever_it_was_set_to() {
+    let mut clock = FixedClock(0);
+    assert_eq!(clock.now(), 0);
+    clock.0 = 42;
+    assert_eq!(clock.now(), 42);
+}
+
+#[test]
+fn system_clock_reports_seconds_since_the_unix_epoch() {
+    let before = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let now = SystemClock.now();
+    assert!(now >= before);
+}
+
+#[test]
+fn validating_builder_rejects_an_empty_admin_id() {
+    assert_eq!(
+        ValidatingBuilder::new().with_admin(String::new()).build(),
+        Err(VoteError::EmptyAdminId)
+    );
+}
+
+#[test]
+fn validating_builder_rejects_a_duplicate_option() {
+    assert_eq!(
+        ValidatingBuilder::new()
+            .with_admin(admin())
+            .with_option("A".to_string())
+            .with_option("A".to_string())
+            .build()
+            .map(|_| ()),
+        Err(VoteError::DuplicateOption)
+    );
+}
+
+#[test]
+fn set_quorum_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        state.set_quorum(&"mallory".to_string(), Quorum::Absolute(1)),
+        Err(VoteError::NotAdmin)
+    );
+    assert_eq!(state.quorum, None);
+}
+
+#[test]
+fn set_quorum_is_rejected_once_voting_has_started() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(
+        state.set_quorum(&admin(), Quorum::Absolute(1)),
+        Err(VoteError::VotingAlreadyStarted)
+    );
+}
+
+#[test]
+fn end_voting_rejects_turnout_below_an_absolute_quorum() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .build();
+    state.set_quorum(&admin(), Quorum::Absolute(2)).unwrap();
+    transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(
+        transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10),
+        Err(VoteError::QuorumNotMet)
+    );
+    assert_phase(&state, VotingPhase::VotingActive);
+}
+
+#[test]
+fn end_voting_rejects_turnout_below_a_percentage_quorum() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .build();
+    state.set_quorum(&admin(), Quorum::Percentage(100)).unwrap();
+    transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(
+        transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10),
+        Err(VoteError::QuorumNotMet)
+    );
+}
+
+#[test]
+fn end_voting_succeeds_once_quorum_is_met() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .build();
+    state.set_quorum(&admin(), Quorum::Absolute(2)).unwrap();
+    transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    state.cast_vote(bob(), "A".to_string(), 1).unwrap();
+
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+    assert_phase(&state, VotingPhase::ResultsFinalized);
+}
+
+#[test]
+fn override_quorum_bypasses_a_failing_quorum_check() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_voter(alice())
+        .with_voter(bob())
+        .build();
+    state.set_quorum(&admin(), Quorum::Absolute(2)).unwrap();
+    transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: true }, 10).unwrap();
+    assert_phase(&state, VotingPhase::ResultsFinalized);
+}
+
+#[test]
+fn no_quorum_configured_means_end_voting_always_succeeds() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+    assert_phase(&state, VotingPhase::ResultsFinalized);
+}
+
+#[test]
+fn set_tie_break_policy_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(
+        state.set_tie_break_policy(&"mallory".to_string(), TieBreakPolicy::FirstRegisteredOption),
+        Err(VoteError::NotAdmin)
+    );
+}
+
+#[test]
+fn set_tie_break_policy_is_rejected_once_voting_has_started() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(
+        state.set_tie_break_policy(&admin(), TieBreakPolicy::FirstRegisteredOption),
+        Err(VoteError::VotingAlreadyStarted)
+    );
+}
+
+#[test]
+fn winner_reports_the_single_option_with_the_most_votes() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_candidate("B")
+        .with_voter(alice())
+        .with_voter(bob())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 1).unwrap();
+    state.cast_vote(bob(), "A".to_string(), 1).unwrap();
+
+    assert_eq!(state.winner(None), Ok("A".to_string()));
+}
+
+#[test]
+fn winner_defaults_to_reporting_a_tie_as_an_error() {
+    // Two untouched options are tied at zero votes each.
+    let state = VotingStateBuilder::new().with_candidate("A").with_candidate("B").build();
+    assert_eq!(state.winner(None), Err(VoteError::TieUnresolved));
+}
+
+#[test]
+fn winner_under_first_registered_option_picks_the_earliest_added_tied_option() {
+    let mut state = VotingStateBuilder::new().with_candidate("B").with_candidate("A").build();
+    state.set_tie_break_policy(&admin(), TieBreakPolicy::FirstRegisteredOption).unwrap();
+    assert_eq!(state.winner(None), Ok("B".to_string()));
+}
+
+#[test]
+fn winner_under_admin_decides_requires_a_valid_admin_choice() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_candidate("B").build();
+    state.set_tie_break_policy(&admin(), TieBreakPolicy::AdminDecides).unwrap();
+    assert_eq!(state.winner(None), Err(VoteError::TieUnresolved));
+    assert_eq!(state.winner(Some(&"B".to_string())), Ok("B".to_string()));
+}
+
+#[test]
+fn winner_under_random_with_seed_deterministically_picks_a_tied_option() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_candidate("B").build();
+    state.set_tie_break_policy(&admin(), TieBreakPolicy::RandomWithSeed(1)).unwrap();
+    assert_eq!(state.winner(None), Ok("B".to_string()));
+}
+
+#[test]
+fn winner_rejects_an_election_with_no_options() {
+    let state = VotingStateBuilder::new().build();
+    assert_eq!(state.winner(None), Err(VoteError::InvalidOption));
+}
+
+#[test]
+fn get_results_rejects_a_read_before_results_are_finalized() {
+    let state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.get_results(None), Err(VoteError::VotingNotActive));
+}
+
+#[test]
+fn get_results_reports_the_winner_counts_percentages_and_turnout() {
+    let mut state = VotingStateBuilder::new()
+        .with_candidate("A")
+        .with_candidate("B")
+        .with_voter(alice())
+        .with_voter(bob())
+        .voting_started(10, 0)
+        .build();
+    state.cast_vote(alice(), "A".to_string(), 0).unwrap();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    let results = state.get_results(None).unwrap();
+    assert_eq!(
+        results,
+        ElectionResults {
+            winner: Some("A".to_string()),
+            counts: [("A".to_string(), 1), ("B".to_string(), 0)].into_iter().collect(),
+            percentages: [("A".to_string(), 100.0), ("B".to_string(), 0.0)].into_iter().collect(),
+            turnout: 50.0,
+            invalid_vote_attempts: 0,
+        }
+    );
+}
+
+#[test]
+fn get_results_reports_a_zero_turnout_election_without_dividing_by_zero() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").voting_started(10, 0).build();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    let results = state.get_results(None).unwrap();
+    assert_eq!(results.turnout, 0.0);
+    assert_eq!(results.percentages[&"A".to_string()], 0.0);
+}
+
+#[test]
+fn get_results_reports_none_for_a_winner_when_the_tie_is_unresolved() {
+    let mut state =
+        VotingStateBuilder::new().with_candidate("A").with_candidate("B").voting_started(10, 0).build();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    assert_eq!(state.get_results(None).unwrap().winner, None);
+}
+
+#[test]
+fn get_results_counts_every_rejected_cast_vote_as_an_invalid_attempt() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.cast_vote(alice(), "not-an-option".to_string(), 0).unwrap_err();
+    state.cast_vote("mallory".to_string(), "A".to_string(), 0).unwrap_err();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    assert_eq!(state.get_results(None).unwrap().invalid_vote_attempts, 2);
+}
+
+#[test]
+fn audit_log_records_a_rejected_action_and_the_error_it_returned() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").voting_started(10, 0).build();
+    state.register_voter(alice()).unwrap_err();
+
+    let entry = state.audit_log().last().unwrap();
+    assert_eq!(entry.caller_id, alice());
+    assert_eq!(entry.action, "register_voter");
+    assert_eq!(entry.timestamp, None);
+    assert_eq!(entry.rejected, Some(VoteError::InvalidTransition));
+}
+
+#[test]
+fn audit_log_records_a_successful_action_with_no_rejection() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.cast_vote(alice(), "A".to_string(), 5).unwrap();
+
+    let entry = state.audit_log().last().unwrap();
+    assert_eq!(entry.action, "cast_vote");
+    assert_eq!(entry.timestamp, Some(5));
+    assert_eq!(entry.rejected, None);
+}
+
+#[test]
+fn audit_log_for_filters_to_one_callers_entries() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).build();
+    state.register_voter(alice()).unwrap_err();
+    state.register_voter(bob()).unwrap();
+
+    let alice_entries = state.audit_log_for(&alice());
+    assert_eq!(alice_entries.len(), 2);
+    assert!(alice_entries.iter().all(|entry| entry.caller_id == alice()));
+    assert_eq!(alice_entries[1].rejected, Some(VoteError::AlreadyRegistered));
+}
+
+#[test]
+fn export_audit_log_rejects_a_read_before_results_are_finalized() {
+    let state = VotingStateBuilder::new().build();
+    assert_eq!(state.export_audit_log(), Err(VoteError::VotingNotActive));
+}
+
+#[test]
+fn export_audit_log_reports_the_full_trail_once_finalized() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.cast_vote(alice(), "A".to_string(), 0).unwrap();
+    transition(&mut state, admin(), Action::EndVoting { override_quorum: false }, 10).unwrap();
+
+    let exported = state.export_audit_log().unwrap();
+    assert!(exported.iter().any(|entry| entry.action == "cast_vote" && entry.rejected.is_none()));
+    assert!(exported.iter().any(|entry| entry.action == "end_voting" && entry.rejected.is_none()));
+}
+
+#[test]
+fn pause_voting_moves_to_paused_and_blocks_cast_vote() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.pause_voting(&admin(), 3).unwrap();
+    assert_phase(&state, VotingPhase::Paused);
+    assert_eq!(state.cast_vote(alice(), "A".to_string(), 4), Err(VoteError::VotingNotActive));
+}
+
+#[test]
+fn pause_voting_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.pause_voting(&"mallory".to_string(), 3), Err(VoteError::NotAdmin));
+}
+
+#[test]
+fn pause_voting_rejects_a_call_outside_voting_active() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(state.pause_voting(&admin(), 0), Err(VoteError::InvalidTransition));
+}
+
+#[test]
+fn resume_voting_returns_to_voting_active_and_extends_the_window_by_the_pause() {
+    let mut state = VotingStateBuilder::new().with_candidate("A").with_voter(alice()).voting_started(10, 0).build();
+    state.pause_voting(&admin(), 3).unwrap();
+    state.resume_voting(&admin(), 7).unwrap();
+
+    assert_phase(&state, VotingPhase::VotingActive);
+    assert_eq!(state.voting_end_time, 14); // 10 + (7 - 3) paused seconds
+    state.cast_vote(alice(), "A".to_string(), 8).unwrap();
+}
+
+#[test]
+fn resume_voting_rejects_a_call_when_not_paused() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.resume_voting(&admin(), 3), Err(VoteError::InvalidTransition));
+}
+
+#[test]
+fn resume_voting_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    state.pause_voting(&admin(), 3).unwrap();
+    assert_eq!(state.resume_voting(&"mallory".to_string(), 5), Err(VoteError::NotAdmin));
+}
+
+#[test]
+fn extend_voting_pushes_out_voting_end_time_and_tracks_the_total() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    state.extend_voting(&admin(), 5, 1).unwrap();
+    assert_eq!(state.voting_end_time, 15);
+    assert_eq!(state.total_extension, 5);
+}
+
+#[test]
+fn extend_voting_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.extend_voting(&"mallory".to_string(), 5, 1), Err(VoteError::NotAdmin));
+}
+
+#[test]
+fn extend_voting_rejects_a_call_outside_voting_active() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(state.extend_voting(&admin(), 5, 0), Err(VoteError::VotingNotActive));
+}
+
+#[test]
+fn extend_voting_rejects_an_extension_past_the_configured_maximum() {
+    // `set_max_extension` only allows `RegistrationOpen`, so it has to
+    // run before `transition` starts voting.
+    let mut state = VotingStateBuilder::new().with_candidate("A").build();
+    state.set_max_extension(&admin(), 5).unwrap();
+    transition(&mut state, admin(), Action::StartVoting { duration: 10 }, 0).unwrap();
+
+    state.extend_voting(&admin(), 3, 1).unwrap();
+    assert_eq!(state.extend_voting(&admin(), 3, 2), Err(VoteError::ExtensionLimitExceeded));
+    assert_eq!(state.total_extension, 3);
+}
+
+#[test]
+fn set_max_extension_is_admin_gated() {
+    let mut state = VotingStateBuilder::new().build();
+    assert_eq!(state.set_max_extension(&"mallory".to_string(), 5), Err(VoteError::NotAdmin));
+}
+
+#[test]
+fn set_max_extension_rejects_a_call_once_voting_has_started() {
+    let mut state = VotingStateBuilder::new().voting_started(10, 0).build();
+    assert_eq!(state.set_max_extension(&admin(), 5), Err(VoteError::VotingAlreadyStarted));
+}
+
+#[test]
+fn validating_builder_builds_a_fully_configured_state() {
+    let state = ValidatingBuilder::new()
+        .with_admin(admin())
+        .with_option("A".to_string())
+        .with_voter(alice())
+        .voting_active(10, 0)
+        .build()
+        .unwrap();
+    assert_phase(&state, VotingPhase::VotingActive);
+    assert_eq!(state.registered_voters, HashSet::from([alice()]));
+}
@@ -0,0 +1,76 @@
+//! Integration tests for the `weight-accounting` feature, gated with
+//! `required-features` in `Cargo.toml` so a plain `cargo test` (no
+//! features) skips this file instead of failing to compile against a
+//! module that isn't there.
+
+use voting_state_machine::weight::WeightMeter;
+use voting_state_machine::{Action, VotingState};
+
+#[test]
+fn register_voter_is_recorded_with_its_reads_writes_and_bytes() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut meter = WeightMeter::new();
+
+    meter.register_voter(&mut state, "alice".to_string()).unwrap();
+
+    let calls = meter.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].operation, "register_voter");
+    assert_eq!(calls[0].weight.reads, 1);
+    assert_eq!(calls[0].weight.writes, 1);
+    assert_eq!(calls[0].weight.bytes, 5); // "alice"
+}
+
+#[test]
+fn weight_is_recorded_even_when_the_underlying_call_is_rejected() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut meter = WeightMeter::new();
+
+    meter.register_voter(&mut state, "alice".to_string()).unwrap();
+    assert!(meter.register_voter(&mut state, "alice".to_string()).is_err());
+
+    assert_eq!(meter.calls().len(), 2);
+}
+
+#[test]
+fn total_sums_every_call_recorded_so_far() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut meter = WeightMeter::new();
+
+    meter.register_voter(&mut state, "alice".to_string()).unwrap();
+    meter.register_voter(&mut state, "bob".to_string()).unwrap();
+    meter.add_vote_option(&mut state, "yes".to_string());
+
+    let total = meter.total();
+    assert_eq!(total.reads, 3);
+    assert_eq!(total.writes, 3);
+    assert_eq!(total.bytes, 5 + 3 + 3); // "alice" + "bob" + "yes"
+}
+
+#[test]
+fn by_operation_groups_weight_by_the_operation_that_caused_it() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut meter = WeightMeter::new();
+
+    meter.register_voter(&mut state, "alice".to_string()).unwrap();
+    meter.register_voter(&mut state, "bob".to_string()).unwrap();
+    meter.add_vote_option(&mut state, "yes".to_string());
+
+    let by_operation = meter.by_operation();
+    assert_eq!(by_operation[&"register_voter"].reads, 2);
+    assert_eq!(by_operation[&"add_vote_option"].reads, 1);
+}
+
+#[test]
+fn transition_is_priced_by_which_action_ran() {
+    let mut state = VotingState::new("admin".to_string());
+    let mut meter = WeightMeter::new();
+
+    meter
+        .transition(&mut state, "admin".to_string(), Action::StartVoting { duration: 10 }, 0)
+        .unwrap();
+
+    let calls = meter.calls();
+    assert_eq!(calls[0].operation, "start_voting");
+    assert_eq!(calls[0].weight, voting_state_machine::weight::Weight { reads: 1, writes: 2, bytes: 16 });
+}
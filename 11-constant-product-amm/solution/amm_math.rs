@@ -0,0 +1,128 @@
+//! Pure constant-product AMM math, kept free of any chain environment or
+//! `#[ink::contract]` macro expansion so it can be property-tested on its
+//! own, the same reasoning behind `approval_curve.rs` in Task 3.
+
+pub type Balance = u128;
+
+pub const PERMILL_MAX: u32 = 1_000_000;
+
+/// Integer square root via Newton's method, used to size a pool's very
+/// first liquidity deposit (`sqrt(amount_a * amount_b)` shares, the same
+/// convention Uniswap v2 uses so no side of an empty pool can dictate the
+/// exchange rate by itself).
+pub fn isqrt(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// LP shares minted for a deposit of `amount_a`/`amount_b` into a pool
+/// currently holding `reserve_a`/`reserve_b` against `total_shares`
+/// outstanding. The first deposit into an empty pool prices shares at
+/// `sqrt(amount_a * amount_b)`; every deposit after that is credited the
+/// smaller of the two sides' proportional share count, so an unbalanced
+/// deposit can't mint more than its true contribution to either side.
+pub fn shares_for_deposit(
+    reserve_a: Balance,
+    reserve_b: Balance,
+    total_shares: Balance,
+    amount_a: Balance,
+    amount_b: Balance,
+) -> Balance {
+    if total_shares == 0 {
+        return isqrt(amount_a.saturating_mul(amount_b));
+    }
+    let share_a = amount_a.saturating_mul(total_shares) / reserve_a;
+    let share_b = amount_b.saturating_mul(total_shares) / reserve_b;
+    share_a.min(share_b)
+}
+
+/// Output amount for a constant-product swap, with `fee_ppm` parts per
+/// million taken out of the input before the `x * y = k` exchange, the
+/// same mechanism (and default 0.3%, `fee_ppm = 3_000`) as Uniswap v2.
+pub fn amount_out(amount_in: Balance, reserve_in: Balance, reserve_out: Balance, fee_ppm: u32) -> Balance {
+    let amount_in_with_fee = amount_in.saturating_mul((PERMILL_MAX - fee_ppm) as u128);
+    let numerator = amount_in_with_fee.saturating_mul(reserve_out);
+    let denominator = reserve_in
+        .saturating_mul(PERMILL_MAX as u128)
+        .saturating_add(amount_in_with_fee);
+    numerator.checked_div(denominator).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isqrt_is_exact_on_perfect_squares() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(144), 12);
+        assert_eq!(isqrt(1_000_000), 1_000);
+    }
+
+    #[test]
+    fn isqrt_rounds_down_between_squares() {
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(2), 1);
+    }
+
+    #[test]
+    fn initial_deposit_prices_shares_at_the_geometric_mean() {
+        assert_eq!(shares_for_deposit(0, 0, 0, 100, 400), isqrt(100 * 400));
+    }
+
+    #[test]
+    fn later_deposit_is_capped_by_the_weaker_side() {
+        // Pool at 1000:2000; a deposit offering 100:500 is only worth what
+        // its scarcer side (100 of reserve_a, 10% of the pool) buys.
+        let shares = shares_for_deposit(1_000, 2_000, 500, 100, 500);
+        assert_eq!(shares, 50);
+    }
+
+    #[test]
+    fn amount_out_never_drains_the_pool() {
+        for reserve_in in [10u128, 1_000, 1_000_000] {
+            for reserve_out in [10u128, 1_000, 1_000_000] {
+                for amount_in in [1u128, 50, 10_000] {
+                    let out = amount_out(amount_in, reserve_in, reserve_out, 3_000);
+                    assert!(out < reserve_out, "swap must never exhaust the output reserve");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn amount_out_preserves_the_constant_product_invariant() {
+        // With a positive fee, the post-swap product must never fall below
+        // the pre-swap one: the fee is value left behind in the pool.
+        for reserve_in in [1_000u128, 50_000] {
+            for reserve_out in [1_000u128, 50_000] {
+                for amount_in in [1u128, 100, 5_000] {
+                    let out = amount_out(amount_in, reserve_in, reserve_out, 3_000);
+                    let k_before = reserve_in * reserve_out;
+                    let k_after = (reserve_in + amount_in) * (reserve_out - out);
+                    assert!(
+                        k_after >= k_before,
+                        "k shrank: in={amount_in} reserve_in={reserve_in} reserve_out={reserve_out} out={out}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_fee_swap_holds_the_invariant_almost_exactly() {
+        let out = amount_out(100, 10_000, 10_000, 0);
+        let k_before = 10_000u128 * 10_000;
+        let k_after = (10_000 + 100) * (10_000 - out);
+        assert!(k_after >= k_before);
+    }
+}
@@ -0,0 +1,361 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub mod amm_math;
+
+/// A constant-product pool over two Task 9 `Psp22Token`s, reached through
+/// the strongly-typed `Psp22TokenRef` ink! generates for a contract
+/// compiled as a dependency, rather than the raw selector/bytes approach
+/// Task 7's multisig needs for callees it knows nothing about ahead of
+/// time.
+#[ink::contract]
+pub mod constant_product_amm {
+    use crate::amm_math;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use psp22_token::psp22_token::Psp22TokenRef;
+
+    /// Flat 0.3% swap fee, left in the pool to compensate liquidity
+    /// providers, the same default Uniswap v2 uses.
+    const FEE_PPM: u32 = 3_000;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum AmmError {
+        ZeroAmount,
+        InsufficientLiquidity,
+        InsufficientShares,
+        SlippageExceeded,
+        TransferFailed,
+    }
+
+    /// Emitted when a provider deposits both legs and receives LP shares.
+    #[ink(event)]
+    pub struct LiquidityAdded {
+        #[ink(topic)]
+        provider: AccountId,
+        amount_a: Balance,
+        amount_b: Balance,
+        shares_minted: Balance,
+    }
+
+    /// Emitted when a provider burns LP shares and withdraws both legs.
+    #[ink(event)]
+    pub struct LiquidityRemoved {
+        #[ink(topic)]
+        provider: AccountId,
+        amount_a: Balance,
+        amount_b: Balance,
+        shares_burned: Balance,
+    }
+
+    /// Emitted on every swap, `a_to_b` recording which leg was sold.
+    #[ink(event)]
+    pub struct Swapped {
+        #[ink(topic)]
+        trader: AccountId,
+        a_to_b: bool,
+        amount_in: Balance,
+        amount_out: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct ConstantProductAmm {
+        token_a: Psp22TokenRef,
+        token_b: Psp22TokenRef,
+        reserve_a: Balance,
+        reserve_b: Balance,
+        total_shares: Balance,
+        shares: Mapping<AccountId, Balance>,
+    }
+
+    impl ConstantProductAmm {
+        #[ink(constructor)]
+        pub fn new(token_a: Psp22TokenRef, token_b: Psp22TokenRef) -> Self {
+            Self {
+                token_a,
+                token_b,
+                reserve_a: 0,
+                reserve_b: 0,
+                total_shares: 0,
+                shares: Mapping::default(),
+            }
+        }
+
+        /// Pulls `amount_a`/`amount_b` from the caller (who must have
+        /// approved this pool over both tokens first) and mints LP shares
+        /// sized by `amm_math::shares_for_deposit`.
+        #[ink(message)]
+        pub fn add_liquidity(
+            &mut self,
+            amount_a: Balance,
+            amount_b: Balance,
+            min_shares: Balance,
+        ) -> Result<Balance, AmmError> {
+            if amount_a == 0 || amount_b == 0 {
+                return Err(AmmError::ZeroAmount);
+            }
+            let minted = amm_math::shares_for_deposit(
+                self.reserve_a,
+                self.reserve_b,
+                self.total_shares,
+                amount_a,
+                amount_b,
+            );
+            if minted < min_shares {
+                return Err(AmmError::SlippageExceeded);
+            }
+
+            let caller = self.env().caller();
+            let pool = self.env().account_id();
+            self.token_a
+                .transfer_from(caller, pool, amount_a, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+            self.token_b
+                .transfer_from(caller, pool, amount_b, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+
+            self.reserve_a += amount_a;
+            self.reserve_b += amount_b;
+            self.total_shares += minted;
+            let caller_shares = self.shares_of(caller);
+            self.shares.insert(caller, &(caller_shares + minted));
+
+            self.env().emit_event(LiquidityAdded {
+                provider: caller,
+                amount_a,
+                amount_b,
+                shares_minted: minted,
+            });
+            Ok(minted)
+        }
+
+        /// Burns `shares_burned` of the caller's LP shares and returns a
+        /// proportional slice of both reserves.
+        #[ink(message)]
+        pub fn remove_liquidity(
+            &mut self,
+            shares_burned: Balance,
+            min_amount_a: Balance,
+            min_amount_b: Balance,
+        ) -> Result<(Balance, Balance), AmmError> {
+            if shares_burned == 0 {
+                return Err(AmmError::ZeroAmount);
+            }
+            let caller = self.env().caller();
+            let caller_shares = self.shares_of(caller);
+            if caller_shares < shares_burned {
+                return Err(AmmError::InsufficientShares);
+            }
+
+            let amount_a = self.reserve_a * shares_burned / self.total_shares;
+            let amount_b = self.reserve_b * shares_burned / self.total_shares;
+            if amount_a < min_amount_a || amount_b < min_amount_b {
+                return Err(AmmError::SlippageExceeded);
+            }
+
+            self.shares.insert(caller, &(caller_shares - shares_burned));
+            self.total_shares -= shares_burned;
+            self.reserve_a -= amount_a;
+            self.reserve_b -= amount_b;
+
+            self.token_a
+                .transfer(caller, amount_a, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+            self.token_b
+                .transfer(caller, amount_b, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+
+            self.env().emit_event(LiquidityRemoved {
+                provider: caller,
+                amount_a,
+                amount_b,
+                shares_burned,
+            });
+            Ok((amount_a, amount_b))
+        }
+
+        /// Sells `amount_in` of token A for token B. Quoted against the
+        /// current reserves before either token contract is touched, so a
+        /// `SlippageExceeded` failure never costs a cross-contract call.
+        #[ink(message)]
+        pub fn swap_exact_a_for_b(
+            &mut self,
+            amount_in: Balance,
+            min_amount_out: Balance,
+        ) -> Result<Balance, AmmError> {
+            let amount_out = self.quote_and_check(amount_in, self.reserve_a, self.reserve_b, min_amount_out)?;
+
+            let caller = self.env().caller();
+            let pool = self.env().account_id();
+            self.token_a
+                .transfer_from(caller, pool, amount_in, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+            self.token_b
+                .transfer(caller, amount_out, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+
+            self.reserve_a += amount_in;
+            self.reserve_b -= amount_out;
+            self.env().emit_event(Swapped {
+                trader: caller,
+                a_to_b: true,
+                amount_in,
+                amount_out,
+            });
+            Ok(amount_out)
+        }
+
+        /// Sells `amount_in` of token B for token A. Mirror image of
+        /// `swap_exact_a_for_b`.
+        #[ink(message)]
+        pub fn swap_exact_b_for_a(
+            &mut self,
+            amount_in: Balance,
+            min_amount_out: Balance,
+        ) -> Result<Balance, AmmError> {
+            let amount_out = self.quote_and_check(amount_in, self.reserve_b, self.reserve_a, min_amount_out)?;
+
+            let caller = self.env().caller();
+            let pool = self.env().account_id();
+            self.token_b
+                .transfer_from(caller, pool, amount_in, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+            self.token_a
+                .transfer(caller, amount_out, Vec::new())
+                .map_err(|_| AmmError::TransferFailed)?;
+
+            self.reserve_b += amount_in;
+            self.reserve_a -= amount_out;
+            self.env().emit_event(Swapped {
+                trader: caller,
+                a_to_b: false,
+                amount_in,
+                amount_out,
+            });
+            Ok(amount_out)
+        }
+
+        #[ink(message)]
+        pub fn reserves(&self) -> (Balance, Balance) {
+            (self.reserve_a, self.reserve_b)
+        }
+
+        #[ink(message)]
+        pub fn shares_of(&self, account: AccountId) -> Balance {
+            self.shares.get(account).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn total_shares(&self) -> Balance {
+            self.total_shares
+        }
+
+        fn quote_and_check(
+            &self,
+            amount_in: Balance,
+            reserve_in: Balance,
+            reserve_out: Balance,
+            min_amount_out: Balance,
+        ) -> Result<Balance, AmmError> {
+            if amount_in == 0 {
+                return Err(AmmError::ZeroAmount);
+            }
+            if reserve_in == 0 || reserve_out == 0 {
+                return Err(AmmError::InsufficientLiquidity);
+            }
+            let amount_out = amm_math::amount_out(amount_in, reserve_in, reserve_out, FEE_PPM);
+            if amount_out < min_amount_out {
+                return Err(AmmError::SlippageExceeded);
+            }
+            Ok(amount_out)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::call::FromAccountId;
+
+        /// The guard-clause tests below never call through `token_a`/
+        /// `token_b`, so these refs just need to wrap *some* `AccountId` —
+        /// there is no real `Psp22Token` deployed at it.
+        fn new_pool() -> ConstantProductAmm {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let token_a = Psp22TokenRef::from_account_id(accounts.django);
+            let token_b = Psp22TokenRef::from_account_id(accounts.eve);
+            ConstantProductAmm::new(token_a, token_b)
+        }
+
+        #[ink::test]
+        fn add_liquidity_rejects_a_zero_amount() {
+            let mut pool = new_pool();
+            assert_eq!(
+                pool.add_liquidity(0, 100, 0),
+                Err(AmmError::ZeroAmount)
+            );
+        }
+
+        #[ink::test]
+        fn add_liquidity_enforces_the_minimum_shares() {
+            let mut pool = new_pool();
+            // Empty pool: shares minted = isqrt(100 * 100) = 100.
+            assert_eq!(
+                pool.add_liquidity(100, 100, 101),
+                Err(AmmError::SlippageExceeded)
+            );
+        }
+
+        #[ink::test]
+        fn remove_liquidity_rejects_a_zero_amount() {
+            let mut pool = new_pool();
+            assert_eq!(
+                pool.remove_liquidity(0, 0, 0),
+                Err(AmmError::ZeroAmount)
+            );
+        }
+
+        #[ink::test]
+        fn remove_liquidity_requires_enough_shares() {
+            let mut pool = new_pool();
+            assert_eq!(
+                pool.remove_liquidity(1, 0, 0),
+                Err(AmmError::InsufficientShares)
+            );
+        }
+
+        #[ink::test]
+        fn swap_rejects_a_zero_amount() {
+            let mut pool = new_pool();
+            assert_eq!(
+                pool.swap_exact_a_for_b(0, 0),
+                Err(AmmError::ZeroAmount)
+            );
+        }
+
+        #[ink::test]
+        fn swap_requires_existing_liquidity() {
+            let mut pool = new_pool();
+            assert_eq!(
+                pool.swap_exact_a_for_b(100, 0),
+                Err(AmmError::InsufficientLiquidity)
+            );
+        }
+
+        #[ink::test]
+        fn swap_enforces_the_minimum_output() {
+            // Off-chain tests have no real token contract to settle a swap
+            // against (the same limitation Task 7's multisig tests call
+            // out), so this drives reserves straight into storage to
+            // exercise the slippage guard, which runs before any
+            // cross-contract call is made.
+            let mut pool = new_pool();
+            pool.reserve_a = 1_000;
+            pool.reserve_b = 1_000;
+            let quoted = amm_math::amount_out(100, 1_000, 1_000, 3_000);
+            assert_eq!(
+                pool.swap_exact_a_for_b(100, quoted + 1),
+                Err(AmmError::SlippageExceeded)
+            );
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Solution for Task 12: a staking and rewards simulation modeled on
+//! `pallet-staking`'s own vocabulary (bonded/active/unlocking, eras,
+//! slashing), built as a plain library the way Task 1's voting state
+//! machine is - no chain environment, reusing Task 1's [`Clock`] as its
+//! only time source.
+
+use std::collections::BTreeMap;
+
+pub use voting_state_machine::{Clock, FixedClock};
+use voting_state_machine::Timestamp;
+
+pub type AccountId = String;
+pub type Balance = u128;
+pub type EraIndex = u32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StakingError {
+    NotBonded,
+    InsufficientActiveStake,
+    ZeroAmount,
+}
+
+/// A slice of stake queued to unlock once the chain reaches `era`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlockChunk {
+    pub value: Balance,
+    pub era: EraIndex,
+}
+
+/// One account's bonded stake, split the same way `pallet-staking`'s
+/// `StakingLedger` is: `active` earns rewards and is slashed first;
+/// `total` only drops once `withdraw_unbonded` clears a matured chunk.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Ledger {
+    pub total: Balance,
+    pub active: Balance,
+    pub unlocking: Vec<UnlockChunk>,
+}
+
+pub struct Staking<C: Clock> {
+    clock: C,
+    current_era: EraIndex,
+    unbonding_period_eras: EraIndex,
+    ledgers: BTreeMap<AccountId, Ledger>,
+    era_started_at: BTreeMap<EraIndex, Timestamp>,
+}
+
+impl<C: Clock> Staking<C> {
+    pub fn new(clock: C, unbonding_period_eras: EraIndex) -> Self {
+        let mut era_started_at = BTreeMap::new();
+        era_started_at.insert(0, clock.now());
+        Self {
+            clock,
+            current_era: 0,
+            unbonding_period_eras,
+            ledgers: BTreeMap::new(),
+            era_started_at,
+        }
+    }
+
+    /// Adds `amount` to `who`'s active and total stake, creating a fresh
+    /// ledger if `who` has never bonded before.
+    pub fn bond(&mut self, who: AccountId, amount: Balance) -> Result<(), StakingError> {
+        if amount == 0 {
+            return Err(StakingError::ZeroAmount);
+        }
+        let ledger = self.ledgers.entry(who).or_default();
+        ledger.total += amount;
+        ledger.active += amount;
+        Ok(())
+    }
+
+    /// Moves `amount` from `who`'s active stake into a new unlock chunk
+    /// maturing at `current_era + unbonding_period_eras`.
+    pub fn unbond(&mut self, who: &str, amount: Balance) -> Result<(), StakingError> {
+        if amount == 0 {
+            return Err(StakingError::ZeroAmount);
+        }
+        let ledger = self.ledgers.get_mut(who).ok_or(StakingError::NotBonded)?;
+        if ledger.active < amount {
+            return Err(StakingError::InsufficientActiveStake);
+        }
+        ledger.active -= amount;
+        ledger.unlocking.push(UnlockChunk {
+            value: amount,
+            era: self.current_era + self.unbonding_period_eras,
+        });
+        Ok(())
+    }
+
+    /// Drops every unlock chunk that has matured, returning the total
+    /// freed and reducing `total` by the same amount.
+    pub fn withdraw_unbonded(&mut self, who: &str) -> Balance {
+        let Some(ledger) = self.ledgers.get_mut(who) else {
+            return 0;
+        };
+        let current_era = self.current_era;
+        let mut freed = 0;
+        ledger.unlocking.retain(|chunk| {
+            if chunk.era <= current_era {
+                freed += chunk.value;
+                false
+            } else {
+                true
+            }
+        });
+        ledger.total -= freed;
+        freed
+    }
+
+    /// Advances to the next era, recording `clock.now()` as its start
+    /// time, and returns the new era index.
+    pub fn advance_era(&mut self) -> EraIndex {
+        self.current_era += 1;
+        self.era_started_at.insert(self.current_era, self.clock.now());
+        self.current_era
+    }
+
+    /// Splits `reward_pool` across every ledger's active stake, pro-rata
+    /// to the total active stake across all ledgers, crediting each
+    /// share straight into `active` (auto-compounding). A pool with no
+    /// active stake to reward is simply not distributed.
+    pub fn distribute_rewards(&mut self, reward_pool: Balance) {
+        let total_active = self.total_active_stake();
+        if total_active == 0 {
+            return;
+        }
+        for ledger in self.ledgers.values_mut() {
+            let share = reward_pool * ledger.active / total_active;
+            ledger.active += share;
+            ledger.total += share;
+        }
+    }
+
+    /// Removes `amount` from `who`'s stake, active first and then
+    /// unlocking chunks oldest-first (unbonding funds are still
+    /// slashable, same as in `pallet-staking`). Returns the amount
+    /// actually slashed, capped at the ledger's total.
+    pub fn slash(&mut self, who: &str, amount: Balance) -> Balance {
+        let Some(ledger) = self.ledgers.get_mut(who) else {
+            return 0;
+        };
+        let mut remaining = amount.min(ledger.total);
+        let slashed = remaining;
+
+        let from_active = remaining.min(ledger.active);
+        ledger.active -= from_active;
+        remaining -= from_active;
+
+        ledger.unlocking.sort_by_key(|chunk| chunk.era);
+        for chunk in ledger.unlocking.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let from_chunk = remaining.min(chunk.value);
+            chunk.value -= from_chunk;
+            remaining -= from_chunk;
+        }
+        ledger.unlocking.retain(|chunk| chunk.value > 0);
+        ledger.total -= slashed;
+        slashed
+    }
+
+    pub fn ledger_of(&self, who: &str) -> Option<&Ledger> {
+        self.ledgers.get(who)
+    }
+
+    pub fn current_era(&self) -> EraIndex {
+        self.current_era
+    }
+
+    pub fn total_active_stake(&self) -> Balance {
+        self.ledgers.values().map(|ledger| ledger.active).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staking() -> Staking<FixedClock> {
+        Staking::new(FixedClock(1_000), 3)
+    }
+
+    #[test]
+    fn bond_accumulates_active_and_total_stake() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        staking.bond("alice".into(), 50).unwrap();
+        let ledger = staking.ledger_of("alice").unwrap();
+        assert_eq!(ledger.active, 150);
+        assert_eq!(ledger.total, 150);
+    }
+
+    #[test]
+    fn unbond_requires_enough_active_stake() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        assert_eq!(
+            staking.unbond("alice", 200),
+            Err(StakingError::InsufficientActiveStake)
+        );
+    }
+
+    #[test]
+    fn withdraw_unbonded_waits_for_the_unbonding_period() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        staking.unbond("alice", 40).unwrap();
+
+        assert_eq!(staking.withdraw_unbonded("alice"), 0);
+        for _ in 0..3 {
+            staking.advance_era();
+        }
+        assert_eq!(staking.withdraw_unbonded("alice"), 40);
+        let ledger = staking.ledger_of("alice").unwrap();
+        assert_eq!(ledger.total, 60);
+        assert!(ledger.unlocking.is_empty());
+    }
+
+    #[test]
+    fn advance_era_timestamps_the_new_era() {
+        let mut staking = staking();
+        assert_eq!(staking.current_era(), 0);
+        assert_eq!(staking.advance_era(), 1);
+        assert_eq!(staking.era_started_at[&1], 1_000);
+    }
+
+    #[test]
+    fn rewards_are_split_pro_rata_to_active_stake() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 300).unwrap();
+        staking.bond("bob".into(), 100).unwrap();
+        staking.distribute_rewards(400);
+        assert_eq!(staking.ledger_of("alice").unwrap().active, 300 + 300);
+        assert_eq!(staking.ledger_of("bob").unwrap().active, 100 + 100);
+    }
+
+    #[test]
+    fn distribute_rewards_is_a_no_op_with_no_active_stake() {
+        let mut staking = staking();
+        staking.distribute_rewards(1_000);
+        assert_eq!(staking.total_active_stake(), 0);
+    }
+
+    #[test]
+    fn slash_hits_active_stake_first() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        assert_eq!(staking.slash("alice", 30), 30);
+        let ledger = staking.ledger_of("alice").unwrap();
+        assert_eq!(ledger.active, 70);
+        assert_eq!(ledger.total, 70);
+    }
+
+    #[test]
+    fn slash_reaches_into_unbonding_chunks_once_active_is_exhausted() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        staking.unbond("alice", 80).unwrap();
+        // 20 active, 80 unlocking.
+        assert_eq!(staking.slash("alice", 50), 50);
+        let ledger = staking.ledger_of("alice").unwrap();
+        assert_eq!(ledger.active, 0);
+        assert_eq!(ledger.unlocking[0].value, 50);
+        assert_eq!(ledger.total, 50);
+    }
+
+    #[test]
+    fn slash_caps_at_the_ledger_total() {
+        let mut staking = staking();
+        staking.bond("alice".into(), 100).unwrap();
+        assert_eq!(staking.slash("alice", 500), 100);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 0);
+    }
+
+    #[test]
+    fn slash_on_an_unbonded_account_is_a_no_op() {
+        let mut staking = staking();
+        assert_eq!(staking.slash("ghost", 10), 0);
+    }
+}
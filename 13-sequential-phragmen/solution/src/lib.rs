@@ -0,0 +1,226 @@
+//! Solution for Task 13: sequential Phragmén, the method `pallet-staking`
+//! (via `sp-npos-elections`) uses to turn nominator approvals into an
+//! elected validator set. This isn't a port of `sp-npos-elections` itself -
+//! that crate runs the whole computation in fixed-point integer arithmetic
+//! and follows it with an iterative balancing pass, both there to make the
+//! result safe and deterministic on chain. This crate keeps the part worth
+//! teaching (electing one candidate at a time by minimizing supporters' max
+//! load) over plain `f64` accounting and skips balancing, so its numbers
+//! won't match `sp-npos-elections` exactly - see the README for the full
+//! scope note.
+
+use std::collections::BTreeMap;
+
+pub type CandidateId = String;
+pub type VoterId = String;
+pub type Stake = u128;
+
+#[derive(Debug, Clone)]
+pub struct Voter {
+    pub id: VoterId,
+    pub stake: Stake,
+    pub approvals: Vec<CandidateId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectionResult {
+    pub winners: Vec<CandidateId>,
+    /// Each voter's final Phragmén load, for inspecting how evenly stake
+    /// ended up spread across winners.
+    pub voter_loads: BTreeMap<VoterId, f64>,
+    /// For each winner, the `(voter, stake)` pairs backing it - a voter's
+    /// stake is split evenly across however many winners it approved.
+    pub support: BTreeMap<CandidateId, Vec<(VoterId, Stake)>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PhragmenError {
+    NoCandidates,
+    NotEnoughCandidates,
+}
+
+/// Elects `num_to_elect` candidates out of `candidates`, using `voters`'
+/// approval stake. See the module doc comment and README for how this
+/// differs from `sp-npos-elections`.
+pub fn sequential_phragmen(
+    candidates: &[CandidateId],
+    voters: &[Voter],
+    num_to_elect: usize,
+) -> Result<ElectionResult, PhragmenError> {
+    if candidates.is_empty() {
+        return Err(PhragmenError::NoCandidates);
+    }
+    if num_to_elect > candidates.len() {
+        return Err(PhragmenError::NotEnoughCandidates);
+    }
+
+    let mut loads: BTreeMap<VoterId, f64> = voters.iter().map(|v| (v.id.clone(), 0.0)).collect();
+    let mut elected = Vec::new();
+    let mut remaining: Vec<&CandidateId> = candidates.iter().collect();
+
+    while elected.len() < num_to_elect {
+        let mut best: Option<(&CandidateId, f64)> = None;
+        for &candidate in &remaining {
+            let supporters: Vec<&Voter> = voters
+                .iter()
+                .filter(|v| v.approvals.contains(candidate))
+                .collect();
+            let total_stake: u128 = supporters.iter().map(|v| v.stake).sum();
+            if total_stake == 0 {
+                continue;
+            }
+            let weighted_load: f64 = supporters
+                .iter()
+                .map(|v| loads[&v.id] * v.stake as f64)
+                .sum();
+            let score = (1.0 + weighted_load) / total_stake as f64;
+            match best {
+                Some((_, best_score)) if score >= best_score => {}
+                _ => best = Some((candidate, score)),
+            }
+        }
+
+        let Some((winner, score)) = best else {
+            break;
+        };
+        let winner = winner.clone();
+        for voter in voters.iter().filter(|v| v.approvals.contains(&winner)) {
+            let load = loads.get_mut(&voter.id).unwrap();
+            if *load < score {
+                *load = score;
+            }
+        }
+        remaining.retain(|c| **c != winner);
+        elected.push(winner);
+    }
+
+    let support = build_support(&elected, voters);
+    Ok(ElectionResult {
+        winners: elected,
+        voter_loads: loads,
+        support,
+    })
+}
+
+fn build_support(
+    elected: &[CandidateId],
+    voters: &[Voter],
+) -> BTreeMap<CandidateId, Vec<(VoterId, Stake)>> {
+    let mut support: BTreeMap<CandidateId, Vec<(VoterId, Stake)>> =
+        elected.iter().map(|c| (c.clone(), Vec::new())).collect();
+    for voter in voters {
+        let backed: Vec<&CandidateId> = elected
+            .iter()
+            .filter(|c| voter.approvals.contains(c))
+            .collect();
+        if backed.is_empty() {
+            continue;
+        }
+        let share = voter.stake / backed.len() as u128;
+        for candidate in backed {
+            support
+                .get_mut(candidate)
+                .unwrap()
+                .push((voter.id.clone(), share));
+        }
+    }
+    support
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voter(id: &str, stake: Stake, approvals: &[&str]) -> Voter {
+        Voter {
+            id: id.to_string(),
+            stake,
+            approvals: approvals.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn candidates(ids: &[&str]) -> Vec<CandidateId> {
+        ids.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn rejects_empty_candidate_list() {
+        let result = sequential_phragmen(&[], &[], 1);
+        assert_eq!(result, Err(PhragmenError::NoCandidates));
+    }
+
+    #[test]
+    fn rejects_electing_more_than_available() {
+        let result = sequential_phragmen(&candidates(&["A"]), &[], 2);
+        assert_eq!(result, Err(PhragmenError::NotEnoughCandidates));
+    }
+
+    #[test]
+    fn candidate_with_no_approvals_is_never_elected() {
+        let voters = vec![voter("v1", 10, &["A"])];
+        let result = sequential_phragmen(&candidates(&["A", "B"]), &voters, 1).unwrap();
+        assert_eq!(result.winners, vec!["A".to_string()]);
+    }
+
+    #[test]
+    fn most_approved_candidate_is_elected_first() {
+        let voters = vec![
+            voter("v1", 10, &["A"]),
+            voter("v2", 10, &["A"]),
+            voter("v3", 10, &["B"]),
+        ];
+        let result = sequential_phragmen(&candidates(&["A", "B"]), &voters, 1).unwrap();
+        // A has twice the backing stake of B, so it minimizes score first.
+        assert_eq!(result.winners, vec!["A".to_string()]);
+    }
+
+    #[test]
+    fn electing_all_candidates_seats_every_approved_one() {
+        let voters = vec![voter("v1", 10, &["A", "B"]), voter("v2", 5, &["B", "C"])];
+        let result = sequential_phragmen(&candidates(&["A", "B", "C"]), &voters, 3).unwrap();
+        let mut winners = result.winners.clone();
+        winners.sort();
+        assert_eq!(winners, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn electing_a_second_seat_favors_voters_not_yet_backing_a_winner() {
+        // v1 and v2 both back A; only v3 backs B. Once A is elected, v1/v2
+        // carry load, so B (backed solely by fresh stake) beats a second
+        // candidate that only v1/v2 also approve.
+        let voters = vec![
+            voter("v1", 10, &["A", "C"]),
+            voter("v2", 10, &["A", "C"]),
+            voter("v3", 10, &["B"]),
+        ];
+        let result = sequential_phragmen(&candidates(&["A", "B", "C"]), &voters, 2).unwrap();
+        assert_eq!(result.winners, vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn support_splits_a_voters_stake_across_its_elected_approvals() {
+        let voters = vec![voter("v1", 100, &["A", "B"])];
+        let result = sequential_phragmen(&candidates(&["A", "B"]), &voters, 2).unwrap();
+        assert_eq!(result.support["A"], vec![("v1".to_string(), 50)]);
+        assert_eq!(result.support["B"], vec![("v1".to_string(), 50)]);
+    }
+
+    #[test]
+    fn support_for_a_winner_never_exceeds_its_approving_stake() {
+        let voters = vec![
+            voter("v1", 30, &["A"]),
+            voter("v2", 20, &["A", "B"]),
+            voter("v3", 10, &["B"]),
+        ];
+        let result = sequential_phragmen(&candidates(&["A", "B"]), &voters, 2).unwrap();
+        for (candidate, backers) in &result.support {
+            let approving_stake: u128 = voters
+                .iter()
+                .filter(|v| v.approvals.contains(candidate))
+                .map(|v| v.stake)
+                .sum();
+            let backed: u128 = backers.iter().map(|(_, s)| s).sum();
+            assert!(backed <= approving_stake);
+        }
+    }
+}
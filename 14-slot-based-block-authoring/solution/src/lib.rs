@@ -0,0 +1,234 @@
+//! Solution for Task 14: a toy slot-authoring simulation covering
+//! round-robin Aura, VRF-threshold BABE (behind a [`VrfStub`] the caller
+//! supplies), and longest-chain fork choice over the resulting blocks.
+//! See the README for what's deliberately left out (a real VRF, the exact
+//! BABE threshold curve, on-chain randomness).
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub type Slot = u64;
+pub type AuthorityId = String;
+pub type BlockHash = u64;
+
+/// Round-robin authoring, the way Aura assigns every slot to exactly one
+/// authority in a fixed rotation.
+#[derive(Debug, Clone)]
+pub struct AuraAuthorities {
+    pub authorities: Vec<AuthorityId>,
+}
+
+impl AuraAuthorities {
+    pub fn author_for_slot(&self, slot: Slot) -> Option<&AuthorityId> {
+        if self.authorities.is_empty() {
+            return None;
+        }
+        self.authorities.get(slot as usize % self.authorities.len())
+    }
+}
+
+/// A pseudo-random output for `(authority, slot)`, standing in for a real
+/// VRF proof. See the README's scope note - this is not cryptography.
+pub trait VrfStub {
+    fn output(&self, authority: &AuthorityId, slot: Slot) -> u64;
+}
+
+/// The provided `VrfStub`: hashes `(seed, authority, slot)` with a plain
+/// `Hasher`. Deterministic given the same seed, and spreads outputs
+/// roughly uniformly over `u64`, which is all a simulation needs.
+pub struct HashVrf {
+    pub seed: u64,
+}
+
+impl VrfStub for HashVrf {
+    fn output(&self, authority: &AuthorityId, slot: Slot) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.seed.hash(&mut hasher);
+        authority.hash(&mut hasher);
+        slot.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BabeAuthority {
+    pub id: AuthorityId,
+    pub weight: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlotClaim {
+    Primary(AuthorityId),
+    Secondary(AuthorityId),
+    Empty,
+}
+
+pub struct BabeAuthoring<'a, V: VrfStub> {
+    pub authorities: &'a [BabeAuthority],
+    pub vrf: V,
+}
+
+impl<V: VrfStub> BabeAuthoring<'_, V> {
+    /// Claims `slot` for whichever authority's VRF output falls under its
+    /// weight-scaled threshold; see the README for how the threshold and
+    /// the primary/secondary/empty fallback ladder work.
+    pub fn claim_slot(&self, slot: Slot) -> SlotClaim {
+        let total_weight: u64 = self.authorities.iter().map(|a| a.weight).sum();
+        if total_weight == 0 {
+            return SlotClaim::Empty;
+        }
+
+        let eligible = self.authorities.iter().filter_map(|authority| {
+            let output = self.vrf.output(&authority.id, slot);
+            let threshold =
+                (authority.weight as u128 * u64::MAX as u128 / total_weight as u128) as u64;
+            (output < threshold).then_some((authority, output))
+        });
+
+        if let Some((winner, _)) = eligible.min_by_key(|(_, output)| *output) {
+            return SlotClaim::Primary(winner.id.clone());
+        }
+
+        let idx = slot as usize % self.authorities.len();
+        SlotClaim::Secondary(self.authorities[idx].id.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub number: u64,
+    pub slot: Slot,
+    pub author: AuthorityId,
+    pub parent_hash: BlockHash,
+    pub hash: BlockHash,
+}
+
+impl Block {
+    pub fn genesis() -> Self {
+        Self {
+            number: 0,
+            slot: 0,
+            author: "genesis".to_string(),
+            parent_hash: 0,
+            hash: 0,
+        }
+    }
+
+    /// Builds the next block on top of `self`, hashing `(number, slot,
+    /// author, parent_hash)` into its own hash.
+    pub fn child(&self, slot: Slot, author: AuthorityId) -> Self {
+        let number = self.number + 1;
+        let mut hasher = DefaultHasher::new();
+        number.hash(&mut hasher);
+        slot.hash(&mut hasher);
+        author.hash(&mut hasher);
+        self.hash.hash(&mut hasher);
+        Self {
+            number,
+            slot,
+            author,
+            parent_hash: self.hash,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+/// Longest-chain fork choice: the tip with the greatest `number` wins,
+/// ties broken by the smaller hash so the choice stays deterministic when
+/// two tips sit at the same height.
+pub fn longest_chain_tip(tips: &[Block]) -> Option<&Block> {
+    tips.iter().fold(None, |best, block| match best {
+        None => Some(block),
+        Some(current) => {
+            let block_wins = block.number > current.number
+                || (block.number == current.number && block.hash < current.hash);
+            Some(if block_wins { block } else { current })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn aura_round_robins_through_authorities() {
+        let aura = AuraAuthorities {
+            authorities: vec!["alice".into(), "bob".into(), "carol".into()],
+        };
+        assert_eq!(aura.author_for_slot(0).unwrap(), "alice");
+        assert_eq!(aura.author_for_slot(1).unwrap(), "bob");
+        assert_eq!(aura.author_for_slot(3).unwrap(), "alice");
+    }
+
+    #[test]
+    fn aura_with_no_authorities_authors_nothing() {
+        let aura = AuraAuthorities { authorities: vec![] };
+        assert_eq!(aura.author_for_slot(0), None);
+    }
+
+    struct StaticVrf(HashMap<(AuthorityId, Slot), u64>);
+
+    impl VrfStub for StaticVrf {
+        fn output(&self, authority: &AuthorityId, slot: Slot) -> u64 {
+            *self.0.get(&(authority.clone(), slot)).unwrap_or(&u64::MAX)
+        }
+    }
+
+    fn babe_authorities() -> Vec<BabeAuthority> {
+        vec![
+            BabeAuthority { id: "alice".into(), weight: 1 },
+            BabeAuthority { id: "bob".into(), weight: 1 },
+        ]
+    }
+
+    #[test]
+    fn babe_claims_primary_for_the_lowest_eligible_output() {
+        let authorities = babe_authorities();
+        let vrf = StaticVrf(HashMap::from([
+            (("alice".to_string(), 0), 10),
+            (("bob".to_string(), 0), 5),
+        ]));
+        let babe = BabeAuthoring { authorities: &authorities, vrf };
+        assert_eq!(babe.claim_slot(0), SlotClaim::Primary("bob".to_string()));
+    }
+
+    #[test]
+    fn babe_falls_back_to_secondary_when_nobody_is_eligible() {
+        let authorities = babe_authorities();
+        let vrf = StaticVrf(HashMap::new()); // unwrap_or(u64::MAX) -> nobody under threshold
+        let babe = BabeAuthoring { authorities: &authorities, vrf };
+        assert_eq!(babe.claim_slot(1), SlotClaim::Secondary("bob".to_string()));
+    }
+
+    #[test]
+    fn babe_with_zero_total_weight_is_empty() {
+        let authorities = vec![BabeAuthority { id: "alice".into(), weight: 0 }];
+        let vrf = StaticVrf(HashMap::new());
+        let babe = BabeAuthoring { authorities: &authorities, vrf };
+        assert_eq!(babe.claim_slot(0), SlotClaim::Empty);
+    }
+
+    #[test]
+    fn longest_chain_wins_on_height() {
+        let genesis = Block::genesis();
+        let short = genesis.child(1, "alice".into());
+        let mid = short.child(2, "bob".into());
+        let long = mid.child(3, "carol".into());
+        let tips = [short.clone(), long.clone()];
+        let tip = longest_chain_tip(&tips).unwrap();
+        assert_eq!(tip, &long);
+    }
+
+    #[test]
+    fn equal_height_tips_break_ties_on_the_smaller_hash() {
+        let genesis = Block::genesis();
+        let a = genesis.child(1, "alice".into());
+        let b = genesis.child(1, "bob".into());
+        let expected = if a.hash < b.hash { &a } else { &b };
+        let tips = [a.clone(), b.clone()];
+        let tip = longest_chain_tip(&tips).unwrap();
+        assert_eq!(tip, expected);
+    }
+}
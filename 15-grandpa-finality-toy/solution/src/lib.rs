@@ -0,0 +1,290 @@
+//! Solution for Task 15: a single-round GRANDPA finality simulation -
+//! weighted prevote/precommit tallies over a block tree, with ancestry-
+//! aware supermajority tallying and structural equivocation detection.
+//! See the README for how this reuses Task 14's `Block` and what's
+//! deliberately left out (multiple rounds, real signatures, gossip).
+
+use std::collections::BTreeMap;
+
+pub use slot_based_block_authoring::Block;
+
+pub type BlockHash = u64;
+pub type Weight = u64;
+pub type VoterId = String;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GrandpaError {
+    UnknownBlock,
+    Equivocation { voter: VoterId },
+}
+
+#[derive(Debug, Clone)]
+pub struct Voter {
+    pub id: VoterId,
+    pub weight: Weight,
+}
+
+/// A connected set of blocks rooted at a genesis block, indexed by hash.
+#[derive(Debug, Clone)]
+pub struct BlockTree {
+    blocks: BTreeMap<BlockHash, Block>,
+    genesis_hash: BlockHash,
+}
+
+impl BlockTree {
+    pub fn new(genesis: Block) -> Self {
+        let genesis_hash = genesis.hash;
+        let mut blocks = BTreeMap::new();
+        blocks.insert(genesis_hash, genesis);
+        Self { blocks, genesis_hash }
+    }
+
+    /// Inserts `block`, failing if its parent isn't already in the tree.
+    pub fn insert(&mut self, block: Block) -> Result<(), GrandpaError> {
+        if !self.blocks.contains_key(&block.parent_hash) {
+            return Err(GrandpaError::UnknownBlock);
+        }
+        self.blocks.insert(block.hash, block);
+        Ok(())
+    }
+
+    pub fn contains(&self, hash: BlockHash) -> bool {
+        self.blocks.contains_key(&hash)
+    }
+
+    pub fn get(&self, hash: BlockHash) -> Option<&Block> {
+        self.blocks.get(&hash)
+    }
+
+    pub fn parent_of(&self, hash: BlockHash) -> Option<BlockHash> {
+        let block = self.blocks.get(&hash)?;
+        (block.hash != self.genesis_hash).then_some(block.parent_hash)
+    }
+
+    /// Walks `descendant`'s parent chain looking for `ancestor`. A block
+    /// is its own ancestor.
+    pub fn is_ancestor(&self, ancestor: BlockHash, descendant: BlockHash) -> bool {
+        let mut cursor = descendant;
+        loop {
+            if cursor == ancestor {
+                return true;
+            }
+            match self.parent_of(cursor) {
+                Some(parent) => cursor = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+/// One voting stage (prevote or precommit) over a shared `BlockTree`.
+pub struct VoteTally<'a> {
+    tree: &'a BlockTree,
+    total_weight: Weight,
+    votes: BTreeMap<VoterId, (BlockHash, Weight)>,
+}
+
+impl<'a> VoteTally<'a> {
+    pub fn new(tree: &'a BlockTree, voters: &[Voter]) -> Self {
+        Self {
+            tree,
+            total_weight: voters.iter().map(|v| v.weight).sum(),
+            votes: BTreeMap::new(),
+        }
+    }
+
+    /// Records `voter`'s vote for `block`. Re-voting for the same block is
+    /// a no-op; voting for a different block than an existing vote is an
+    /// equivocation.
+    pub fn vote(&mut self, voter: VoterId, weight: Weight, block: BlockHash) -> Result<(), GrandpaError> {
+        if !self.tree.contains(block) {
+            return Err(GrandpaError::UnknownBlock);
+        }
+        if let Some((existing_block, _)) = self.votes.get(&voter) {
+            if *existing_block != block {
+                return Err(GrandpaError::Equivocation { voter });
+            }
+            return Ok(());
+        }
+        self.votes.insert(voter, (block, weight));
+        Ok(())
+    }
+
+    /// Tallies every vote's weight onto the voted block and all of its
+    /// ancestors, then returns the deepest block carrying at least 2/3 of
+    /// the total voting weight.
+    pub fn supermajority_block(&self) -> Option<BlockHash> {
+        if self.total_weight == 0 {
+            return None;
+        }
+        let threshold = self.total_weight * 2 / 3 + 1;
+
+        let mut totals: BTreeMap<BlockHash, Weight> = BTreeMap::new();
+        for (block, weight) in self.votes.values() {
+            let mut cursor = *block;
+            loop {
+                *totals.entry(cursor).or_insert(0) += weight;
+                match self.tree.parent_of(cursor) {
+                    Some(parent) => cursor = parent,
+                    None => break,
+                }
+            }
+        }
+
+        totals
+            .into_iter()
+            .filter(|(_, weight)| *weight >= threshold)
+            .filter_map(|(hash, _)| self.tree.get(hash).map(|b| (hash, b.number)))
+            .max_by_key(|(_, number)| *number)
+            .map(|(hash, _)| hash)
+    }
+}
+
+/// A full GRANDPA round: a prevote stage followed by a precommit stage
+/// over the same tree and voter set.
+pub struct Round<'a> {
+    pub prevotes: VoteTally<'a>,
+    pub precommits: VoteTally<'a>,
+}
+
+impl<'a> Round<'a> {
+    pub fn new(tree: &'a BlockTree, voters: &[Voter]) -> Self {
+        Self {
+            prevotes: VoteTally::new(tree, voters),
+            precommits: VoteTally::new(tree, voters),
+        }
+    }
+
+    /// Finalizes the round if the precommit supermajority block is at
+    /// least as deep as, and a descendant of, the prevote supermajority
+    /// block - GRANDPA never finalizes on precommits alone.
+    pub fn finalize(&self) -> Option<BlockHash> {
+        let tree = self.prevotes.tree;
+        let prevote_block = self.prevotes.supermajority_block()?;
+        let precommit_block = self.precommits.supermajority_block()?;
+        let prevote_number = tree.get(prevote_block)?.number;
+        let precommit_number = tree.get(precommit_block)?.number;
+
+        if precommit_number >= prevote_number && tree.is_ancestor(prevote_block, precommit_block) {
+            Some(precommit_block)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: u64) -> (BlockTree, Vec<Block>) {
+        let genesis = Block::genesis();
+        let mut blocks = vec![genesis.clone()];
+        let mut tree = BlockTree::new(genesis);
+        for i in 1..=len {
+            let parent = blocks.last().unwrap();
+            let block = parent.child(i, format!("author-{i}"));
+            tree.insert(block.clone()).unwrap();
+            blocks.push(block);
+        }
+        (tree, blocks)
+    }
+
+    fn voters() -> Vec<Voter> {
+        vec![
+            Voter { id: "v1".into(), weight: 1 },
+            Voter { id: "v2".into(), weight: 1 },
+            Voter { id: "v3".into(), weight: 1 },
+            Voter { id: "v4".into(), weight: 1 },
+        ]
+    }
+
+    #[test]
+    fn insert_rejects_a_block_with_an_unknown_parent() {
+        let (mut tree, _) = chain(1);
+        let orphan = Block { number: 5, slot: 5, author: "x".into(), parent_hash: 999, hash: 1234 };
+        assert_eq!(tree.insert(orphan), Err(GrandpaError::UnknownBlock));
+    }
+
+    #[test]
+    fn is_ancestor_walks_the_parent_chain() {
+        let (tree, blocks) = chain(3);
+        assert!(tree.is_ancestor(blocks[0].hash, blocks[3].hash));
+        assert!(tree.is_ancestor(blocks[3].hash, blocks[3].hash));
+        assert!(!tree.is_ancestor(blocks[3].hash, blocks[0].hash));
+    }
+
+    #[test]
+    fn vote_for_an_unknown_block_is_rejected() {
+        let (tree, _) = chain(1);
+        let mut tally = VoteTally::new(&tree, &voters());
+        assert_eq!(tally.vote("v1".into(), 1, 999), Err(GrandpaError::UnknownBlock));
+    }
+
+    #[test]
+    fn voting_for_a_different_block_is_an_equivocation() {
+        let (tree, blocks) = chain(2);
+        let mut tally = VoteTally::new(&tree, &voters());
+        tally.vote("v1".into(), 1, blocks[1].hash).unwrap();
+        assert_eq!(
+            tally.vote("v1".into(), 1, blocks[2].hash),
+            Err(GrandpaError::Equivocation { voter: "v1".into() })
+        );
+    }
+
+    #[test]
+    fn repeating_the_same_vote_is_not_an_equivocation() {
+        let (tree, blocks) = chain(2);
+        let mut tally = VoteTally::new(&tree, &voters());
+        tally.vote("v1".into(), 1, blocks[1].hash).unwrap();
+        assert_eq!(tally.vote("v1".into(), 1, blocks[1].hash), Ok(()));
+    }
+
+    #[test]
+    fn supermajority_requires_two_thirds_of_total_weight() {
+        let (tree, blocks) = chain(1);
+        let mut tally = VoteTally::new(&tree, &voters());
+        tally.vote("v1".into(), 1, blocks[1].hash).unwrap();
+        tally.vote("v2".into(), 1, blocks[1].hash).unwrap();
+        assert_eq!(tally.supermajority_block(), None);
+        tally.vote("v3".into(), 1, blocks[1].hash).unwrap();
+        assert_eq!(tally.supermajority_block(), Some(blocks[1].hash));
+    }
+
+    #[test]
+    fn supermajority_counts_votes_for_descendants_toward_their_ancestors() {
+        let (tree, blocks) = chain(2);
+        let mut tally = VoteTally::new(&tree, &voters());
+        // v1 votes for block 1; v2-v4 vote for block 2, a descendant of
+        // block 1. Block 1 reaches supermajority purely from votes cast
+        // for its descendant, but block 2 (deeper, and itself at
+        // supermajority) is still the one returned.
+        tally.vote("v1".into(), 1, blocks[1].hash).unwrap();
+        tally.vote("v2".into(), 1, blocks[2].hash).unwrap();
+        tally.vote("v3".into(), 1, blocks[2].hash).unwrap();
+        tally.vote("v4".into(), 1, blocks[2].hash).unwrap();
+        assert_eq!(tally.supermajority_block(), Some(blocks[2].hash));
+    }
+
+    #[test]
+    fn round_finalizes_when_prevote_and_precommit_agree() {
+        let (tree, blocks) = chain(2);
+        let mut round = Round::new(&tree, &voters());
+        for voter in ["v1", "v2", "v3"] {
+            round.prevotes.vote(voter.into(), 1, blocks[2].hash).unwrap();
+            round.precommits.vote(voter.into(), 1, blocks[2].hash).unwrap();
+        }
+        assert_eq!(round.finalize(), Some(blocks[2].hash));
+    }
+
+    #[test]
+    fn round_does_not_finalize_without_a_precommit_supermajority() {
+        let (tree, blocks) = chain(2);
+        let mut round = Round::new(&tree, &voters());
+        for voter in ["v1", "v2", "v3"] {
+            round.prevotes.vote(voter.into(), 1, blocks[2].hash).unwrap();
+        }
+        round.precommits.vote("v1".into(), 1, blocks[2].hash).unwrap();
+        assert_eq!(round.finalize(), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Solution for Task 17: the hash functions Substrate storage keys are
+//! built from, plus the storage-key derivation itself. See the README for
+//! why `twox_128` isn't a single hash call and why `Blake2_128Concat`
+//! appends the raw key after its hash.
+
+use std::hash::Hasher;
+
+use blake2::digest::consts::{U16, U32};
+use blake2::{Blake2b, Digest};
+use sha3::Keccak256;
+
+pub fn blake2_128(data: &[u8]) -> [u8; 16] {
+    let mut hasher = Blake2b::<U16>::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn xxh64(seed: u64, data: &[u8]) -> [u8; 8] {
+    let mut hasher = twox_hash::XxHash64::with_seed(seed);
+    hasher.write(data);
+    hasher.finish().to_le_bytes()
+}
+
+pub fn twox_64(data: &[u8]) -> [u8; 8] {
+    xxh64(0, data)
+}
+
+/// Two `XxHash64` digests, seeded `0` and `1`, concatenated - Substrate's
+/// own `twox_128`, not a single wider xxHash variant.
+pub fn twox_128(data: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    out[..8].copy_from_slice(&xxh64(0, data));
+    out[8..].copy_from_slice(&xxh64(1, data));
+    out
+}
+
+pub fn keccak_256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// The 32-byte prefix every entry of the `pallet`/`item` storage item
+/// lives under, regardless of whether it's a plain value or a map.
+pub fn storage_prefix(pallet: &str, item: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[..16].copy_from_slice(&twox_128(pallet.as_bytes()));
+    out[16..].copy_from_slice(&twox_128(item.as_bytes()));
+    out
+}
+
+/// `Blake2_128Concat`: the key's hash followed by the key itself, so a
+/// raw storage key can be walked back to the original key it came from.
+pub fn blake2_128_concat(key: &[u8]) -> Vec<u8> {
+    let mut out = blake2_128(key).to_vec();
+    out.extend_from_slice(key);
+    out
+}
+
+/// The full raw storage key for one `StorageMap` entry hashed with
+/// `Blake2_128Concat` - `storage_prefix(pallet, item) ++
+/// blake2_128_concat(key)`.
+pub fn storage_map_key(pallet: &str, item: &str, key: &[u8]) -> Vec<u8> {
+    let mut out = storage_prefix(pallet, item).to_vec();
+    out.extend_from_slice(&blake2_128_concat(key));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twox_128_matches_the_well_known_system_account_prefix() {
+        // twox_128("System") ++ twox_128("Account") - the raw storage
+        // prefix under which every account's `System::Account` entry
+        // lives on any Substrate-based chain.
+        assert_eq!(
+            storage_prefix("System", "Account"),
+            [
+                0x26, 0xaa, 0x39, 0x4e, 0xea, 0x56, 0x30, 0xe0, 0x7c, 0x48, 0xae, 0x0c, 0x95,
+                0x58, 0xce, 0xf7, 0xb9, 0x9d, 0x88, 0x0e, 0xc6, 0x81, 0x79, 0x9c, 0x0c, 0xf3,
+                0x0e, 0x88, 0x86, 0x37, 0x1d, 0xa9,
+            ]
+        );
+    }
+
+    #[test]
+    fn twox_128_matches_the_well_known_sudo_key_prefix() {
+        assert_eq!(
+            storage_prefix("Sudo", "Key"),
+            [
+                0x5c, 0x0d, 0x11, 0x76, 0xa5, 0x68, 0xc1, 0xf9, 0x29, 0x44, 0x34, 0x0d, 0xbf,
+                0xed, 0x9e, 0x9c, 0x53, 0x0e, 0xbc, 0xa7, 0x03, 0xc8, 0x59, 0x10, 0xe7, 0x16,
+                0x4c, 0xb7, 0xd1, 0xc9, 0xe4, 0x7b,
+            ]
+        );
+    }
+
+    #[test]
+    fn twox_64_is_the_first_half_of_twox_128() {
+        assert_eq!(twox_64(b"System"), twox_128(b"System")[..8]);
+    }
+
+    #[test]
+    fn blake2_128_concat_ends_with_the_original_key() {
+        let key = b"alice";
+        let concatenated = blake2_128_concat(key);
+        assert_eq!(concatenated.len(), 16 + key.len());
+        assert_eq!(&concatenated[16..], key);
+        assert_eq!(&concatenated[..16], blake2_128(key));
+    }
+
+    #[test]
+    fn storage_map_key_starts_with_the_pallet_item_prefix() {
+        let key = storage_map_key("System", "Account", b"alice");
+        assert_eq!(&key[..32], storage_prefix("System", "Account"));
+        assert_eq!(&key[32..], blake2_128_concat(b"alice"));
+    }
+
+    #[test]
+    fn blake2_128_and_blake2_256_differ_in_length_not_just_content() {
+        assert_eq!(blake2_128(b"hello").len(), 16);
+        assert_eq!(blake2_256(b"hello").len(), 32);
+    }
+
+    #[test]
+    fn keccak_256_is_deterministic_and_content_dependent() {
+        assert_eq!(keccak_256(b"hello"), keccak_256(b"hello"));
+        assert_ne!(keccak_256(b"hello"), keccak_256(b"world"));
+    }
+}
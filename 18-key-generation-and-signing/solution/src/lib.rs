@@ -0,0 +1,243 @@
+//! Solution for Task 18: sr25519/ed25519 keypair generation, signing, and
+//! verification via `sp-core`, plus a [`Keyring`] that wires those keys
+//! into Task 1's `voting_state_machine::SignatureVerifier` mode. See the
+//! README for why HDKD derivation is only offered for sr25519.
+
+use std::collections::BTreeMap;
+
+use sp_core::{ed25519, sr25519, DeriveJunction, Pair};
+use voting_state_machine::{SignatureVerifier, UserId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Sr25519,
+    Ed25519,
+}
+
+/// A generated keypair, kept as the concrete `sp_core` pair rather than
+/// raw bytes so signing never has to re-parse a key.
+pub enum Keypair {
+    Sr25519(Box<sr25519::Pair>),
+    Ed25519(Box<ed25519::Pair>),
+}
+
+impl Keypair {
+    pub fn generate(scheme: Scheme) -> Self {
+        match scheme {
+            Scheme::Sr25519 => Keypair::Sr25519(Box::new(sr25519::Pair::generate().0)),
+            Scheme::Ed25519 => Keypair::Ed25519(Box::new(ed25519::Pair::generate().0)),
+        }
+    }
+
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Keypair::Sr25519(_) => Scheme::Sr25519,
+            Keypair::Ed25519(_) => Scheme::Ed25519,
+        }
+    }
+
+    pub fn public_bytes(&self) -> [u8; 32] {
+        match self {
+            Keypair::Sr25519(pair) => pair.public().0,
+            Keypair::Ed25519(pair) => pair.public().0,
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        match self {
+            Keypair::Sr25519(pair) => pair.sign(message).0,
+            Keypair::Ed25519(pair) => pair.sign(message).0,
+        }
+    }
+
+    /// (stretch) HDKD: derives a soft child key from `self` using `index`
+    /// as the derivation junction - the same mechanism behind Substrate's
+    /// `//Alice//stash`-style derivation paths. Only offered for sr25519:
+    /// `sp-core` doesn't implement derivation for ed25519 at all, since
+    /// soft derivation isn't safe for a scheme whose public key is a hash
+    /// of its seed.
+    pub fn derive_soft_child(&self, index: u8) -> Option<Keypair> {
+        match self {
+            Keypair::Sr25519(pair) => {
+                let junction = DeriveJunction::soft(index);
+                let (derived, _) = pair.derive(std::iter::once(junction), None).ok()?;
+                Some(Keypair::Sr25519(Box::new(derived)))
+            }
+            Keypair::Ed25519(_) => None,
+        }
+    }
+}
+
+/// Verifies a standalone `(scheme, public key, message, signature)` tuple,
+/// independent of any [`Keyring`] - the building block [`Keyring::verify`]
+/// is implemented on top of.
+pub fn verify(scheme: Scheme, public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    match scheme {
+        Scheme::Sr25519 => sr25519::Pair::verify(
+            &sr25519::Signature::from_raw(*signature),
+            message,
+            &sr25519::Public::from_raw(*public),
+        ),
+        Scheme::Ed25519 => ed25519::Pair::verify(
+            &ed25519::Signature::from_raw(*signature),
+            message,
+            &ed25519::Public::from_raw(*public),
+        ),
+    }
+}
+
+/// Maps each registered voter's [`UserId`] to the `(scheme, public key)`
+/// they sign votes with, and implements `SignatureVerifier` so it can be
+/// handed straight to `VotingState::cast_vote_signed`.
+#[derive(Debug, Default, Clone)]
+pub struct Keyring {
+    keys: BTreeMap<UserId, (Scheme, [u8; 32])>,
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, user_id: UserId, keypair: &Keypair) {
+        self.keys
+            .insert(user_id, (keypair.scheme(), keypair.public_bytes()));
+    }
+}
+
+impl SignatureVerifier for Keyring {
+    fn verify(&self, signer: &UserId, message: &[u8], signature: &[u8]) -> bool {
+        let Some((scheme, public)) = self.keys.get(signer) else {
+            return false;
+        };
+        let Ok(signature) = <[u8; 64]>::try_from(signature) else {
+            return false;
+        };
+        verify(*scheme, public, message, &signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voting_state_machine::{signed_vote_message, Action, VotingState};
+
+    #[test]
+    fn sr25519_roundtrips_through_sign_and_verify() {
+        let pair = Keypair::generate(Scheme::Sr25519);
+        let signature = pair.sign(b"hello");
+        assert!(verify(
+            Scheme::Sr25519,
+            &pair.public_bytes(),
+            b"hello",
+            &signature
+        ));
+        assert!(!verify(
+            Scheme::Sr25519,
+            &pair.public_bytes(),
+            b"goodbye",
+            &signature
+        ));
+    }
+
+    #[test]
+    fn ed25519_roundtrips_through_sign_and_verify() {
+        let pair = Keypair::generate(Scheme::Ed25519);
+        let signature = pair.sign(b"hello");
+        assert!(verify(
+            Scheme::Ed25519,
+            &pair.public_bytes(),
+            b"hello",
+            &signature
+        ));
+        assert!(!verify(
+            Scheme::Ed25519,
+            &pair.public_bytes(),
+            b"goodbye",
+            &signature
+        ));
+    }
+
+    #[test]
+    fn derived_sr25519_child_signs_with_a_different_key_than_its_parent() {
+        let parent = Keypair::generate(Scheme::Sr25519);
+        let child = parent.derive_soft_child(1).unwrap();
+        assert_ne!(parent.public_bytes(), child.public_bytes());
+
+        let signature = child.sign(b"hello");
+        assert!(verify(
+            Scheme::Sr25519,
+            &child.public_bytes(),
+            b"hello",
+            &signature
+        ));
+        assert!(!verify(
+            Scheme::Sr25519,
+            &parent.public_bytes(),
+            b"hello",
+            &signature
+        ));
+    }
+
+    #[test]
+    fn ed25519_has_no_derivation_support() {
+        let pair = Keypair::generate(Scheme::Ed25519);
+        assert!(pair.derive_soft_child(1).is_none());
+    }
+
+    #[test]
+    fn keyring_wires_up_cast_vote_signed() {
+        let mut state = VotingState::new("admin".to_string());
+        state.add_vote_option("A".to_string());
+        state.register_voter("alice".to_string()).unwrap();
+        voting_state_machine::transition(
+            &mut state,
+            "admin".to_string(),
+            Action::StartVoting { duration: 10 },
+            0,
+        )
+        .unwrap();
+
+        let alice = Keypair::generate(Scheme::Sr25519);
+        let mut keyring = Keyring::new();
+        keyring.register("alice".to_string(), &alice);
+
+        let message = signed_vote_message(&"alice".to_string(), &"A".to_string());
+        let signature = alice.sign(&message);
+
+        state
+            .cast_vote_signed(
+                "alice".to_string(),
+                "A".to_string(),
+                1,
+                &signature,
+                &keyring,
+            )
+            .unwrap();
+        assert_eq!(state.vote_counts["A"], 1);
+    }
+
+    #[test]
+    fn keyring_rejects_a_signature_from_an_unregistered_signer() {
+        let mut state = VotingState::new("admin".to_string());
+        state.add_vote_option("A".to_string());
+        state.register_voter("alice".to_string()).unwrap();
+        voting_state_machine::transition(
+            &mut state,
+            "admin".to_string(),
+            Action::StartVoting { duration: 10 },
+            0,
+        )
+        .unwrap();
+
+        let mallory = Keypair::generate(Scheme::Sr25519);
+        let keyring = Keyring::new();
+        let message = signed_vote_message(&"alice".to_string(), &"A".to_string());
+        let signature = mallory.sign(&message);
+
+        assert_eq!(
+            state.cast_vote_signed("alice".to_string(), "A".to_string(), 1, &signature, &keyring),
+            Err(voting_state_machine::VoteError::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Solution for Task 19: SS58 address encode/decode from scratch, plus an
+//! [`Ss58Validator`] that plugs SS58-checked addresses into Task 1's
+//! voting library as a `UserIdValidator`. See the README for the checksum
+//! and multi-byte prefix format this is built from.
+
+use base58::{FromBase58, ToBase58};
+use blake2::{Blake2b512, Digest};
+use voting_state_machine::{UserId, UserIdValidator};
+
+const SS58_PREFIX: &[u8] = b"SS58PRE";
+const CHECKSUM_LEN: usize = 2;
+const ACCOUNT_ID_LEN: usize = 32;
+
+pub const POLKADOT: u16 = 0;
+pub const KUSAMA: u16 = 2;
+pub const GENERIC_SUBSTRATE: u16 = 42;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Ss58Error {
+    InvalidBase58,
+    TooShort,
+    PrefixOutOfRange,
+    UnexpectedLength,
+    BadChecksum,
+}
+
+fn ss58_hash(data: &[u8]) -> [u8; 64] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(SS58_PREFIX);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Network-prefix bytes per the SS58 spec: one byte for idents `0..=63`,
+/// two bytes (the ident's bits packed across both, with a `01` tag in the
+/// first byte's top two bits) for `64..=16_383`.
+fn encode_prefix(prefix: u16) -> Result<Vec<u8>, Ss58Error> {
+    match prefix {
+        0..=63 => Ok(vec![prefix as u8]),
+        64..=16_383 => {
+            let first = ((prefix & 0b0000_0000_1111_1100) >> 2) as u8;
+            let second = ((prefix >> 8) as u8) | (((prefix & 0b0000_0000_0000_0011) as u8) << 6);
+            Ok(vec![first | 0b0100_0000, second])
+        }
+        _ => Err(Ss58Error::PrefixOutOfRange),
+    }
+}
+
+/// Inverse of [`encode_prefix`]; returns `(prefix, bytes consumed)`.
+fn decode_prefix(body: &[u8]) -> Result<(u16, usize), Ss58Error> {
+    match body.first() {
+        None => Err(Ss58Error::TooShort),
+        Some(&first) if first & 0b1100_0000 != 0b0100_0000 => Ok((first as u16, 1)),
+        Some(&first) => {
+            let second = *body.get(1).ok_or(Ss58Error::TooShort)?;
+            let ident_low6 = (first & 0b0011_1111) as u16;
+            let ident_bits0_1 = ((second >> 6) & 0b11) as u16;
+            let ident_bits8_13 = (second & 0b0011_1111) as u16;
+            let prefix = (ident_bits8_13 << 8) | (ident_low6 << 2) | ident_bits0_1;
+            Ok((prefix, 2))
+        }
+    }
+}
+
+/// Encodes a 32-byte account id (an sr25519/ed25519 public key) under
+/// `prefix` as an SS58 address.
+pub fn encode(prefix: u16, account_id: &[u8; ACCOUNT_ID_LEN]) -> Result<String, Ss58Error> {
+    let mut body = encode_prefix(prefix)?;
+    body.extend_from_slice(account_id);
+    let checksum = ss58_hash(&body);
+    body.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+    Ok(body.to_base58())
+}
+
+/// Decodes an SS58 address back into its network prefix and 32-byte
+/// account id, recomputing and checking the checksum along the way.
+pub fn decode(address: &str) -> Result<(u16, [u8; ACCOUNT_ID_LEN]), Ss58Error> {
+    let body = address.from_base58().map_err(|_| Ss58Error::InvalidBase58)?;
+    let (prefix, prefix_len) = decode_prefix(&body)?;
+    if body.len() != prefix_len + ACCOUNT_ID_LEN + CHECKSUM_LEN {
+        return Err(Ss58Error::UnexpectedLength);
+    }
+    let unchecked = &body[..prefix_len + ACCOUNT_ID_LEN];
+    let claimed_checksum = &body[prefix_len + ACCOUNT_ID_LEN..];
+    let expected_checksum = ss58_hash(unchecked);
+    if claimed_checksum != &expected_checksum[..CHECKSUM_LEN] {
+        return Err(Ss58Error::BadChecksum);
+    }
+    let mut account_id = [0u8; ACCOUNT_ID_LEN];
+    account_id.copy_from_slice(&unchecked[prefix_len..]);
+    Ok((prefix, account_id))
+}
+
+/// A `UserIdValidator` that only accepts `UserId`s which are well-formed
+/// SS58 addresses for `prefix` - the voter-id validator option the voting
+/// library can opt into, in place of accepting any string.
+pub struct Ss58Validator {
+    pub prefix: u16,
+}
+
+impl UserIdValidator for Ss58Validator {
+    fn validate(&self, user_id: &UserId) -> bool {
+        matches!(decode(user_id), Ok((prefix, _)) if prefix == self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voting_state_machine::{VoteError, VotingState};
+
+    // The sr25519 public key for the well-known development account
+    // "Alice" - the addresses below are real SS58 addresses for it,
+    // cross-checked against `sp_core::crypto::Ss58Codec`.
+    const ALICE: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+        0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+        0xa2, 0x7,
+    ];
+
+    #[test]
+    fn encodes_alice_as_a_known_polkadot_address() {
+        assert_eq!(
+            encode(POLKADOT, &ALICE).unwrap(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHqQrST"
+        );
+    }
+
+    #[test]
+    fn encodes_alice_as_a_known_kusama_address() {
+        assert_eq!(
+            encode(KUSAMA, &ALICE).unwrap(),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5UoyR3w"
+        );
+    }
+
+    #[test]
+    fn encodes_alice_as_a_known_generic_substrate_address() {
+        assert_eq!(
+            encode(GENERIC_SUBSTRATE, &ALICE).unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKEVsw"
+        );
+    }
+
+    #[test]
+    fn round_trips_through_a_two_byte_prefix() {
+        let address = encode(16_383, &ALICE).unwrap();
+        assert_eq!(decode(&address).unwrap(), (16_383, ALICE));
+    }
+
+    #[test]
+    fn decodes_each_known_address_back_to_alice() {
+        for (prefix, address) in [
+            (POLKADOT, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHqQrST"),
+            (KUSAMA, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5UoyR3w"),
+            (
+                GENERIC_SUBSTRATE,
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKEVsw",
+            ),
+        ] {
+            assert_eq!(decode(address).unwrap(), (prefix, ALICE));
+        }
+    }
+
+    #[test]
+    fn rejects_a_flipped_checksum_byte() {
+        let mut address = encode(GENERIC_SUBSTRATE, &ALICE).unwrap();
+        address.replace_range(0..1, if address.starts_with('5') { "6" } else { "5" });
+        assert!(matches!(
+            decode(&address),
+            Err(Ss58Error::BadChecksum) | Err(Ss58Error::InvalidBase58)
+        ));
+    }
+
+    #[test]
+    fn prefix_above_16383_is_rejected() {
+        assert_eq!(encode(16_384, &ALICE), Err(Ss58Error::PrefixOutOfRange));
+    }
+
+    #[test]
+    fn ss58_validator_wires_into_register_voter_checked() {
+        let mut state = VotingState::new("admin".to_string());
+        let validator = Ss58Validator {
+            prefix: GENERIC_SUBSTRATE,
+        };
+        let alice_address = encode(GENERIC_SUBSTRATE, &ALICE).unwrap();
+
+        state
+            .register_voter_checked(alice_address.clone(), &validator)
+            .unwrap();
+        assert_eq!(state.registered_voters, std::collections::HashSet::from([alice_address]));
+
+        assert_eq!(
+            state.register_voter_checked("not an address".to_string(), &validator),
+            Err(VoteError::InvalidUserId)
+        );
+    }
+
+    #[test]
+    fn ss58_validator_rejects_an_address_for_a_different_network() {
+        let validator = Ss58Validator { prefix: POLKADOT };
+        let kusama_address = encode(KUSAMA, &ALICE).unwrap();
+        assert!(!validator.validate(&kusama_address));
+    }
+}
@@ -0,0 +1,242 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use voting_contract::voting_contract::VotingContractRef;
+
+#[ink::contract]
+mod wishlist {
+    use super::VotingContractRef;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum WishlistError {
+        NotOwner,
+        ItemNotFound,
+        NoLinkedRound,
+        ProposalNotPassed,
+        AlreadyWithdrawn,
+        TransferFailed,
+    }
+
+    #[ink(storage)]
+    pub struct Wishlist {
+        owner: AccountId,
+        items: Vec<String>,
+        voting_contract: AccountId,
+        /// Voting round whose approval gates spending on an item, set by
+        /// `link_round` once the owner opens a proposal for it.
+        item_round: Mapping<u32, u32>,
+        withdrawn: Mapping<u32, ()>,
+    }
+
+    impl Wishlist {
+        #[ink(constructor)]
+        pub fn new(voting_contract: AccountId) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                items: Vec::new(),
+                voting_contract,
+                item_round: Mapping::default(),
+                withdrawn: Mapping::default(),
+            }
+        }
+
+        /// Adds `item` to the wishlist and returns its id, used to later
+        /// `link_round` and `withdraw` against it. Owner-only.
+        #[ink(message)]
+        pub fn add_item(&mut self, item: String) -> Result<u32, WishlistError> {
+            self.ensure_owner()?;
+            let id = self.items.len() as u32;
+            self.items.push(item);
+            Ok(id)
+        }
+
+        #[ink(message)]
+        pub fn get_items(&self) -> Vec<String> {
+            self.items.clone()
+        }
+
+        /// Associates `item_id` with a `voting_contract` round: `withdraw`
+        /// will only succeed once that round has passed.
+        #[ink(message)]
+        pub fn link_round(&mut self, item_id: u32, round: u32) -> Result<(), WishlistError> {
+            self.ensure_owner()?;
+            if item_id as usize >= self.items.len() {
+                return Err(WishlistError::ItemNotFound);
+            }
+            self.item_round.insert(item_id, &round);
+            Ok(())
+        }
+
+        /// Pays `amount` to `to` for `item_id`, but only once the voting
+        /// round linked to it (via `link_round`) has passed on the
+        /// `voting_contract`. Queried cross-contract, not cached, so a round
+        /// that is disputed and overturned after `link_round` is still
+        /// honoured correctly.
+        #[ink(message)]
+        pub fn withdraw(
+            &mut self,
+            item_id: u32,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), WishlistError> {
+            self.ensure_owner()?;
+            if self.withdrawn.contains(item_id) {
+                return Err(WishlistError::AlreadyWithdrawn);
+            }
+            let round = self
+                .item_round
+                .get(item_id)
+                .ok_or(WishlistError::NoLinkedRound)?;
+            let voting: VotingContractRef = ink::env::call::FromAccountId::from_account_id(self.voting_contract);
+            if voting.round_passed(round) != Some(true) {
+                return Err(WishlistError::ProposalNotPassed);
+            }
+            self.withdrawn.insert(item_id, &());
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| WishlistError::TransferFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<(), WishlistError> {
+            if self.env().caller() != self.owner {
+                return Err(WishlistError::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn add_item_is_owner_only() {
+            let mut contract = Wishlist::new(AccountId::from([0x1; 32]));
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.add_item(String::from("telescope")),
+                Err(WishlistError::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        fn get_items_returns_every_added_item() {
+            let mut contract = Wishlist::new(AccountId::from([0x1; 32]));
+            contract.add_item(String::from("telescope")).unwrap();
+            contract.add_item(String::from("microscope")).unwrap();
+            assert_eq!(
+                contract.get_items(),
+                Vec::from([String::from("telescope"), String::from("microscope")])
+            );
+        }
+
+        #[ink::test]
+        fn withdraw_requires_a_linked_round() {
+            let mut contract = Wishlist::new(AccountId::from([0x1; 32]));
+            contract.add_item(String::from("telescope")).unwrap();
+            assert_eq!(
+                contract.withdraw(0, AccountId::from([0x2; 32]), 0),
+                Err(WishlistError::NoLinkedRound)
+            );
+        }
+
+        #[ink::test]
+        fn withdraw_rejects_an_unknown_item() {
+            let mut contract = Wishlist::new(AccountId::from([0x1; 32]));
+            assert_eq!(contract.link_round(0, 0), Err(WishlistError::ItemNotFound));
+        }
+    }
+
+    /// Deploys both contracts on a real `substrate-contracts-node` and
+    /// drives the full approve-then-withdraw flow end to end. Gated behind
+    /// the `e2e-tests` feature since it needs a node on `$PATH`; excluded
+    /// from the default `cargo test` run.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+        use voting_contract::voting_contract::VotingContractRef;
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn wishlist_withdraw_honours_a_passed_vote<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut voting_constructor =
+                VotingContractRef::new(Vec::from([String::from("buy the telescope")]));
+            let voting = client
+                .instantiate("voting-contract", &ink_e2e::alice(), &mut voting_constructor)
+                .submit()
+                .await
+                .expect("voting-contract instantiate failed")
+                .call_builder::<VotingContractRef>();
+
+            let mut wishlist_constructor = WishlistRef::new(voting.account_id());
+            let mut wishlist = client
+                .instantiate("wishlist", &ink_e2e::alice(), &mut wishlist_constructor)
+                .submit()
+                .await
+                .expect("wishlist instantiate failed")
+                .call_builder::<WishlistRef>();
+
+            client
+                .call(&ink_e2e::alice(), &voting.register())
+                .submit()
+                .await
+                .expect("register failed");
+            client
+                .call(&ink_e2e::alice(), &voting.start_voting(10))
+                .submit()
+                .await
+                .expect("start_voting failed");
+            client
+                .call(&ink_e2e::alice(), &voting.vote(0, 0))
+                .submit()
+                .await
+                .expect("vote failed");
+            client
+                .call(&ink_e2e::alice(), &voting.finalize())
+                .submit()
+                .await
+                .expect("finalize failed");
+            client
+                .call(&ink_e2e::alice(), &voting.certify_results())
+                .submit()
+                .await
+                .expect("certify_results failed");
+
+            let add_item = client
+                .call(
+                    &ink_e2e::alice(),
+                    &wishlist.add_item(String::from("telescope")),
+                )
+                .submit()
+                .await
+                .expect("add_item failed");
+            let item_id = add_item.return_value().expect("add_item returned an error");
+
+            client
+                .call(&ink_e2e::alice(), &wishlist.link_round(item_id, 0))
+                .submit()
+                .await
+                .expect("link_round failed");
+
+            let withdraw = client
+                .call(
+                    &ink_e2e::alice(),
+                    &wishlist.withdraw(item_id, ink_e2e::bob().account_id(), 0),
+                )
+                .submit()
+                .await
+                .expect("withdraw failed");
+            assert!(withdraw.return_value().is_ok());
+
+            Ok(())
+        }
+    }
+}
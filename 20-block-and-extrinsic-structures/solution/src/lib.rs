@@ -0,0 +1,244 @@
+//! Solution for Task 20: `Header`/`Block`/`UncheckedExtrinsic`-like types
+//! with SCALE encoding, parent-hash linking, and chain validation - the
+//! types the Task 21 light-client exercise consumes. See the README for
+//! why `extrinsics_root` isn't a real trie root here.
+
+use hashing_utilities::blake2_256;
+use parity_scale_codec::{Decode, Encode};
+
+pub type Hash = [u8; 32];
+pub type BlockNumber = u64;
+pub type AccountId = String;
+
+const GENESIS_PARENT_HASH: Hash = [0u8; 32];
+
+/// An opaque, already-encoded call - this task models extrinsic
+/// structure and linking, not a dispatchable call enum, so the call body
+/// is just bytes a real runtime would decode further.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Call {
+    pub function: String,
+    pub args: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ExtrinsicSignature {
+    pub signer: AccountId,
+    pub signature_bytes: Vec<u8>,
+}
+
+/// Mirrors Substrate's `UncheckedExtrinsic`: a call, plus an optional
+/// signature for anything that isn't an inherent.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct UncheckedExtrinsic {
+    pub signature: Option<ExtrinsicSignature>,
+    pub call: Call,
+}
+
+impl UncheckedExtrinsic {
+    pub fn unsigned(call: Call) -> Self {
+        Self {
+            signature: None,
+            call,
+        }
+    }
+
+    pub fn signed(signer: AccountId, signature_bytes: Vec<u8>, call: Call) -> Self {
+        Self {
+            signature: Some(ExtrinsicSignature {
+                signer,
+                signature_bytes,
+            }),
+            call,
+        }
+    }
+}
+
+/// A simplified digest of a block's extrinsics - `blake2_256` over their
+/// concatenated SCALE encodings, not a real extrinsics trie root (see the
+/// README). Still catches any change to the extrinsic list or its order.
+pub fn extrinsics_root(extrinsics: &[UncheckedExtrinsic]) -> Hash {
+    let mut encoded = Vec::new();
+    for extrinsic in extrinsics {
+        encoded.extend(extrinsic.encode());
+    }
+    blake2_256(&encoded)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Header {
+    pub parent_hash: Hash,
+    pub number: BlockNumber,
+    pub state_root: Hash,
+    pub extrinsics_root: Hash,
+}
+
+impl Header {
+    pub fn genesis(state_root: Hash) -> Self {
+        Self {
+            parent_hash: GENESIS_PARENT_HASH,
+            number: 0,
+            state_root,
+            extrinsics_root: extrinsics_root(&[]),
+        }
+    }
+
+    pub fn hash(&self) -> Hash {
+        blake2_256(&self.encode())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Block {
+    pub header: Header,
+    pub extrinsics: Vec<UncheckedExtrinsic>,
+}
+
+impl Block {
+    pub fn genesis(state_root: Hash) -> Self {
+        Self {
+            header: Header::genesis(state_root),
+            extrinsics: Vec::new(),
+        }
+    }
+
+    /// Builds the next block on top of `self`, linking it by parent hash
+    /// and number and deriving `extrinsics_root` from `extrinsics`.
+    /// `state_root` is supplied by the caller since this task has no
+    /// state trie of its own to compute one from.
+    pub fn child(&self, state_root: Hash, extrinsics: Vec<UncheckedExtrinsic>) -> Block {
+        let header = Header {
+            parent_hash: self.header.hash(),
+            number: self.header.number + 1,
+            state_root,
+            extrinsics_root: extrinsics_root(&extrinsics),
+        };
+        Block { header, extrinsics }
+    }
+
+    /// Whether `header.extrinsics_root` actually matches `extrinsics`.
+    pub fn has_consistent_extrinsics_root(&self) -> bool {
+        self.header.extrinsics_root == extrinsics_root(&self.extrinsics)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChainError {
+    NumberNotSequential { at: BlockNumber },
+    ParentHashMismatch { at: BlockNumber },
+    ExtrinsicsRootMismatch { at: BlockNumber },
+}
+
+/// Checks that `blocks` form a single, correctly linked chain: numbers
+/// increase by exactly one, each header's `parent_hash` matches the
+/// previous block's actual hash, and each block's `extrinsics_root`
+/// matches its own extrinsics.
+pub fn validate_chain(blocks: &[Block]) -> Result<(), ChainError> {
+    for block in blocks {
+        if !block.has_consistent_extrinsics_root() {
+            return Err(ChainError::ExtrinsicsRootMismatch {
+                at: block.header.number,
+            });
+        }
+    }
+    for pair in blocks.windows(2) {
+        let (parent, child) = (&pair[0], &pair[1]);
+        if child.header.number != parent.header.number + 1 {
+            return Err(ChainError::NumberNotSequential {
+                at: child.header.number,
+            });
+        }
+        if child.header.parent_hash != parent.header.hash() {
+            return Err(ChainError::ParentHashMismatch {
+                at: child.header.number,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call() -> Call {
+        Call {
+            function: "transfer".to_string(),
+            args: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn genesis_has_no_parent_and_number_zero() {
+        let genesis = Block::genesis([1u8; 32]);
+        assert_eq!(genesis.header.parent_hash, [0u8; 32]);
+        assert_eq!(genesis.header.number, 0);
+    }
+
+    #[test]
+    fn child_links_to_its_parents_actual_hash() {
+        let genesis = Block::genesis([1u8; 32]);
+        let child = genesis.child([2u8; 32], vec![]);
+        assert_eq!(child.header.parent_hash, genesis.header.hash());
+        assert_eq!(child.header.number, 1);
+    }
+
+    #[test]
+    fn a_well_formed_chain_validates() {
+        let genesis = Block::genesis([1u8; 32]);
+        let block1 = genesis.child([2u8; 32], vec![UncheckedExtrinsic::unsigned(call())]);
+        let block2 = block1.child([3u8; 32], vec![]);
+        assert_eq!(validate_chain(&[genesis, block1, block2]), Ok(()));
+    }
+
+    #[test]
+    fn detects_a_skipped_block_number() {
+        let genesis = Block::genesis([1u8; 32]);
+        let block1 = genesis.child([2u8; 32], vec![]);
+        let mut block2 = block1.child([3u8; 32], vec![]);
+        block2.header.number = 3;
+        assert_eq!(
+            validate_chain(&[genesis, block1, block2]),
+            Err(ChainError::NumberNotSequential { at: 3 })
+        );
+    }
+
+    #[test]
+    fn detects_a_forged_parent_hash() {
+        let genesis = Block::genesis([1u8; 32]);
+        let block1 = genesis.child([2u8; 32], vec![]);
+        let mut block2 = block1.child([3u8; 32], vec![]);
+        block2.header.parent_hash = [0xffu8; 32];
+        assert_eq!(
+            validate_chain(&[genesis, block1, block2]),
+            Err(ChainError::ParentHashMismatch { at: 2 })
+        );
+    }
+
+    #[test]
+    fn detects_an_extrinsics_root_that_does_not_match_its_block() {
+        let genesis = Block::genesis([1u8; 32]);
+        let mut block1 = genesis.child([2u8; 32], vec![UncheckedExtrinsic::unsigned(call())]);
+        block1.extrinsics.push(UncheckedExtrinsic::unsigned(call()));
+        assert_eq!(
+            validate_chain(&[genesis, block1]),
+            Err(ChainError::ExtrinsicsRootMismatch { at: 1 })
+        );
+    }
+
+    #[test]
+    fn scale_round_trips_a_block() {
+        let genesis = Block::genesis([1u8; 32]);
+        let block1 = genesis.child(
+            [2u8; 32],
+            vec![UncheckedExtrinsic::signed(
+                "alice".to_string(),
+                vec![9, 9, 9],
+                call(),
+            )],
+        );
+        let encoded = block1.encode();
+        let decoded = Block::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, block1);
+    }
+}
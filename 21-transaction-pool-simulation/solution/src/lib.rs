@@ -0,0 +1,254 @@
+//! Solution for Task 21: a priority- and nonce-ordered transaction pool,
+//! mirroring Substrate's pool conceptually (ready/future queues,
+//! replacement by fee, longevity eviction). See the README for what's
+//! simplified relative to the real `sc-transaction-pool`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type AccountId = String;
+pub type Nonce = u64;
+/// Higher is more important; modeled on a transaction's tip/fee rather
+/// than computed from one, since this task has no fee model of its own.
+pub type Priority = u64;
+pub type BlockNumber = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// A unique identifier standing in for a transaction hash.
+    pub id: u64,
+    pub signer: AccountId,
+    pub nonce: Nonce,
+    pub priority: Priority,
+    /// Number of blocks after `submitted_at` the transaction stays valid.
+    pub longevity: BlockNumber,
+    pub submitted_at: BlockNumber,
+}
+
+impl Transaction {
+    pub fn expires_at(&self) -> BlockNumber {
+        self.submitted_at + self.longevity
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PoolError {
+    /// A transaction already occupies this `(signer, nonce)` slot with
+    /// priority `>=` the incoming one.
+    PriorityTooLow,
+}
+
+/// Holds every submitted transaction, keyed by `(signer, nonce)` so at
+/// most one transaction per account per nonce survives at a time.
+#[derive(Debug, Default)]
+pub struct TransactionPool {
+    next_nonce: BTreeMap<AccountId, Nonce>,
+    transactions: BTreeMap<(AccountId, Nonce), Transaction>,
+}
+
+impl TransactionPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tells the pool the nonce the chain expects next from `account` -
+    /// called after a block executes, or to seed a freshly-seen account.
+    pub fn set_expected_nonce(&mut self, account: AccountId, nonce: Nonce) {
+        self.next_nonce.insert(account, nonce);
+    }
+
+    pub fn expected_nonce(&self, account: &AccountId) -> Nonce {
+        self.next_nonce.get(account).copied().unwrap_or(0)
+    }
+
+    /// Inserts `tx`, replacing whatever already occupies its
+    /// `(signer, nonce)` slot only if `tx.priority` is strictly higher -
+    /// a resubmission at the same or lower fee doesn't bump the existing
+    /// transaction out of the pool.
+    pub fn submit(&mut self, tx: Transaction) -> Result<(), PoolError> {
+        let key = (tx.signer.clone(), tx.nonce);
+        if let Some(existing) = self.transactions.get(&key) {
+            if tx.priority <= existing.priority {
+                return Err(PoolError::PriorityTooLow);
+            }
+        }
+        self.transactions.insert(key, tx);
+        Ok(())
+    }
+
+    /// Drops every transaction whose longevity has run out as of
+    /// `current_block`.
+    pub fn evict_expired(&mut self, current_block: BlockNumber) {
+        self.transactions
+            .retain(|_, tx| tx.expires_at() > current_block);
+    }
+
+    /// The contiguous run of each account's transactions starting at its
+    /// expected nonce, as of `current_block`, ordered by priority
+    /// (highest first) with an account's own transactions kept in nonce
+    /// order relative to each other. A transaction that has expired
+    /// blocks any later nonce behind it from becoming ready, the same way
+    /// a missing transaction would.
+    pub fn ready(&self, current_block: BlockNumber) -> Vec<Transaction> {
+        let accounts: BTreeSet<&AccountId> =
+            self.transactions.keys().map(|(account, _)| account).collect();
+
+        let mut ready = Vec::new();
+        for account in accounts {
+            let mut nonce = self.expected_nonce(account);
+            while let Some(tx) = self.transactions.get(&(account.clone(), nonce)) {
+                if tx.expires_at() <= current_block {
+                    break;
+                }
+                ready.push(tx.clone());
+                nonce += 1;
+            }
+        }
+
+        ready.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.signer.cmp(&b.signer))
+                .then_with(|| a.nonce.cmp(&b.nonce))
+        });
+        ready
+    }
+
+    /// Everything submitted and unexpired that isn't in `ready` - either
+    /// it's waiting on an earlier nonce from the same account, or an
+    /// earlier nonce in front of it has expired.
+    pub fn future(&self, current_block: BlockNumber) -> Vec<Transaction> {
+        let ready_ids: BTreeSet<u64> = self.ready(current_block).iter().map(|tx| tx.id).collect();
+        self.transactions
+            .values()
+            .filter(|tx| tx.expires_at() > current_block && !ready_ids.contains(&tx.id))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn tx(id: u64, signer: &str, nonce: Nonce, priority: Priority) -> Transaction {
+        Transaction {
+            id,
+            signer: signer.to_string(),
+            nonce,
+            priority,
+            longevity: 100,
+            submitted_at: 0,
+        }
+    }
+
+    #[test]
+    fn a_higher_priority_resubmission_replaces_the_existing_transaction() {
+        let mut pool = TransactionPool::new();
+        pool.submit(tx(1, "alice", 0, 10)).unwrap();
+        pool.submit(tx(2, "alice", 0, 20)).unwrap();
+        assert_eq!(pool.ready(0), vec![tx(2, "alice", 0, 20)]);
+    }
+
+    #[test]
+    fn a_lower_or_equal_priority_resubmission_is_rejected() {
+        let mut pool = TransactionPool::new();
+        pool.submit(tx(1, "alice", 0, 10)).unwrap();
+        assert_eq!(pool.submit(tx(2, "alice", 0, 10)), Err(PoolError::PriorityTooLow));
+        assert_eq!(pool.submit(tx(3, "alice", 0, 5)), Err(PoolError::PriorityTooLow));
+        assert_eq!(pool.ready(0), vec![tx(1, "alice", 0, 10)]);
+    }
+
+    #[test]
+    fn a_nonce_gap_keeps_the_later_transaction_in_future() {
+        let mut pool = TransactionPool::new();
+        pool.submit(tx(1, "alice", 1, 10)).unwrap();
+        assert!(pool.ready(0).is_empty());
+        assert_eq!(pool.future(0), vec![tx(1, "alice", 1, 10)]);
+
+        pool.submit(tx(2, "alice", 0, 10)).unwrap();
+        assert_eq!(pool.ready(0), vec![tx(2, "alice", 0, 10), tx(1, "alice", 1, 10)]);
+        assert!(pool.future(0).is_empty());
+    }
+
+    #[test]
+    fn ready_is_ordered_by_priority_across_accounts() {
+        let mut pool = TransactionPool::new();
+        pool.submit(tx(1, "alice", 0, 5)).unwrap();
+        pool.submit(tx(2, "bob", 0, 50)).unwrap();
+        assert_eq!(pool.ready(0), vec![tx(2, "bob", 0, 50), tx(1, "alice", 0, 5)]);
+    }
+
+    #[test]
+    fn evict_expired_removes_transactions_past_their_longevity() {
+        let mut pool = TransactionPool::new();
+        let mut expiring = tx(1, "alice", 0, 10);
+        expiring.longevity = 5;
+        pool.submit(expiring).unwrap();
+
+        pool.evict_expired(10);
+        assert!(pool.ready(10).is_empty());
+        assert!(pool.future(10).is_empty());
+    }
+
+    #[test]
+    fn an_expired_transaction_blocks_a_later_nonce_from_becoming_ready() {
+        let mut pool = TransactionPool::new();
+        let mut stalled = tx(1, "alice", 0, 10);
+        stalled.longevity = 5;
+        pool.submit(stalled).unwrap();
+        pool.submit(tx(2, "alice", 1, 10)).unwrap();
+
+        assert!(pool.ready(10).is_empty());
+        assert_eq!(pool.future(10), vec![tx(2, "alice", 1, 10)]);
+    }
+
+    #[test]
+    fn set_expected_nonce_admits_transactions_already_on_chain() {
+        let mut pool = TransactionPool::new();
+        pool.set_expected_nonce("alice".to_string(), 5);
+        pool.submit(tx(1, "alice", 5, 10)).unwrap();
+        assert_eq!(pool.ready(0), vec![tx(1, "alice", 5, 10)]);
+    }
+
+    /// No property-testing crate is vendored for this workspace, so this
+    /// stress-tests the pool's invariants over many random submissions
+    /// against a seeded RNG instead of a hand-written reference model.
+    #[test]
+    fn fuzzed_submissions_always_keep_ready_and_future_disjoint_and_contiguous() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut pool = TransactionPool::new();
+        let signers = ["alice", "bob", "carol"];
+
+        for id in 0..500u64 {
+            let signer = signers[rng.gen_range(0..signers.len())];
+            let nonce = rng.gen_range(0..10);
+            let priority = rng.gen_range(0..100);
+            let _ = pool.submit(tx(id, signer, nonce, priority));
+        }
+
+        let current_block = 0;
+        let ready = pool.ready(current_block);
+        let future = pool.future(current_block);
+
+        let ready_ids: BTreeSet<u64> = ready.iter().map(|tx| tx.id).collect();
+        let future_ids: BTreeSet<u64> = future.iter().map(|tx| tx.id).collect();
+        assert!(ready_ids.is_disjoint(&future_ids));
+
+        for signer in signers {
+            let account = signer.to_string();
+            let mut account_ready: Vec<&Transaction> = ready
+                .iter()
+                .filter(|tx| tx.signer == account)
+                .collect();
+            account_ready.sort_by_key(|tx| tx.nonce);
+            for (offset, tx) in account_ready.iter().enumerate() {
+                assert_eq!(tx.nonce, pool.expected_nonce(&account) + offset as u64);
+            }
+        }
+
+        for window in ready.windows(2) {
+            assert!(window[0].priority >= window[1].priority);
+        }
+    }
+}
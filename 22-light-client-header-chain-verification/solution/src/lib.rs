@@ -0,0 +1,336 @@
+//! Solution for Task 22: a light client that verifies a sequence of
+//! [`Header`]s from Task 20 against a known authority set, checking
+//! hash links and signature quorums and flagging forks and equivocations
+//! instead of rejecting them outright. See the README for why those two
+//! are surfaced rather than treated as import errors.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use block_and_extrinsic_structures::{BlockNumber, Hash, Header};
+use key_generation_and_signing::{verify, Scheme};
+
+pub type AuthorityId = String;
+
+#[derive(Debug, Clone)]
+pub struct Authority {
+    pub id: AuthorityId,
+    pub scheme: Scheme,
+    pub public_key: [u8; 32],
+}
+
+/// A header together with the authority signatures over its hash -
+/// this task's stand-in for GRANDPA justifications.
+#[derive(Debug, Clone)]
+pub struct SignedHeader {
+    pub header: Header,
+    pub signatures: Vec<(AuthorityId, [u8; 64])>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ImportError {
+    /// `header.parent_hash` doesn't match any previously imported header
+    /// (or the light client's genesis).
+    UnknownParent,
+    /// A signature claims to be from an `AuthorityId` not in the current
+    /// authority set.
+    UnknownAuthority,
+    /// A signature didn't verify against its claimed authority's key.
+    InvalidSignature,
+    /// Fewer than a `2f+1` quorum of distinct authorities signed this
+    /// header.
+    InsufficientSignatures,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Imported,
+    /// Imported, but another header already occupies this block number -
+    /// the chain has forked at this height.
+    ImportedAsFork,
+}
+
+/// Verifies and imports [`SignedHeader`]s against a fixed authority set,
+/// tracking enough history to report forks (two valid headers at the same
+/// number) and equivocations (one authority signing two different
+/// headers at the same number).
+pub struct LightClient {
+    authorities: Vec<Authority>,
+    genesis_hash: Hash,
+    headers: BTreeMap<Hash, Header>,
+    hashes_at_height: BTreeMap<BlockNumber, BTreeSet<Hash>>,
+    authority_votes: BTreeMap<(AuthorityId, BlockNumber), BTreeSet<Hash>>,
+}
+
+impl LightClient {
+    pub fn new(authorities: Vec<Authority>, genesis: Header) -> Self {
+        let genesis_hash = genesis.hash();
+        let mut headers = BTreeMap::new();
+        let mut hashes_at_height = BTreeMap::new();
+        headers.insert(genesis_hash, genesis.clone());
+        hashes_at_height.insert(genesis.number, BTreeSet::from([genesis_hash]));
+        Self {
+            authorities,
+            genesis_hash,
+            headers,
+            hashes_at_height,
+            authority_votes: BTreeMap::new(),
+        }
+    }
+
+    pub fn genesis_hash(&self) -> Hash {
+        self.genesis_hash
+    }
+
+    fn authority(&self, id: &AuthorityId) -> Option<&Authority> {
+        self.authorities.iter().find(|authority| &authority.id == id)
+    }
+
+    fn quorum(&self) -> usize {
+        self.authorities.len() * 2 / 3 + 1
+    }
+
+    /// Verifies every signature in `signed`, then checks that a quorum of
+    /// distinct, known authorities actually signed `signed.header`'s hash.
+    fn verify_signatures(&self, signed: &SignedHeader) -> Result<BTreeSet<AuthorityId>, ImportError> {
+        let message = signed.header.hash();
+        let mut signers = BTreeSet::new();
+        for (authority_id, signature) in &signed.signatures {
+            let authority = self
+                .authority(authority_id)
+                .ok_or(ImportError::UnknownAuthority)?;
+            if !verify(authority.scheme, &authority.public_key, &message, signature) {
+                return Err(ImportError::InvalidSignature);
+            }
+            signers.insert(authority_id.clone());
+        }
+        if signers.len() < self.quorum() {
+            return Err(ImportError::InsufficientSignatures);
+        }
+        Ok(signers)
+    }
+
+    pub fn import(&mut self, signed: SignedHeader) -> Result<ImportOutcome, ImportError> {
+        if !self.headers.contains_key(&signed.header.parent_hash) {
+            return Err(ImportError::UnknownParent);
+        }
+        let signers = self.verify_signatures(&signed)?;
+
+        let hash = signed.header.hash();
+        let number = signed.header.number;
+        let heights = self.hashes_at_height.entry(number).or_default();
+        let outcome = if heights.is_empty() {
+            ImportOutcome::Imported
+        } else {
+            ImportOutcome::ImportedAsFork
+        };
+        heights.insert(hash);
+        self.headers.insert(hash, signed.header);
+
+        for authority_id in signers {
+            self.authority_votes
+                .entry((authority_id, number))
+                .or_default()
+                .insert(hash);
+        }
+
+        Ok(outcome)
+    }
+
+    /// Block numbers where more than one header has been imported.
+    pub fn forks(&self) -> Vec<BlockNumber> {
+        self.hashes_at_height
+            .iter()
+            .filter(|(_, hashes)| hashes.len() > 1)
+            .map(|(number, _)| *number)
+            .collect()
+    }
+
+    /// `(authority, block number)` pairs where that authority signed more
+    /// than one distinct header at the same number.
+    pub fn equivocations(&self) -> Vec<(AuthorityId, BlockNumber)> {
+        self.authority_votes
+            .iter()
+            .filter(|(_, hashes)| hashes.len() > 1)
+            .map(|((authority_id, number), _)| (authority_id.clone(), *number))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use key_generation_and_signing::Keypair;
+
+    fn authority(id: &str) -> (Authority, Keypair) {
+        let keypair = Keypair::generate(Scheme::Sr25519);
+        (
+            Authority {
+                id: id.to_string(),
+                scheme: Scheme::Sr25519,
+                public_key: keypair.public_bytes(),
+            },
+            keypair,
+        )
+    }
+
+    fn sign(header: &Header, authority_id: &str, keypair: &Keypair) -> (AuthorityId, [u8; 64]) {
+        (authority_id.to_string(), keypair.sign(&header.hash()))
+    }
+
+    fn header(parent_hash: Hash, number: BlockNumber) -> Header {
+        Header {
+            parent_hash,
+            number,
+            state_root: [number as u8; 32],
+            extrinsics_root: block_and_extrinsic_structures::extrinsics_root(&[]),
+        }
+    }
+
+    fn setup() -> (LightClient, Vec<(Authority, Keypair)>, Header) {
+        let signers = vec![
+            authority("a"),
+            authority("b"),
+            authority("c"),
+            authority("d"),
+        ];
+        let authorities = signers.iter().map(|(a, _)| a.clone()).collect();
+        let genesis = Header {
+            parent_hash: [0u8; 32],
+            number: 0,
+            state_root: [0u8; 32],
+            extrinsics_root: block_and_extrinsic_structures::extrinsics_root(&[]),
+        };
+        let client = LightClient::new(authorities, genesis.clone());
+        (client, signers, genesis)
+    }
+
+    #[test]
+    fn imports_a_header_signed_by_a_quorum_of_authorities() {
+        let (mut client, signers, genesis) = setup();
+        let block1 = header(genesis.hash(), 1);
+        let signed = SignedHeader {
+            header: block1.clone(),
+            signatures: vec![
+                sign(&block1, "a", &signers[0].1),
+                sign(&block1, "b", &signers[1].1),
+                sign(&block1, "c", &signers[2].1),
+            ],
+        };
+        assert_eq!(client.import(signed), Ok(ImportOutcome::Imported));
+    }
+
+    #[test]
+    fn rejects_a_header_without_a_quorum() {
+        let (mut client, signers, genesis) = setup();
+        let block1 = header(genesis.hash(), 1);
+        let signed = SignedHeader {
+            header: block1.clone(),
+            signatures: vec![
+                sign(&block1, "a", &signers[0].1),
+                sign(&block1, "b", &signers[1].1),
+            ],
+        };
+        assert_eq!(
+            client.import(signed),
+            Err(ImportError::InsufficientSignatures)
+        );
+    }
+
+    #[test]
+    fn rejects_an_unknown_parent() {
+        let (mut client, signers, _genesis) = setup();
+        let orphan = header([0xffu8; 32], 5);
+        let signed = SignedHeader {
+            header: orphan.clone(),
+            signatures: vec![
+                sign(&orphan, "a", &signers[0].1),
+                sign(&orphan, "b", &signers[1].1),
+                sign(&orphan, "c", &signers[2].1),
+            ],
+        };
+        assert_eq!(client.import(signed), Err(ImportError::UnknownParent));
+    }
+
+    #[test]
+    fn rejects_a_forged_signature() {
+        let (mut client, signers, genesis) = setup();
+        let block1 = header(genesis.hash(), 1);
+        let wrong_message_signature = signers[0].1.sign(b"not the header hash");
+        let signed = SignedHeader {
+            header: block1.clone(),
+            signatures: vec![
+                ("a".to_string(), wrong_message_signature),
+                sign(&block1, "b", &signers[1].1),
+                sign(&block1, "c", &signers[2].1),
+            ],
+        };
+        assert_eq!(client.import(signed), Err(ImportError::InvalidSignature));
+    }
+
+    #[test]
+    fn detects_a_fork_at_the_same_height() {
+        let (mut client, signers, genesis) = setup();
+        let mut block1a = header(genesis.hash(), 1);
+        block1a.state_root = [1u8; 32];
+        let mut block1b = header(genesis.hash(), 1);
+        block1b.state_root = [2u8; 32];
+
+        let signed_a = SignedHeader {
+            header: block1a.clone(),
+            signatures: vec![
+                sign(&block1a, "a", &signers[0].1),
+                sign(&block1a, "b", &signers[1].1),
+                sign(&block1a, "c", &signers[2].1),
+            ],
+        };
+        let signed_b = SignedHeader {
+            header: block1b.clone(),
+            signatures: vec![
+                sign(&block1b, "a", &signers[0].1),
+                sign(&block1b, "b", &signers[1].1),
+                sign(&block1b, "d", &signers[3].1),
+            ],
+        };
+
+        assert_eq!(client.import(signed_a), Ok(ImportOutcome::Imported));
+        assert_eq!(client.import(signed_b), Ok(ImportOutcome::ImportedAsFork));
+        assert_eq!(client.forks(), vec![1]);
+    }
+
+    #[test]
+    fn detects_an_authority_equivocating_at_the_same_height() {
+        let (mut client, signers, genesis) = setup();
+        let mut block1a = header(genesis.hash(), 1);
+        block1a.state_root = [1u8; 32];
+        let mut block1b = header(genesis.hash(), 1);
+        block1b.state_root = [2u8; 32];
+
+        let signed_a = SignedHeader {
+            header: block1a.clone(),
+            signatures: vec![
+                sign(&block1a, "a", &signers[0].1),
+                sign(&block1a, "b", &signers[1].1),
+                sign(&block1a, "c", &signers[2].1),
+            ],
+        };
+        // With a 3-of-4 quorum, two conflicting quorum certificates must
+        // share at least `3 + 3 - 4 = 2` signers - here "a" and "b" both
+        // sign conflicting headers at height 1, the quorum-intersection
+        // property a light client relies on to always catch equivocators.
+        let signed_b = SignedHeader {
+            header: block1b.clone(),
+            signatures: vec![
+                sign(&block1b, "a", &signers[0].1),
+                sign(&block1b, "b", &signers[1].1),
+                sign(&block1b, "d", &signers[3].1),
+            ],
+        };
+
+        client.import(signed_a).unwrap();
+        client.import(signed_b).unwrap();
+        assert_eq!(
+            client.equivocations(),
+            vec![("a".to_string(), 1), ("b".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Solution for Task 23: talks to a public Polkadot node directly over
+//! `jsonrpsee`, with no `subxt` in the loop, to show what subxt does
+//! under the hood - build a storage key by hand (reusing Task 17's
+//! hashing utilities), send a raw `state_getStorage` call, and
+//! SCALE-decode the result.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use hashing_utilities::storage_map_key;
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::WsClientBuilder;
+use parity_scale_codec::Decode;
+use ss58_address_codec::decode as ss58_decode;
+
+/// Parity's public Polkadot RPC endpoint - see
+/// https://wiki.polkadot.network/docs/maintain-endpoints.
+const DEFAULT_ENDPOINT: &str = "wss://rpc.polkadot.io";
+
+/// Alice's well-known Polkadot SS58 address, as a default so the binary
+/// does something sensible with no arguments.
+const DEFAULT_ADDRESS: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHqQrST";
+
+#[derive(Parser)]
+#[command(about = "Reads a System::Account storage entry from a Polkadot node over raw JSON-RPC")]
+struct Cli {
+    /// WebSocket URL of the node to query.
+    #[arg(long, default_value = DEFAULT_ENDPOINT)]
+    url: String,
+
+    /// SS58 address to look up in `System::Account`.
+    #[arg(long, default_value = DEFAULT_ADDRESS)]
+    address: String,
+}
+
+/// `pallet_balances::AccountData`'s shape in the `System::Account` entry -
+/// only the fields this task reads.
+#[derive(Debug, Decode)]
+struct AccountData {
+    free: u128,
+    reserved: u128,
+    frozen: u128,
+    #[allow(dead_code)]
+    flags: u128,
+}
+
+/// `frame_system::AccountInfo<Nonce, AccountData>`.
+#[derive(Debug, Decode)]
+struct AccountInfo {
+    nonce: u32,
+    consumers: u32,
+    providers: u32,
+    sufficients: u32,
+    data: AccountData,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let (_prefix, account_id) =
+        ss58_decode(&cli.address).map_err(|err| anyhow::anyhow!("{err:?}"))?;
+
+    let client = WsClientBuilder::default()
+        .build(&cli.url)
+        .await
+        .with_context(|| format!("failed to connect to {}", cli.url))?;
+
+    let finalized_head: String = client
+        .request("chain_getFinalizedHead", rpc_params![])
+        .await
+        .context("chain_getFinalizedHead failed")?;
+    println!("finalized head: {finalized_head}");
+
+    let storage_key = storage_map_key("System", "Account", &account_id);
+    let storage_key_hex = format!("0x{}", hex::encode(&storage_key));
+
+    let raw_value: Option<String> = client
+        .request(
+            "state_getStorage",
+            rpc_params![storage_key_hex, &finalized_head],
+        )
+        .await
+        .context("state_getStorage failed")?;
+
+    let Some(raw_value) = raw_value else {
+        println!("no System::Account entry for {} (account never touched)", cli.address);
+        return Ok(());
+    };
+
+    let encoded = hex::decode(raw_value.trim_start_matches("0x"))
+        .context("node returned a storage value that wasn't valid hex")?;
+    let account = AccountInfo::decode(&mut &encoded[..])
+        .context("failed to SCALE-decode the System::Account entry")?;
+
+    println!(
+        "nonce={} consumers={} providers={} sufficients={} free={} reserved={} frozen={}",
+        account.nonce,
+        account.consumers,
+        account.providers,
+        account.sufficients,
+        account.data.free,
+        account.data.reserved,
+        account.data.frozen,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Solution for Task 25: an off-chain worker loop in the style of
+//! Substrate's `pallet_example_offchain_worker` - fetch a price over HTTP,
+//! sign the observation, and submit it to an on-chain-style [`Oracle`] as
+//! an authorized feed. The HTTP call is behind [`PriceSource`] so tests
+//! never touch the network. See the README for why submission is signed
+//! rather than just trusted because it came from the worker loop.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use key_generation_and_signing::Keypair;
+use voting_state_machine::{SignatureVerifier, UserId};
+
+pub type Price = u128;
+pub type Round = u64;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FetchError {
+    /// The HTTP request itself failed (timeout, connection refused, ...).
+    Unreachable,
+    /// A response came back but wasn't a price this worker can parse.
+    InvalidResponse,
+}
+
+/// Abstracts the one HTTP call an off-chain worker makes here, so tests
+/// supply a canned response instead of making a real request - the same
+/// role `sp_runtime::offchain::http` plays in a real off-chain worker,
+/// stripped down to the single request/response pair this task needs.
+pub trait PriceSource {
+    fn fetch_price(&self) -> Result<Price, FetchError>;
+}
+
+/// The message a feeder signs over: binds the price to a specific round so
+/// a captured signature can't be replayed into a later one.
+pub fn price_observation_message(caller_id: &UserId, round: Round, price: Price) -> Vec<u8> {
+    let mut message = caller_id.clone().into_bytes();
+    message.push(0);
+    message.extend(round.to_le_bytes());
+    message.extend(price.to_le_bytes());
+    message
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OracleError {
+    /// `caller_id` isn't in the authorized feeder set.
+    NotAuthorized,
+    /// The signature didn't verify for `caller_id` over this round/price.
+    InvalidSignature,
+}
+
+/// Collects signed price observations per round from a fixed set of
+/// authorized feeders - the off-chain worker's on-chain counterpart.
+#[derive(Debug, Default)]
+pub struct Oracle {
+    authorized_feeders: BTreeSet<UserId>,
+    observations: BTreeMap<Round, BTreeMap<UserId, Price>>,
+}
+
+impl Oracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn authorize(&mut self, feeder: UserId) {
+        self.authorized_feeders.insert(feeder);
+    }
+
+    pub fn is_authorized(&self, feeder: &UserId) -> bool {
+        self.authorized_feeders.contains(feeder)
+    }
+
+    pub fn submit_price_signed<V: SignatureVerifier>(
+        &mut self,
+        caller_id: UserId,
+        round: Round,
+        price: Price,
+        signature: &[u8],
+        verifier: &V,
+    ) -> Result<(), OracleError> {
+        if !self.is_authorized(&caller_id) {
+            return Err(OracleError::NotAuthorized);
+        }
+        let message = price_observation_message(&caller_id, round, price);
+        if !verifier.verify(&caller_id, &message, signature) {
+            return Err(OracleError::InvalidSignature);
+        }
+        self.observations
+            .entry(round)
+            .or_default()
+            .insert(caller_id, price);
+        Ok(())
+    }
+
+    /// The median of every observation submitted for `round` so far, or
+    /// `None` if nobody has reported yet.
+    pub fn median_price(&self, round: Round) -> Option<Price> {
+        let mut prices: Vec<Price> = self.observations.get(&round)?.values().copied().collect();
+        if prices.is_empty() {
+            return None;
+        }
+        prices.sort_unstable();
+        Some(prices[prices.len() / 2])
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OffchainWorkerError {
+    Fetch(FetchError),
+    Oracle(OracleError),
+}
+
+/// One off-chain worker tick: fetch a price from `source`, sign it as
+/// `caller_id`, and submit it to `oracle`. Returns the price observed so a
+/// caller (or a test) can compare it against what ends up in the oracle.
+pub fn run_offchain_worker<S: PriceSource, V: SignatureVerifier>(
+    source: &S,
+    keypair: &Keypair,
+    caller_id: UserId,
+    round: Round,
+    oracle: &mut Oracle,
+    verifier: &V,
+) -> Result<Price, OffchainWorkerError> {
+    let price = source.fetch_price().map_err(OffchainWorkerError::Fetch)?;
+    let message = price_observation_message(&caller_id, round, price);
+    let signature = keypair.sign(&message);
+    oracle
+        .submit_price_signed(caller_id, round, price, &signature, verifier)
+        .map_err(OffchainWorkerError::Oracle)?;
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use key_generation_and_signing::{Keyring, Scheme};
+
+    struct FixedPrice(Price);
+
+    impl PriceSource for FixedPrice {
+        fn fetch_price(&self) -> Result<Price, FetchError> {
+            Ok(self.0)
+        }
+    }
+
+    struct UnreachableSource;
+
+    impl PriceSource for UnreachableSource {
+        fn fetch_price(&self) -> Result<Price, FetchError> {
+            Err(FetchError::Unreachable)
+        }
+    }
+
+    #[test]
+    fn offchain_worker_signs_and_submits_the_fetched_price() {
+        let keypair = Keypair::generate(Scheme::Sr25519);
+        let mut keyring = Keyring::new();
+        keyring.register("alice".to_string(), &keypair);
+
+        let mut oracle = Oracle::new();
+        oracle.authorize("alice".to_string());
+
+        let price = run_offchain_worker(
+            &FixedPrice(4200),
+            &keypair,
+            "alice".to_string(),
+            1,
+            &mut oracle,
+            &keyring,
+        )
+        .unwrap();
+
+        assert_eq!(price, 4200);
+        assert_eq!(oracle.median_price(1), Some(4200));
+    }
+
+    #[test]
+    fn offchain_worker_propagates_a_fetch_failure() {
+        let keypair = Keypair::generate(Scheme::Sr25519);
+        let keyring = Keyring::new();
+        let mut oracle = Oracle::new();
+
+        let result = run_offchain_worker(
+            &UnreachableSource,
+            &keypair,
+            "alice".to_string(),
+            1,
+            &mut oracle,
+            &keyring,
+        );
+
+        assert_eq!(
+            result,
+            Err(OffchainWorkerError::Fetch(FetchError::Unreachable))
+        );
+    }
+
+    #[test]
+    fn oracle_rejects_a_submission_from_an_unauthorized_feeder() {
+        let keypair = Keypair::generate(Scheme::Sr25519);
+        let mut keyring = Keyring::new();
+        keyring.register("mallory".to_string(), &keypair);
+        let mut oracle = Oracle::new();
+
+        let message = price_observation_message(&"mallory".to_string(), 1, 100);
+        let signature = keypair.sign(&message);
+        let result = oracle.submit_price_signed(
+            "mallory".to_string(),
+            1,
+            100,
+            &signature,
+            &keyring,
+        );
+
+        assert_eq!(result, Err(OracleError::NotAuthorized));
+    }
+
+    #[test]
+    fn oracle_rejects_a_submission_with_a_signature_for_a_different_price() {
+        let keypair = Keypair::generate(Scheme::Sr25519);
+        let mut keyring = Keyring::new();
+        keyring.register("alice".to_string(), &keypair);
+        let mut oracle = Oracle::new();
+        oracle.authorize("alice".to_string());
+
+        let signature = keypair.sign(&price_observation_message(&"alice".to_string(), 1, 100));
+        let result = oracle.submit_price_signed("alice".to_string(), 1, 999, &signature, &keyring);
+
+        assert_eq!(result, Err(OracleError::InvalidSignature));
+    }
+
+    #[test]
+    fn median_price_is_the_middle_observation_across_feeders() {
+        let mut oracle = Oracle::new();
+        for (name, price) in [("a", 10u128), ("b", 20), ("c", 30)] {
+            let keypair = Keypair::generate(Scheme::Sr25519);
+            let mut keyring = Keyring::new();
+            keyring.register(name.to_string(), &keypair);
+            oracle.authorize(name.to_string());
+            let signature = keypair.sign(&price_observation_message(&name.to_string(), 1, price));
+            oracle
+                .submit_price_signed(name.to_string(), 1, price, &signature, &keyring)
+                .unwrap();
+        }
+
+        assert_eq!(oracle.median_price(1), Some(20));
+    }
+
+    #[test]
+    fn median_price_is_none_before_any_submission() {
+        let oracle = Oracle::new();
+        assert_eq!(oracle.median_price(1), None);
+    }
+}
@@ -0,0 +1,297 @@
+//! Solution for Task 26: a versioned in-memory "runtime" wrapping the
+//! voting library's voter registry, plus a `try-runtime`-shaped
+//! [`Migration`] trait - `pre_upgrade` snapshots an invariant,
+//! [`Migration::migrate`] does the actual storage transform, and
+//! `post_upgrade` checks the snapshot still holds. See the README for why
+//! the snapshot is opaque bytes rather than a typed value.
+
+use std::collections::BTreeMap;
+
+use voting_state_machine::{Timestamp, UserId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StorageVersion(pub u16);
+
+pub mod v1 {
+    use super::UserId;
+
+    /// The original layout: registered voters kept in insertion order,
+    /// with nothing stopping the same id from being pushed twice.
+    #[derive(Debug, Clone, Default)]
+    pub struct VoterRegistry {
+        pub voters: Vec<UserId>,
+    }
+}
+
+pub mod v2 {
+    use super::{BTreeMap, Timestamp, UserId};
+
+    /// The upgraded layout: a map keyed by voter, which can't hold a
+    /// duplicate, plus a new field every voter needs a value for.
+    #[derive(Debug, Clone, Default)]
+    pub struct VoterRegistry {
+        pub voters: BTreeMap<UserId, VoterRecord>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct VoterRecord {
+        pub registered_at: Timestamp,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Storage {
+    V1(v1::VoterRegistry),
+    V2(v2::VoterRegistry),
+}
+
+impl Storage {
+    pub fn version(&self) -> StorageVersion {
+        match self {
+            Storage::V1(_) => StorageVersion(1),
+            Storage::V2(_) => StorageVersion(2),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The runtime's current storage version doesn't match what this
+    /// migration expects to migrate *from*.
+    WrongStorageVersion {
+        expected: StorageVersion,
+        found: StorageVersion,
+    },
+    /// `pre_upgrade` found `v1::VoterRegistry::voters` already violating
+    /// the uniqueness the V2 map is about to start enforcing silently.
+    DuplicateVoter(UserId),
+    /// `post_upgrade` found the migrated storage didn't preserve the
+    /// invariant `pre_upgrade` snapshotted.
+    PostUpgradeInvariantViolated,
+}
+
+/// One storage migration step, shaped after `frame_support`'s
+/// `OnRuntimeUpgrade`: `pre_upgrade`/`post_upgrade` run under `try-runtime`
+/// to catch a migration that "succeeds" but silently corrupts state;
+/// `migrate` is the transform that actually runs on-chain.
+pub trait Migration {
+    fn source_version(&self) -> StorageVersion;
+    fn target_version(&self) -> StorageVersion;
+
+    /// Snapshots whatever invariant this migration must preserve, as
+    /// opaque bytes - same as `try-runtime`, which can't assume anything
+    /// about the pre- and post-upgrade types sharing a common shape.
+    fn pre_upgrade(&self, storage: &Storage) -> Result<Vec<u8>, MigrationError>;
+
+    fn migrate(&self, storage: Storage) -> Result<Storage, MigrationError>;
+
+    fn post_upgrade(&self, storage: &Storage, pre_state: Vec<u8>) -> Result<(), MigrationError>;
+}
+
+/// Migrates the voter registry from a `Vec` to a `Map`, stamping every
+/// existing voter with `registered_at: now` since V1 never recorded when a
+/// voter registered.
+pub struct V1ToV2Migration {
+    pub now: Timestamp,
+}
+
+impl Migration for V1ToV2Migration {
+    fn source_version(&self) -> StorageVersion {
+        StorageVersion(1)
+    }
+
+    fn target_version(&self) -> StorageVersion {
+        StorageVersion(2)
+    }
+
+    fn pre_upgrade(&self, storage: &Storage) -> Result<Vec<u8>, MigrationError> {
+        let Storage::V1(registry) = storage else {
+            return Err(MigrationError::WrongStorageVersion {
+                expected: self.source_version(),
+                found: storage.version(),
+            });
+        };
+        let mut seen = std::collections::BTreeSet::new();
+        for voter in &registry.voters {
+            if !seen.insert(voter.clone()) {
+                return Err(MigrationError::DuplicateVoter(voter.clone()));
+            }
+        }
+        Ok((registry.voters.len() as u64).to_le_bytes().to_vec())
+    }
+
+    fn migrate(&self, storage: Storage) -> Result<Storage, MigrationError> {
+        let Storage::V1(registry) = storage else {
+            return Err(MigrationError::WrongStorageVersion {
+                expected: self.source_version(),
+                found: storage.version(),
+            });
+        };
+        let voters = registry
+            .voters
+            .into_iter()
+            .map(|voter| {
+                (
+                    voter,
+                    v2::VoterRecord {
+                        registered_at: self.now,
+                    },
+                )
+            })
+            .collect();
+        Ok(Storage::V2(v2::VoterRegistry { voters }))
+    }
+
+    fn post_upgrade(&self, storage: &Storage, pre_state: Vec<u8>) -> Result<(), MigrationError> {
+        let Storage::V2(registry) = storage else {
+            return Err(MigrationError::PostUpgradeInvariantViolated);
+        };
+        let before = u64::from_le_bytes(
+            pre_state
+                .try_into()
+                .map_err(|_| MigrationError::PostUpgradeInvariantViolated)?,
+        );
+        if registry.voters.len() as u64 != before {
+            return Err(MigrationError::PostUpgradeInvariantViolated);
+        }
+        Ok(())
+    }
+}
+
+/// Holds the versioned storage and runs migrations against it, refusing to
+/// apply one that doesn't start from the runtime's current version.
+pub struct Runtime {
+    storage: Storage,
+}
+
+impl Runtime {
+    pub fn new_v1(voters: Vec<UserId>) -> Self {
+        Self {
+            storage: Storage::V1(v1::VoterRegistry { voters }),
+        }
+    }
+
+    pub fn version(&self) -> StorageVersion {
+        self.storage.version()
+    }
+
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn apply_migration<M: Migration>(&mut self, migration: M) -> Result<(), MigrationError> {
+        if self.storage.version() != migration.source_version() {
+            return Err(MigrationError::WrongStorageVersion {
+                expected: migration.source_version(),
+                found: self.storage.version(),
+            });
+        }
+        let pre_state = migration.pre_upgrade(&self.storage)?;
+        let old_storage = std::mem::replace(&mut self.storage, Storage::V1(v1::VoterRegistry::default()));
+        self.storage = migration.migrate(old_storage)?;
+        migration.post_upgrade(&self.storage, pre_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_every_voter_into_the_v2_map_with_the_upgrade_timestamp() {
+        let mut runtime = Runtime::new_v1(vec!["alice".to_string(), "bob".to_string()]);
+
+        runtime.apply_migration(V1ToV2Migration { now: 100 }).unwrap();
+
+        assert_eq!(runtime.version(), StorageVersion(2));
+        let Storage::V2(registry) = runtime.storage() else {
+            panic!("expected V2 storage after migration");
+        };
+        assert_eq!(registry.voters.len(), 2);
+        assert_eq!(
+            registry.voters["alice"],
+            v2::VoterRecord { registered_at: 100 }
+        );
+    }
+
+    #[test]
+    fn pre_upgrade_rejects_a_v1_registry_with_a_duplicate_voter() {
+        let mut runtime = Runtime::new_v1(vec!["alice".to_string(), "alice".to_string()]);
+
+        let result = runtime.apply_migration(V1ToV2Migration { now: 100 });
+
+        assert_eq!(result, Err(MigrationError::DuplicateVoter("alice".to_string())));
+        assert_eq!(runtime.version(), StorageVersion(1));
+    }
+
+    #[test]
+    fn apply_migration_refuses_to_run_against_the_wrong_storage_version() {
+        let mut runtime = Runtime::new_v1(vec!["alice".to_string()]);
+        runtime.apply_migration(V1ToV2Migration { now: 100 }).unwrap();
+
+        // Running the same V1->V2 migration again should refuse: the
+        // runtime is already on V2.
+        let result = runtime.apply_migration(V1ToV2Migration { now: 200 });
+
+        assert_eq!(
+            result,
+            Err(MigrationError::WrongStorageVersion {
+                expected: StorageVersion(1),
+                found: StorageVersion(2),
+            })
+        );
+    }
+
+    #[test]
+    fn a_migration_that_drops_a_voter_fails_its_own_post_upgrade_check() {
+        struct LossyMigration;
+
+        impl Migration for LossyMigration {
+            fn source_version(&self) -> StorageVersion {
+                StorageVersion(1)
+            }
+
+            fn target_version(&self) -> StorageVersion {
+                StorageVersion(2)
+            }
+
+            fn pre_upgrade(&self, storage: &Storage) -> Result<Vec<u8>, MigrationError> {
+                let Storage::V1(registry) = storage else {
+                    panic!("expected V1 storage");
+                };
+                Ok((registry.voters.len() as u64).to_le_bytes().to_vec())
+            }
+
+            fn migrate(&self, _storage: Storage) -> Result<Storage, MigrationError> {
+                // Deliberately buggy: drops every voter instead of
+                // carrying them forward.
+                Ok(Storage::V2(v2::VoterRegistry::default()))
+            }
+
+            fn post_upgrade(
+                &self,
+                storage: &Storage,
+                pre_state: Vec<u8>,
+            ) -> Result<(), MigrationError> {
+                let Storage::V2(registry) = storage else {
+                    return Err(MigrationError::PostUpgradeInvariantViolated);
+                };
+                let before = u64::from_le_bytes(
+                    pre_state
+                        .try_into()
+                        .map_err(|_| MigrationError::PostUpgradeInvariantViolated)?,
+                );
+                if registry.voters.len() as u64 != before {
+                    return Err(MigrationError::PostUpgradeInvariantViolated);
+                }
+                Ok(())
+            }
+        }
+
+        let mut runtime = Runtime::new_v1(vec!["alice".to_string(), "bob".to_string()]);
+
+        let result = runtime.apply_migration(LossyMigration);
+
+        assert_eq!(result, Err(MigrationError::PostUpgradeInvariantViolated));
+    }
+}
@@ -0,0 +1,263 @@
+//! Solution for Task 28: `pallet-treasury`'s spend-proposal workflow as a
+//! plain Rust simulation - propose with a bond, get approved by a council
+//! (or, in a real chain, Task 27's referenda engine), and get paid out (or
+//! requeued, if the pot's short) on a period boundary that also burns a
+//! configurable fraction of whatever's left. See the README for the order
+//! payout and burn happen in and why it matters.
+
+use std::collections::BTreeMap;
+
+use voting_state_machine::UserId;
+
+pub type Balance = u128;
+pub type ProposalIndex = u32;
+
+/// Parts-per-million, the same unit `sp_arithmetic::Permill` uses.
+pub type Permill = u32;
+
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub proposer: UserId,
+    pub beneficiary: UserId,
+    pub value: Balance,
+    pub bond: Balance,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TreasuryError {
+    /// `bond` was below `bond_minimum.max(bond_percentage * value)`.
+    BondTooLow,
+    UnknownProposal,
+    /// A proposal can only be approved (or rejected) once.
+    AlreadyDecided,
+}
+
+/// One completed payout from an `on_period` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payout {
+    pub index: ProposalIndex,
+    pub beneficiary_receives: Balance,
+    pub bond_returned: Balance,
+}
+
+pub struct Treasury {
+    pot: Balance,
+    bond_percentage: Permill,
+    bond_minimum: Balance,
+    burn_permill: Permill,
+    proposals: BTreeMap<ProposalIndex, Proposal>,
+    approved: Vec<ProposalIndex>,
+    next_index: ProposalIndex,
+    total_burned: Balance,
+}
+
+impl Treasury {
+    pub fn new(pot: Balance, bond_percentage: Permill, bond_minimum: Balance, burn_permill: Permill) -> Self {
+        Self {
+            pot,
+            bond_percentage,
+            bond_minimum,
+            burn_permill,
+            proposals: BTreeMap::new(),
+            approved: Vec::new(),
+            next_index: 0,
+            total_burned: 0,
+        }
+    }
+
+    pub fn pot(&self) -> Balance {
+        self.pot
+    }
+
+    pub fn total_burned(&self) -> Balance {
+        self.total_burned
+    }
+
+    fn required_bond(&self, value: Balance) -> Balance {
+        let percentage_bond = (value.saturating_mul(u128::from(self.bond_percentage))) / 1_000_000;
+        percentage_bond.max(self.bond_minimum)
+    }
+
+    /// Deposits new funds into the pot - block rewards, slashes, transaction
+    /// fees, anything a real chain routes into `pallet-treasury`'s account.
+    pub fn fund(&mut self, amount: Balance) {
+        self.pot += amount;
+    }
+
+    pub fn propose_spend(
+        &mut self,
+        proposer: UserId,
+        beneficiary: UserId,
+        value: Balance,
+        bond: Balance,
+    ) -> Result<ProposalIndex, TreasuryError> {
+        if bond < self.required_bond(value) {
+            return Err(TreasuryError::BondTooLow);
+        }
+        let index = self.next_index;
+        self.next_index += 1;
+        self.proposals.insert(
+            index,
+            Proposal {
+                proposer,
+                beneficiary,
+                value,
+                bond,
+            },
+        );
+        Ok(index)
+    }
+
+    /// Approves a proposal for payout at the next `on_period` boundary -
+    /// this task doesn't care whether the approval came from a council
+    /// motion or Task 27's `ReferendaEngine` reaching `Approved`, only that
+    /// something authorized outside this crate made the call.
+    pub fn approve(&mut self, index: ProposalIndex) -> Result<(), TreasuryError> {
+        if !self.proposals.contains_key(&index) {
+            return Err(TreasuryError::UnknownProposal);
+        }
+        if self.approved.contains(&index) {
+            return Err(TreasuryError::AlreadyDecided);
+        }
+        self.approved.push(index);
+        Ok(())
+    }
+
+    /// Rejects a proposal, slashing its bond - burned outright, not
+    /// returned to the pot, the same as `pallet-treasury`'s `reject_proposal`.
+    pub fn reject(&mut self, index: ProposalIndex) -> Result<(), TreasuryError> {
+        let proposal = self
+            .proposals
+            .remove(&index)
+            .ok_or(TreasuryError::UnknownProposal)?;
+        self.total_burned += proposal.bond;
+        Ok(())
+    }
+
+    /// Runs one spend-period boundary: pays out every approved proposal the
+    /// pot can currently afford, in the order they were approved, leaving
+    /// any it can't afford yet queued for the next period; then burns
+    /// `burn_permill` of whatever's left in the pot.
+    pub fn on_period(&mut self) -> Vec<Payout> {
+        let mut payouts = Vec::new();
+        let mut still_queued = Vec::new();
+        for index in std::mem::take(&mut self.approved) {
+            let proposal = self
+                .proposals
+                .get(&index)
+                .expect("an approved index always has a matching proposal");
+            if proposal.value <= self.pot {
+                self.pot -= proposal.value;
+                payouts.push(Payout {
+                    index,
+                    beneficiary_receives: proposal.value,
+                    bond_returned: proposal.bond,
+                });
+                self.proposals.remove(&index);
+            } else {
+                still_queued.push(index);
+            }
+        }
+        self.approved = still_queued;
+
+        let burned = (self.pot.saturating_mul(u128::from(self.burn_permill))) / 1_000_000;
+        self.pot -= burned;
+        self.total_burned += burned;
+
+        payouts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propose_spend_rejects_a_bond_below_the_required_minimum() {
+        let mut treasury = Treasury::new(10_000, 50_000, 10, 0);
+
+        let result = treasury.propose_spend("alice".to_string(), "bob".to_string(), 1_000, 5);
+
+        assert_eq!(result, Err(TreasuryError::BondTooLow));
+    }
+
+    #[test]
+    fn on_period_pays_out_an_approved_proposal_and_returns_its_bond() {
+        let mut treasury = Treasury::new(10_000, 50_000, 10, 0);
+        let index = treasury
+            .propose_spend("alice".to_string(), "bob".to_string(), 1_000, 50)
+            .unwrap();
+        treasury.approve(index).unwrap();
+
+        let payouts = treasury.on_period();
+
+        assert_eq!(
+            payouts,
+            vec![Payout {
+                index,
+                beneficiary_receives: 1_000,
+                bond_returned: 50,
+            }]
+        );
+        assert_eq!(treasury.pot(), 9_000);
+    }
+
+    #[test]
+    fn on_period_burns_a_configurable_fraction_of_whatever_is_left() {
+        // 10% burn, nothing approved: the whole pot is the "whatever's left".
+        let mut treasury = Treasury::new(10_000, 50_000, 10, 100_000);
+
+        treasury.on_period();
+
+        assert_eq!(treasury.pot(), 9_000);
+        assert_eq!(treasury.total_burned(), 1_000);
+    }
+
+    #[test]
+    fn an_approved_proposal_the_pot_cannot_afford_is_requeued_not_dropped() {
+        let mut treasury = Treasury::new(500, 50_000, 10, 0);
+        let index = treasury
+            .propose_spend("alice".to_string(), "bob".to_string(), 1_000, 500)
+            .unwrap();
+        treasury.approve(index).unwrap();
+
+        let payouts = treasury.on_period();
+        assert!(payouts.is_empty());
+        assert_eq!(treasury.pot(), 500);
+
+        treasury.fund(600);
+        let payouts = treasury.on_period();
+        assert_eq!(
+            payouts,
+            vec![Payout {
+                index,
+                beneficiary_receives: 1_000,
+                bond_returned: 500,
+            }]
+        );
+    }
+
+    #[test]
+    fn rejecting_a_proposal_burns_its_bond_instead_of_refunding_it() {
+        let mut treasury = Treasury::new(10_000, 50_000, 10, 0);
+        let index = treasury
+            .propose_spend("alice".to_string(), "bob".to_string(), 1_000, 50)
+            .unwrap();
+
+        treasury.reject(index).unwrap();
+
+        assert_eq!(treasury.total_burned(), 50);
+        assert_eq!(treasury.approve(index), Err(TreasuryError::UnknownProposal));
+    }
+
+    #[test]
+    fn a_proposal_cannot_be_approved_twice() {
+        let mut treasury = Treasury::new(10_000, 50_000, 10, 0);
+        let index = treasury
+            .propose_spend("alice".to_string(), "bob".to_string(), 1_000, 50)
+            .unwrap();
+        treasury.approve(index).unwrap();
+
+        assert_eq!(treasury.approve(index), Err(TreasuryError::AlreadyDecided));
+    }
+}
@@ -0,0 +1,243 @@
+//! Solution for Task 29: `pallet-identity`'s set-identity / request-judgement
+//! / provide-judgement cycle, plus an [`IdentityRegistry`] that implements
+//! Task 1's `UserIdValidator` so a deployment can require a `KnownGood`
+//! judgement before letting someone register to vote. See the README for
+//! why judgement is keyed per-registrar rather than collapsed to one flag.
+
+use std::collections::BTreeMap;
+
+use voting_state_machine::{UserId, UserIdValidator};
+
+pub type RegistrarIndex = u32;
+pub type Balance = u128;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdentityInfo {
+    pub display_name: String,
+    pub legal_name: String,
+    pub email: String,
+}
+
+/// Mirrors `pallet_identity::Judgement`'s levels, minus `Erroneous`/
+/// `LowQuality`'s finer distinctions this task doesn't need - only
+/// `KnownGood` is ever checked by [`IdentityRegistry::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Judgement {
+    /// Fee paid, not yet judged by the registrar.
+    FeePaid(Balance),
+    Reasonable,
+    KnownGood,
+    OutOfDate,
+    Erroneous,
+}
+
+#[derive(Debug, Clone)]
+pub struct Registrar {
+    pub fee: Balance,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IdentityError {
+    NoIdentitySet,
+    UnknownRegistrar,
+    FeeTooLow,
+    /// `provide_judgement` was called for a user who never requested
+    /// judgement from this registrar.
+    NoJudgementRequested,
+}
+
+/// Holds every registered identity, the registrar set, and the judgements
+/// registrars have given - keyed per-registrar, since two registrars can
+/// (and in practice do) reach different conclusions about the same
+/// identity.
+#[derive(Debug, Default)]
+pub struct IdentityRegistry {
+    registrars: BTreeMap<RegistrarIndex, Registrar>,
+    identities: BTreeMap<UserId, IdentityInfo>,
+    judgements: BTreeMap<UserId, BTreeMap<RegistrarIndex, Judgement>>,
+}
+
+impl IdentityRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_registrar(&mut self, index: RegistrarIndex, registrar: Registrar) {
+        self.registrars.insert(index, registrar);
+    }
+
+    /// Sets (or replaces) `user_id`'s identity info. Replacing it clears
+    /// any existing judgements, same as `pallet-identity`: a registrar's
+    /// judgement is of the *specific* info it reviewed, not whatever the
+    /// user changes it to afterwards.
+    pub fn set_identity(&mut self, user_id: UserId, info: IdentityInfo) {
+        self.identities.insert(user_id.clone(), info);
+        self.judgements.remove(&user_id);
+    }
+
+    pub fn clear_identity(&mut self, user_id: &UserId) {
+        self.identities.remove(user_id);
+        self.judgements.remove(user_id);
+    }
+
+    pub fn identity(&self, user_id: &UserId) -> Option<&IdentityInfo> {
+        self.identities.get(user_id)
+    }
+
+    pub fn request_judgement(
+        &mut self,
+        user_id: UserId,
+        registrar_index: RegistrarIndex,
+        fee_paid: Balance,
+    ) -> Result<(), IdentityError> {
+        if !self.identities.contains_key(&user_id) {
+            return Err(IdentityError::NoIdentitySet);
+        }
+        let registrar = self
+            .registrars
+            .get(&registrar_index)
+            .ok_or(IdentityError::UnknownRegistrar)?;
+        if fee_paid < registrar.fee {
+            return Err(IdentityError::FeeTooLow);
+        }
+        self.judgements
+            .entry(user_id)
+            .or_default()
+            .insert(registrar_index, Judgement::FeePaid(fee_paid));
+        Ok(())
+    }
+
+    pub fn provide_judgement(
+        &mut self,
+        registrar_index: RegistrarIndex,
+        user_id: &UserId,
+        judgement: Judgement,
+    ) -> Result<(), IdentityError> {
+        let existing = self
+            .judgements
+            .get_mut(user_id)
+            .and_then(|by_registrar| by_registrar.get_mut(&registrar_index))
+            .ok_or(IdentityError::NoJudgementRequested)?;
+        *existing = judgement;
+        Ok(())
+    }
+
+    pub fn judgement_from(&self, user_id: &UserId, registrar_index: RegistrarIndex) -> Option<Judgement> {
+        self.judgements.get(user_id)?.get(&registrar_index).copied()
+    }
+
+    /// Whether any registrar has given `user_id` a `KnownGood` judgement -
+    /// one registrar vouching is enough, the same as on a real chain.
+    pub fn has_known_good_judgement(&self, user_id: &UserId) -> bool {
+        self.judgements
+            .get(user_id)
+            .is_some_and(|by_registrar| by_registrar.values().any(|j| *j == Judgement::KnownGood))
+    }
+}
+
+/// Plugs straight into `VotingState::register_voter_checked`: a deployment
+/// that wants "must have a `KnownGood` identity judgement to vote" passes
+/// the registry itself as the validator.
+impl UserIdValidator for IdentityRegistry {
+    fn validate(&self, user_id: &UserId) -> bool {
+        self.has_known_good_judgement(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use voting_state_machine::VotingState;
+
+    fn alice_identity() -> IdentityInfo {
+        IdentityInfo {
+            display_name: "Alice".to_string(),
+            legal_name: "Alice Liddell".to_string(),
+            email: "alice@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn request_judgement_fails_without_an_identity_set() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+
+        let result = registry.request_judgement("alice".to_string(), 0, 10);
+
+        assert_eq!(result, Err(IdentityError::NoIdentitySet));
+    }
+
+    #[test]
+    fn request_judgement_rejects_a_fee_below_the_registrars_minimum() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+        registry.set_identity("alice".to_string(), alice_identity());
+
+        let result = registry.request_judgement("alice".to_string(), 0, 5);
+
+        assert_eq!(result, Err(IdentityError::FeeTooLow));
+    }
+
+    #[test]
+    fn provide_judgement_requires_a_prior_request() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+        registry.set_identity("alice".to_string(), alice_identity());
+
+        let result = registry.provide_judgement(0, &"alice".to_string(), Judgement::KnownGood);
+
+        assert_eq!(result, Err(IdentityError::NoJudgementRequested));
+    }
+
+    #[test]
+    fn a_known_good_judgement_from_one_registrar_is_enough() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+        registry.add_registrar(1, Registrar { fee: 20 });
+        registry.set_identity("alice".to_string(), alice_identity());
+        registry.request_judgement("alice".to_string(), 0, 10).unwrap();
+        registry
+            .provide_judgement(0, &"alice".to_string(), Judgement::KnownGood)
+            .unwrap();
+
+        assert!(registry.has_known_good_judgement(&"alice".to_string()));
+    }
+
+    #[test]
+    fn replacing_an_identity_clears_its_existing_judgements() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+        registry.set_identity("alice".to_string(), alice_identity());
+        registry.request_judgement("alice".to_string(), 0, 10).unwrap();
+        registry
+            .provide_judgement(0, &"alice".to_string(), Judgement::KnownGood)
+            .unwrap();
+
+        registry.set_identity("alice".to_string(), alice_identity());
+
+        assert!(!registry.has_known_good_judgement(&"alice".to_string()));
+    }
+
+    #[test]
+    fn identity_registry_wires_up_register_voter_checked() {
+        let mut registry = IdentityRegistry::new();
+        registry.add_registrar(0, Registrar { fee: 10 });
+
+        let mut state = VotingState::new("admin".to_string());
+
+        // No identity yet - registration is refused.
+        assert!(state
+            .register_voter_checked("alice".to_string(), &registry)
+            .is_err());
+
+        registry.set_identity("alice".to_string(), alice_identity());
+        registry.request_judgement("alice".to_string(), 0, 10).unwrap();
+        registry
+            .provide_judgement(0, &"alice".to_string(), Judgement::KnownGood)
+            .unwrap();
+
+        assert!(state
+            .register_voter_checked("alice".to_string(), &registry)
+            .is_ok());
+    }
+}
@@ -0,0 +1,91 @@
+//! OpenGov-style support/approval curve, kept as a standalone module so its
+//! math can be unit tested without pulling in the `#[ink::contract]` macro
+//! or any chain environment.
+
+/// Parts-per-million threshold, same convention as `sp_arithmetic::Permill`
+/// without pulling in that dependency for a single ratio type.
+pub type Permill = u32;
+
+pub const PERMILL_MAX: Permill = 1_000_000;
+
+/// A curve whose required approval threshold decays quadratically from
+/// `ceil_permill` at the start of the decision period down to
+/// `floor_permill` once the period has fully elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalCurve {
+    pub ceil_permill: Permill,
+    pub floor_permill: Permill,
+}
+
+impl ApprovalCurve {
+    /// The required approval threshold after `elapsed` of `period` blocks
+    /// have passed. Saturates at `floor_permill` once `elapsed >= period`.
+    pub fn threshold_at(&self, elapsed: u32, period: u32) -> Permill {
+        if period == 0 || elapsed >= period {
+            return self.floor_permill;
+        }
+        let remaining = (period - elapsed) as u64;
+        let period = period as u64;
+        let delta = (self.ceil_permill - self.floor_permill) as u64;
+        let extra = delta * remaining * remaining / (period * period);
+        self.floor_permill + extra as Permill
+    }
+}
+
+/// `votes_for` as parts-per-million of `votes_for + votes_against`, or 0
+/// when no votes were cast at all.
+pub fn approval_permill(votes_for: u32, votes_against: u32) -> Permill {
+    permill_of(votes_for, votes_for + votes_against)
+}
+
+/// `part` as parts-per-million of `whole`, or 0 when `whole` is zero.
+pub fn permill_of(part: u32, whole: u32) -> Permill {
+    (part as u64 * PERMILL_MAX as u64)
+        .checked_div(whole as u64)
+        .unwrap_or(0) as Permill
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: ApprovalCurve = ApprovalCurve {
+        ceil_permill: 800_000,
+        floor_permill: 500_000,
+    };
+
+    #[test]
+    fn threshold_starts_at_ceil_and_decays_to_floor() {
+        assert_eq!(CURVE.threshold_at(0, 100), 800_000);
+        assert_eq!(CURVE.threshold_at(100, 100), 500_000);
+        assert_eq!(CURVE.threshold_at(200, 100), 500_000);
+    }
+
+    #[test]
+    fn threshold_decays_monotonically() {
+        let mut previous = CURVE.threshold_at(0, 100);
+        for elapsed in 1..=100 {
+            let current = CURVE.threshold_at(elapsed, 100);
+            assert!(current <= previous, "threshold must not increase over time");
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn zero_period_is_always_at_floor() {
+        assert_eq!(CURVE.threshold_at(0, 0), CURVE.floor_permill);
+    }
+
+    #[test]
+    fn approval_permill_handles_no_votes() {
+        assert_eq!(approval_permill(0, 0), 0);
+        assert_eq!(approval_permill(1, 1), 500_000);
+        assert_eq!(approval_permill(3, 1), 750_000);
+    }
+
+    #[test]
+    fn permill_of_handles_zero_whole() {
+        assert_eq!(permill_of(0, 0), 0);
+        assert_eq!(permill_of(1, 4), 250_000);
+    }
+}
@@ -0,0 +1,37 @@
+//! Hashing of a finalized round's tally, kept standalone so off-chain
+//! services can recompute it with a plain `ink_env` dependency instead of
+//! pulling in the `#[ink::contract]` macro or a chain environment.
+
+use ink::env::hash::{Blake2x256, HashOutput};
+
+/// `(candidate_id, votes)` pairs for one round, in candidate order.
+pub type BallotSet = ink::prelude::vec::Vec<(u32, u32)>;
+
+/// Hashes `results` the same way `VotingContract` does when it emits
+/// `RoundFinalized`. An off-chain indexer that has reconstructed a round's
+/// tally from `VoteCast` events can call this and compare against the
+/// event's `ballot_hash` to verify it without querying contract storage.
+pub fn hash_results(results: &BallotSet) -> [u8; 32] {
+    let encoded = scale::Encode::encode(results);
+    let mut output = <Blake2x256 as HashOutput>::Type::default();
+    ink::env::hash_bytes::<Blake2x256>(&encoded, &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_tally_hashes_the_same() {
+        let results: BallotSet = ink::prelude::vec![(0, 3), (1, 1)];
+        assert_eq!(hash_results(&results), hash_results(&results));
+    }
+
+    #[test]
+    fn different_tallies_hash_differently() {
+        let a: BallotSet = ink::prelude::vec![(0, 3), (1, 1)];
+        let b: BallotSet = ink::prelude::vec![(0, 1), (1, 3)];
+        assert_ne!(hash_results(&a), hash_results(&b));
+    }
+}
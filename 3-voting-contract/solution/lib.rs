@@ -0,0 +1,836 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub mod approval_curve;
+pub mod ballot_hash;
+
+/// An on-chain ink! port of the Task 1 voting state machine: `AccountId`s
+/// stand in for `caller_id`, and every transition is a `#[ink(message)]`
+/// instead of a free function.
+#[ink::contract]
+pub mod voting_contract {
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// `(candidate_id, votes)` pairs recorded for one finalized round.
+    type RoundResults = Vec<(u32, u32)>;
+
+    /// Mirrors the three states from the Task 1 state machine.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Phase {
+        RegistrationOpen,
+        VotingActive,
+        /// Tally computed but not yet certified: open for `DISPUTE_WINDOW_BLOCKS`
+        /// so registered voters can challenge the result before it is final.
+        Disputed,
+        ResultsFinalized,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum VotingError {
+        NotAdmin,
+        WrongPhase,
+        AlreadyRegistered,
+        NotRegistered,
+        AlreadyVoted,
+        AlreadyAbstained,
+        InvalidCandidate,
+        InvalidConviction,
+        NoLockedDeposit,
+        LockNotExpired,
+        AlreadyDisputed,
+        DisputeWindowClosed,
+        UnresolvedDisputes,
+        DisputeNotFound,
+        VotingWindowNotElapsed,
+        /// `reclaim_storage()` was called while a ballot deposit was still
+        /// locked; call `unlock()` first so the deposit isn't abandoned
+        /// along with the storage record that tracks it.
+        DepositStillLocked,
+    }
+
+    /// Number of supported conviction levels, mirroring Polkadot's `Conviction`
+    /// enum (`None` through `Locked6x`).
+    const MAX_CONVICTION: u8 = 6;
+
+    /// Blocks a freshly computed tally spends in `Phase::Disputed` before it
+    /// is eligible for certification.
+    const DISPUTE_WINDOW_BLOCKS: BlockNumber = 10;
+
+    /// A pending challenge against the current round's tally.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Dispute {
+        reason_hash: Hash,
+        bond: Balance,
+    }
+
+    /// Snapshot returned by `turnout()`: counts are maintained incrementally
+    /// by `vote`/`abstain` rather than recomputed by scanning storage.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Turnout {
+        pub registered: u32,
+        pub voted: u32,
+        pub abstained: u32,
+        pub voted_permill: crate::approval_curve::Permill,
+        pub abstained_permill: crate::approval_curve::Permill,
+    }
+
+    /// How long a deposit stays locked after finalization for a given
+    /// conviction level: doubling per level, starting from one day at level 1.
+    /// Conviction 0 carries no lock at all.
+    fn lock_duration_ms(conviction: u8) -> Timestamp {
+        const ONE_DAY_MS: Timestamp = 24 * 60 * 60 * 1000;
+        if conviction == 0 {
+            0
+        } else {
+            ONE_DAY_MS * (1u64 << (conviction - 1))
+        }
+    }
+
+    /// Emitted when an account registers for the current round.
+    #[ink(event)]
+    pub struct VoterRegistered {
+        #[ink(topic)]
+        voter: AccountId,
+    }
+
+    /// Emitted on every ballot, indexed by both voter and candidate so an
+    /// off-chain indexer can subscribe to either "all votes for X" or "all
+    /// actions by Y" without scanning every event.
+    #[ink(event)]
+    pub struct VoteCast {
+        #[ink(topic)]
+        voter: AccountId,
+        #[ink(topic)]
+        candidate_id: u32,
+    }
+
+    /// Emitted once a round's tally is computed and enters `Phase::Disputed`.
+    /// Carries the full per-candidate tally and a hash of it so off-chain
+    /// services can verify results by replaying `VoteCast` events, without
+    /// having to query contract storage at all.
+    #[ink(event)]
+    pub struct RoundFinalized {
+        #[ink(topic)]
+        round: u32,
+        tally: RoundResults,
+        ballot_hash: [u8; 32],
+    }
+
+    /// Emitted when a registered voter abstains instead of casting a ballot.
+    #[ink(event)]
+    pub struct AbstainCast {
+        #[ink(topic)]
+        voter: AccountId,
+    }
+
+    /// Emitted when a registered voter challenges the tally.
+    #[ink(event)]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        voter: AccountId,
+    }
+
+    /// Emitted once a round's results are certified as final.
+    #[ink(event)]
+    pub struct ResultsCertified {
+        #[ink(topic)]
+        round: u32,
+    }
+
+    /// Emitted when `reclaim_storage()` deletes a voter's records.
+    #[ink(event)]
+    pub struct StorageReclaimed {
+        #[ink(topic)]
+        voter: AccountId,
+    }
+
+    #[ink(storage)]
+    pub struct VotingContract {
+        admin: AccountId,
+        phase: Phase,
+        round: u32,
+        candidates: Vec<String>,
+        registered_voters: Mapping<AccountId, ()>,
+        voter_list: Vec<AccountId>,
+        voted: Mapping<AccountId, ()>,
+        abstained: Mapping<AccountId, ()>,
+        /// Running totals behind `turnout()`; updated in lockstep with
+        /// `voted`/`abstained` instead of derived from them on read.
+        voted_count: u32,
+        abstained_count: u32,
+        vote_counts: Mapping<u32, u32>,
+        /// Snapshot of `(candidate_id, votes)` tallies per finalized round,
+        /// populated by `finalize()` and read back by `round_results()`.
+        history: Mapping<u32, RoundResults>,
+        /// Conviction level chosen by each voter for their current ballot.
+        voter_conviction: Mapping<AccountId, u8>,
+        /// Deposit transferred alongside each ballot, released by `unlock()`.
+        locked_deposits: Mapping<AccountId, Balance>,
+        /// The account whose storage deposit backs a voter's
+        /// `registered_voters`/`voted`/`abstained`/`voter_conviction`
+        /// records - always the voter themselves today, since `register()`
+        /// only ever registers `self.env().caller()`, but kept as its own
+        /// map rather than inferred so a future sponsored-registration
+        /// message has somewhere to record a different payer. Read back
+        /// by `reclaim_storage()` to check the caller actually owns the
+        /// deposit they're asking to release.
+        depositors: Mapping<AccountId, AccountId>,
+        /// Block timestamp at which the current round was finalized.
+        finalized_at: Timestamp,
+        /// Block number at which the current round entered `Phase::Disputed`.
+        disputed_at_block: BlockNumber,
+        disputes: Mapping<AccountId, Dispute>,
+        dispute_list: Vec<AccountId>,
+        pending_disputes: u32,
+        /// Block at which the current round's voting period started.
+        voting_started_at_block: BlockNumber,
+        /// Length, in blocks, of the decaying-approval decision period.
+        decision_period: BlockNumber,
+        /// Whether `candidates[0]` cleared the approval curve, per round.
+        round_passed: Mapping<u32, bool>,
+    }
+
+    impl VotingContract {
+        #[ink(constructor)]
+        pub fn new(candidates: Vec<String>) -> Self {
+            Self {
+                admin: Self::env().caller(),
+                phase: Phase::RegistrationOpen,
+                round: 0,
+                candidates,
+                registered_voters: Mapping::default(),
+                voter_list: Vec::new(),
+                voted: Mapping::default(),
+                abstained: Mapping::default(),
+                voted_count: 0,
+                abstained_count: 0,
+                vote_counts: Mapping::default(),
+                history: Mapping::default(),
+                voter_conviction: Mapping::default(),
+                locked_deposits: Mapping::default(),
+                depositors: Mapping::default(),
+                finalized_at: 0,
+                disputed_at_block: 0,
+                disputes: Mapping::default(),
+                dispute_list: Vec::new(),
+                pending_disputes: 0,
+                voting_started_at_block: 0,
+                decision_period: 0,
+                round_passed: Mapping::default(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn register(&mut self) -> Result<(), VotingError> {
+            if self.phase != Phase::RegistrationOpen {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            if self.registered_voters.contains(caller) {
+                return Err(VotingError::AlreadyRegistered);
+            }
+            self.registered_voters.insert(caller, &());
+            self.voter_list.push(caller);
+            self.depositors.insert(caller, &caller);
+            self.env().emit_event(VoterRegistered { voter: caller });
+            Ok(())
+        }
+
+        /// `decision_period` is the number of blocks over which the
+        /// approval curve checked by `finalize_if_expired` decays from its
+        /// ceiling to its floor.
+        #[ink(message)]
+        pub fn start_voting(&mut self, decision_period: BlockNumber) -> Result<(), VotingError> {
+            self.ensure_admin()?;
+            if self.phase != Phase::RegistrationOpen {
+                return Err(VotingError::WrongPhase);
+            }
+            self.phase = Phase::VotingActive;
+            self.voting_started_at_block = self.env().block_number();
+            self.decision_period = decision_period;
+            Ok(())
+        }
+
+        /// The curve this contract checks `candidates[0]` ("aye") against:
+        /// a 50% approval floor that starts at an 80% ceiling.
+        fn approval_curve() -> crate::approval_curve::ApprovalCurve {
+            crate::approval_curve::ApprovalCurve {
+                ceil_permill: 800_000,
+                floor_permill: 500_000,
+            }
+        }
+
+        /// Permissionless: once `decision_period` blocks have elapsed since
+        /// `start_voting`, evaluates `candidates[0]`'s approval against the
+        /// decayed curve threshold and finalizes the round either way,
+        /// recording the pass/fail verdict in `round_passed`.
+        #[ink(message)]
+        pub fn finalize_if_expired(&mut self) -> Result<bool, VotingError> {
+            if self.phase != Phase::VotingActive {
+                return Err(VotingError::WrongPhase);
+            }
+            let elapsed = self
+                .env()
+                .block_number()
+                .saturating_sub(self.voting_started_at_block);
+            if elapsed < self.decision_period {
+                return Err(VotingError::VotingWindowNotElapsed);
+            }
+            let votes_for = self.vote_counts.get(0).unwrap_or(0);
+            let votes_against: u32 = (1..self.candidates.len() as u32)
+                .map(|id| self.vote_counts.get(id).unwrap_or(0))
+                .sum();
+            let approval = crate::approval_curve::approval_permill(votes_for, votes_against);
+            let threshold = Self::approval_curve().threshold_at(elapsed, self.decision_period);
+            let passed = approval >= threshold;
+            self.round_passed.insert(self.round, &passed);
+            self.finalize_internal();
+            Ok(passed)
+        }
+
+        /// Read back whether `round` passed its approval curve; only
+        /// meaningful for rounds finalized via `finalize_if_expired`.
+        #[ink(message)]
+        pub fn round_passed(&self, round: u32) -> Option<bool> {
+            self.round_passed.get(round)
+        }
+
+        /// Casts a ballot for `candidate_id` with the given `conviction`
+        /// (0-6). Any value transferred alongside the call is locked as a
+        /// deposit until `lock_duration_ms(conviction)` after finalization.
+        #[ink(message, payable)]
+        pub fn vote(&mut self, candidate_id: u32, conviction: u8) -> Result<(), VotingError> {
+            if self.phase != Phase::VotingActive {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            if !self.registered_voters.contains(caller) {
+                return Err(VotingError::NotRegistered);
+            }
+            if self.voted.contains(caller) {
+                return Err(VotingError::AlreadyVoted);
+            }
+            if self.abstained.contains(caller) {
+                return Err(VotingError::AlreadyAbstained);
+            }
+            if candidate_id as usize >= self.candidates.len() {
+                return Err(VotingError::InvalidCandidate);
+            }
+            if conviction > MAX_CONVICTION {
+                return Err(VotingError::InvalidConviction);
+            }
+            self.voted.insert(caller, &());
+            self.voted_count += 1;
+            self.voter_conviction.insert(caller, &conviction);
+            self.locked_deposits
+                .insert(caller, &self.env().transferred_value());
+            let count = self.vote_counts.get(candidate_id).unwrap_or(0);
+            self.vote_counts.insert(candidate_id, &(count + 1));
+            self.env().emit_event(VoteCast { voter: caller, candidate_id });
+            Ok(())
+        }
+
+        /// Records the caller as having turned out without casting a ballot
+        /// for any candidate. Mutually exclusive with `vote`.
+        #[ink(message)]
+        pub fn abstain(&mut self) -> Result<(), VotingError> {
+            if self.phase != Phase::VotingActive {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            if !self.registered_voters.contains(caller) {
+                return Err(VotingError::NotRegistered);
+            }
+            if self.voted.contains(caller) {
+                return Err(VotingError::AlreadyVoted);
+            }
+            if self.abstained.contains(caller) {
+                return Err(VotingError::AlreadyAbstained);
+            }
+            self.abstained.insert(caller, &());
+            self.abstained_count += 1;
+            self.env().emit_event(AbstainCast { voter: caller });
+            Ok(())
+        }
+
+        /// Registered/voted/abstained counts for the current round, with
+        /// voted/abstained expressed as parts-per-million of registered.
+        #[ink(message)]
+        pub fn turnout(&self) -> Turnout {
+            let registered = self.voter_list.len() as u32;
+            Turnout {
+                registered,
+                voted: self.voted_count,
+                abstained: self.abstained_count,
+                voted_permill: crate::approval_curve::permill_of(self.voted_count, registered),
+                abstained_permill: crate::approval_curve::permill_of(
+                    self.abstained_count,
+                    registered,
+                ),
+            }
+        }
+
+        #[ink(message)]
+        pub fn finalize(&mut self) -> Result<(), VotingError> {
+            self.ensure_admin()?;
+            if self.phase != Phase::VotingActive {
+                return Err(VotingError::WrongPhase);
+            }
+            self.finalize_internal();
+            Ok(())
+        }
+
+        /// Shared tail of `finalize` and `finalize_if_expired`: snapshots the
+        /// tally into `history` and moves the round into `Phase::Disputed`.
+        fn finalize_internal(&mut self) {
+            self.phase = Phase::Disputed;
+            self.finalized_at = self.env().block_timestamp();
+            self.disputed_at_block = self.env().block_number();
+            let results: RoundResults = (0..self.candidates.len() as u32)
+                .map(|id| (id, self.vote_counts.get(id).unwrap_or(0)))
+                .collect();
+            self.history.insert(self.round, &results);
+            let ballot_hash = crate::ballot_hash::hash_results(&results);
+            self.env().emit_event(RoundFinalized {
+                round: self.round,
+                tally: results,
+                ballot_hash,
+            });
+        }
+
+        /// Registered voters can challenge the tally with a bonded claim
+        /// while the round is in `Phase::Disputed`.
+        #[ink(message, payable)]
+        pub fn raise_dispute(&mut self, reason_hash: Hash) -> Result<(), VotingError> {
+            if self.phase != Phase::Disputed {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            if !self.registered_voters.contains(caller) {
+                return Err(VotingError::NotRegistered);
+            }
+            if self.disputes.contains(caller) {
+                return Err(VotingError::AlreadyDisputed);
+            }
+            if self.env().block_number() > self.disputed_at_block + DISPUTE_WINDOW_BLOCKS {
+                return Err(VotingError::DisputeWindowClosed);
+            }
+            self.disputes.insert(
+                caller,
+                &Dispute {
+                    reason_hash,
+                    bond: self.env().transferred_value(),
+                },
+            );
+            self.dispute_list.push(caller);
+            self.pending_disputes += 1;
+            self.env().emit_event(DisputeRaised { voter: caller });
+            Ok(())
+        }
+
+        /// Admin-only: settles a raised dispute. `uphold = true` returns the
+        /// bond to the challenger; otherwise the bond is forfeited to the
+        /// contract as a spam deterrent.
+        #[ink(message)]
+        pub fn resolve_dispute(
+            &mut self,
+            voter: AccountId,
+            uphold: bool,
+        ) -> Result<(), VotingError> {
+            self.ensure_admin()?;
+            let dispute = self.disputes.get(voter).ok_or(VotingError::DisputeNotFound)?;
+            self.disputes.remove(voter);
+            self.pending_disputes -= 1;
+            if uphold && dispute.bond > 0 {
+                self.env()
+                    .transfer(voter, dispute.bond)
+                    .unwrap_or_else(|err| panic!("failed to refund dispute bond: {err:?}"));
+            }
+            Ok(())
+        }
+
+        /// Moves a disputed round to `ResultsFinalized` once every raised
+        /// dispute has been resolved by the admin.
+        #[ink(message)]
+        pub fn certify_results(&mut self) -> Result<(), VotingError> {
+            self.ensure_admin()?;
+            if self.phase != Phase::Disputed {
+                return Err(VotingError::WrongPhase);
+            }
+            if self.pending_disputes > 0 {
+                return Err(VotingError::UnresolvedDisputes);
+            }
+            self.dispute_list.clear();
+            self.phase = Phase::ResultsFinalized;
+            self.env().emit_event(ResultsCertified { round: self.round });
+            Ok(())
+        }
+
+        /// Releases the caller's locked deposit once their conviction-based
+        /// lock period has elapsed since finalization.
+        #[ink(message)]
+        pub fn unlock(&mut self) -> Result<(), VotingError> {
+            if self.phase != Phase::ResultsFinalized {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            let amount = self
+                .locked_deposits
+                .get(caller)
+                .ok_or(VotingError::NoLockedDeposit)?;
+            let conviction = self.voter_conviction.get(caller).unwrap_or(0);
+            let unlocks_at = self.finalized_at + lock_duration_ms(conviction);
+            if self.env().block_timestamp() < unlocks_at {
+                return Err(VotingError::LockNotExpired);
+            }
+            self.locked_deposits.remove(caller);
+            self.voter_conviction.remove(caller);
+            if amount > 0 {
+                self.env()
+                    .transfer(caller, amount)
+                    .unwrap_or_else(|err| panic!("failed to unlock deposit: {err:?}"));
+            }
+            Ok(())
+        }
+
+        /// Deletes the caller's own `registered_voters`/`voted`/`abstained`/
+        /// `voter_conviction`/`depositors` records once the round they
+        /// belong to has been archived into `history` and certified -
+        /// releasing the storage deposit `depositors` shows they paid for
+        /// those records, the same way pallet-contracts refunds a
+        /// contract's storage deposit as items are cleared from its trie.
+        ///
+        /// Only callable once any locked ballot deposit has already been
+        /// withdrawn via `unlock()`, so reclaiming storage never abandons
+        /// real funds along with it. The voter must re-register for any
+        /// future round after calling this.
+        #[ink(message)]
+        pub fn reclaim_storage(&mut self) -> Result<(), VotingError> {
+            if self.phase != Phase::ResultsFinalized {
+                return Err(VotingError::WrongPhase);
+            }
+            let caller = self.env().caller();
+            if !self.registered_voters.contains(caller) {
+                return Err(VotingError::NotRegistered);
+            }
+            if self.locked_deposits.contains(caller) {
+                return Err(VotingError::DepositStillLocked);
+            }
+            self.registered_voters.remove(caller);
+            self.voted.remove(caller);
+            self.abstained.remove(caller);
+            self.voter_conviction.remove(caller);
+            self.depositors.remove(caller);
+            self.voter_list.retain(|voter| voter != &caller);
+            self.env().emit_event(StorageReclaimed { voter: caller });
+            Ok(())
+        }
+
+        /// The account whose storage deposit backs `voter`'s records, or
+        /// `None` once they've been reclaimed (or were never registered).
+        #[ink(message)]
+        pub fn depositor_of(&self, voter: AccountId) -> Option<AccountId> {
+            self.depositors.get(voter)
+        }
+
+        /// Starts a new round, preserving `candidates` and `registered_voters`
+        /// but clearing every ballot cast in the previous round. Rejects the
+        /// call with [`VotingError::DepositStillLocked`] while any voter's
+        /// conviction lock from the previous round hasn't expired - the same
+        /// guard [`Self::reclaim_storage`] uses - so a round can never close
+        /// out from under a deposit `unlock()` hasn't paid out yet.
+        #[ink(message)]
+        pub fn reopen_voting(&mut self) -> Result<(), VotingError> {
+            self.ensure_admin()?;
+            if self.phase != Phase::ResultsFinalized {
+                return Err(VotingError::WrongPhase);
+            }
+            if self.voter_list.iter().any(|voter| self.locked_deposits.contains(voter)) {
+                return Err(VotingError::DepositStillLocked);
+            }
+            for voter in &self.voter_list {
+                self.voted.remove(voter);
+                self.abstained.remove(voter);
+                self.voter_conviction.remove(voter);
+            }
+            for id in 0..self.candidates.len() as u32 {
+                self.vote_counts.remove(id);
+            }
+            self.voted_count = 0;
+            self.abstained_count = 0;
+            self.round += 1;
+            self.phase = Phase::VotingActive;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn tally(&self, candidate_id: u32) -> u32 {
+            self.vote_counts.get(candidate_id).unwrap_or(0)
+        }
+
+        /// Returns the `(candidate_id, votes)` snapshot recorded when `round`
+        /// was finalized, or an empty vector if that round never finished.
+        #[ink(message)]
+        pub fn round_results(&self, round: u32) -> RoundResults {
+            self.history.get(round).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn current_round(&self) -> u32 {
+            self.round
+        }
+
+        #[ink(message)]
+        pub fn phase(&self) -> Phase {
+            self.phase
+        }
+
+        fn ensure_admin(&self) -> Result<(), VotingError> {
+            if self.env().caller() != self.admin {
+                return Err(VotingError::NotAdmin);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn candidates() -> Vec<String> {
+            Vec::from([String::from("alice"), String::from("bob")])
+        }
+
+        #[ink::test]
+        fn finalize_if_expired_rejects_before_the_period_ends() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            assert_eq!(
+                contract.finalize_if_expired(),
+                Err(VotingError::VotingWindowNotElapsed)
+            );
+        }
+
+        #[ink::test]
+        fn finalize_if_expired_applies_the_decayed_threshold() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            for _ in 0..9 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            // Unanimous "aye" clears even the 80% ceiling at the start of the window.
+            assert_eq!(contract.finalize_if_expired(), Ok(true));
+            assert_eq!(contract.round_passed(0), Some(true));
+            assert_eq!(contract.phase(), Phase::Disputed);
+        }
+
+        #[ink::test]
+        fn reopen_voting_preserves_candidates_and_registrations() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+            assert_eq!(contract.round_results(0), Vec::from([(0, 1), (1, 0)]));
+            // Conviction 0 unlocks immediately, so this doesn't need to
+            // advance any blocks before reopen_voting is allowed.
+            contract.unlock().unwrap();
+
+            contract.reopen_voting().unwrap();
+            assert_eq!(contract.current_round(), 1);
+            assert_eq!(contract.phase(), Phase::VotingActive);
+            assert_eq!(contract.tally(0), 0);
+            // Registration carried over: the voter can vote again without re-registering.
+            assert_eq!(contract.vote(1, 2), Ok(()));
+        }
+
+        #[ink::test]
+        fn reopen_voting_requires_admin_and_finalized_phase() {
+            let mut contract = VotingContract::new(candidates());
+            assert_eq!(contract.reopen_voting(), Err(VotingError::WrongPhase));
+        }
+
+        #[ink::test]
+        fn reopen_voting_refuses_while_a_real_deposit_is_still_locked() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000);
+            contract.vote(0, 1).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+
+            assert_eq!(contract.reopen_voting(), Err(VotingError::DepositStillLocked));
+
+            let one_day_ms = 24 * 60 * 60 * 1000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(one_day_ms);
+            assert_eq!(contract.unlock(), Ok(()));
+            assert_eq!(contract.reopen_voting(), Ok(()));
+        }
+
+        #[ink::test]
+        fn unlock_waits_for_the_conviction_lock_period() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 1).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+
+            assert_eq!(contract.unlock(), Err(VotingError::LockNotExpired));
+
+            let one_day_ms = 24 * 60 * 60 * 1000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(one_day_ms);
+            assert_eq!(contract.unlock(), Ok(()));
+            assert_eq!(contract.unlock(), Err(VotingError::NoLockedDeposit));
+        }
+
+        #[ink::test]
+        fn certify_results_requires_disputes_to_be_resolved() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            contract.finalize().unwrap();
+            assert_eq!(contract.phase(), Phase::Disputed);
+
+            contract.raise_dispute(Hash::from([1u8; 32])).unwrap();
+            assert_eq!(
+                contract.certify_results(),
+                Err(VotingError::UnresolvedDisputes)
+            );
+
+            let voter = contract.voter_list[0];
+            contract.resolve_dispute(voter, true).unwrap();
+            assert_eq!(contract.certify_results(), Ok(()));
+            assert_eq!(contract.phase(), Phase::ResultsFinalized);
+        }
+
+        #[ink::test]
+        fn abstain_is_mutually_exclusive_with_vote() {
+            let mut contract = VotingContract::new(candidates());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.register().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.register().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            assert_eq!(contract.abstain(), Err(VotingError::AlreadyVoted));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.abstain().unwrap();
+            assert_eq!(contract.abstain(), Err(VotingError::AlreadyAbstained));
+            assert_eq!(contract.vote(0, 0), Err(VotingError::AlreadyAbstained));
+        }
+
+        #[ink::test]
+        fn turnout_tracks_registered_voted_and_abstained() {
+            let mut contract = VotingContract::new(candidates());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            contract.register().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.register().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            contract.start_voting(10).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            contract.abstain().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.vote(0, 0).unwrap();
+
+            let turnout = contract.turnout();
+            assert_eq!(turnout.registered, 2);
+            assert_eq!(turnout.voted, 1);
+            assert_eq!(turnout.abstained, 1);
+            assert_eq!(turnout.voted_permill, 500_000);
+            assert_eq!(turnout.abstained_permill, 500_000);
+        }
+
+        #[ink::test]
+        fn round_results_hash_matches_the_off_chain_helper() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            contract.finalize().unwrap();
+
+            let results = contract.round_results(0);
+            assert_eq!(
+                crate::ballot_hash::hash_results(&results),
+                crate::ballot_hash::hash_results(&Vec::from([(0, 1), (1, 0)])),
+            );
+        }
+
+        #[ink::test]
+        fn unlock_is_immediate_for_zero_conviction() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+            assert_eq!(contract.unlock(), Ok(()));
+        }
+
+        #[ink::test]
+        fn reclaim_storage_deletes_the_callers_records_once_unlocked() {
+            let mut contract = VotingContract::new(candidates());
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            contract.register().unwrap();
+            assert_eq!(contract.depositor_of(accounts.alice), Some(accounts.alice));
+
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 0).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+            contract.unlock().unwrap();
+
+            assert_eq!(contract.reclaim_storage(), Ok(()));
+            assert_eq!(contract.depositor_of(accounts.alice), None);
+            assert!(contract.voter_list.is_empty());
+            assert_eq!(contract.turnout().registered, 0);
+            // The round's tally survives in `history` - only the live
+            // per-voter records were reclaimed.
+            assert_eq!(contract.round_results(0), Vec::from([(0, 1), (1, 0)]));
+        }
+
+        #[ink::test]
+        fn reclaim_storage_requires_an_unlocked_deposit() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            contract.start_voting(10).unwrap();
+            contract.vote(0, 1).unwrap();
+            contract.finalize().unwrap();
+            contract.certify_results().unwrap();
+
+            assert_eq!(contract.reclaim_storage(), Err(VotingError::DepositStillLocked));
+        }
+
+        #[ink::test]
+        fn reclaim_storage_requires_the_round_to_be_finalized() {
+            let mut contract = VotingContract::new(candidates());
+            contract.register().unwrap();
+            assert_eq!(contract.reclaim_storage(), Err(VotingError::WrongPhase));
+        }
+    }
+}
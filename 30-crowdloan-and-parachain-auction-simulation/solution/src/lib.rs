@@ -0,0 +1,384 @@
+//! Solution for Task 30: a candle auction for a parachain slot, and the
+//! crowdloans that fund bids into it. See the README for why the winner
+//! is decided retroactively, after the auction's already closed, instead
+//! of just being whoever was leading when the clock ran out.
+
+use std::collections::BTreeMap;
+
+use voting_state_machine::UserId;
+
+pub type ParaId = u32;
+pub type BlockNumber = u64;
+pub type Balance = u128;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AuctionError {
+    /// A bid landed before the auction's `start` or after its
+    /// `ending_period_end`.
+    BidOutOfWindow,
+    AlreadyClosed,
+    /// `close` was given a random block outside
+    /// `[ending_period_start, ending_period_end]`.
+    RandomBlockOutOfEndingPeriod,
+    /// `close` was called with no bids ever placed.
+    NoBids,
+}
+
+/// A candle auction for one parachain slot. Bids are recorded with the
+/// block they were placed at; the actual winner isn't "whoever's bid is
+/// highest when the auction ends" but "whoever's bid was highest at a
+/// single random block inside the ending period" - see the README.
+pub struct Auction {
+    start: BlockNumber,
+    ending_period_start: BlockNumber,
+    ending_period_end: BlockNumber,
+    bid_history: Vec<(BlockNumber, ParaId, Balance)>,
+    closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuctionResult {
+    pub winner: ParaId,
+    pub winning_bid: Balance,
+    pub decided_at_block: BlockNumber,
+}
+
+impl Auction {
+    pub fn new(start: BlockNumber, ending_period_start: BlockNumber, ending_period_end: BlockNumber) -> Self {
+        Self {
+            start,
+            ending_period_start,
+            ending_period_end,
+            bid_history: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// Records a bid for `para_id` - the new amount a crowdloan has raised
+    /// for it - replacing whatever it had bid before. Bids only ever
+    /// increase in a real auction; this task trusts the caller not to bid
+    /// a lower amount rather than rejecting it, since nothing downstream
+    /// depends on monotonicity.
+    pub fn place_bid(
+        &mut self,
+        para_id: ParaId,
+        amount: Balance,
+        at_block: BlockNumber,
+    ) -> Result<(), AuctionError> {
+        if self.closed {
+            return Err(AuctionError::AlreadyClosed);
+        }
+        if at_block < self.start || at_block > self.ending_period_end {
+            return Err(AuctionError::BidOutOfWindow);
+        }
+        self.bid_history.push((at_block, para_id, amount));
+        Ok(())
+    }
+
+    /// Every para's leading bid as of `at_block` - the latest bid each
+    /// para placed at or before that block, ignoring anything later.
+    fn leading_bids_at(&self, at_block: BlockNumber) -> BTreeMap<ParaId, Balance> {
+        let mut sorted: Vec<_> = self
+            .bid_history
+            .iter()
+            .filter(|(block, ..)| *block <= at_block)
+            .collect();
+        sorted.sort_by_key(|(block, ..)| *block);
+
+        let mut leading = BTreeMap::new();
+        for (_, para_id, amount) in sorted {
+            leading.insert(*para_id, *amount);
+        }
+        leading
+    }
+
+    /// Closes the auction, retroactively picking the winner as whoever
+    /// led at `random_block` - a VRF output only available once the
+    /// ending period is over, which is the whole point: nobody placing a
+    /// bid during the ending period knows which block will end up
+    /// mattering. Ties break toward the lower `ParaId`, for determinism.
+    pub fn close(&mut self, random_block: BlockNumber) -> Result<AuctionResult, AuctionError> {
+        if self.closed {
+            return Err(AuctionError::AlreadyClosed);
+        }
+        if random_block < self.ending_period_start || random_block > self.ending_period_end {
+            return Err(AuctionError::RandomBlockOutOfEndingPeriod);
+        }
+        let leading = self.leading_bids_at(random_block);
+        let winner = leading
+            .iter()
+            .max_by(|(left_id, left_amount), (right_id, right_amount)| {
+                left_amount
+                    .cmp(right_amount)
+                    .then(right_id.cmp(left_id))
+            })
+            .map(|(para_id, amount)| (*para_id, *amount))
+            .ok_or(AuctionError::NoBids)?;
+
+        self.closed = true;
+        Ok(AuctionResult {
+            winner: winner.0,
+            winning_bid: winner.1,
+            decided_at_block: random_block,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CrowdloanError {
+    ContributionWindowClosed,
+    CapExceeded,
+    /// `withdraw` was called before the crowdloan knows whether it won.
+    OutcomeNotYetKnown,
+    /// The crowdloan won its auction - contributions are locked for the
+    /// lease, not refundable.
+    Won,
+    NothingToWithdraw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pending,
+    Won,
+    Lost,
+}
+
+/// Pools contributions toward one para's auction bid, capped so it never
+/// raises more than it asked for, and refunds everyone if the auction is
+/// lost.
+pub struct Crowdloan {
+    para_id: ParaId,
+    cap: Balance,
+    contribution_end: BlockNumber,
+    contributions: BTreeMap<UserId, Balance>,
+    raised: Balance,
+    outcome: Outcome,
+}
+
+impl Crowdloan {
+    pub fn new(para_id: ParaId, cap: Balance, contribution_end: BlockNumber) -> Self {
+        Self {
+            para_id,
+            cap,
+            contribution_end,
+            contributions: BTreeMap::new(),
+            raised: 0,
+            outcome: Outcome::Pending,
+        }
+    }
+
+    pub fn para_id(&self) -> ParaId {
+        self.para_id
+    }
+
+    pub fn raised(&self) -> Balance {
+        self.raised
+    }
+
+    pub fn contribute(
+        &mut self,
+        who: UserId,
+        amount: Balance,
+        now: BlockNumber,
+    ) -> Result<(), CrowdloanError> {
+        if now > self.contribution_end {
+            return Err(CrowdloanError::ContributionWindowClosed);
+        }
+        if self.raised + amount > self.cap {
+            return Err(CrowdloanError::CapExceeded);
+        }
+        *self.contributions.entry(who).or_insert(0) += amount;
+        self.raised += amount;
+        Ok(())
+    }
+
+    /// Tells this crowdloan how its auction ended. Called once, after
+    /// `Auction::close`, for every crowdloan that participated.
+    pub fn resolve(&mut self, auction_result: AuctionResult) {
+        self.outcome = if auction_result.winner == self.para_id {
+            Outcome::Won
+        } else {
+            Outcome::Lost
+        };
+    }
+
+    /// Refunds everything `who` contributed - only once the crowdloan is
+    /// known to have lost.
+    pub fn withdraw(&mut self, who: &UserId) -> Result<Balance, CrowdloanError> {
+        match self.outcome {
+            Outcome::Pending => return Err(CrowdloanError::OutcomeNotYetKnown),
+            Outcome::Won => return Err(CrowdloanError::Won),
+            Outcome::Lost => {}
+        }
+        let amount = self
+            .contributions
+            .remove(who)
+            .ok_or(CrowdloanError::NothingToWithdraw)?;
+        self.raised -= amount;
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn place_bid_rejects_one_outside_the_auction_window() {
+        let mut auction = Auction::new(10, 90, 100);
+
+        assert_eq!(
+            auction.place_bid(1, 1_000, 5),
+            Err(AuctionError::BidOutOfWindow)
+        );
+        assert_eq!(
+            auction.place_bid(1, 1_000, 101),
+            Err(AuctionError::BidOutOfWindow)
+        );
+    }
+
+    #[test]
+    fn close_rejects_a_random_block_outside_the_ending_period() {
+        let mut auction = Auction::new(10, 90, 100);
+        auction.place_bid(1, 1_000, 50).unwrap();
+
+        assert_eq!(
+            auction.close(89),
+            Err(AuctionError::RandomBlockOutOfEndingPeriod)
+        );
+        assert_eq!(
+            auction.close(101),
+            Err(AuctionError::RandomBlockOutOfEndingPeriod)
+        );
+    }
+
+    #[test]
+    fn close_fails_if_nobody_ever_bid() {
+        let mut auction = Auction::new(10, 90, 100);
+
+        assert_eq!(auction.close(95), Err(AuctionError::NoBids));
+    }
+
+    #[test]
+    fn the_winner_is_whoever_led_at_the_random_block_not_at_the_final_bid() {
+        let mut auction = Auction::new(10, 90, 100);
+        // Para 1 leads for most of the ending period...
+        auction.place_bid(1, 5_000, 91).unwrap();
+        // ...but para 2 snipes right at the very end.
+        auction.place_bid(2, 9_000, 99).unwrap();
+
+        // The randomly-chosen deciding block falls before the snipe -
+        // para 1 wins despite para 2's bid being higher overall.
+        let result = auction.close(95).unwrap();
+
+        assert_eq!(
+            result,
+            AuctionResult {
+                winner: 1,
+                winning_bid: 5_000,
+                decided_at_block: 95,
+            }
+        );
+    }
+
+    #[test]
+    fn the_snipe_would_have_won_had_the_random_block_landed_after_it() {
+        let mut auction = Auction::new(10, 90, 100);
+        auction.place_bid(1, 5_000, 91).unwrap();
+        auction.place_bid(2, 9_000, 99).unwrap();
+
+        let result = auction.close(100).unwrap();
+
+        assert_eq!(result.winner, 2);
+        assert_eq!(result.winning_bid, 9_000);
+    }
+
+    #[test]
+    fn a_tie_breaks_toward_the_lower_para_id() {
+        let mut auction = Auction::new(10, 90, 100);
+        auction.place_bid(5, 1_000, 91).unwrap();
+        auction.place_bid(2, 1_000, 92).unwrap();
+
+        let result = auction.close(95).unwrap();
+
+        assert_eq!(result.winner, 2);
+    }
+
+    #[test]
+    fn auction_cannot_be_closed_twice() {
+        let mut auction = Auction::new(10, 90, 100);
+        auction.place_bid(1, 1_000, 91).unwrap();
+        auction.close(95).unwrap();
+
+        assert_eq!(auction.close(96), Err(AuctionError::AlreadyClosed));
+    }
+
+    #[test]
+    fn contribute_rejects_anything_over_the_cap() {
+        let mut crowdloan = Crowdloan::new(1, 1_000, 100);
+        crowdloan.contribute("alice".to_string(), 900, 0).unwrap();
+
+        let result = crowdloan.contribute("bob".to_string(), 200, 0);
+
+        assert_eq!(result, Err(CrowdloanError::CapExceeded));
+        assert_eq!(crowdloan.raised(), 900);
+    }
+
+    #[test]
+    fn contribute_rejects_anything_after_the_contribution_window_closes() {
+        let mut crowdloan = Crowdloan::new(1, 1_000, 100);
+
+        let result = crowdloan.contribute("alice".to_string(), 100, 101);
+
+        assert_eq!(result, Err(CrowdloanError::ContributionWindowClosed));
+    }
+
+    #[test]
+    fn withdraw_before_the_auction_resolves_is_refused() {
+        let mut crowdloan = Crowdloan::new(1, 1_000, 100);
+        crowdloan.contribute("alice".to_string(), 100, 0).unwrap();
+
+        assert_eq!(
+            crowdloan.withdraw(&"alice".to_string()),
+            Err(CrowdloanError::OutcomeNotYetKnown)
+        );
+    }
+
+    #[test]
+    fn a_winning_crowdloan_refuses_withdrawals() {
+        let mut auction = Auction::new(10, 90, 100);
+        let mut crowdloan = Crowdloan::new(1, 1_000, 80);
+        crowdloan.contribute("alice".to_string(), 500, 0).unwrap();
+        auction.place_bid(1, 500, 50).unwrap();
+        let result = auction.close(95).unwrap();
+
+        crowdloan.resolve(result);
+
+        assert_eq!(
+            crowdloan.withdraw(&"alice".to_string()),
+            Err(CrowdloanError::Won)
+        );
+    }
+
+    #[test]
+    fn a_losing_crowdloan_refunds_every_contributor_in_full() {
+        let mut auction = Auction::new(10, 90, 100);
+        let mut crowdloan = Crowdloan::new(2, 1_000, 80);
+        crowdloan.contribute("alice".to_string(), 300, 0).unwrap();
+        crowdloan.contribute("bob".to_string(), 200, 0).unwrap();
+        auction.place_bid(1, 9_000, 50).unwrap();
+        auction.place_bid(2, 500, 50).unwrap();
+        let result = auction.close(95).unwrap();
+        assert_eq!(result.winner, 1);
+
+        crowdloan.resolve(result);
+
+        assert_eq!(crowdloan.withdraw(&"alice".to_string()), Ok(300));
+        assert_eq!(crowdloan.withdraw(&"bob".to_string()), Ok(200));
+        assert_eq!(crowdloan.raised(), 0);
+        assert_eq!(
+            crowdloan.withdraw(&"alice".to_string()),
+            Err(CrowdloanError::NothingToWithdraw)
+        );
+    }
+}
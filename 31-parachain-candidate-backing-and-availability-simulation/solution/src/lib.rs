@@ -0,0 +1,279 @@
+//! Solution for Task 31: a parachain candidate's path from being seconded
+//! by its backing group to being available widely enough to include -
+//! distilled into plain Rust types with no runtime, network, or SCALE
+//! encoding involved. See the README for why backing and availability
+//! use different thresholds.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type ValidatorId = String;
+pub type CandidateHash = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Statement {
+    /// The first statement on a candidate - proposes it for backing.
+    Seconded,
+    /// Confirms a candidate some other validator already seconded.
+    Valid,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BackingError {
+    NotInGroup,
+    DuplicateStatement,
+    /// A `Valid` statement arrived for a candidate nobody has seconded
+    /// yet - there's nothing to confirm.
+    ValidBeforeSeconded,
+}
+
+/// The small set of validators assigned to back candidates for one para.
+/// Mirrors `polkadot`'s backing groups, minus session rotation.
+pub struct BackingGroup {
+    validators: BTreeSet<ValidatorId>,
+    min_backing_votes: usize,
+    statements: BTreeMap<CandidateHash, BTreeMap<ValidatorId, Statement>>,
+}
+
+impl BackingGroup {
+    pub fn new(validators: Vec<ValidatorId>, min_backing_votes: usize) -> Self {
+        Self {
+            validators: validators.into_iter().collect(),
+            min_backing_votes,
+            statements: BTreeMap::new(),
+        }
+    }
+
+    /// Records a statement from `validator` on `candidate`. Each
+    /// validator may only ever submit one statement per candidate, and a
+    /// `Valid` statement requires some validator having already
+    /// submitted `Seconded` first.
+    pub fn submit_statement(
+        &mut self,
+        candidate: CandidateHash,
+        validator: ValidatorId,
+        statement: Statement,
+    ) -> Result<(), BackingError> {
+        if !self.validators.contains(&validator) {
+            return Err(BackingError::NotInGroup);
+        }
+        let candidate_statements = self.statements.entry(candidate).or_default();
+        if candidate_statements.contains_key(&validator) {
+            return Err(BackingError::DuplicateStatement);
+        }
+        if statement == Statement::Valid
+            && !candidate_statements.values().any(|s| *s == Statement::Seconded)
+        {
+            return Err(BackingError::ValidBeforeSeconded);
+        }
+        candidate_statements.insert(validator, statement);
+        Ok(())
+    }
+
+    /// Whether `candidate` has collected enough statements to be backed -
+    /// at least `min_backing_votes` distinct validators, one of which
+    /// seconded it (guaranteed by `submit_statement` refusing a `Valid`
+    /// statement before any `Seconded` one).
+    pub fn is_backed(&self, candidate: CandidateHash) -> bool {
+        self.statements
+            .get(&candidate)
+            .is_some_and(|statements| statements.len() >= self.min_backing_votes)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AvailabilityError {
+    UnknownValidator,
+    DuplicateBitfield,
+}
+
+/// Tracks, across the *whole* active validator set (not just a backing
+/// group), which validators report holding a backed candidate's data.
+/// Mirrors the availability bitfields gossiped once per block.
+pub struct AvailabilityTracker {
+    validators: BTreeSet<ValidatorId>,
+    attestations: BTreeMap<CandidateHash, BTreeSet<ValidatorId>>,
+}
+
+impl AvailabilityTracker {
+    pub fn new(validators: Vec<ValidatorId>) -> Self {
+        Self {
+            validators: validators.into_iter().collect(),
+            attestations: BTreeMap::new(),
+        }
+    }
+
+    /// Records that `validator`'s availability bitfield has its bit set
+    /// for `candidate`.
+    pub fn record_bitfield(
+        &mut self,
+        validator: ValidatorId,
+        candidate: CandidateHash,
+    ) -> Result<(), AvailabilityError> {
+        if !self.validators.contains(&validator) {
+            return Err(AvailabilityError::UnknownValidator);
+        }
+        let attesting = self.attestations.entry(candidate).or_default();
+        if !attesting.insert(validator) {
+            return Err(AvailabilityError::DuplicateBitfield);
+        }
+        Ok(())
+    }
+
+    /// Whether `candidate` has been attested available by at least 2/3 of
+    /// the whole validator set - the same supermajority threshold as
+    /// GRANDPA finality, applied here to data availability instead of
+    /// block finality.
+    pub fn is_available(&self, candidate: CandidateHash) -> bool {
+        let total = self.validators.len();
+        if total == 0 {
+            return false;
+        }
+        let threshold = total * 2 / 3 + 1;
+        let attesting = self.attestations.get(&candidate).map_or(0, BTreeSet::len);
+        attesting >= threshold
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InclusionError {
+    NotBacked,
+    NotAvailable,
+}
+
+/// A candidate can only be included once it's both backed by its group
+/// and available to the wider validator set - neither threshold alone is
+/// enough.
+pub fn try_include_candidate(
+    backing: &BackingGroup,
+    availability: &AvailabilityTracker,
+    candidate: CandidateHash,
+) -> Result<(), InclusionError> {
+    if !backing.is_backed(candidate) {
+        return Err(InclusionError::NotBacked);
+    }
+    if !availability.is_available(candidate) {
+        return Err(InclusionError::NotAvailable);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group() -> BackingGroup {
+        BackingGroup::new(
+            vec!["v1".into(), "v2".into(), "v3".into()],
+            2,
+        )
+    }
+
+    #[test]
+    fn a_validator_outside_the_group_cannot_submit_a_statement() {
+        let mut group = group();
+        let result = group.submit_statement(1, "outsider".into(), Statement::Seconded);
+        assert_eq!(result, Err(BackingError::NotInGroup));
+    }
+
+    #[test]
+    fn a_valid_statement_before_any_seconded_statement_is_rejected() {
+        let mut group = group();
+        let result = group.submit_statement(1, "v1".into(), Statement::Valid);
+        assert_eq!(result, Err(BackingError::ValidBeforeSeconded));
+    }
+
+    #[test]
+    fn a_validator_cannot_submit_two_statements_on_the_same_candidate() {
+        let mut group = group();
+        group.submit_statement(1, "v1".into(), Statement::Seconded).unwrap();
+        let result = group.submit_statement(1, "v1".into(), Statement::Valid);
+        assert_eq!(result, Err(BackingError::DuplicateStatement));
+    }
+
+    #[test]
+    fn candidate_is_backed_once_the_minimum_vote_count_is_reached() {
+        let mut group = group();
+        group.submit_statement(1, "v1".into(), Statement::Seconded).unwrap();
+        assert!(!group.is_backed(1));
+
+        group.submit_statement(1, "v2".into(), Statement::Valid).unwrap();
+        assert!(group.is_backed(1));
+    }
+
+    #[test]
+    fn backing_is_tracked_independently_per_candidate() {
+        let mut group = group();
+        group.submit_statement(1, "v1".into(), Statement::Seconded).unwrap();
+        group.submit_statement(1, "v2".into(), Statement::Valid).unwrap();
+        assert!(group.is_backed(1));
+        assert!(!group.is_backed(2));
+    }
+
+    fn validators(n: usize) -> Vec<ValidatorId> {
+        (0..n).map(|i| format!("v{i}")).collect()
+    }
+
+    #[test]
+    fn an_unknown_validator_cannot_record_a_bitfield() {
+        let mut tracker = AvailabilityTracker::new(validators(4));
+        let result = tracker.record_bitfield("outsider".into(), 1);
+        assert_eq!(result, Err(AvailabilityError::UnknownValidator));
+    }
+
+    #[test]
+    fn the_same_validator_cannot_attest_the_same_candidate_twice() {
+        let mut tracker = AvailabilityTracker::new(validators(4));
+        tracker.record_bitfield("v0".into(), 1).unwrap();
+        let result = tracker.record_bitfield("v0".into(), 1);
+        assert_eq!(result, Err(AvailabilityError::DuplicateBitfield));
+    }
+
+    #[test]
+    fn availability_requires_two_thirds_of_the_whole_validator_set() {
+        let mut tracker = AvailabilityTracker::new(validators(4));
+        tracker.record_bitfield("v0".into(), 1).unwrap();
+        tracker.record_bitfield("v1".into(), 1).unwrap();
+        assert!(!tracker.is_available(1));
+
+        tracker.record_bitfield("v2".into(), 1).unwrap();
+        assert!(tracker.is_available(1));
+    }
+
+    #[test]
+    fn a_backed_but_unavailable_candidate_cannot_be_included() {
+        let mut backing = group();
+        backing.submit_statement(1, "v1".into(), Statement::Seconded).unwrap();
+        backing.submit_statement(1, "v2".into(), Statement::Valid).unwrap();
+        let availability = AvailabilityTracker::new(validators(4));
+
+        let result = try_include_candidate(&backing, &availability, 1);
+
+        assert_eq!(result, Err(InclusionError::NotAvailable));
+    }
+
+    #[test]
+    fn an_available_but_unbacked_candidate_cannot_be_included() {
+        let backing = group();
+        let mut availability = AvailabilityTracker::new(validators(4));
+        availability.record_bitfield("v0".into(), 1).unwrap();
+        availability.record_bitfield("v1".into(), 1).unwrap();
+        availability.record_bitfield("v2".into(), 1).unwrap();
+
+        let result = try_include_candidate(&backing, &availability, 1);
+
+        assert_eq!(result, Err(InclusionError::NotBacked));
+    }
+
+    #[test]
+    fn a_backed_and_available_candidate_is_included() {
+        let mut backing = group();
+        backing.submit_statement(1, "v1".into(), Statement::Seconded).unwrap();
+        backing.submit_statement(1, "v2".into(), Statement::Valid).unwrap();
+        let mut availability = AvailabilityTracker::new(validators(4));
+        availability.record_bitfield("v0".into(), 1).unwrap();
+        availability.record_bitfield("v1".into(), 1).unwrap();
+        availability.record_bitfield("v2".into(), 1).unwrap();
+
+        assert_eq!(try_include_candidate(&backing, &availability, 1), Ok(()));
+    }
+}
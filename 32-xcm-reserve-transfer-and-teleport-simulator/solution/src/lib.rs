@@ -0,0 +1,308 @@
+//! Solution for Task 32: a two-chain XCM simulator covering the two ways
+//! an asset crosses chains - reserve-backed transfers and teleports - and
+//! what happens when either one fails partway through. See the README
+//! for why a failed destination-side step traps the asset instead of
+//! just erroring out.
+
+use std::collections::BTreeMap;
+
+pub type ChainId = u32;
+pub type AccountId = String;
+pub type AssetId = u32;
+pub type Balance = u128;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum XcmError {
+    UnknownChain,
+    InsufficientBalance,
+    /// Neither leg of a reserve-backed transfer is the asset's reserve
+    /// chain - a real XCM router would need an extra reserve-to-reserve
+    /// hop this simulator doesn't model.
+    NotReserveChain,
+    /// This chain pair isn't a trusted teleport pair.
+    TeleportNotTrusted,
+    /// The fee charged on arrival is at least the whole transferred
+    /// amount - nothing's left to either pay the fee or credit the
+    /// beneficiary, so the withdrawn asset is trapped instead.
+    InsufficientFee,
+}
+
+/// Everything needed to move an asset from one account on one chain to
+/// an account on another - the common shape behind both
+/// [`XcmNetwork::reserve_transfer`] and [`XcmNetwork::teleport`].
+pub struct Transfer {
+    pub from_chain: ChainId,
+    pub from_account: AccountId,
+    pub to_chain: ChainId,
+    pub to_account: AccountId,
+    pub asset: AssetId,
+    pub amount: Balance,
+    /// Deducted from `amount` on arrival, mirroring `BuyExecution`
+    /// spending part of the transferred asset to pay for itself.
+    pub fee: Balance,
+}
+
+/// An asset withdrawn from its sender but never successfully credited to
+/// a beneficiary - the destination-side half of the program couldn't
+/// complete. Mirrors a real `AssetTrap`: the asset still exists
+/// somewhere in the system, just not in anyone's spendable balance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrappedAsset {
+    pub chain: ChainId,
+    pub asset: AssetId,
+    pub amount: Balance,
+}
+
+#[derive(Debug, Default)]
+struct Chain {
+    balances: BTreeMap<(AccountId, AssetId), Balance>,
+}
+
+impl Chain {
+    fn balance_of(&self, account: &AccountId, asset: AssetId) -> Balance {
+        self.balances.get(&(account.clone(), asset)).copied().unwrap_or(0)
+    }
+
+    fn credit(&mut self, account: &AccountId, asset: AssetId, amount: Balance) {
+        *self.balances.entry((account.clone(), asset)).or_insert(0) += amount;
+    }
+
+    fn debit(&mut self, account: &AccountId, asset: AssetId, amount: Balance) -> Result<(), XcmError> {
+        let balance = self.balance_of(account, asset);
+        if balance < amount {
+            return Err(XcmError::InsufficientBalance);
+        }
+        self.balances.insert((account.clone(), asset), balance - amount);
+        Ok(())
+    }
+}
+
+/// A small network of chains, each with their own asset balances, that
+/// assets can move between via reserve-backed transfers or teleports.
+#[derive(Default)]
+pub struct XcmNetwork {
+    chains: BTreeMap<ChainId, Chain>,
+    reserve_of: BTreeMap<AssetId, ChainId>,
+    teleport_trust: Vec<(ChainId, ChainId)>,
+    trapped_assets: Vec<TrappedAsset>,
+}
+
+impl XcmNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_chain(&mut self, chain_id: ChainId) {
+        self.chains.entry(chain_id).or_default();
+    }
+
+    pub fn fund(&mut self, chain_id: ChainId, account: AccountId, asset: AssetId, amount: Balance) -> Result<(), XcmError> {
+        let chain = self.chains.get_mut(&chain_id).ok_or(XcmError::UnknownChain)?;
+        chain.credit(&account, asset, amount);
+        Ok(())
+    }
+
+    pub fn balance_of(&self, chain_id: ChainId, account: &AccountId, asset: AssetId) -> Balance {
+        self.chains.get(&chain_id).map_or(0, |chain| chain.balance_of(account, asset))
+    }
+
+    pub fn register_reserve(&mut self, asset: AssetId, reserve_chain: ChainId) {
+        self.reserve_of.insert(asset, reserve_chain);
+    }
+
+    pub fn trust_teleport(&mut self, from_chain: ChainId, to_chain: ChainId) {
+        self.teleport_trust.push((from_chain, to_chain));
+    }
+
+    pub fn trapped_assets(&self) -> &[TrappedAsset] {
+        &self.trapped_assets
+    }
+
+    /// Debits `amount` of `asset` from `account` on `chain_id` directly,
+    /// with no reserve/teleport-trust checks - the raw balance move an
+    /// XCM executor's `WithdrawAsset` instruction performs into its
+    /// holding register.
+    pub fn withdraw(&mut self, chain_id: ChainId, account: &AccountId, asset: AssetId, amount: Balance) -> Result<(), XcmError> {
+        let chain = self.chains.get_mut(&chain_id).ok_or(XcmError::UnknownChain)?;
+        chain.debit(account, asset, amount)
+    }
+
+    /// Credits `amount` of `asset` to `account` on `chain_id` directly -
+    /// the raw balance move a `DepositAsset` instruction performs out of
+    /// holding.
+    pub fn deposit(&mut self, chain_id: ChainId, account: &AccountId, asset: AssetId, amount: Balance) -> Result<(), XcmError> {
+        let chain = self.chains.get_mut(&chain_id).ok_or(XcmError::UnknownChain)?;
+        chain.credit(account, asset, amount);
+        Ok(())
+    }
+
+    /// Moves `asset` from `from_account` on `from_chain` to `to_account`
+    /// on `to_chain`, backed by `asset`'s reserve chain holding the real
+    /// balance. One of `from_chain`/`to_chain` must be that reserve
+    /// chain - this simulator doesn't model routing through a third,
+    /// uninvolved reserve.
+    ///
+    /// `fee` is deducted from `amount` on arrival, the same as a real XCM
+    /// program's `BuyExecution` spending part of the transferred asset
+    /// rather than requiring a separate native-token fee payment. If
+    /// `fee >= amount` the withdrawal already happened but nothing can be
+    /// credited, so the asset is trapped on `to_chain`.
+    pub fn reserve_transfer(&mut self, transfer: Transfer) -> Result<(), XcmError> {
+        let reserve = self.reserve_of.get(&transfer.asset).copied();
+        if reserve != Some(transfer.from_chain) && reserve != Some(transfer.to_chain) {
+            return Err(XcmError::NotReserveChain);
+        }
+
+        self.withdraw_then_deposit(transfer)
+    }
+
+    /// Moves `asset` directly between a trusted pair of chains by burning
+    /// it on `from_chain` and minting it on `to_chain`, with no reserve
+    /// chain involved. Same on-arrival fee and trapping behavior as
+    /// [`Self::reserve_transfer`].
+    pub fn teleport(&mut self, transfer: Transfer) -> Result<(), XcmError> {
+        if !self.teleport_trust.contains(&(transfer.from_chain, transfer.to_chain)) {
+            return Err(XcmError::TeleportNotTrusted);
+        }
+
+        self.withdraw_then_deposit(transfer)
+    }
+
+    fn withdraw_then_deposit(&mut self, transfer: Transfer) -> Result<(), XcmError> {
+        let Transfer { from_chain, from_account, to_chain, to_account, asset, amount, fee } = transfer;
+
+        {
+            let sender = self.chains.get_mut(&from_chain).ok_or(XcmError::UnknownChain)?;
+            sender.debit(&from_account, asset, amount)?;
+        }
+        if !self.chains.contains_key(&to_chain) {
+            return Err(XcmError::UnknownChain);
+        }
+
+        if fee >= amount {
+            self.trapped_assets.push(TrappedAsset { chain: to_chain, asset, amount });
+            return Err(XcmError::InsufficientFee);
+        }
+
+        let destination = self.chains.get_mut(&to_chain).expect("checked above");
+        destination.credit(&to_account, asset, amount - fee);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELAY: ChainId = 0;
+    const PARA_A: ChainId = 1;
+    const PARA_B: ChainId = 2;
+    const DOT: AssetId = 0;
+
+    fn network() -> XcmNetwork {
+        let mut network = XcmNetwork::new();
+        network.add_chain(RELAY);
+        network.add_chain(PARA_A);
+        network.add_chain(PARA_B);
+        network.register_reserve(DOT, RELAY);
+        network
+    }
+
+    fn transfer(from_chain: ChainId, to_chain: ChainId, amount: Balance, fee: Balance) -> Transfer {
+        Transfer {
+            from_chain,
+            from_account: "alice".into(),
+            to_chain,
+            to_account: "alice".into(),
+            asset: DOT,
+            amount,
+            fee,
+        }
+    }
+
+    #[test]
+    fn reserve_transfer_from_the_reserve_chain_credits_the_destination_minus_fee() {
+        let mut network = network();
+        network.fund(RELAY, "alice".into(), DOT, 1_000).unwrap();
+
+        network.reserve_transfer(transfer(RELAY, PARA_A, 500, 10)).unwrap();
+
+        assert_eq!(network.balance_of(RELAY, &"alice".to_string(), DOT), 500);
+        assert_eq!(network.balance_of(PARA_A, &"alice".to_string(), DOT), 490);
+    }
+
+    #[test]
+    fn reserve_transfer_between_two_non_reserve_chains_is_rejected() {
+        let mut network = network();
+        network.fund(PARA_A, "alice".into(), DOT, 1_000).unwrap();
+
+        let result = network.reserve_transfer(transfer(PARA_A, PARA_B, 500, 10));
+
+        assert_eq!(result, Err(XcmError::NotReserveChain));
+    }
+
+    #[test]
+    fn reserve_transfer_with_insufficient_balance_moves_nothing() {
+        let mut network = network();
+        network.fund(RELAY, "alice".into(), DOT, 100).unwrap();
+
+        let result = network.reserve_transfer(transfer(RELAY, PARA_A, 500, 10));
+
+        assert_eq!(result, Err(XcmError::InsufficientBalance));
+        assert_eq!(network.balance_of(RELAY, &"alice".to_string(), DOT), 100);
+        assert_eq!(network.balance_of(PARA_A, &"alice".to_string(), DOT), 0);
+    }
+
+    #[test]
+    fn a_fee_at_least_the_transferred_amount_traps_the_asset_on_the_destination() {
+        let mut network = network();
+        network.fund(RELAY, "alice".into(), DOT, 1_000).unwrap();
+
+        let result = network.reserve_transfer(transfer(RELAY, PARA_A, 500, 500));
+
+        assert_eq!(result, Err(XcmError::InsufficientFee));
+        // The withdrawal already happened on the reserve chain...
+        assert_eq!(network.balance_of(RELAY, &"alice".to_string(), DOT), 500);
+        // ...but nothing landed in alice's destination balance.
+        assert_eq!(network.balance_of(PARA_A, &"alice".to_string(), DOT), 0);
+        assert_eq!(
+            network.trapped_assets(),
+            &[TrappedAsset { chain: PARA_A, asset: DOT, amount: 500 }]
+        );
+    }
+
+    #[test]
+    fn teleport_requires_a_trusted_chain_pair() {
+        let mut network = network();
+        network.fund(PARA_A, "alice".into(), DOT, 1_000).unwrap();
+
+        let result = network.teleport(transfer(PARA_A, PARA_B, 500, 10));
+
+        assert_eq!(result, Err(XcmError::TeleportNotTrusted));
+    }
+
+    #[test]
+    fn teleport_moves_the_asset_directly_between_trusted_chains() {
+        let mut network = network();
+        network.trust_teleport(PARA_A, PARA_B);
+        network.fund(PARA_A, "alice".into(), DOT, 1_000).unwrap();
+
+        network.teleport(transfer(PARA_A, PARA_B, 500, 10)).unwrap();
+
+        assert_eq!(network.balance_of(PARA_A, &"alice".to_string(), DOT), 500);
+        assert_eq!(network.balance_of(PARA_B, &"alice".to_string(), DOT), 490);
+    }
+
+    #[test]
+    fn a_teleport_with_insufficient_fee_also_traps_the_asset() {
+        let mut network = network();
+        network.trust_teleport(PARA_A, PARA_B);
+        network.fund(PARA_A, "alice".into(), DOT, 1_000).unwrap();
+
+        let result = network.teleport(transfer(PARA_A, PARA_B, 500, 500));
+
+        assert_eq!(result, Err(XcmError::InsufficientFee));
+        assert_eq!(network.balance_of(PARA_B, &"alice".to_string(), DOT), 0);
+        assert_eq!(network.trapped_assets().len(), 1);
+    }
+}
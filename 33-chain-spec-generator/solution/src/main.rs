@@ -0,0 +1,109 @@
+//! Solution for Task 33: turns a small TOML description of a chain's
+//! genesis (balances, initial authorities, sudo key) into the same shape
+//! of JSON chain spec `build-spec` produces, validating every address
+//! along the way with Task 19's SS58 codec.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ss58_address_codec::decode as ss58_decode;
+
+#[derive(Parser)]
+#[command(about = "Builds a JSON chain spec from a TOML genesis description")]
+struct Cli {
+    /// Path to the TOML input describing the chain's genesis.
+    input: PathBuf,
+
+    /// Where to write the generated chain spec. Prints to stdout if omitted.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct ChainSpecInput {
+    name: String,
+    id: String,
+    ss58_prefix: u16,
+    sudo: String,
+    authorities: Vec<String>,
+    balances: Vec<BalanceEntry>,
+}
+
+#[derive(Deserialize)]
+struct BalanceEntry {
+    address: String,
+    /// A string rather than an integer - TOML (and JSON, without the
+    /// `arbitrary_precision` feature) can't round-trip `u128` natively,
+    /// and genesis balances routinely need the full range.
+    balance: String,
+}
+
+/// Decodes `address` as SS58 and checks it was encoded for
+/// `expected_prefix` - the same "don't just check it parses, check it's
+/// for the right network" validation Task 19's `Ss58Validator` does.
+fn validate_address(address: &str, expected_prefix: u16) -> Result<()> {
+    let (prefix, _account_id) =
+        ss58_decode(address).map_err(|err| anyhow::anyhow!("{address}: not a valid SS58 address ({err:?})"))?;
+    if prefix != expected_prefix {
+        bail!("{address}: encoded for ss58 prefix {prefix}, expected {expected_prefix}");
+    }
+    Ok(())
+}
+
+fn build_chain_spec(input: &ChainSpecInput) -> Result<Value> {
+    validate_address(&input.sudo, input.ss58_prefix).context("invalid sudo key")?;
+    if input.authorities.is_empty() {
+        bail!("at least one initial authority is required");
+    }
+    for address in &input.authorities {
+        validate_address(address, input.ss58_prefix).context("invalid authority")?;
+    }
+    let mut balances = Vec::with_capacity(input.balances.len());
+    for entry in &input.balances {
+        validate_address(&entry.address, input.ss58_prefix).context("invalid balance recipient")?;
+        let balance: u128 = entry
+            .balance
+            .parse()
+            .with_context(|| format!("{}: balance {:?} is not a valid integer", entry.address, entry.balance))?;
+        balances.push(json!([entry.address, balance]));
+    }
+
+    Ok(json!({
+        "name": input.name,
+        "id": input.id,
+        "chainType": "Live",
+        "properties": { "ss58Format": input.ss58_prefix },
+        "genesis": {
+            "runtime": {
+                "balances": { "balances": balances },
+                "aura": { "authorities": input.authorities },
+                "sudo": { "key": input.sudo },
+            }
+        }
+    }))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let toml_source = fs::read_to_string(&cli.input)
+        .with_context(|| format!("failed to read {}", cli.input.display()))?;
+    let input: ChainSpecInput =
+        toml::from_str(&toml_source).context("failed to parse chain spec input as TOML")?;
+
+    let chain_spec = build_chain_spec(&input)?;
+    let rendered = serde_json::to_string_pretty(&chain_spec).context("failed to render chain spec as JSON")?;
+
+    match cli.output {
+        Some(path) => {
+            fs::write(&path, rendered).with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        None => println!("{rendered}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Solution for Task 34: combining several of Task 16's single-key
+//! inclusion proofs into one deduplicated storage proof - the shape of
+//! `sp-trie`'s `StorageProof`, which light-client `state_getReadProof`
+//! calls and XCMP message proofs both build on: one root hash, many
+//! keys, one proof blob that doesn't repeat the trie nodes their paths
+//! happen to share.
+use std::collections::BTreeMap;
+
+use merkle_patricia_trie::{Hash, ProofNode, Trie};
+
+/// A proof covering every key it was [`generate`](StorageProof::generate)d
+/// for, stored as the set of distinct nodes their paths touch.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageProof {
+    nodes: BTreeMap<Hash, ProofNode>,
+}
+
+impl StorageProof {
+    /// Builds a combined proof for every key in `keys`. Returns `None` if
+    /// any key isn't in `trie`, same as a single `Trie::prove` would.
+    pub fn generate(trie: &Trie, keys: &[&[u8]]) -> Option<Self> {
+        let mut nodes = BTreeMap::new();
+        for key in keys {
+            let proof = trie.prove(key)?;
+            for node in proof.nodes {
+                nodes.insert(node.hash(), node);
+            }
+        }
+        Some(Self { nodes })
+    }
+
+    /// How many distinct nodes this proof stores - smaller than
+    /// `keys.len()` times a single proof's depth whenever two keys'
+    /// paths share an ancestor branch.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Checks `key`/`value` against `root_hash` by walking the shared
+    /// node set from the root down one nibble at a time, rather than
+    /// `Proof::verify`'s single stored path.
+    pub fn verify(&self, root_hash: Hash, key: &[u8], value: &[u8]) -> bool {
+        let Some(mut node) = self.nodes.get(&root_hash) else {
+            return false;
+        };
+        let mut nibbles = key_to_nibbles(key);
+        loop {
+            match node {
+                ProofNode::Leaf { nibbles: leaf_nibbles, value: leaf_value } => {
+                    return *leaf_nibbles == nibbles && leaf_value.as_slice() == value;
+                }
+                ProofNode::Branch { child_hashes, value: branch_value } => {
+                    if nibbles.is_empty() {
+                        return branch_value.as_deref() == Some(value);
+                    }
+                    let nibble = nibbles.remove(0) as usize;
+                    match self.nodes.get(&child_hashes[nibble]) {
+                        Some(next) => node = next,
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn key_to_nibbles(key: &[u8]) -> Vec<u8> {
+    let mut nibbles = Vec::with_capacity(key.len() * 2);
+    for byte in key {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    nibbles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_trie() -> Trie {
+        let mut trie = Trie::new();
+        trie.insert(b"alice", b"100".to_vec());
+        trie.insert(b"alicia", b"999".to_vec());
+        trie.insert(b"bob", b"200".to_vec());
+        trie
+    }
+
+    #[test]
+    fn proof_verifies_every_requested_key() {
+        let trie = sample_trie();
+        let proof = StorageProof::generate(&trie, &[b"alice", b"bob"]).unwrap();
+        let root = trie.root_hash();
+        assert!(proof.verify(root, b"alice", b"100"));
+        assert!(proof.verify(root, b"bob", b"200"));
+    }
+
+    #[test]
+    fn proof_rejects_a_wrong_value() {
+        let trie = sample_trie();
+        let proof = StorageProof::generate(&trie, &[b"alice"]).unwrap();
+        assert!(!proof.verify(trie.root_hash(), b"alice", b"999"));
+    }
+
+    #[test]
+    fn proof_rejects_a_wrong_root() {
+        let trie = sample_trie();
+        let proof = StorageProof::generate(&trie, &[b"alice"]).unwrap();
+        let mut wrong_root = trie.root_hash();
+        wrong_root[0] ^= 0xff;
+        assert!(!proof.verify(wrong_root, b"alice", b"100"));
+    }
+
+    #[test]
+    fn proof_rejects_a_key_it_was_not_generated_for() {
+        let trie = sample_trie();
+        let proof = StorageProof::generate(&trie, &[b"alice"]).unwrap();
+        assert!(!proof.verify(trie.root_hash(), b"bob", b"200"));
+    }
+
+    #[test]
+    fn generate_returns_none_for_a_missing_key() {
+        let trie = sample_trie();
+        assert!(StorageProof::generate(&trie, &[b"alice", b"carol"]).is_none());
+    }
+
+    #[test]
+    fn shared_ancestors_are_stored_once() {
+        let trie = sample_trie();
+        let combined = StorageProof::generate(&trie, &[b"alice", b"alicia"]).unwrap();
+        let alice_only = StorageProof::generate(&trie, &[b"alice"]).unwrap();
+        let alicia_only = StorageProof::generate(&trie, &[b"alicia"]).unwrap();
+        assert!(combined.node_count() < alice_only.node_count() + alicia_only.node_count());
+    }
+}
@@ -0,0 +1,389 @@
+//! Solution for Task 35: the BEEFY half of a trustless bridge - a
+//! Merkle Mountain Range (MMR) committing to every message a chain has
+//! ever emitted, a validator set's signatures over a *commitment*
+//! (the current MMR root plus a block number), and the MMR inclusion
+//! proof that ties one payload back to that root. A bridge relayer only
+//! ever has to ship one commitment, its signatures, and one short proof
+//! per message - never the whole message history - for the other side
+//! to accept it.
+//!
+//! See the README for the scope cuts against real `pallet-mmr`/BEEFY:
+//! this MMR's proofs carry every peak hash rather than the minimal
+//! "peak bagging" proof, and signatures are checked one at a time
+//! rather than aggregated.
+
+use std::collections::BTreeSet;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use sp_core::{ecdsa, Pair};
+
+type Blake2b256 = Blake2b<U32>;
+
+pub type Hash = [u8; 32];
+
+fn hash(tag: &[u8], data: &[u8]) -> Hash {
+    let mut hasher = Blake2b256::new();
+    hasher.update(tag);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn hash_leaf(payload: &[u8]) -> Hash {
+    hash(b"leaf", payload)
+}
+
+fn hash_node(left: Hash, right: Hash) -> Hash {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(&left);
+    data[32..].copy_from_slice(&right);
+    hash(b"node", &data)
+}
+
+fn hash_bag(acc: Hash, peak: Hash) -> Hash {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(&acc);
+    data[32..].copy_from_slice(&peak);
+    hash(b"bag", &data)
+}
+
+/// Folds a chain's peak hashes into one root, right-to-left, so appending
+/// a leaf only ever changes the peaks it actually touches.
+fn bag_peaks(peaks: &[Hash]) -> Hash {
+    let mut iter = peaks.iter().rev();
+    let mut acc = *iter.next().expect("an MMR always has at least one peak");
+    for peak in iter {
+        acc = hash_bag(*peak, acc);
+    }
+    acc
+}
+
+/// An append-only Merkle Mountain Range over leaf payloads.
+#[derive(Debug, Clone, Default)]
+pub struct Mmr {
+    leaves: Vec<Hash>,
+}
+
+impl Mmr {
+    pub fn new() -> Self {
+        Self { leaves: Vec::new() }
+    }
+
+    pub fn append(&mut self, payload: &[u8]) {
+        self.leaves.push(hash_leaf(payload));
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Decomposes the leaf count into descending powers of two - the size
+    /// of each "mountain" (a perfect binary tree) the MMR is currently
+    /// made of.
+    fn peak_sizes(&self) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        let remaining = self.leaves.len();
+        let mut size = 1usize << (usize::BITS - 1);
+        while size > 0 {
+            if remaining & size != 0 {
+                sizes.push(size);
+            }
+            size >>= 1;
+        }
+        sizes
+    }
+
+    /// The root of the perfect binary tree over `leaves[start..start+size]`.
+    fn peak_hash(&self, start: usize, size: usize) -> Hash {
+        if size == 1 {
+            return self.leaves[start];
+        }
+        let half = size / 2;
+        hash_node(self.peak_hash(start, half), self.peak_hash(start + half, half))
+    }
+
+    fn peak_hashes(&self) -> Vec<Hash> {
+        let mut offset = 0;
+        let mut peaks = Vec::new();
+        for size in self.peak_sizes() {
+            peaks.push(self.peak_hash(offset, size));
+            offset += size;
+        }
+        peaks
+    }
+
+    /// The bridge's point of trust: one hash committing to every payload
+    /// appended so far. `None` for an empty MMR.
+    pub fn root(&self) -> Option<Hash> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(bag_peaks(&self.peak_hashes()))
+    }
+
+    /// Merkle path from `leaf_index` up to its mountain's peak, bottom-up
+    /// (closest-to-leaf sibling first), plus every peak hash needed to
+    /// re-bag the root.
+    fn collect_siblings(&self, start: usize, size: usize, local_index: usize, out: &mut Vec<Hash>) {
+        if size == 1 {
+            return;
+        }
+        let half = size / 2;
+        if local_index < half {
+            self.collect_siblings(start, half, local_index, out);
+            out.push(self.peak_hash(start + half, half));
+        } else {
+            self.collect_siblings(start + half, half, local_index - half, out);
+            out.push(self.peak_hash(start, half));
+        }
+    }
+
+    pub fn prove(&self, leaf_index: usize) -> Option<MmrProof> {
+        if leaf_index >= self.leaves.len() {
+            return None;
+        }
+        let mut offset = 0;
+        for (peak_index, size) in self.peak_sizes().into_iter().enumerate() {
+            if leaf_index < offset + size {
+                let local_index = leaf_index - offset;
+                let mut siblings = Vec::new();
+                self.collect_siblings(offset, size, local_index, &mut siblings);
+                return Some(MmrProof {
+                    leaf: self.leaves[leaf_index],
+                    leaf_index: local_index,
+                    siblings,
+                    peak_index,
+                    peak_hashes: self.peak_hashes(),
+                });
+            }
+            offset += size;
+        }
+        None
+    }
+}
+
+/// Proves one leaf's membership in an MMR root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmrProof {
+    leaf: Hash,
+    leaf_index: usize,
+    siblings: Vec<Hash>,
+    peak_index: usize,
+    peak_hashes: Vec<Hash>,
+}
+
+impl MmrProof {
+    /// Recomputes this leaf's mountain peak from `siblings`, then re-bags
+    /// every peak and checks the result against `root`.
+    pub fn verify(&self, root: Hash) -> bool {
+        let mut acc = self.leaf;
+        let mut index = self.leaf_index;
+        for sibling in &self.siblings {
+            acc = if index.is_multiple_of(2) { hash_node(acc, *sibling) } else { hash_node(*sibling, acc) };
+            index /= 2;
+        }
+        match self.peak_hashes.get(self.peak_index) {
+            Some(peak) if *peak == acc => bag_peaks(&self.peak_hashes) == root,
+            _ => false,
+        }
+    }
+}
+
+/// What a BEEFY validator set actually signs: the chain's current MMR
+/// root, pinned to the block it was produced at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment {
+    pub mmr_root: Hash,
+    pub block_number: u32,
+}
+
+impl Commitment {
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut buf = self.mmr_root.to_vec();
+        buf.extend_from_slice(&self.block_number.to_le_bytes());
+        buf
+    }
+}
+
+/// The validator set a bridge on the other side trusts, identified by
+/// their ECDSA public keys - the scheme real BEEFY signs with, unlike
+/// GRANDPA/Aura's sr25519/ed25519.
+#[derive(Debug, Clone)]
+pub struct ValidatorSet {
+    validators: Vec<ecdsa::Public>,
+}
+
+impl ValidatorSet {
+    pub fn new(validators: Vec<ecdsa::Public>) -> Self {
+        Self { validators }
+    }
+
+    /// Same 2/3-plus-one supermajority threshold GRANDPA's `VoteTally`
+    /// and the availability tracker use: enough signers that no two
+    /// disjoint quorums could both be honest.
+    fn threshold(&self) -> usize {
+        self.validators.len() * 2 / 3 + 1
+    }
+
+    /// Checks each `(validator_index, signature)` pair against that
+    /// validator's public key and the commitment's signing payload, and
+    /// accepts only if a supermajority of *distinct* validators signed -
+    /// a validator's signature counts once even if listed twice.
+    pub fn verify_commitment(&self, commitment: &Commitment, signatures: &[(u32, ecdsa::Signature)]) -> bool {
+        let payload = commitment.signing_payload();
+        let mut signers = BTreeSet::new();
+        for (validator_index, signature) in signatures {
+            let Some(public) = self.validators.get(*validator_index as usize) else {
+                continue;
+            };
+            if ecdsa::Pair::verify(signature, &payload, public) {
+                signers.insert(*validator_index);
+            }
+        }
+        signers.len() >= self.threshold()
+    }
+}
+
+/// Verifies a bridged message end to end: `payload` hashes to the leaf
+/// `proof` claims, `proof` checks out against `commitment`'s MMR root,
+/// and a supermajority of `validator_set` signed that commitment.
+pub fn verify_message(
+    payload: &[u8],
+    proof: &MmrProof,
+    commitment: &Commitment,
+    signatures: &[(u32, ecdsa::Signature)],
+    validator_set: &ValidatorSet,
+) -> bool {
+    hash_leaf(payload) == proof.leaf
+        && proof.verify(commitment.mmr_root)
+        && validator_set.verify_commitment(commitment, signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        mmr: Mmr,
+        validator_pairs: Vec<ecdsa::Pair>,
+        validator_set: ValidatorSet,
+    }
+
+    fn build_fixture(payloads: &[&[u8]], validator_count: u32) -> Fixture {
+        let mut mmr = Mmr::new();
+        for payload in payloads {
+            mmr.append(payload);
+        }
+        let validator_pairs: Vec<ecdsa::Pair> = (0..validator_count)
+            .map(|i| ecdsa::Pair::from_seed_slice(&[i as u8 + 1; 32]).unwrap())
+            .collect();
+        let validator_set = ValidatorSet::new(validator_pairs.iter().map(|pair| pair.public()).collect());
+        Fixture { mmr, validator_pairs, validator_set }
+    }
+
+    fn sign_commitment(fixture: &Fixture, commitment: &Commitment, signer_indices: &[u32]) -> Vec<(u32, ecdsa::Signature)> {
+        let payload = commitment.signing_payload();
+        signer_indices
+            .iter()
+            .map(|&i| (i, fixture.validator_pairs[i as usize].sign(&payload)))
+            .collect()
+    }
+
+    #[test]
+    fn mmr_proof_verifies_every_leaf_at_every_size() {
+        for leaf_count in 1..=9 {
+            let payloads: Vec<Vec<u8>> = (0..leaf_count).map(|i| vec![i as u8]).collect();
+            let mut mmr = Mmr::new();
+            for payload in &payloads {
+                mmr.append(payload);
+            }
+            let root = mmr.root().unwrap();
+            for i in 0..leaf_count {
+                let proof = mmr.prove(i).unwrap();
+                assert!(proof.verify(root), "leaf {i} failed to verify at size {leaf_count}");
+            }
+        }
+    }
+
+    #[test]
+    fn mmr_proof_rejects_a_wrong_root() {
+        let mut mmr = Mmr::new();
+        mmr.append(b"a");
+        mmr.append(b"b");
+        let proof = mmr.prove(0).unwrap();
+        let mut wrong_root = mmr.root().unwrap();
+        wrong_root[0] ^= 0xff;
+        assert!(!proof.verify(wrong_root));
+    }
+
+    #[test]
+    fn mmr_prove_returns_none_out_of_range() {
+        let mut mmr = Mmr::new();
+        mmr.append(b"a");
+        assert!(mmr.prove(1).is_none());
+    }
+
+    #[test]
+    fn commitment_verifies_with_a_supermajority() {
+        let fixture = build_fixture(&[b"a", b"b", b"c"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let signatures = sign_commitment(&fixture, &commitment, &[0, 1, 2]);
+        assert!(fixture.validator_set.verify_commitment(&commitment, &signatures));
+    }
+
+    #[test]
+    fn commitment_rejects_fewer_than_a_supermajority() {
+        let fixture = build_fixture(&[b"a", b"b", b"c"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let signatures = sign_commitment(&fixture, &commitment, &[0, 1]);
+        assert!(!fixture.validator_set.verify_commitment(&commitment, &signatures));
+    }
+
+    #[test]
+    fn commitment_rejects_a_duplicated_signer_padding_out_the_count() {
+        let fixture = build_fixture(&[b"a", b"b", b"c"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let mut signatures = sign_commitment(&fixture, &commitment, &[0, 1]);
+        signatures.push(signatures[0]);
+        assert!(!fixture.validator_set.verify_commitment(&commitment, &signatures));
+    }
+
+    #[test]
+    fn commitment_rejects_a_signature_over_a_different_block_number() {
+        let fixture = build_fixture(&[b"a", b"b", b"c"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let tampered = Commitment { block_number: 11, ..commitment };
+        let signatures = sign_commitment(&fixture, &commitment, &[0, 1, 2]);
+        assert!(!fixture.validator_set.verify_commitment(&tampered, &signatures));
+    }
+
+    #[test]
+    fn verify_message_accepts_a_correctly_signed_and_proven_message() {
+        let fixture = build_fixture(&[b"message-0", b"message-1", b"message-2"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let signatures = sign_commitment(&fixture, &commitment, &[0, 1, 2]);
+        let proof = fixture.mmr.prove(1).unwrap();
+        assert!(verify_message(b"message-1", &proof, &commitment, &signatures, &fixture.validator_set));
+    }
+
+    #[test]
+    fn verify_message_rejects_a_payload_that_does_not_match_the_proof() {
+        let fixture = build_fixture(&[b"message-0", b"message-1"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let signatures = sign_commitment(&fixture, &commitment, &[0, 1, 2]);
+        let proof = fixture.mmr.prove(1).unwrap();
+        assert!(!verify_message(b"forged-message", &proof, &commitment, &signatures, &fixture.validator_set));
+    }
+
+    #[test]
+    fn verify_message_rejects_an_unsigned_commitment() {
+        let fixture = build_fixture(&[b"message-0"], 4);
+        let commitment = Commitment { mmr_root: fixture.mmr.root().unwrap(), block_number: 10 };
+        let proof = fixture.mmr.prove(0).unwrap();
+        assert!(!verify_message(b"message-0", &proof, &commitment, &[], &fixture.validator_set));
+    }
+}
@@ -0,0 +1,251 @@
+//! Solution for Task 36: a tiny XCM instruction interpreter - a
+//! `Vec<Instruction>` program executed one instruction at a time against
+//! a per-message holding register, the execution model a real
+//! `xcm-executor` runs: `WithdrawAsset` moves funds from the origin's
+//! balance into holding, `BuyExecution` pays a fee out of holding and
+//! buys a weight budget (the barrier every later instruction is checked
+//! against), `Transact` spends its declared weight against that budget
+//! without actually dispatching anything, and `DepositAsset` moves
+//! whatever's left in holding to a beneficiary. Runs against Task 32's
+//! `XcmNetwork` for the real balance moves.
+
+use std::collections::BTreeMap;
+
+use xcm_reserve_transfer_and_teleport_simulator::{AccountId, AssetId, Balance, ChainId, XcmError, XcmNetwork};
+
+pub type Weight = u64;
+
+/// The fixed weight `DepositAsset` costs - real XCM looks this up from a
+/// per-instruction weight table rather than taking it as a parameter.
+const DEPOSIT_ASSET_WEIGHT: Weight = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// Withdraws `amount` of `asset` from the program's origin account
+    /// into holding.
+    WithdrawAsset { asset: AssetId, amount: Balance },
+    /// Pays `fee` of `asset` out of holding and buys a `weight_limit`
+    /// budget for every instruction that runs after it.
+    BuyExecution { asset: AssetId, fee: Balance, weight_limit: Weight },
+    /// A stand-in for dispatching an arbitrary call: spends
+    /// `require_weight_at_most` of the bought weight budget and does
+    /// nothing else.
+    Transact { require_weight_at_most: Weight },
+    /// Moves all of holding's current balance of `asset` to `beneficiary`.
+    DepositAsset { asset: AssetId, beneficiary: AccountId },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecutorError {
+    /// `Transact` or `DepositAsset` ran before any `BuyExecution` - the
+    /// same `AllowTopLevelPaidExecutionFrom`-style barrier a real
+    /// executor uses to refuse running unpaid programs.
+    Barrier,
+    /// An instruction needed more weight than the `BuyExecution` budget
+    /// had left.
+    WeightLimitExceeded,
+    /// An instruction tried to spend more of an asset than holding had.
+    HoldingUnderflow,
+    Network(XcmError),
+}
+
+/// Executes one XCM program against `network`, on behalf of `origin` on
+/// `chain_id`.
+pub struct Executor<'a> {
+    network: &'a mut XcmNetwork,
+    chain_id: ChainId,
+    origin: AccountId,
+    holding: BTreeMap<AssetId, Balance>,
+    weight_remaining: Option<Weight>,
+}
+
+impl<'a> Executor<'a> {
+    pub fn new(network: &'a mut XcmNetwork, chain_id: ChainId, origin: AccountId) -> Self {
+        Self { network, chain_id, origin, holding: BTreeMap::new(), weight_remaining: None }
+    }
+
+    pub fn holding_of(&self, asset: AssetId) -> Balance {
+        self.holding.get(&asset).copied().unwrap_or(0)
+    }
+
+    pub fn execute(&mut self, program: &[Instruction]) -> Result<(), ExecutorError> {
+        for instruction in program {
+            self.execute_one(instruction)?;
+        }
+        Ok(())
+    }
+
+    fn execute_one(&mut self, instruction: &Instruction) -> Result<(), ExecutorError> {
+        match instruction {
+            Instruction::WithdrawAsset { asset, amount } => {
+                self.network
+                    .withdraw(self.chain_id, &self.origin, *asset, *amount)
+                    .map_err(ExecutorError::Network)?;
+                *self.holding.entry(*asset).or_insert(0) += amount;
+                Ok(())
+            }
+            Instruction::BuyExecution { asset, fee, weight_limit } => {
+                let balance = self.holding.entry(*asset).or_insert(0);
+                if *balance < *fee {
+                    return Err(ExecutorError::HoldingUnderflow);
+                }
+                *balance -= fee;
+                self.weight_remaining = Some(*weight_limit);
+                Ok(())
+            }
+            Instruction::Transact { require_weight_at_most } => {
+                self.spend_weight(*require_weight_at_most)
+            }
+            Instruction::DepositAsset { asset, beneficiary } => {
+                self.spend_weight(DEPOSIT_ASSET_WEIGHT)?;
+                let amount = self.holding.remove(asset).unwrap_or(0);
+                self.network
+                    .deposit(self.chain_id, beneficiary, *asset, amount)
+                    .map_err(ExecutorError::Network)
+            }
+        }
+    }
+
+    /// Checks the barrier (a weight budget must already be bought) and
+    /// deducts `amount` from it, failing if that would go negative.
+    fn spend_weight(&mut self, amount: Weight) -> Result<(), ExecutorError> {
+        let remaining = self.weight_remaining.ok_or(ExecutorError::Barrier)?;
+        if amount > remaining {
+            return Err(ExecutorError::WeightLimitExceeded);
+        }
+        self.weight_remaining = Some(remaining - amount);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN: ChainId = 1;
+    const DOT: AssetId = 0;
+
+    fn network_with_balance(account: &str, amount: Balance) -> XcmNetwork {
+        let mut network = XcmNetwork::new();
+        network.add_chain(CHAIN);
+        network.fund(CHAIN, account.to_string(), DOT, amount).unwrap();
+        network
+    }
+
+    #[test]
+    fn withdraw_buy_execution_deposit_moves_the_remainder_to_the_beneficiary() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::BuyExecution { asset: DOT, fee: 10, weight_limit: 100 },
+            Instruction::DepositAsset { asset: DOT, beneficiary: "bob".to_string() },
+        ];
+        executor.execute(&program).unwrap();
+
+        assert_eq!(executor.holding_of(DOT), 0);
+        assert_eq!(network.balance_of(CHAIN, &"alice".to_string(), DOT), 500);
+        assert_eq!(network.balance_of(CHAIN, &"bob".to_string(), DOT), 490);
+    }
+
+    #[test]
+    fn transact_spends_weight_without_moving_any_asset() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::BuyExecution { asset: DOT, fee: 10, weight_limit: 100 },
+            Instruction::Transact { require_weight_at_most: 40 },
+            Instruction::DepositAsset { asset: DOT, beneficiary: "bob".to_string() },
+        ];
+        executor.execute(&program).unwrap();
+
+        assert_eq!(network.balance_of(CHAIN, &"bob".to_string(), DOT), 490);
+    }
+
+    #[test]
+    fn transact_before_buy_execution_hits_the_barrier() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::Transact { require_weight_at_most: 40 },
+        ];
+        let result = executor.execute(&program);
+
+        assert_eq!(result, Err(ExecutorError::Barrier));
+    }
+
+    #[test]
+    fn deposit_asset_before_buy_execution_also_hits_the_barrier() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::DepositAsset { asset: DOT, beneficiary: "bob".to_string() },
+        ];
+        let result = executor.execute(&program);
+
+        assert_eq!(result, Err(ExecutorError::Barrier));
+    }
+
+    #[test]
+    fn transact_over_the_bought_weight_limit_is_rejected() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::BuyExecution { asset: DOT, fee: 10, weight_limit: 10 },
+            Instruction::Transact { require_weight_at_most: 40 },
+        ];
+        let result = executor.execute(&program);
+
+        assert_eq!(result, Err(ExecutorError::WeightLimitExceeded));
+    }
+
+    #[test]
+    fn buy_execution_with_insufficient_holding_fails() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 5 },
+            Instruction::BuyExecution { asset: DOT, fee: 10, weight_limit: 100 },
+        ];
+        let result = executor.execute(&program);
+
+        assert_eq!(result, Err(ExecutorError::HoldingUnderflow));
+    }
+
+    #[test]
+    fn withdraw_asset_with_insufficient_balance_fails_before_touching_holding() {
+        let mut network = network_with_balance("alice", 5);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [Instruction::WithdrawAsset { asset: DOT, amount: 500 }];
+        let result = executor.execute(&program);
+
+        assert_eq!(result, Err(ExecutorError::Network(XcmError::InsufficientBalance)));
+        assert_eq!(executor.holding_of(DOT), 0);
+    }
+
+    #[test]
+    fn depositing_an_asset_never_withdrawn_into_holding_deposits_nothing() {
+        let mut network = network_with_balance("alice", 1_000);
+        let mut executor = Executor::new(&mut network, CHAIN, "alice".to_string());
+
+        let program = [
+            Instruction::WithdrawAsset { asset: DOT, amount: 500 },
+            Instruction::BuyExecution { asset: DOT, fee: 10, weight_limit: 100 },
+            Instruction::DepositAsset { asset: DOT + 1, beneficiary: "bob".to_string() },
+        ];
+        executor.execute(&program).unwrap();
+
+        assert_eq!(network.balance_of(CHAIN, &"bob".to_string(), DOT + 1), 0);
+    }
+}
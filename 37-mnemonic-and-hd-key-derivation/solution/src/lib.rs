@@ -0,0 +1,203 @@
+//! Solution for Task 37: BIP-39 mnemonic phrases and Substrate-style
+//! `//hard/soft` hierarchical derivation, the multi-level path Task 18's
+//! `derive_soft_child` never needed since it only derives one soft
+//! junction. Reuses `sp_core`'s `Pair`/`DeriveJunction` for every bit of
+//! actual cryptography - nothing here reimplements BIP-39 or the SR25519
+//! HDKD scheme, only the `//hard/soft` path syntax on top of it. See the
+//! README for how the tests check this against `subkey`-equivalent
+//! vectors.
+
+use sp_core::{crypto::DEV_PHRASE, ed25519, sr25519, DeriveJunction, Pair};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Sr25519,
+    Ed25519,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyError {
+    InvalidPhrase,
+    InvalidPath,
+    /// `sp-core` doesn't implement derivation for ed25519 at all, since
+    /// soft derivation isn't safe for a scheme whose public key is a hash
+    /// of its seed - the same scope cut Task 18's `derive_soft_child`
+    /// made.
+    DerivationUnsupported,
+}
+
+pub enum Keypair {
+    Sr25519(Box<sr25519::Pair>),
+    Ed25519(Box<ed25519::Pair>),
+}
+
+impl Keypair {
+    /// Generates a fresh random BIP-39 mnemonic and the keypair it
+    /// derives - the same pair `subkey generate` prints.
+    pub fn generate_with_phrase(scheme: Scheme, password: Option<&str>) -> (Self, String) {
+        match scheme {
+            Scheme::Sr25519 => {
+                let (pair, phrase, _seed) = sr25519::Pair::generate_with_phrase(password);
+                (Keypair::Sr25519(Box::new(pair)), phrase)
+            }
+            Scheme::Ed25519 => {
+                let (pair, phrase, _seed) = ed25519::Pair::generate_with_phrase(password);
+                (Keypair::Ed25519(Box::new(pair)), phrase)
+            }
+        }
+    }
+
+    /// Recovers the keypair a BIP-39 `phrase` derives - the same one
+    /// `subkey inspect "<phrase>"` would print.
+    pub fn from_phrase(scheme: Scheme, phrase: &str, password: Option<&str>) -> Result<Self, KeyError> {
+        match scheme {
+            Scheme::Sr25519 => {
+                let (pair, _seed) =
+                    sr25519::Pair::from_phrase(phrase, password).map_err(|_| KeyError::InvalidPhrase)?;
+                Ok(Keypair::Sr25519(Box::new(pair)))
+            }
+            Scheme::Ed25519 => {
+                let (pair, _seed) =
+                    ed25519::Pair::from_phrase(phrase, password).map_err(|_| KeyError::InvalidPhrase)?;
+                Ok(Keypair::Ed25519(Box::new(pair)))
+            }
+        }
+    }
+
+    /// Derives `path` (e.g. `"//Alice"`) from `sp_core`'s public
+    /// development phrase - the same insecure, widely-published phrase
+    /// `--dev` chains and `subkey`'s well-known dev accounts both use.
+    pub fn dev_account(scheme: Scheme, path: &str) -> Result<Self, KeyError> {
+        Self::from_phrase(scheme, DEV_PHRASE, None)?.derive(path)
+    }
+
+    pub fn public_bytes(&self) -> [u8; 32] {
+        match self {
+            Keypair::Sr25519(pair) => pair.public().0,
+            Keypair::Ed25519(pair) => pair.public().0,
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        match self {
+            Keypair::Sr25519(pair) => pair.sign(message).0,
+            Keypair::Ed25519(pair) => pair.sign(message).0,
+        }
+    }
+
+    /// Derives a child key along `path`, a chain of `//hard` and `/soft`
+    /// junctions in the same syntax `subkey`/`Pair::from_string` accept.
+    /// Only sr25519 supports derivation; see [`KeyError::DerivationUnsupported`].
+    pub fn derive(&self, path: &str) -> Result<Self, KeyError> {
+        match self {
+            Keypair::Sr25519(pair) => {
+                let junctions = parse_path(path)?;
+                let (derived, _) = pair.derive(junctions.into_iter(), None).map_err(|_| KeyError::InvalidPath)?;
+                Ok(Keypair::Sr25519(Box::new(derived)))
+            }
+            Keypair::Ed25519(_) => Err(KeyError::DerivationUnsupported),
+        }
+    }
+}
+
+/// Splits a `//hard/soft` path into [`DeriveJunction`]s: `//` starts a
+/// hard junction, a lone `/` a soft one, and a purely numeric junction
+/// value is encoded as a `u64` index rather than a string - mirroring
+/// `DeriveJunction`'s own `From<&str>` conversion.
+fn parse_path(path: &str) -> Result<Vec<DeriveJunction>, KeyError> {
+    let mut junctions = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        let hard = rest.starts_with("//");
+        rest = rest.strip_prefix(if hard { "//" } else { "/" }).ok_or(KeyError::InvalidPath)?;
+        let end = rest.find('/').unwrap_or(rest.len());
+        let (value, remainder) = rest.split_at(end);
+        if value.is_empty() {
+            return Err(KeyError::InvalidPath);
+        }
+        let junction = match value.parse::<u64>() {
+            Ok(index) => DeriveJunction::soft(index),
+            Err(_) => DeriveJunction::soft(value),
+        };
+        junctions.push(if hard { junction.harden() } else { junction });
+        rest = remainder;
+    }
+    Ok(junctions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `subkey inspect --scheme sr25519 "//Alice"` against `DEV_PHRASE`,
+    /// the public key printed on every Substrate tutorial's `--dev` chain.
+    #[test]
+    fn dev_account_alice_matches_the_well_known_vector() {
+        let alice = Keypair::dev_account(Scheme::Sr25519, "//Alice").unwrap();
+        let expected: [u8; 32] = [
+            0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6, 0x82, 0x2c,
+            0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+        ];
+        assert_eq!(alice.public_bytes(), expected);
+    }
+
+    #[test]
+    fn dev_account_bob_matches_the_well_known_vector() {
+        let bob = Keypair::dev_account(Scheme::Sr25519, "//Bob").unwrap();
+        let expected: [u8; 32] = [
+            0x8e, 0xaf, 0x04, 0x15, 0x16, 0x87, 0x73, 0x63, 0x26, 0xc9, 0xfe, 0xa1, 0x7e, 0x25, 0xfc, 0x52, 0x87, 0x61,
+            0x36, 0x93, 0xc9, 0x12, 0x90, 0x9c, 0xb2, 0x26, 0xaa, 0x47, 0x94, 0xf2, 0x6a, 0x48,
+        ];
+        assert_eq!(bob.public_bytes(), expected);
+    }
+
+    #[test]
+    fn derive_matches_an_independently_parsed_suri_for_mixed_paths() {
+        for path in ["//Alice", "/soft", "//Alice/soft", "//Alice//0", "//5", "/42//hard"] {
+            let derived = Keypair::from_phrase(Scheme::Sr25519, DEV_PHRASE, None).unwrap().derive(path).unwrap();
+            let oracle = sr25519::Pair::from_string(&format!("{DEV_PHRASE}{path}"), None).unwrap();
+            assert_eq!(derived.public_bytes(), oracle.public().0, "path {path} diverged from sp-core's own parser");
+        }
+    }
+
+    #[test]
+    fn from_phrase_is_deterministic() {
+        let (_, phrase) = Keypair::generate_with_phrase(Scheme::Sr25519, None);
+        let first = Keypair::from_phrase(Scheme::Sr25519, &phrase, None).unwrap();
+        let second = Keypair::from_phrase(Scheme::Sr25519, &phrase, None).unwrap();
+        assert_eq!(first.public_bytes(), second.public_bytes());
+    }
+
+    #[test]
+    fn different_paths_derive_different_keys() {
+        let alice = Keypair::dev_account(Scheme::Sr25519, "//Alice").unwrap();
+        let bob = Keypair::dev_account(Scheme::Sr25519, "//Bob").unwrap();
+        assert_ne!(alice.public_bytes(), bob.public_bytes());
+    }
+
+    #[test]
+    fn ed25519_derivation_is_unsupported() {
+        let pair = Keypair::from_phrase(Scheme::Ed25519, DEV_PHRASE, None).unwrap();
+        assert!(matches!(pair.derive("//Alice"), Err(KeyError::DerivationUnsupported)));
+    }
+
+    #[test]
+    fn a_path_not_starting_with_a_slash_is_rejected() {
+        let pair = Keypair::from_phrase(Scheme::Sr25519, DEV_PHRASE, None).unwrap();
+        assert!(matches!(pair.derive("Alice"), Err(KeyError::InvalidPath)));
+    }
+
+    #[test]
+    fn an_invalid_phrase_is_rejected() {
+        let result = Keypair::from_phrase(Scheme::Sr25519, "not a valid bip39 phrase at all", None);
+        assert_eq!(result.err(), Some(KeyError::InvalidPhrase));
+    }
+
+    #[test]
+    fn signatures_verify_against_the_derived_public_key() {
+        let alice = Keypair::dev_account(Scheme::Sr25519, "//Alice").unwrap();
+        let signature = alice.sign(b"hello world");
+        let public = sr25519::Public::from_raw(alice.public_bytes());
+        assert!(sr25519::Pair::verify(&sr25519::Signature::from_raw(signature), b"hello world", &public));
+    }
+}
@@ -0,0 +1,179 @@
+//! Solution for Task 38: `pallet-utility`'s `batch`/`batch_all` semantics,
+//! run over Task 5's `pallet-voting` `Call` enum instead of a generic
+//! `RuntimeCall` - `batch` dispatches calls one at a time and stops at
+//! the first failure, keeping whatever already succeeded; `batch_all`
+//! wraps the whole sequence in a storage transaction so a single failure
+//! rolls back every call in the batch, not just the failing one. See the
+//! README for why this matters on-chain.
+
+use frame_support::{
+    construct_runtime, derive_impl,
+    sp_runtime::{traits::Dispatchable, BuildStorage, DispatchError},
+    storage::transactional::with_storage_layer,
+    traits::ConstU32,
+};
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Voting: pallet_voting,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_voting::Config for Runtime {
+    type WeightInfo = ();
+    type MaxVoters = ConstU32<1_000>;
+    type MaxCandidates = ConstU32<16>;
+}
+
+pub const ADMIN: u64 = 1;
+
+/// Builds a fresh externalities with `ADMIN` as the voting pallet's admin
+/// and no candidates seeded, ready for `batch`/`batch_all` to run
+/// `pallet_voting::Call`s against.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_voting::GenesisConfig::<Runtime> { admin: Some(ADMIN), candidates: vec![] }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// How a [`batch`] run of calls fared: how many ran successfully before
+/// either running out of calls or hitting `error`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BatchOutcome {
+    pub calls_executed: usize,
+    pub error: Option<(usize, DispatchError)>,
+}
+
+/// The call (by index) that failed inside [`batch_all`]'s storage
+/// transaction, along with why. Implements `From<DispatchError>` only to
+/// satisfy `with_storage_layer`'s bound for its own transactional-layer-
+/// limit error, which carries no call index of its own and so is
+/// reported as index `0` - a case these short, flat batches never
+/// actually hit.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BatchAllError {
+    pub index: usize,
+    pub error: DispatchError,
+}
+
+impl From<DispatchError> for BatchAllError {
+    fn from(error: DispatchError) -> Self {
+        Self { index: 0, error }
+    }
+}
+
+/// Dispatches `calls` one at a time as `origin`, stopping at the first
+/// one that errors. Every call that ran before the failure keeps its
+/// effects - the same best-effort semantics `pallet-utility::batch`
+/// gives a real runtime, which is why it emits a `BatchInterrupted`
+/// event instead of failing the whole extrinsic.
+pub fn batch(origin: RuntimeOrigin, calls: Vec<RuntimeCall>) -> BatchOutcome {
+    let total = calls.len();
+    for (index, call) in calls.into_iter().enumerate() {
+        if let Err(error) = call.dispatch(origin.clone()) {
+            return BatchOutcome { calls_executed: index, error: Some((index, error.error)) };
+        }
+    }
+    BatchOutcome { calls_executed: total, error: None }
+}
+
+/// Dispatches `calls` one at a time as `origin` inside a single storage
+/// transaction: if every call succeeds the whole batch commits, but a
+/// single failure rolls back every call in the batch, including the
+/// ones that already "succeeded" - the atomicity `pallet-utility::batch_all`
+/// gives up `batch`'s best-effort partial progress for.
+pub fn batch_all(origin: RuntimeOrigin, calls: Vec<RuntimeCall>) -> Result<(), BatchAllError> {
+    with_storage_layer(|| {
+        for (index, call) in calls.into_iter().enumerate() {
+            call.dispatch(origin.clone()).map_err(|error| BatchAllError { index, error: error.error })?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: u64 = 2;
+    const BOB: u64 = 3;
+
+    fn register() -> RuntimeCall {
+        pallet_voting::Call::register {}.into()
+    }
+
+    fn start_voting() -> RuntimeCall {
+        pallet_voting::Call::start_voting {}.into()
+    }
+
+    #[test]
+    fn batch_stops_at_the_first_error_but_keeps_earlier_effects() {
+        new_test_ext().execute_with(|| {
+            let calls = vec![register(), register(), start_voting()];
+            let outcome = batch(RuntimeOrigin::signed(ALICE), calls);
+
+            assert_eq!(
+                outcome,
+                BatchOutcome {
+                    calls_executed: 1,
+                    error: Some((1, pallet_voting::Error::<Runtime>::AlreadyRegistered.into())),
+                }
+            );
+            // Alice's registration from call 0 is still in effect, even
+            // though the batch as a whole didn't finish.
+            assert!(pallet_voting::RegisteredVoters::<Runtime>::contains_key(ALICE));
+        });
+    }
+
+    #[test]
+    fn batch_all_rolls_back_every_call_on_a_single_failure() {
+        new_test_ext().execute_with(|| {
+            let calls = vec![register(), register()];
+            let result = batch_all(RuntimeOrigin::signed(ALICE), calls);
+
+            assert_eq!(
+                result,
+                Err(BatchAllError { index: 1, error: pallet_voting::Error::<Runtime>::AlreadyRegistered.into() })
+            );
+            // Unlike `batch`, Alice's first registration was rolled back
+            // along with the second call that actually failed.
+            assert!(!pallet_voting::RegisteredVoters::<Runtime>::contains_key(ALICE));
+        });
+    }
+
+    #[test]
+    fn batch_all_commits_every_call_when_all_succeed() {
+        new_test_ext().execute_with(|| {
+            // Both calls need to succeed under the same origin, so have
+            // the admin register itself before starting the vote.
+            let calls = vec![register(), start_voting()];
+            assert_eq!(batch_all(RuntimeOrigin::signed(ADMIN), calls), Ok(()));
+
+            assert!(pallet_voting::RegisteredVoters::<Runtime>::contains_key(ADMIN));
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::VotingActive);
+        });
+    }
+
+    #[test]
+    fn batch_runs_every_call_when_none_fail() {
+        new_test_ext().execute_with(|| {
+            let outcome = batch(RuntimeOrigin::signed(BOB), vec![register()]);
+            assert_eq!(outcome, BatchOutcome { calls_executed: 1, error: None });
+
+            let outcome = batch(RuntimeOrigin::signed(ALICE), vec![register()]);
+            assert_eq!(outcome, BatchOutcome { calls_executed: 1, error: None });
+        });
+    }
+}
@@ -0,0 +1,271 @@
+//! Solution for Task 39: `pallet-vesting`'s locked-balance model as a
+//! plain Rust library - a [`VestingSchedule`] locks a balance down to
+//! zero either all at once (a cliff) or steadily over a block range
+//! (linear), and [`Ledger`] tracks free balances alongside however many
+//! schedules an account is carrying, the same way `pallet-balances`'
+//! locks sit on top of its free balance. No chain environment; the
+//! caller passes `now` directly the way Task 1's `transition` takes a
+//! raw `Timestamp` instead of a `Clock`.
+
+use std::collections::BTreeMap;
+
+pub use voting_state_machine::UserId;
+
+pub type Balance = u128;
+pub type BlockNumber = u32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VestingError {
+    InsufficientUsableBalance,
+    UnknownSchedule,
+    /// `merge_schedules` needs two distinct schedule indices.
+    SameSchedule,
+}
+
+/// How a [`VestingSchedule`]'s locked amount decreases as blocks pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingKind {
+    /// Nothing unlocks before `release_at`; the full amount unlocks in
+    /// one go at that block.
+    Cliff { release_at: BlockNumber },
+    /// Unlocks in equal steps from `start` to `start + duration`, the
+    /// same shape `pallet-vesting`'s `locked, per_block, starting_block`
+    /// triple produces.
+    Linear { start: BlockNumber, duration: BlockNumber },
+}
+
+/// Locks `locked` of an account's balance, releasing it according to
+/// `kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub locked: Balance,
+    pub kind: VestingKind,
+}
+
+impl VestingSchedule {
+    pub fn cliff(locked: Balance, release_at: BlockNumber) -> Self {
+        Self { locked, kind: VestingKind::Cliff { release_at } }
+    }
+
+    pub fn linear(locked: Balance, start: BlockNumber, duration: BlockNumber) -> Self {
+        Self { locked, kind: VestingKind::Linear { start, duration } }
+    }
+
+    /// How much of `locked` is still locked at block `now`.
+    pub fn locked_at(&self, now: BlockNumber) -> Balance {
+        match self.kind {
+            VestingKind::Cliff { release_at } => {
+                if now >= release_at {
+                    0
+                } else {
+                    self.locked
+                }
+            }
+            VestingKind::Linear { start, duration } => {
+                if now <= start {
+                    self.locked
+                } else {
+                    let elapsed = (now - start).min(duration);
+                    if duration == 0 {
+                        0
+                    } else {
+                        self.locked - self.locked * Balance::from(elapsed) / Balance::from(duration)
+                    }
+                }
+            }
+        }
+    }
+
+    /// How much of `locked` has unlocked by block `now` - the
+    /// complement of [`Self::locked_at`].
+    pub fn vested_amount(&self, now: BlockNumber) -> Balance {
+        self.locked - self.locked_at(now)
+    }
+
+    /// The block by which every last unit of `locked` has unlocked.
+    fn fully_vested_at(&self) -> BlockNumber {
+        match self.kind {
+            VestingKind::Cliff { release_at } => release_at,
+            VestingKind::Linear { start, duration } => start + duration,
+        }
+    }
+
+    /// Combines `self` and `other` into a single linear schedule that
+    /// unlocks their combined still-locked amount (as of `now`) evenly
+    /// up to whichever of the two finishes unlocking last -
+    /// `pallet-vesting::merge_schedules`'s job of freeing up a schedule
+    /// slot without forfeiting anything either schedule had left to
+    /// unlock.
+    pub fn merge(&self, other: &VestingSchedule, now: BlockNumber) -> VestingSchedule {
+        let combined_locked = self.locked_at(now) + other.locked_at(now);
+        let end = self.fully_vested_at().max(other.fully_vested_at()).max(now);
+        VestingSchedule::linear(combined_locked, now, end - now)
+    }
+}
+
+/// Free balances plus however many vesting schedules are locking part of
+/// them, per account.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    free: BTreeMap<UserId, Balance>,
+    schedules: BTreeMap<UserId, Vec<VestingSchedule>>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_balance(&mut self, who: UserId, amount: Balance) {
+        self.free.insert(who, amount);
+    }
+
+    pub fn free_balance(&self, who: &UserId) -> Balance {
+        self.free.get(who).copied().unwrap_or(0)
+    }
+
+    pub fn schedules(&self, who: &UserId) -> &[VestingSchedule] {
+        self.schedules.get(who).map_or(&[], |schedules| schedules.as_slice())
+    }
+
+    /// Locks `schedule.locked` of `who`'s balance - vesting schedules
+    /// stack, the same way a second `vested_transfer` to an account adds
+    /// a second schedule rather than replacing the first.
+    pub fn add_vesting_schedule(&mut self, who: UserId, schedule: VestingSchedule) {
+        self.schedules.entry(who).or_default().push(schedule);
+    }
+
+    /// The sum of every schedule's still-locked amount at block `now`.
+    pub fn locked_balance(&self, who: &UserId, now: BlockNumber) -> Balance {
+        self.schedules(who).iter().map(|schedule| schedule.locked_at(now)).sum()
+    }
+
+    /// `free_balance` minus `locked_balance` - what's actually spendable
+    /// at block `now`.
+    pub fn usable_balance(&self, who: &UserId, now: BlockNumber) -> Balance {
+        self.free_balance(who).saturating_sub(self.locked_balance(who, now))
+    }
+
+    /// Moves `amount` from `from` to `to`, failing if it would dip into
+    /// `from`'s still-locked balance at block `now`.
+    pub fn transfer(&mut self, from: &UserId, to: UserId, amount: Balance, now: BlockNumber) -> Result<(), VestingError> {
+        if self.usable_balance(from, now) < amount {
+            return Err(VestingError::InsufficientUsableBalance);
+        }
+        *self.free.get_mut(from).expect("usable_balance already confirmed a free balance exists") -= amount;
+        *self.free.entry(to).or_insert(0) += amount;
+        Ok(())
+    }
+
+    /// Replaces the schedules at `first` and `second` with the single
+    /// schedule [`VestingSchedule::merge`] produces, freeing up a slot -
+    /// `pallet-vesting::merge_schedules`'s whole point.
+    pub fn merge_schedules(&mut self, who: &UserId, first: usize, second: usize, now: BlockNumber) -> Result<(), VestingError> {
+        if first == second {
+            return Err(VestingError::SameSchedule);
+        }
+        let schedules = self.schedules.get_mut(who).ok_or(VestingError::UnknownSchedule)?;
+        let a = *schedules.get(first).ok_or(VestingError::UnknownSchedule)?;
+        let b = *schedules.get(second).ok_or(VestingError::UnknownSchedule)?;
+        let merged = a.merge(&b, now);
+
+        let mut keep_index = [first, second];
+        keep_index.sort_unstable();
+        schedules.remove(keep_index[1]);
+        schedules.remove(keep_index[0]);
+        schedules.push(merged);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cliff_is_fully_locked_until_release_then_fully_unlocked() {
+        let schedule = VestingSchedule::cliff(1_000, 100);
+        assert_eq!(schedule.locked_at(0), 1_000);
+        assert_eq!(schedule.locked_at(99), 1_000);
+        assert_eq!(schedule.locked_at(100), 0);
+        assert_eq!(schedule.locked_at(200), 0);
+    }
+
+    #[test]
+    fn linear_unlocks_evenly_across_the_duration() {
+        let schedule = VestingSchedule::linear(1_000, 100, 10);
+        assert_eq!(schedule.locked_at(100), 1_000);
+        assert_eq!(schedule.locked_at(105), 500);
+        assert_eq!(schedule.locked_at(110), 0);
+        // Past the duration it stays fully unlocked.
+        assert_eq!(schedule.locked_at(200), 0);
+        // Before `start` nothing has unlocked yet.
+        assert_eq!(schedule.locked_at(50), 1_000);
+    }
+
+    #[test]
+    fn vested_amount_is_the_complement_of_locked_at() {
+        let schedule = VestingSchedule::linear(1_000, 0, 4);
+        for block in 0..=4 {
+            assert_eq!(schedule.vested_amount(block) + schedule.locked_at(block), 1_000);
+        }
+    }
+
+    #[test]
+    fn usable_balance_excludes_every_schedules_locked_amount() {
+        let alice: UserId = "alice".into();
+        let mut ledger = Ledger::new();
+        ledger.set_balance(alice.clone(), 1_000);
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::cliff(400, 50));
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::linear(600, 0, 10));
+
+        assert_eq!(ledger.usable_balance(&alice, 0), 0);
+        assert_eq!(ledger.usable_balance(&alice, 5), 300);
+        assert_eq!(ledger.usable_balance(&alice, 10), 600);
+        assert_eq!(ledger.usable_balance(&alice, 50), 1_000);
+    }
+
+    #[test]
+    fn transfer_is_rejected_once_it_would_dip_into_the_locked_balance() {
+        let alice: UserId = "alice".into();
+        let bob: UserId = "bob".into();
+        let mut ledger = Ledger::new();
+        ledger.set_balance(alice.clone(), 1_000);
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::linear(1_000, 0, 10));
+
+        assert_eq!(ledger.transfer(&alice, bob.clone(), 1, 0), Err(VestingError::InsufficientUsableBalance));
+
+        assert_eq!(ledger.transfer(&alice, bob.clone(), 500, 5), Ok(()));
+        assert_eq!(ledger.free_balance(&alice), 500);
+        assert_eq!(ledger.free_balance(&bob), 500);
+    }
+
+    #[test]
+    fn merging_two_schedules_preserves_their_combined_still_locked_amount() {
+        let alice: UserId = "alice".into();
+        let mut ledger = Ledger::new();
+        ledger.set_balance(alice.clone(), 2_000);
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::linear(1_000, 0, 10));
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::cliff(1_000, 20));
+
+        let before = ledger.locked_balance(&alice, 5);
+        ledger.merge_schedules(&alice, 0, 1, 5).unwrap();
+        let after = ledger.locked_balance(&alice, 5);
+
+        assert_eq!(before, after);
+        assert_eq!(ledger.schedules(&alice).len(), 1);
+        // The merged schedule keeps unlocking up to the later of the two
+        // original end blocks.
+        assert_eq!(ledger.locked_balance(&alice, 20), 0);
+    }
+
+    #[test]
+    fn merge_schedules_rejects_an_unknown_index() {
+        let alice: UserId = "alice".into();
+        let mut ledger = Ledger::new();
+        ledger.add_vesting_schedule(alice.clone(), VestingSchedule::linear(1_000, 0, 10));
+
+        assert_eq!(ledger.merge_schedules(&alice, 0, 1, 0), Err(VestingError::UnknownSchedule));
+        assert_eq!(ledger.merge_schedules(&alice, 0, 0, 0), Err(VestingError::SameSchedule));
+    }
+}
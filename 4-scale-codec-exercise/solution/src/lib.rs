@@ -0,0 +1,748 @@
+//! A from-scratch implementation of the slice of [SCALE] that this repo's
+//! other tasks rely on, kept dependency-free so `Encode`/`Decode` can be
+//! studied without reading through `parity-scale-codec`'s generality.
+//!
+//! [SCALE]: https://github.com/paritytech/parity-scale-codec
+
+use voting_state_machine::{AuditEntry, Quorum, TieBreakPolicy, VoteError, VotingPhase, VotingState};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    UnexpectedEof,
+    InvalidBool(u8),
+    InvalidEnumVariant(u8),
+    InvalidUtf8,
+}
+
+pub trait Encode {
+    fn encode_to(&self, buf: &mut Vec<u8>);
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_to(&mut buf);
+        buf
+    }
+}
+
+pub trait Decode: Sized {
+    /// Consumes the bytes it needs from the front of `*input`, advancing it.
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+fn take<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8], DecodeError> {
+    if input.len() < n {
+        return Err(DecodeError::UnexpectedEof);
+    }
+    let (front, rest) = input.split_at(n);
+    *input = rest;
+    Ok(front)
+}
+
+impl Encode for bool {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.push(if *self { 1 } else { 0 });
+    }
+}
+
+impl Decode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match take(input, 1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(DecodeError::InvalidBool(other)),
+        }
+    }
+}
+
+macro_rules! impl_fixed_width_int {
+    ($ty:ty) => {
+        impl Encode for $ty {
+            fn encode_to(&self, buf: &mut Vec<u8>) {
+                buf.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                let bytes = take(input, core::mem::size_of::<$ty>())?;
+                Ok(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+            }
+        }
+    };
+}
+
+impl_fixed_width_int!(u8);
+impl_fixed_width_int!(u16);
+impl_fixed_width_int!(u32);
+impl_fixed_width_int!(u64);
+
+/// SCALE's variable-length encoding for non-negative integers: small values
+/// cost as little as one byte, at the expense of a two-bit encoding-mode tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compact(pub u64);
+
+impl Encode for Compact {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        let value = self.0;
+        if value < (1 << 6) {
+            buf.push((value as u8) << 2);
+        } else if value < (1 << 14) {
+            buf.extend_from_slice(&(((value as u16) << 2) | 0b01).to_le_bytes());
+        } else if value < (1 << 30) {
+            buf.extend_from_slice(&(((value as u32) << 2) | 0b10).to_le_bytes());
+        } else {
+            let bytes = value.to_le_bytes();
+            let used = bytes.iter().rposition(|&b| b != 0).map_or(1, |i| i + 1).max(4);
+            buf.push((((used - 4) as u8) << 2) | 0b11);
+            buf.extend_from_slice(&bytes[..used]);
+        }
+    }
+}
+
+impl Decode for Compact {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let first = take(input, 1)?[0];
+        match first & 0b11 {
+            0b00 => Ok(Compact((first >> 2) as u64)),
+            0b01 => {
+                let second = take(input, 1)?[0];
+                let raw = u16::from_le_bytes([first, second]);
+                Ok(Compact((raw >> 2) as u64))
+            }
+            0b10 => {
+                let rest = take(input, 3)?;
+                let raw = u32::from_le_bytes([first, rest[0], rest[1], rest[2]]);
+                Ok(Compact((raw >> 2) as u64))
+            }
+            _ => {
+                let used = (first >> 2) as usize + 4;
+                let bytes = take(input, used)?;
+                let mut buf = [0u8; 8];
+                buf[..used].copy_from_slice(bytes);
+                Ok(Compact(u64::from_le_bytes(buf)))
+            }
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        Compact(self.len() as u64).encode_to(buf);
+        for item in self {
+            item.encode_to(buf);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = Compact::decode(input)?.0 as usize;
+        (0..len).map(|_| T::decode(input)).collect()
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            None => buf.push(0),
+            Some(value) => {
+                buf.push(1);
+                value.encode_to(buf);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match take(input, 1)?[0] {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(input)?)),
+            other => Err(DecodeError::InvalidBool(other)),
+        }
+    }
+}
+
+impl Encode for String {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.as_bytes().to_vec().encode_to(buf);
+    }
+}
+
+impl Decode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let bytes = Vec::<u8>::decode(input)?;
+        String::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+/// A hand-written enum used to exercise discriminant + variant-field
+/// encoding independently of [`VotingPhase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Custom(u8, u8, u8),
+}
+
+impl Encode for Color {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            Color::Red => buf.push(0),
+            Color::Green => buf.push(1),
+            Color::Blue => buf.push(2),
+            Color::Custom(r, g, b) => {
+                buf.push(3);
+                r.encode_to(buf);
+                g.encode_to(buf);
+                b.encode_to(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Color {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match take(input, 1)?[0] {
+            0 => Ok(Color::Red),
+            1 => Ok(Color::Green),
+            2 => Ok(Color::Blue),
+            3 => Ok(Color::Custom(
+                u8::decode(input)?,
+                u8::decode(input)?,
+                u8::decode(input)?,
+            )),
+            other => Err(DecodeError::InvalidEnumVariant(other)),
+        }
+    }
+}
+
+impl Encode for VotingPhase {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        let discriminant: u8 = match self {
+            VotingPhase::RegistrationOpen => 0,
+            VotingPhase::VotingActive => 1,
+            VotingPhase::ResultsFinalized => 2,
+            VotingPhase::Paused => 3,
+        };
+        discriminant.encode_to(buf);
+    }
+}
+
+impl Decode for VotingPhase {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(VotingPhase::RegistrationOpen),
+            1 => Ok(VotingPhase::VotingActive),
+            2 => Ok(VotingPhase::ResultsFinalized),
+            3 => Ok(VotingPhase::Paused),
+            other => Err(DecodeError::InvalidEnumVariant(other)),
+        }
+    }
+}
+
+impl Encode for Quorum {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            Quorum::Absolute(minimum) => {
+                0u8.encode_to(buf);
+                minimum.encode_to(buf);
+            }
+            Quorum::Percentage(percent) => {
+                1u8.encode_to(buf);
+                percent.encode_to(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Quorum {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(Quorum::Absolute(u64::decode(input)?)),
+            1 => Ok(Quorum::Percentage(u8::decode(input)?)),
+            other => Err(DecodeError::InvalidEnumVariant(other)),
+        }
+    }
+}
+
+impl Encode for TieBreakPolicy {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            TieBreakPolicy::Error => 0u8.encode_to(buf),
+            TieBreakPolicy::FirstRegisteredOption => 1u8.encode_to(buf),
+            TieBreakPolicy::AdminDecides => 2u8.encode_to(buf),
+            TieBreakPolicy::RandomWithSeed(seed) => {
+                3u8.encode_to(buf);
+                seed.encode_to(buf);
+            }
+        }
+    }
+}
+
+impl Decode for TieBreakPolicy {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(TieBreakPolicy::Error),
+            1 => Ok(TieBreakPolicy::FirstRegisteredOption),
+            2 => Ok(TieBreakPolicy::AdminDecides),
+            3 => Ok(TieBreakPolicy::RandomWithSeed(u64::decode(input)?)),
+            other => Err(DecodeError::InvalidEnumVariant(other)),
+        }
+    }
+}
+
+impl Encode for VoteError {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        let discriminant: u8 = match self {
+            VoteError::NotAdmin => 0,
+            VoteError::InvalidTransition => 1,
+            VoteError::InvalidDuration => 2,
+            VoteError::VotingWindowNotElapsed => 3,
+            VoteError::AlreadyRegistered => 4,
+            VoteError::NotRegistered => 5,
+            VoteError::AlreadyVoted => 6,
+            VoteError::VotingNotActive => 7,
+            VoteError::InvalidOption => 8,
+            VoteError::OutsideVotingWindow => 9,
+            VoteError::InvalidSignature => 10,
+            VoteError::InvalidUserId => 11,
+            VoteError::NotAPerson => 12,
+            VoteError::EmptyAdminId => 13,
+            VoteError::DuplicateOption => 14,
+            VoteError::VotingAlreadyStarted => 15,
+            VoteError::OptionHasVotes => 16,
+            VoteError::PollNotFound => 17,
+            VoteError::PollAlreadyExists => 18,
+            VoteError::QuorumNotMet => 19,
+            VoteError::TieUnresolved => 20,
+            VoteError::ExtensionLimitExceeded => 21,
+        };
+        discriminant.encode_to(buf);
+    }
+}
+
+impl Decode for VoteError {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(VoteError::NotAdmin),
+            1 => Ok(VoteError::InvalidTransition),
+            2 => Ok(VoteError::InvalidDuration),
+            3 => Ok(VoteError::VotingWindowNotElapsed),
+            4 => Ok(VoteError::AlreadyRegistered),
+            5 => Ok(VoteError::NotRegistered),
+            6 => Ok(VoteError::AlreadyVoted),
+            7 => Ok(VoteError::VotingNotActive),
+            8 => Ok(VoteError::InvalidOption),
+            9 => Ok(VoteError::OutsideVotingWindow),
+            10 => Ok(VoteError::InvalidSignature),
+            11 => Ok(VoteError::InvalidUserId),
+            12 => Ok(VoteError::NotAPerson),
+            13 => Ok(VoteError::EmptyAdminId),
+            14 => Ok(VoteError::DuplicateOption),
+            15 => Ok(VoteError::VotingAlreadyStarted),
+            16 => Ok(VoteError::OptionHasVotes),
+            17 => Ok(VoteError::PollNotFound),
+            18 => Ok(VoteError::PollAlreadyExists),
+            19 => Ok(VoteError::QuorumNotMet),
+            20 => Ok(VoteError::TieUnresolved),
+            21 => Ok(VoteError::ExtensionLimitExceeded),
+            other => Err(DecodeError::InvalidEnumVariant(other)),
+        }
+    }
+}
+
+impl Encode for AuditEntry {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.caller_id.encode_to(buf);
+        self.action.encode_to(buf);
+        self.timestamp.encode_to(buf);
+        self.rejected.encode_to(buf);
+    }
+}
+
+impl Decode for AuditEntry {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(AuditEntry {
+            caller_id: String::decode(input)?,
+            action: String::decode(input)?,
+            timestamp: Option::<u64>::decode(input)?,
+            rejected: Option::<VoteError>::decode(input)?,
+        })
+    }
+}
+
+impl Encode for VotingState {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.current_voting_state.encode_to(buf);
+        // `registered_voters`/`votes_cast` are `HashSet`s now (see
+        // `voting-state-machine`'s own doc comment on them), with no
+        // encoding order of their own; sort before encoding so the same
+        // state always produces the same bytes.
+        let mut registered_voters: Vec<String> = self.registered_voters.iter().cloned().collect();
+        registered_voters.sort();
+        registered_voters.encode_to(buf);
+        let mut votes_cast: Vec<String> = self.votes_cast.iter().cloned().collect();
+        votes_cast.sort();
+        votes_cast.encode_to(buf);
+        // `BTreeMap` already iterates in sorted key order, matching how
+        // `parity-scale-codec` encodes it: as a `Compact` length followed by
+        // each `(key, value)` pair in that same order.
+        let entries: Vec<(String, u64)> = self
+            .vote_counts
+            .iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+        entries.encode_to(buf);
+        // Same `BTreeMap`-as-sorted-entries trick as `vote_counts` above.
+        let voters_by_option: Vec<(String, Vec<String>)> = self
+            .votes_by_option
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        voters_by_option.encode_to(buf);
+        self.voting_start_time.encode_to(buf);
+        self.voting_end_time.encode_to(buf);
+        self.admin_id.encode_to(buf);
+        self.quorum.encode_to(buf);
+        self.option_order.encode_to(buf);
+        self.tie_break_policy.encode_to(buf);
+        self.invalid_vote_attempts.encode_to(buf);
+        self.audit_log.encode_to(buf);
+        self.paused_at.encode_to(buf);
+        self.max_extension.encode_to(buf);
+        self.total_extension.encode_to(buf);
+    }
+}
+
+impl Decode for VotingState {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let current_voting_state = VotingPhase::decode(input)?;
+        let registered_voters: std::collections::HashSet<String> = Vec::<String>::decode(input)?.into_iter().collect();
+        let votes_cast: std::collections::HashSet<String> = Vec::<String>::decode(input)?.into_iter().collect();
+        let entries = Vec::<(String, u64)>::decode(input)?;
+        let voters_by_option = Vec::<(String, Vec<String>)>::decode(input)?;
+        let voting_start_time = u64::decode(input)?;
+        let voting_end_time = u64::decode(input)?;
+        let admin_id = String::decode(input)?;
+        let quorum = Option::<Quorum>::decode(input)?;
+        let option_order = Vec::<String>::decode(input)?;
+        let tie_break_policy = TieBreakPolicy::decode(input)?;
+        let invalid_vote_attempts = u64::decode(input)?;
+        let audit_log = Vec::<AuditEntry>::decode(input)?;
+        let paused_at = Option::<u64>::decode(input)?;
+        let max_extension = Option::<u64>::decode(input)?;
+        let total_extension = u64::decode(input)?;
+        Ok(VotingState {
+            current_voting_state,
+            registered_voters,
+            votes_cast,
+            vote_counts: entries.into_iter().collect(),
+            votes_by_option: voters_by_option.into_iter().collect(),
+            voting_start_time,
+            voting_end_time,
+            admin_id,
+            quorum,
+            option_order,
+            tie_break_policy,
+            invalid_vote_attempts,
+            audit_log,
+            paused_at,
+            max_extension,
+            total_extension,
+        })
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.0.encode_to(buf);
+        self.1.encode_to(buf);
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for (A, B) {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parity_scale_codec::Encode as _;
+
+    fn roundtrips<T: Encode + Decode + PartialEq + core::fmt::Debug>(value: T) {
+        let bytes = value.encode();
+        let mut cursor = bytes.as_slice();
+        assert_eq!(T::decode(&mut cursor).unwrap(), value);
+        assert!(cursor.is_empty(), "decode should consume every byte");
+    }
+
+    #[test]
+    fn integers_roundtrip() {
+        roundtrips(0u8);
+        roundtrips(255u8);
+        roundtrips(0u64);
+        roundtrips(u64::MAX);
+    }
+
+    #[test]
+    fn integers_match_parity_scale_codec() {
+        assert_eq!(Encode::encode(&42u8), parity_scale_codec::Encode::encode(&42u8));
+        assert_eq!(
+            Encode::encode(&1_000u16),
+            parity_scale_codec::Encode::encode(&1_000u16)
+        );
+        assert_eq!(
+            Encode::encode(&70_000u32),
+            parity_scale_codec::Encode::encode(&70_000u32)
+        );
+        assert_eq!(
+            Encode::encode(&u64::MAX),
+            parity_scale_codec::Encode::encode(&u64::MAX)
+        );
+    }
+
+    #[test]
+    fn compact_matches_parity_scale_codec_across_every_length_mode() {
+        for value in [0u64, 1, 63, 64, 16_383, 16_384, 1 << 29, 1 << 30, u64::MAX] {
+            assert_eq!(
+                Compact(value).encode(),
+                parity_scale_codec::Compact(value).encode(),
+                "mismatch for {value}",
+            );
+        }
+    }
+
+    #[test]
+    fn compact_roundtrips() {
+        for value in [0u64, 63, 64, 16_383, 16_384, 1 << 30, u64::MAX] {
+            roundtrips(Compact(value));
+        }
+    }
+
+    #[test]
+    fn vec_matches_parity_scale_codec() {
+        let values: Vec<u32> = Vec::from([1, 2, 3, 4, 5]);
+        assert_eq!(
+            Encode::encode(&values),
+            parity_scale_codec::Encode::encode(&values)
+        );
+    }
+
+    #[test]
+    fn option_matches_parity_scale_codec() {
+        assert_eq!(
+            Encode::encode(&Some(7u8)),
+            parity_scale_codec::Encode::encode(&Some(7u8))
+        );
+        assert_eq!(
+            Encode::encode(&None::<u8>),
+            parity_scale_codec::Encode::encode(&None::<u8>)
+        );
+    }
+
+    #[test]
+    fn string_matches_parity_scale_codec() {
+        let value = String::from("hello, scale");
+        assert_eq!(
+            Encode::encode(&value),
+            parity_scale_codec::Encode::encode(&value)
+        );
+    }
+
+    #[test]
+    fn color_roundtrips_every_variant() {
+        roundtrips(Color::Red);
+        roundtrips(Color::Green);
+        roundtrips(Color::Blue);
+        roundtrips(Color::Custom(1, 2, 3));
+    }
+
+    /// Mirrors of `VotingPhase`/`VotingState`, used only so the test suite
+    /// has a `parity-scale-codec`-derived oracle to diff against: the real
+    /// types in `voting-state-machine` deliberately don't depend on SCALE.
+    #[derive(parity_scale_codec::Encode)]
+    enum VotingPhaseMirror {
+        RegistrationOpen,
+        VotingActive,
+        ResultsFinalized,
+        Paused,
+    }
+
+    #[derive(parity_scale_codec::Encode)]
+    enum QuorumMirror {
+        Absolute(u64),
+        Percentage(u8),
+    }
+
+    #[derive(parity_scale_codec::Encode)]
+    enum TieBreakPolicyMirror {
+        Error,
+        FirstRegisteredOption,
+        AdminDecides,
+        RandomWithSeed(u64),
+    }
+
+    #[derive(parity_scale_codec::Encode)]
+    enum VoteErrorMirror {
+        NotAdmin,
+        InvalidTransition,
+        InvalidDuration,
+        VotingWindowNotElapsed,
+        AlreadyRegistered,
+        NotRegistered,
+        AlreadyVoted,
+        VotingNotActive,
+        InvalidOption,
+        OutsideVotingWindow,
+        InvalidSignature,
+        InvalidUserId,
+        NotAPerson,
+        EmptyAdminId,
+        DuplicateOption,
+        VotingAlreadyStarted,
+        OptionHasVotes,
+        PollNotFound,
+        PollAlreadyExists,
+        QuorumNotMet,
+        TieUnresolved,
+        ExtensionLimitExceeded,
+    }
+
+    #[derive(parity_scale_codec::Encode)]
+    struct AuditEntryMirror {
+        caller_id: String,
+        action: String,
+        timestamp: Option<u64>,
+        rejected: Option<VoteErrorMirror>,
+    }
+
+    #[derive(parity_scale_codec::Encode)]
+    struct VotingStateMirror {
+        current_voting_state: VotingPhaseMirror,
+        registered_voters: Vec<String>,
+        votes_cast: Vec<String>,
+        vote_counts: Vec<(String, u64)>,
+        votes_by_option: Vec<(String, Vec<String>)>,
+        voting_start_time: u64,
+        voting_end_time: u64,
+        admin_id: String,
+        quorum: Option<QuorumMirror>,
+        option_order: Vec<String>,
+        tie_break_policy: TieBreakPolicyMirror,
+        invalid_vote_attempts: u64,
+        audit_log: Vec<AuditEntryMirror>,
+        paused_at: Option<u64>,
+        max_extension: Option<u64>,
+        total_extension: u64,
+    }
+
+    #[test]
+    fn voting_phase_matches_its_mirror() {
+        let cases = [
+            (VotingPhase::RegistrationOpen, VotingPhaseMirror::RegistrationOpen),
+            (VotingPhase::VotingActive, VotingPhaseMirror::VotingActive),
+            (VotingPhase::ResultsFinalized, VotingPhaseMirror::ResultsFinalized),
+            (VotingPhase::Paused, VotingPhaseMirror::Paused),
+        ];
+        for (ours, mirror) in cases {
+            assert_eq!(ours.encode(), mirror.encode());
+        }
+    }
+
+    #[test]
+    fn quorum_matches_its_mirror() {
+        let cases = [(Quorum::Absolute(5), QuorumMirror::Absolute(5)), (Quorum::Percentage(60), QuorumMirror::Percentage(60))];
+        for (ours, mirror) in cases {
+            assert_eq!(ours.encode(), mirror.encode());
+        }
+    }
+
+    #[test]
+    fn tie_break_policy_matches_its_mirror() {
+        let cases = [
+            (TieBreakPolicy::Error, TieBreakPolicyMirror::Error),
+            (TieBreakPolicy::FirstRegisteredOption, TieBreakPolicyMirror::FirstRegisteredOption),
+            (TieBreakPolicy::AdminDecides, TieBreakPolicyMirror::AdminDecides),
+            (TieBreakPolicy::RandomWithSeed(7), TieBreakPolicyMirror::RandomWithSeed(7)),
+        ];
+        for (ours, mirror) in cases {
+            assert_eq!(ours.encode(), mirror.encode());
+        }
+    }
+
+    #[test]
+    fn vote_error_matches_its_mirror() {
+        let cases = [
+            (VoteError::NotAdmin, VoteErrorMirror::NotAdmin),
+            (VoteError::InvalidTransition, VoteErrorMirror::InvalidTransition),
+            (VoteError::InvalidDuration, VoteErrorMirror::InvalidDuration),
+            (VoteError::VotingWindowNotElapsed, VoteErrorMirror::VotingWindowNotElapsed),
+            (VoteError::AlreadyRegistered, VoteErrorMirror::AlreadyRegistered),
+            (VoteError::NotRegistered, VoteErrorMirror::NotRegistered),
+            (VoteError::AlreadyVoted, VoteErrorMirror::AlreadyVoted),
+            (VoteError::VotingNotActive, VoteErrorMirror::VotingNotActive),
+            (VoteError::InvalidOption, VoteErrorMirror::InvalidOption),
+            (VoteError::OutsideVotingWindow, VoteErrorMirror::OutsideVotingWindow),
+            (VoteError::InvalidSignature, VoteErrorMirror::InvalidSignature),
+            (VoteError::InvalidUserId, VoteErrorMirror::InvalidUserId),
+            (VoteError::NotAPerson, VoteErrorMirror::NotAPerson),
+            (VoteError::EmptyAdminId, VoteErrorMirror::EmptyAdminId),
+            (VoteError::DuplicateOption, VoteErrorMirror::DuplicateOption),
+            (VoteError::VotingAlreadyStarted, VoteErrorMirror::VotingAlreadyStarted),
+            (VoteError::OptionHasVotes, VoteErrorMirror::OptionHasVotes),
+            (VoteError::PollNotFound, VoteErrorMirror::PollNotFound),
+            (VoteError::PollAlreadyExists, VoteErrorMirror::PollAlreadyExists),
+            (VoteError::QuorumNotMet, VoteErrorMirror::QuorumNotMet),
+            (VoteError::TieUnresolved, VoteErrorMirror::TieUnresolved),
+            (VoteError::ExtensionLimitExceeded, VoteErrorMirror::ExtensionLimitExceeded),
+        ];
+        for (ours, mirror) in cases {
+            assert_eq!(ours.encode(), mirror.encode());
+        }
+    }
+
+    #[test]
+    fn voting_state_matches_its_mirror() {
+        let mut state = VotingState::new("admin".to_string());
+        state.register_voter("alice".to_string()).unwrap();
+        state.add_vote_option("A".to_string());
+
+        let mirror = VotingStateMirror {
+            current_voting_state: VotingPhaseMirror::RegistrationOpen,
+            registered_voters: Vec::from([String::from("alice")]),
+            votes_cast: Vec::new(),
+            vote_counts: Vec::from([(String::from("A"), 0u64)]),
+            votes_by_option: Vec::new(),
+            voting_start_time: 0,
+            voting_end_time: 0,
+            admin_id: String::from("admin"),
+            quorum: None,
+            option_order: Vec::from([String::from("A")]),
+            tie_break_policy: TieBreakPolicyMirror::Error,
+            invalid_vote_attempts: 0,
+            audit_log: Vec::from([AuditEntryMirror {
+                caller_id: String::from("alice"),
+                action: String::from("register_voter"),
+                timestamp: None,
+                rejected: None,
+            }]),
+            paused_at: None,
+            max_extension: None,
+            total_extension: 0,
+        };
+
+        assert_eq!(state.encode(), mirror.encode());
+    }
+
+    #[test]
+    fn voting_state_roundtrips() {
+        let mut state = VotingState::new("admin".to_string());
+        state.register_voter("alice".to_string()).unwrap();
+        state.add_vote_option("A".to_string());
+        roundtrips(state);
+    }
+}
@@ -0,0 +1,314 @@
+//! Solution for Task 40: `pallet-nomination-pools`'s points-based
+//! accounting on top of Task 12's staking simulation - a pool bonds its
+//! members' combined stake as a single account in a [`Staking`], and
+//! members own points against that bonded balance rather than a fixed
+//! amount, so rewards (credited the same way Task 12 already
+//! distributes them) raise the value of a point instead of needing their
+//! own separate accounting. See the README for why points exist at all.
+
+use std::collections::BTreeMap;
+
+pub use staking_rewards_simulation::{Clock, FixedClock};
+use staking_rewards_simulation::{Balance, EraIndex, Staking};
+
+pub type AccountId = String;
+pub type Points = u128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolState {
+    /// Accepts `join` and `bond_extra`.
+    Open,
+    /// No new members may `join`, but existing members can still
+    /// `bond_extra` and `unbond`.
+    Blocked,
+    /// No new members, no more bonding - every member is expected to
+    /// `unbond` and leave. Terminal: a pool never leaves this state.
+    Destroying,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PoolError {
+    PoolNotOpen,
+    PoolDestroying,
+    NotAMember,
+    InsufficientPoints,
+    ZeroAmount,
+}
+
+/// Balance still locked in a past era's unbond, maturing once the
+/// underlying [`Staking`] reaches `era` - the same shape Task 12's
+/// `UnlockChunk` uses, just owned by one member instead of one ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnbondingChunk {
+    pub balance: Balance,
+    pub era: EraIndex,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Member {
+    pub points: Points,
+    pub unbonding: Vec<UnbondingChunk>,
+}
+
+/// One nomination pool: a single bonded account in `staking`, plus
+/// points tracking how much of that bonded balance each member owns.
+pub struct Pool<C: Clock> {
+    state: PoolState,
+    bonded_account: AccountId,
+    points: Points,
+    staking: Staking<C>,
+    members: BTreeMap<AccountId, Member>,
+    unbonding_period_eras: EraIndex,
+}
+
+impl<C: Clock> Pool<C> {
+    pub fn new(clock: C, unbonding_period_eras: EraIndex) -> Self {
+        Self {
+            state: PoolState::Open,
+            bonded_account: "pool-bonded-account".to_string(),
+            points: 0,
+            staking: Staking::new(clock, unbonding_period_eras),
+            members: BTreeMap::new(),
+            unbonding_period_eras,
+        }
+    }
+
+    pub fn state(&self) -> PoolState {
+        self.state
+    }
+
+    /// `Destroying` is terminal; `Open` and `Blocked` otherwise toggle
+    /// freely, the same admin-gated transition `pallet-nomination-pools`
+    /// exposes (minus the admin check, out of scope here).
+    pub fn set_state(&mut self, state: PoolState) -> Result<(), PoolError> {
+        if self.state == PoolState::Destroying {
+            return Err(PoolError::PoolDestroying);
+        }
+        self.state = state;
+        Ok(())
+    }
+
+    /// The pool's total currently-bonded balance - the sum every
+    /// member's points are valued against.
+    pub fn bonded_balance(&self) -> Balance {
+        self.staking.ledger_of(&self.bonded_account).map_or(0, |ledger| ledger.active)
+    }
+
+    pub fn total_points(&self) -> Points {
+        self.points
+    }
+
+    pub fn member(&self, who: &str) -> Option<&Member> {
+        self.members.get(who)
+    }
+
+    /// Converts `amount` of bonded balance into the points it's worth
+    /// right now: 1:1 for the pool's first joiner, otherwise
+    /// proportional to how much a point is currently worth.
+    fn points_for_new_bond(&self, amount: Balance) -> Points {
+        let bonded_balance = self.bonded_balance();
+        if self.points == 0 || bonded_balance == 0 {
+            amount
+        } else {
+            amount * self.points / bonded_balance
+        }
+    }
+
+    fn bond(&mut self, who: &str, amount: Balance) -> Result<(), PoolError> {
+        if amount == 0 {
+            return Err(PoolError::ZeroAmount);
+        }
+        let points_issued = self.points_for_new_bond(amount);
+        self.staking
+            .bond(self.bonded_account.clone(), amount)
+            .expect("amount already checked non-zero");
+        self.points += points_issued;
+        self.members.entry(who.to_string()).or_default().points += points_issued;
+        Ok(())
+    }
+
+    /// Joins the pool (or tops up an existing membership) with `amount`
+    /// of new stake, minting points for it at the pool's current
+    /// points-to-balance ratio. Only allowed while the pool is `Open`.
+    pub fn join(&mut self, who: &str, amount: Balance) -> Result<(), PoolError> {
+        if self.state != PoolState::Open {
+            return Err(PoolError::PoolNotOpen);
+        }
+        self.bond(who, amount)
+    }
+
+    /// Adds `amount` of stake to an already-joined member, allowed in
+    /// any state except `Destroying`.
+    pub fn bond_extra(&mut self, who: &str, amount: Balance) -> Result<(), PoolError> {
+        if self.state == PoolState::Destroying {
+            return Err(PoolError::PoolDestroying);
+        }
+        if !self.members.contains_key(who) {
+            return Err(PoolError::NotAMember);
+        }
+        self.bond(who, amount)
+    }
+
+    /// Converts `points` of `who`'s membership back into bonded balance
+    /// at the pool's current ratio, and queues that balance to unlock
+    /// once the underlying `staking` simulation reaches the unbonding
+    /// era. Allowed in every pool state, including `Destroying` - a
+    /// destroying pool exists precisely so members can leave.
+    pub fn unbond(&mut self, who: &str, points: Points) -> Result<Balance, PoolError> {
+        if points == 0 {
+            return Err(PoolError::ZeroAmount);
+        }
+        let member = self.members.get_mut(who).ok_or(PoolError::NotAMember)?;
+        if member.points < points {
+            return Err(PoolError::InsufficientPoints);
+        }
+
+        let bonded_balance = self.staking.ledger_of(&self.bonded_account).map_or(0, |ledger| ledger.active);
+        let balance = points * bonded_balance / self.points;
+
+        self.staking
+            .unbond(&self.bonded_account, balance)
+            .expect("balance is derived from the pool's own active stake");
+        member.points -= points;
+        self.points -= points;
+
+        let era = self.staking.current_era() + self.unbonding_period_eras;
+        member.unbonding.push(UnbondingChunk { balance, era });
+        Ok(balance)
+    }
+
+    /// Pays out every matured unbonding chunk `who` is owed, removing
+    /// them from their membership record.
+    pub fn withdraw_unbonded(&mut self, who: &str) -> Balance {
+        let Some(member) = self.members.get_mut(who) else {
+            return 0;
+        };
+        let current_era = self.staking.current_era();
+        let mut freed = 0;
+        member.unbonding.retain(|chunk| {
+            if chunk.era <= current_era {
+                freed += chunk.balance;
+                false
+            } else {
+                true
+            }
+        });
+        freed
+    }
+
+    /// Credits `reward` to the pool's bonded balance - every member's
+    /// points become worth proportionally more, with no change to
+    /// anyone's point count.
+    pub fn distribute_rewards(&mut self, reward: Balance) {
+        self.staking.distribute_rewards(reward);
+    }
+
+    pub fn advance_era(&mut self) -> EraIndex {
+        self.staking.advance_era()
+    }
+
+    pub fn current_era(&self) -> EraIndex {
+        self.staking.current_era()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> Pool<FixedClock> {
+        Pool::new(FixedClock(1_000), 3)
+    }
+
+    #[test]
+    fn the_first_joiner_gets_points_one_to_one_with_balance() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        assert_eq!(pool.member("alice").unwrap().points, 100);
+        assert_eq!(pool.total_points(), 100);
+        assert_eq!(pool.bonded_balance(), 100);
+    }
+
+    #[test]
+    fn rewards_make_existing_points_worth_more_for_the_next_joiner() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        pool.distribute_rewards(100);
+        // The pool is now worth 200 for 100 points - a point is worth 2.
+        assert_eq!(pool.bonded_balance(), 200);
+
+        pool.join("bob", 100).unwrap();
+        // Bob's 100 balance only buys half as many points as Alice's did.
+        assert_eq!(pool.member("bob").unwrap().points, 50);
+        assert_eq!(pool.bonded_balance(), 300);
+    }
+
+    #[test]
+    fn join_is_rejected_once_the_pool_is_blocked() {
+        let mut pool = pool();
+        pool.set_state(PoolState::Blocked).unwrap();
+        assert_eq!(pool.join("alice", 100), Err(PoolError::PoolNotOpen));
+    }
+
+    #[test]
+    fn bond_extra_still_works_while_blocked_but_not_while_destroying() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+
+        pool.set_state(PoolState::Blocked).unwrap();
+        pool.bond_extra("alice", 50).unwrap();
+        assert_eq!(pool.member("alice").unwrap().points, 150);
+
+        pool.set_state(PoolState::Destroying).unwrap();
+        assert_eq!(pool.bond_extra("alice", 50), Err(PoolError::PoolDestroying));
+    }
+
+    #[test]
+    fn destroying_is_terminal() {
+        let mut pool = pool();
+        pool.set_state(PoolState::Destroying).unwrap();
+        assert_eq!(pool.set_state(PoolState::Open), Err(PoolError::PoolDestroying));
+    }
+
+    #[test]
+    fn unbond_converts_points_to_balance_at_the_current_ratio() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        pool.distribute_rewards(100);
+        // A point is now worth 2, so unbonding half of alice's points
+        // returns the full original 100.
+        let balance = pool.unbond("alice", 50).unwrap();
+        assert_eq!(balance, 100);
+        assert_eq!(pool.member("alice").unwrap().points, 50);
+        assert_eq!(pool.bonded_balance(), 100);
+    }
+
+    #[test]
+    fn unbond_is_allowed_even_while_destroying() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        pool.set_state(PoolState::Destroying).unwrap();
+        assert!(pool.unbond("alice", 100).is_ok());
+    }
+
+    #[test]
+    fn unbond_rejects_more_points_than_a_member_owns() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        assert_eq!(pool.unbond("alice", 200), Err(PoolError::InsufficientPoints));
+    }
+
+    #[test]
+    fn withdraw_unbonded_waits_for_the_unbonding_period() {
+        let mut pool = pool();
+        pool.join("alice", 100).unwrap();
+        pool.unbond("alice", 40).unwrap();
+
+        assert_eq!(pool.withdraw_unbonded("alice"), 0);
+        for _ in 0..3 {
+            pool.advance_era();
+        }
+        assert_eq!(pool.withdraw_unbonded("alice"), 40);
+        assert!(pool.member("alice").unwrap().unbonding.is_empty());
+    }
+}
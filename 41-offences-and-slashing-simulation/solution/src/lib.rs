@@ -0,0 +1,288 @@
+//! Solution for Task 41: `pallet-staking`'s offences-and-slashing
+//! pipeline as a plain Rust library sitting in front of Task 12's
+//! [`Staking`] - a reported offence doesn't slash immediately, it queues
+//! a [`SlashFraction`] that lands a fixed number of eras later, giving
+//! governance a window to [`SlashingManager::cancel_deferred_slash`] it
+//! first, and [`SlashSpan`] bookkeeping stops a string of reports for
+//! the same underlying misbehavior from each re-slashing an offender
+//! from scratch.
+
+use std::collections::BTreeMap;
+
+pub use staking_rewards_simulation::{Clock, FixedClock};
+use staking_rewards_simulation::{AccountId, Balance, EraIndex, Staking};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffenceKind {
+    Equivocation,
+    Unavailability,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SlashingError {
+    NoPendingSlash,
+}
+
+/// A fraction of an offender's stake to slash, expressed in parts per
+/// billion the way `sp_arithmetic::Perbill` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct SlashFraction(u32);
+
+const PARTS_PER_BILLION: u128 = 1_000_000_000;
+
+impl SlashFraction {
+    pub fn from_percent(percent: u32) -> Self {
+        Self(percent.saturating_mul(10_000_000))
+    }
+
+    pub fn mul_balance(&self, balance: Balance) -> Balance {
+        balance * Balance::from(self.0) / PARTS_PER_BILLION
+    }
+}
+
+/// Tracks the worst fraction slashed so far within the offender's
+/// current span - a later report that's no worse than that doesn't
+/// queue a second slash, the same way `pallet-staking` only ever
+/// applies the highest of several overlapping reports against one span.
+#[derive(Debug, Clone, Copy, Default)]
+struct SlashSpan {
+    index: u32,
+    highest_fraction: SlashFraction,
+}
+
+#[derive(Debug, Clone)]
+struct DeferredSlash {
+    offender: AccountId,
+    kind: OffenceKind,
+    span_index: u32,
+    fraction: SlashFraction,
+    cancelled: bool,
+}
+
+/// A slash that has actually been taken out of an offender's stake -
+/// kept around the way `pallet-staking`'s `Slashed` event lets
+/// observers see what landed and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedSlash {
+    pub offender: AccountId,
+    pub kind: OffenceKind,
+    pub era_applied: EraIndex,
+    pub amount: Balance,
+}
+
+/// Turns offence reports into slashes against a [`Staking`] ledger,
+/// deferring every slash by `slash_defer_duration` eras before it lands.
+pub struct SlashingManager {
+    slash_defer_duration: EraIndex,
+    spans: BTreeMap<AccountId, SlashSpan>,
+    pending: BTreeMap<EraIndex, Vec<DeferredSlash>>,
+    history: Vec<AppliedSlash>,
+}
+
+impl SlashingManager {
+    pub fn new(slash_defer_duration: EraIndex) -> Self {
+        Self {
+            slash_defer_duration,
+            spans: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Queues `fraction` of `offender`'s stake to be slashed once
+    /// `staking` reaches the returned era. A report that's no worse than
+    /// the highest already queued or applied in the offender's current
+    /// span is dropped, matching `pallet-staking`'s "a span only ever
+    /// gets worse" rule.
+    pub fn report_offence<C: Clock>(
+        &mut self,
+        staking: &Staking<C>,
+        offender: AccountId,
+        kind: OffenceKind,
+        fraction: SlashFraction,
+    ) -> EraIndex {
+        let apply_at = staking.current_era() + self.slash_defer_duration;
+        let span = self.spans.entry(offender.clone()).or_default();
+
+        if fraction > span.highest_fraction {
+            span.highest_fraction = fraction;
+            self.pending.entry(apply_at).or_default().push(DeferredSlash {
+                offender,
+                kind,
+                span_index: span.index,
+                fraction,
+                cancelled: false,
+            });
+        }
+        apply_at
+    }
+
+    /// Cancels a slash still queued to land at `apply_at` for `offender`.
+    /// This is `pallet-staking`'s reversal window, open for as long as
+    /// the slash hasn't actually been applied yet.
+    pub fn cancel_deferred_slash(&mut self, apply_at: EraIndex, offender: &str) -> Result<(), SlashingError> {
+        let slash = self
+            .pending
+            .get_mut(&apply_at)
+            .into_iter()
+            .flatten()
+            .find(|slash| slash.offender == offender && !slash.cancelled)
+            .ok_or(SlashingError::NoPendingSlash)?;
+        slash.cancelled = true;
+        Ok(())
+    }
+
+    /// Advances `staking` to its next era, applying every non-cancelled
+    /// slash that was due to land at or before that era first.
+    pub fn advance_era<C: Clock>(&mut self, staking: &mut Staking<C>) -> EraIndex {
+        let new_era = staking.advance_era();
+
+        let due_eras: Vec<EraIndex> = self.pending.range(..=new_era).map(|(era, _)| *era).collect();
+        for era in due_eras {
+            let due = self.pending.remove(&era).unwrap_or_default();
+            for slash in due {
+                if slash.cancelled {
+                    continue;
+                }
+                let exposed = staking.ledger_of(&slash.offender).map_or(0, |ledger| ledger.total);
+                let amount = staking.slash(&slash.offender, slash.fraction.mul_balance(exposed));
+                self.history.push(AppliedSlash {
+                    offender: slash.offender.clone(),
+                    kind: slash.kind,
+                    era_applied: new_era,
+                    amount,
+                });
+
+                // A validator slashed down to nothing has nothing left
+                // for this span to protect - the next report against
+                // them starts a fresh one.
+                if staking.ledger_of(&slash.offender).is_none_or(|ledger| ledger.total == 0) {
+                    if let Some(span) = self.spans.get_mut(&slash.offender) {
+                        if span.index == slash.span_index {
+                            span.index += 1;
+                            span.highest_fraction = SlashFraction::default();
+                        }
+                    }
+                }
+            }
+        }
+        new_era
+    }
+
+    pub fn current_span(&self, offender: &str) -> u32 {
+        self.spans.get(offender).map_or(0, |span| span.index)
+    }
+
+    pub fn history(&self) -> &[AppliedSlash] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn managed() -> (Staking<FixedClock>, SlashingManager) {
+        (Staking::new(FixedClock(1_000), 3), SlashingManager::new(2))
+    }
+
+    #[test]
+    fn a_slash_does_not_land_until_the_deferred_era_is_reached() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        let apply_at = manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(10));
+        assert_eq!(apply_at, 2);
+
+        manager.advance_era(&mut staking);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 1_000);
+
+        manager.advance_era(&mut staking);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 900);
+        assert_eq!(manager.history(), &[AppliedSlash {
+            offender: "alice".into(),
+            kind: OffenceKind::Equivocation,
+            era_applied: 2,
+            amount: 100,
+        }]);
+    }
+
+    #[test]
+    fn cancelling_a_deferred_slash_stops_it_from_landing() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        let apply_at = manager.report_offence(&staking, "alice".into(), OffenceKind::Unavailability, SlashFraction::from_percent(10));
+        manager.cancel_deferred_slash(apply_at, "alice").unwrap();
+
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 1_000);
+        assert!(manager.history().is_empty());
+    }
+
+    #[test]
+    fn cancelling_an_already_applied_slash_is_rejected() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        let apply_at = manager.report_offence(&staking, "alice".into(), OffenceKind::Unavailability, SlashFraction::from_percent(10));
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+
+        assert_eq!(manager.cancel_deferred_slash(apply_at, "alice"), Err(SlashingError::NoPendingSlash));
+    }
+
+    #[test]
+    fn a_milder_report_within_the_same_span_does_not_queue_a_second_slash() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(30));
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(10));
+
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 700);
+        assert_eq!(manager.history().len(), 1);
+    }
+
+    #[test]
+    fn a_harsher_report_within_the_same_span_queues_another_slash() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(10));
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(30));
+
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+
+        // Both slashes land in the same era; the second is computed
+        // against what's left after the first has already been taken,
+        // since this simulation re-reads exposure at application time
+        // rather than snapshotting it when the offence was reported.
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 630);
+        assert_eq!(manager.history().len(), 2);
+    }
+
+    #[test]
+    fn being_slashed_to_zero_starts_a_fresh_span() {
+        let (mut staking, mut manager) = managed();
+        staking.bond("alice".into(), 1_000).unwrap();
+
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(100));
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 0);
+        assert_eq!(manager.current_span("alice"), 1);
+
+        staking.bond("alice".into(), 500).unwrap();
+        // Same fraction as before, but it's a new span, so it queues again.
+        manager.report_offence(&staking, "alice".into(), OffenceKind::Equivocation, SlashFraction::from_percent(100));
+        manager.advance_era(&mut staking);
+        manager.advance_era(&mut staking);
+        assert_eq!(staking.ledger_of("alice").unwrap().total, 0);
+    }
+}
@@ -0,0 +1,389 @@
+//! Solution for Task 42: runs the same set of votes through a
+//! Democracy-v1-style engine and Task 27's OpenGov [`ReferendaEngine`],
+//! then reports which one resolved the proposal first and what it
+//! decided - answering "does OpenGov's confirm-period/curve model reach
+//! a different, or just a differently-timed, outcome than Gov1's flat
+//! supermajority thresholds?" for a given scenario.
+
+use std::collections::BTreeMap;
+
+pub use opengov_referenda_engine::Conviction;
+use opengov_referenda_engine::{ReferendaEngine, Track};
+use voting_state_machine::{Timestamp, UserId};
+
+pub type Balance = u128;
+pub type ReferendumIndex = u32;
+
+/// A Democracy-v1-style engine: a single referendum runs for a fixed
+/// `voting_period` and is decided the moment it ends by one flat
+/// threshold, with no tracks and no decaying curves - the model OpenGov
+/// (Task 27) replaced.
+pub mod gov1 {
+    use super::*;
+
+    /// Mirrors `pallet-democracy`'s `VoteThreshold`, simplified to flat
+    /// approval-ratio cutoffs rather than its turnout-weighted adaptive
+    /// quorum biasing formula (see the README for why).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum VoteThreshold {
+        SuperMajorityApprove,
+        SuperMajorityAgainst,
+        SimpleMajority,
+    }
+
+    impl VoteThreshold {
+        /// Whether `ayes` vs `nays` clears this threshold.
+        fn approved(self, ayes: Balance, nays: Balance) -> bool {
+            if ayes + nays == 0 {
+                return false;
+            }
+            match self {
+                VoteThreshold::SimpleMajority => ayes > nays,
+                VoteThreshold::SuperMajorityApprove => ayes * 3 >= (ayes + nays) * 2,
+                VoteThreshold::SuperMajorityAgainst => nays * 3 < ayes + nays,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct Proposal {
+        pub threshold: VoteThreshold,
+        pub voting_period: Timestamp,
+        pub enactment_delay: Timestamp,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Status {
+        Ongoing,
+        Passed,
+        NotPassed,
+        Enacted,
+    }
+
+    #[derive(Debug)]
+    struct Referendum {
+        proposal: Proposal,
+        started_at: Timestamp,
+        passed_at: Option<Timestamp>,
+        status: Status,
+        ayes: Balance,
+        nays: Balance,
+        voters: BTreeMap<UserId, bool>,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum DemocracyError {
+        UnknownReferendum,
+        AlreadyVoted,
+        NotOngoing,
+    }
+
+    /// Every public referendum currently running - real `pallet-democracy`
+    /// launches one proposal at a time off a public queue; this simulation
+    /// skips that queue and lets a referendum start the moment it's
+    /// submitted, since the comparison only cares about a single
+    /// proposal's own timeline once it's up for a vote.
+    pub struct Democracy {
+        referenda: BTreeMap<ReferendumIndex, Referendum>,
+        next_index: ReferendumIndex,
+    }
+
+    impl Democracy {
+        pub fn new() -> Self {
+            Self { referenda: BTreeMap::new(), next_index: 0 }
+        }
+
+        pub fn submit(&mut self, proposal: Proposal, now: Timestamp) -> ReferendumIndex {
+            let index = self.next_index;
+            self.next_index += 1;
+            self.referenda.insert(index, Referendum {
+                proposal,
+                started_at: now,
+                passed_at: None,
+                status: Status::Ongoing,
+                ayes: 0,
+                nays: 0,
+                voters: BTreeMap::new(),
+            });
+            index
+        }
+
+        pub fn vote(
+            &mut self,
+            index: ReferendumIndex,
+            voter: UserId,
+            aye: bool,
+            balance: Balance,
+            conviction: Conviction,
+        ) -> Result<(), DemocracyError> {
+            let referendum = self.referenda.get_mut(&index).ok_or(DemocracyError::UnknownReferendum)?;
+            if referendum.status != Status::Ongoing {
+                return Err(DemocracyError::NotOngoing);
+            }
+            if referendum.voters.contains_key(&voter) {
+                return Err(DemocracyError::AlreadyVoted);
+            }
+            let weight = conviction.weigh(balance);
+            if aye {
+                referendum.ayes += weight;
+            } else {
+                referendum.nays += weight;
+            }
+            referendum.voters.insert(voter, aye);
+            Ok(())
+        }
+
+        /// Decides `index` once its voting period has elapsed, then
+        /// enacts it `enactment_delay` after that - call this whenever
+        /// time passes, the same way `ReferendaEngine::advance` works.
+        pub fn advance(&mut self, index: ReferendumIndex, now: Timestamp) -> Result<Status, DemocracyError> {
+            let referendum = self.referenda.get_mut(&index).ok_or(DemocracyError::UnknownReferendum)?;
+            match referendum.status {
+                Status::Ongoing => {
+                    if now.saturating_sub(referendum.started_at) >= referendum.proposal.voting_period {
+                        referendum.status = if referendum.proposal.threshold.approved(referendum.ayes, referendum.nays) {
+                            referendum.passed_at = Some(now);
+                            Status::Passed
+                        } else {
+                            Status::NotPassed
+                        };
+                    }
+                }
+                Status::Passed => {
+                    let passed_at = referendum.passed_at.expect("Passed referenda always record passed_at");
+                    if now.saturating_sub(passed_at) >= referendum.proposal.enactment_delay {
+                        referendum.status = Status::Enacted;
+                    }
+                }
+                Status::NotPassed | Status::Enacted => {}
+            }
+            Ok(referendum.status)
+        }
+
+        pub fn status(&self, index: ReferendumIndex) -> Result<Status, DemocracyError> {
+            self.referenda.get(&index).map(|referendum| referendum.status).ok_or(DemocracyError::UnknownReferendum)
+        }
+    }
+
+    impl Default for Democracy {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+/// Where a referendum's decision currently stands, independent of which
+/// engine decided it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl From<gov1::Status> for Outcome {
+    fn from(status: gov1::Status) -> Self {
+        match status {
+            gov1::Status::Ongoing => Outcome::Pending,
+            gov1::Status::Passed | gov1::Status::Enacted => Outcome::Approved,
+            gov1::Status::NotPassed => Outcome::Rejected,
+        }
+    }
+}
+
+impl From<opengov_referenda_engine::Status> for Outcome {
+    fn from(status: opengov_referenda_engine::Status) -> Self {
+        match status {
+            opengov_referenda_engine::Status::Preparing | opengov_referenda_engine::Status::Deciding => Outcome::Pending,
+            opengov_referenda_engine::Status::Approved => Outcome::Approved,
+            opengov_referenda_engine::Status::Rejected => Outcome::Rejected,
+        }
+    }
+}
+
+/// A single cast vote, replayed identically against both engines.
+#[derive(Debug, Clone)]
+pub struct Vote {
+    pub at: Timestamp,
+    pub voter: UserId,
+    pub aye: bool,
+    pub balance: Balance,
+    pub conviction: Conviction,
+}
+
+/// The shared inputs both engines are run against: the same electorate
+/// size, the same votes cast at the same times, sampled for a decision
+/// at the same checkpoints.
+pub struct Scenario {
+    pub total_issuance: Balance,
+    pub votes: Vec<Vote>,
+    pub checkpoints: Vec<Timestamp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeline {
+    pub outcome: Outcome,
+    pub resolved_at: Option<Timestamp>,
+}
+
+pub struct ComparisonReport {
+    pub gov1: Timeline,
+    pub opengov: Timeline,
+}
+
+impl ComparisonReport {
+    /// `Some("gov1")` / `Some("opengov")` if one engine reached a
+    /// terminal outcome strictly before the other, `None` if neither
+    /// resolved within the scenario's checkpoints or they tied.
+    pub fn faster_engine(&self) -> Option<&'static str> {
+        match (self.gov1.resolved_at, self.opengov.resolved_at) {
+            (Some(gov1_at), Some(opengov_at)) if gov1_at < opengov_at => Some("gov1"),
+            (Some(gov1_at), Some(opengov_at)) if opengov_at < gov1_at => Some("opengov"),
+            (Some(_), None) => Some("gov1"),
+            (None, Some(_)) => Some("opengov"),
+            _ => None,
+        }
+    }
+}
+
+/// Runs `scenario` through a [`gov1::Democracy`] referendum governed by
+/// `gov1_proposal` and an OpenGov [`ReferendaEngine`] referendum governed
+/// by `opengov_track`, replaying the same votes into both at the same
+/// timestamps, and reports how each one resolved.
+///
+/// Both referenda are assumed to start deciding at time `0` - the
+/// decision deposit is treated as already paid on the OpenGov side, so
+/// the comparison is about the decision phase both models share, not
+/// about deposit-gathering mechanics only one of them has.
+pub fn run_comparison(scenario: &Scenario, gov1_proposal: gov1::Proposal, opengov_track: Track) -> ComparisonReport {
+    let mut democracy = gov1::Democracy::new();
+    let gov1_index = democracy.submit(gov1_proposal, 0);
+
+    let mut referenda = ReferendaEngine::new(scenario.total_issuance);
+    referenda.add_track(0, opengov_track.clone());
+    let opengov_index = referenda.submit(0).expect("track 0 was just registered");
+    referenda
+        .place_decision_deposit(opengov_index, opengov_track.decision_deposit, 0)
+        .expect("decision deposit is assumed already available for this comparison");
+
+    let mut gov1_timeline = Timeline { outcome: Outcome::Pending, resolved_at: None };
+    let mut opengov_timeline = Timeline { outcome: Outcome::Pending, resolved_at: None };
+
+    let mut votes_applied = 0;
+    let mut checkpoints = scenario.checkpoints.clone();
+    checkpoints.sort_unstable();
+
+    for checkpoint in checkpoints {
+        while votes_applied < scenario.votes.len() && scenario.votes[votes_applied].at <= checkpoint {
+            let vote = &scenario.votes[votes_applied];
+            let _ = democracy.vote(gov1_index, vote.voter.clone(), vote.aye, vote.balance, vote.conviction);
+            let _ = referenda.vote(opengov_index, vote.voter.clone(), vote.aye, vote.balance, vote.conviction);
+            votes_applied += 1;
+        }
+
+        if gov1_timeline.resolved_at.is_none() {
+            let status = democracy.advance(gov1_index, checkpoint).expect("gov1_index was just submitted");
+            let outcome = Outcome::from(status);
+            if outcome != Outcome::Pending {
+                gov1_timeline = Timeline { outcome, resolved_at: Some(checkpoint) };
+            }
+        }
+
+        if opengov_timeline.resolved_at.is_none() {
+            let status = referenda.advance(opengov_index, checkpoint).expect("opengov_index was just submitted");
+            let outcome = Outcome::from(status);
+            if outcome != Outcome::Pending {
+                opengov_timeline = Timeline { outcome, resolved_at: Some(checkpoint) };
+            }
+        }
+    }
+
+    ComparisonReport { gov1: gov1_timeline, opengov: opengov_timeline }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opengov_referenda_engine::Curve;
+
+    fn flat_curve(permill: u32) -> Curve {
+        Curve { ceiling_permill: permill, floor_permill: permill }
+    }
+
+    fn opengov_track() -> Track {
+        Track {
+            decision_deposit: 100,
+            decision_period: 200,
+            confirm_period: 10,
+            min_approval: flat_curve(500_000),
+            min_support: flat_curve(100_000),
+        }
+    }
+
+    fn gov1_proposal() -> gov1::Proposal {
+        gov1::Proposal {
+            threshold: gov1::VoteThreshold::SuperMajorityApprove,
+            voting_period: 200,
+            enactment_delay: 0,
+        }
+    }
+
+    fn scenario(votes: Vec<Vote>, checkpoints: Vec<Timestamp>) -> Scenario {
+        Scenario { total_issuance: 10_000, votes, checkpoints }
+    }
+
+    #[test]
+    fn an_overwhelming_aye_passes_on_both_engines() {
+        let votes = vec![
+            Vote { at: 0, voter: "alice".into(), aye: true, balance: 9_000, conviction: Conviction::Locked1x },
+        ];
+        // OpenGov needs a checkpoint inside the confirm window (5, then
+        // 16 - eleven time units of continuously passing, past the
+        // track's confirm_period of 10) to actually register the
+        // confirmation; Gov1 only decides once voting_period elapses.
+        let report = run_comparison(&scenario(votes, vec![5, 16, 210]), gov1_proposal(), opengov_track());
+
+        assert_eq!(report.gov1.outcome, Outcome::Approved);
+        assert_eq!(report.opengov.outcome, Outcome::Approved);
+    }
+
+    #[test]
+    fn opengov_can_resolve_before_gov1s_fixed_voting_period_ends() {
+        // A landslide aye clears OpenGov's curves well before Gov1's
+        // fixed voting_period has even elapsed: confirmation completes
+        // at t=16 (started confirming at t=5, confirm_period is 10),
+        // while Gov1 only decides once its 200-long voting_period ends.
+        let votes = vec![
+            Vote { at: 0, voter: "alice".into(), aye: true, balance: 9_000, conviction: Conviction::Locked1x },
+        ];
+        let report = run_comparison(&scenario(votes, vec![5, 16, 200]), gov1_proposal(), opengov_track());
+
+        assert_eq!(report.opengov.resolved_at, Some(16));
+        assert_eq!(report.gov1.resolved_at, Some(200));
+        assert_eq!(report.faster_engine(), Some("opengov"));
+    }
+
+    #[test]
+    fn a_weak_minority_aye_is_rejected_by_both_engines() {
+        let votes = vec![
+            Vote { at: 0, voter: "alice".into(), aye: true, balance: 100, conviction: Conviction::Locked1x },
+            Vote { at: 0, voter: "bob".into(), aye: false, balance: 9_000, conviction: Conviction::Locked1x },
+        ];
+        let report = run_comparison(&scenario(votes, vec![200]), gov1_proposal(), opengov_track());
+
+        assert_eq!(report.gov1.outcome, Outcome::Rejected);
+        assert_eq!(report.opengov.outcome, Outcome::Rejected);
+    }
+
+    #[test]
+    fn a_referendum_neither_engine_has_reached_a_checkpoint_for_is_still_pending() {
+        let votes = vec![
+            Vote { at: 0, voter: "alice".into(), aye: true, balance: 9_000, conviction: Conviction::Locked1x },
+        ];
+        let report = run_comparison(&scenario(votes, vec![1]), gov1_proposal(), opengov_track());
+
+        assert_eq!(report.gov1.outcome, Outcome::Pending);
+        assert_eq!(report.opengov.outcome, Outcome::Pending);
+        assert_eq!(report.faster_engine(), None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Solution for Task 43: wraps Task 1's off-chain voting state machine
+//! with a gas meter - every call costs storage reads, storage writes,
+//! and bytes of input, charged against a per-caller gas balance, the way
+//! ink!/EVM contracts (and, under a different name, FRAME extrinsic
+//! weights) make every state change pay for the work it causes before
+//! learners start reasoning about real chains' fee models.
+
+use std::collections::BTreeMap;
+
+use voting_state_machine::{Action, Timestamp, UserId, VoteError, VoteOption, VotingState};
+
+pub type Gas = u64;
+
+/// What one unit of storage access or one byte of input costs, in gas -
+/// a deployment-chosen set of prices, the same way a real chain's
+/// weight-to-fee conversion is a runtime configuration, not a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostModel {
+    pub per_storage_read: Gas,
+    pub per_storage_write: Gas,
+    pub per_byte: Gas,
+}
+
+impl CostModel {
+    pub fn cost(&self, reads: u32, writes: u32, bytes: u32) -> Gas {
+        self.per_storage_read.saturating_mul(Gas::from(reads))
+            + self.per_storage_write.saturating_mul(Gas::from(writes))
+            + self.per_byte.saturating_mul(Gas::from(bytes))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GasError {
+    /// The caller's gas balance can't cover this call's cost; nothing
+    /// was charged and the underlying operation never ran.
+    InsufficientGas { required: Gas, available: Gas },
+    /// Gas was charged (real chains charge weight regardless of whether
+    /// the extrinsic succeeds), but the voting machine itself rejected
+    /// the call.
+    Voting(VoteError),
+}
+
+/// Wraps a [`VotingState`] so every call to it is priced by `cost_model`
+/// and paid for out of the caller's metered balance.
+pub struct GasMeteredVoting {
+    state: VotingState,
+    cost_model: CostModel,
+    balances: BTreeMap<UserId, Gas>,
+}
+
+impl GasMeteredVoting {
+    pub fn new(admin_id: UserId, cost_model: CostModel) -> Self {
+        Self {
+            state: VotingState::new(admin_id),
+            cost_model,
+            balances: BTreeMap::new(),
+        }
+    }
+
+    pub fn state(&self) -> &VotingState {
+        &self.state
+    }
+
+    pub fn set_gas_balance(&mut self, who: UserId, amount: Gas) {
+        self.balances.insert(who, amount);
+    }
+
+    pub fn gas_balance(&self, who: &UserId) -> Gas {
+        self.balances.get(who).copied().unwrap_or(0)
+    }
+
+    /// Charges `who` `required` gas if they can afford it, returning
+    /// [`GasError::InsufficientGas`] (and charging nothing) otherwise.
+    fn charge(&mut self, who: &UserId, required: Gas) -> Result<(), GasError> {
+        let available = self.gas_balance(who);
+        if available < required {
+            return Err(GasError::InsufficientGas { required, available });
+        }
+        self.balances.insert(who.clone(), available - required);
+        Ok(())
+    }
+
+    pub fn register_voter(&mut self, caller_id: UserId) -> Result<(), GasError> {
+        let cost = self.cost_model.cost(1, 1, caller_id.len() as u32);
+        self.charge(&caller_id, cost)?;
+        self.state.register_voter(caller_id).map_err(GasError::Voting)
+    }
+
+    pub fn cast_vote(&mut self, caller_id: UserId, vote_option: VoteOption, now: Timestamp) -> Result<(), GasError> {
+        let bytes = (caller_id.len() + vote_option.len()) as u32;
+        let cost = self.cost_model.cost(3, 2, bytes);
+        self.charge(&caller_id, cost)?;
+        self.state.cast_vote(caller_id, vote_option, now).map_err(GasError::Voting)
+    }
+
+    pub fn add_vote_option(&mut self, caller_id: UserId, option: VoteOption) -> Result<(), GasError> {
+        let cost = self.cost_model.cost(1, 1, option.len() as u32);
+        self.charge(&caller_id, cost)?;
+        self.state.add_vote_option(option);
+        Ok(())
+    }
+
+    /// Meters and applies a phase [`Action`] the way [`voting_state_machine::transition`]
+    /// does, pricing each action kind by the storage it touches.
+    pub fn transition(&mut self, caller_id: UserId, action: Action, now: Timestamp) -> Result<(), GasError> {
+        let (reads, writes, bytes) = match &action {
+            Action::StartRegistration | Action::ResetApplication => (1, 3, 0),
+            Action::StartVoting { .. } => (1, 2, 16),
+            Action::EndVoting { .. } => (2, 1, 0),
+        };
+        let cost = self.cost_model.cost(reads, writes, bytes);
+        self.charge(&caller_id, cost)?;
+        voting_state_machine::transition(&mut self.state, caller_id, action, now).map_err(GasError::Voting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost_model() -> CostModel {
+        CostModel { per_storage_read: 10, per_storage_write: 20, per_byte: 1 }
+    }
+
+    fn meter() -> GasMeteredVoting {
+        GasMeteredVoting::new("admin".to_string(), cost_model())
+    }
+
+    #[test]
+    fn register_voter_charges_the_reads_writes_and_bytes_it_costs() {
+        let mut meter = meter();
+        meter.set_gas_balance("alice".to_string(), 1_000);
+
+        // 1 read + 1 write + 5 bytes ("alice") = 10 + 20 + 5 = 35.
+        meter.register_voter("alice".to_string()).unwrap();
+        assert_eq!(meter.gas_balance(&"alice".to_string()), 965);
+    }
+
+    #[test]
+    fn a_call_the_caller_cannot_afford_is_rejected_and_charges_nothing() {
+        let mut meter = meter();
+        meter.set_gas_balance("alice".to_string(), 10);
+
+        assert_eq!(
+            meter.register_voter("alice".to_string()),
+            Err(GasError::InsufficientGas { required: 35, available: 10 })
+        );
+        assert_eq!(meter.gas_balance(&"alice".to_string()), 10);
+        assert!(meter.state().registered_voters.is_empty());
+    }
+
+    #[test]
+    fn gas_is_spent_even_when_the_underlying_call_fails() {
+        let mut meter = meter();
+        meter.set_gas_balance("alice".to_string(), 1_000);
+        meter.register_voter("alice".to_string()).unwrap();
+        let balance_after_first_call = meter.gas_balance(&"alice".to_string());
+
+        assert_eq!(
+            meter.register_voter("alice".to_string()),
+            Err(GasError::Voting(VoteError::AlreadyRegistered))
+        );
+        assert!(meter.gas_balance(&"alice".to_string()) < balance_after_first_call);
+    }
+
+    #[test]
+    fn repeated_calls_drain_the_balance_to_exhaustion() {
+        let mut meter = meter();
+        meter.set_gas_balance("admin".to_string(), 35);
+
+        meter.add_vote_option("admin".to_string(), "A".to_string()).unwrap();
+        assert_eq!(
+            meter.add_vote_option("admin".to_string(), "B".to_string()),
+            Err(GasError::InsufficientGas { required: 31, available: 4 })
+        );
+    }
+
+    #[test]
+    fn transition_is_metered_and_still_enforces_voting_machine_rules() {
+        let mut meter = meter();
+        meter.set_gas_balance("admin".to_string(), 1_000);
+        meter.set_gas_balance("mallory".to_string(), 1_000);
+
+        assert_eq!(
+            meter.transition("mallory".to_string(), Action::StartVoting { duration: 10 }, 0),
+            Err(GasError::Voting(VoteError::NotAdmin))
+        );
+        // Mallory's gas was still spent on the rejected call.
+        assert!(meter.gas_balance(&"mallory".to_string()) < 1_000);
+
+        meter.transition("admin".to_string(), Action::StartVoting { duration: 10 }, 0).unwrap();
+        assert_eq!(meter.state().voting_end_time, 10);
+    }
+}
@@ -0,0 +1,360 @@
+//! Solution for Task 44: `pallet-proxy`'s delegation model over Task 5's
+//! `pallet-voting` - a real account can let a proxy dispatch calls on
+//! its behalf, filtered by [`ProxyType`], and a proxy with a non-zero
+//! delay must [`ProxyRegistry::announce`] a call and let the delay pass
+//! before anyone can relay it, instead of dispatching immediately. See
+//! the README for why `NonTransfer` doesn't filter anything here.
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use codec::Encode;
+use frame_support::{
+    construct_runtime, derive_impl,
+    sp_runtime::{traits::Dispatchable, BuildStorage, DispatchError},
+    traits::ConstU32,
+};
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Voting: pallet_voting,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_voting::Config for Runtime {
+    type WeightInfo = ();
+    type MaxVoters = ConstU32<1_000>;
+    type MaxCandidates = ConstU32<16>;
+}
+
+pub use common::AccountId;
+pub type BlockNumber = u64;
+/// Identifies a pending call the way `pallet-proxy`'s `CallHashOf<T>`
+/// does, just with `std::hash::Hash` standing in for a real cryptographic
+/// hasher since this is a plain simulation with no hashing pallet to
+/// borrow one from.
+pub type CallHash = u64;
+
+pub const ADMIN: AccountId = 1;
+
+/// Builds a fresh externalities with `ADMIN` as the voting pallet's admin
+/// and no candidates seeded, ready for proxied `pallet_voting::Call`s to
+/// run against.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_voting::GenesisConfig::<Runtime> { admin: Some(ADMIN), candidates: vec![b"Alice".to_vec()] }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Mirrors `pallet-proxy`'s built-in proxy types, minus the ones that
+/// only make sense with pallets this runtime doesn't have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyType {
+    /// Every call is allowed.
+    Any,
+    /// Only the voting pallet's administrative calls (`start_voting`,
+    /// `finalize`) are allowed - the calls that drive the voting phase
+    /// forward, the way `pallet-proxy`'s `Governance` type allows only
+    /// council/democracy calls.
+    Governance,
+    /// Everything except balance transfers - this runtime has no
+    /// balances pallet and so nothing to transfer, so in practice this
+    /// behaves exactly like `Any`. Kept as its own variant so the
+    /// filtering interface matches `pallet-proxy`'s.
+    NonTransfer,
+}
+
+impl ProxyType {
+    fn filter(self, call: &RuntimeCall) -> bool {
+        match self {
+            ProxyType::Any | ProxyType::NonTransfer => true,
+            ProxyType::Governance => matches!(
+                call,
+                RuntimeCall::Voting(pallet_voting::Call::start_voting {})
+                    | RuntimeCall::Voting(pallet_voting::Call::finalize {})
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProxyDefinition {
+    proxy_type: ProxyType,
+    delay: BlockNumber,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Announcement {
+    proxy: AccountId,
+    real: AccountId,
+    call_hash: CallHash,
+    announced_at: BlockNumber,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProxyError {
+    NotProxy,
+    AlreadyProxy,
+    CallFiltered,
+    /// `proxy` was used directly on a proxy relationship that requires
+    /// announcing first.
+    DelayRequiresAnnouncement,
+    NoMatchingAnnouncement,
+    AnnouncementStillDelayed,
+    Dispatch(DispatchError),
+}
+
+/// Hashes `call`'s SCALE encoding the way `pallet-proxy` hashes a call to
+/// compare an announcement against the call that's eventually relayed.
+fn call_hash(call: &RuntimeCall) -> CallHash {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    call.encode().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Tracks every account's proxies and every proxy's pending
+/// announcements - `pallet-proxy`'s two storage maps
+/// (`Proxies`/`Announcements`), kept here as plain Rust state rather
+/// than pallet storage, the same way Task 38's `batch`/`batch_all` sit
+/// above the runtime instead of inside a pallet of their own.
+#[derive(Default)]
+pub struct ProxyRegistry {
+    proxies: BTreeMap<AccountId, Vec<(AccountId, ProxyDefinition)>>,
+    announcements: Vec<Announcement>,
+}
+
+impl ProxyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_proxy(
+        &mut self,
+        real: AccountId,
+        proxy: AccountId,
+        proxy_type: ProxyType,
+        delay: BlockNumber,
+    ) -> Result<(), ProxyError> {
+        let proxies = self.proxies.entry(real).or_default();
+        if proxies.iter().any(|(existing, _)| *existing == proxy) {
+            return Err(ProxyError::AlreadyProxy);
+        }
+        proxies.push((proxy, ProxyDefinition { proxy_type, delay }));
+        Ok(())
+    }
+
+    pub fn remove_proxy(&mut self, real: AccountId, proxy: AccountId) -> Result<(), ProxyError> {
+        let proxies = self.proxies.entry(real).or_default();
+        let before = proxies.len();
+        proxies.retain(|(existing, _)| *existing != proxy);
+        if proxies.len() == before {
+            return Err(ProxyError::NotProxy);
+        }
+        Ok(())
+    }
+
+    fn definition_of(&self, real: AccountId, proxy: AccountId) -> Option<ProxyDefinition> {
+        self.proxies
+            .get(&real)?
+            .iter()
+            .find(|(existing, _)| *existing == proxy)
+            .map(|(_, definition)| *definition)
+    }
+
+    /// Dispatches `call` as `real` on `proxy`'s behalf immediately.
+    /// Proxies with a non-zero delay can't use this - they have to
+    /// [`Self::announce`] the call and wait, then relay it through
+    /// [`Self::proxy_announced`].
+    pub fn proxy(
+        &mut self,
+        real: AccountId,
+        proxy: AccountId,
+        call: RuntimeCall,
+    ) -> Result<(), ProxyError> {
+        let definition = self.definition_of(real, proxy).ok_or(ProxyError::NotProxy)?;
+        if definition.delay != 0 {
+            return Err(ProxyError::DelayRequiresAnnouncement);
+        }
+        if !definition.proxy_type.filter(&call) {
+            return Err(ProxyError::CallFiltered);
+        }
+        call.dispatch(RuntimeOrigin::signed(real)).map(|_| ()).map_err(|error| ProxyError::Dispatch(error.error))
+    }
+
+    /// Records that `proxy` intends to relay `call` on `real`'s behalf -
+    /// the delay window a delayed proxy must sit out before the call can
+    /// actually run, giving `real` a chance to notice and
+    /// [`Self::remove_proxy`] before it does.
+    pub fn announce(&mut self, proxy: AccountId, real: AccountId, call: &RuntimeCall, now: BlockNumber) -> Result<(), ProxyError> {
+        self.definition_of(real, proxy).ok_or(ProxyError::NotProxy)?;
+        self.announcements.push(Announcement { proxy, real, call_hash: call_hash(call), announced_at: now });
+        Ok(())
+    }
+
+    pub fn remove_announcement(&mut self, proxy: AccountId, real: AccountId, call: &RuntimeCall) -> Result<(), ProxyError> {
+        let hash = call_hash(call);
+        let before = self.announcements.len();
+        self.announcements
+            .retain(|announcement| !(announcement.proxy == proxy && announcement.real == real && announcement.call_hash == hash));
+        if self.announcements.len() == before {
+            return Err(ProxyError::NoMatchingAnnouncement);
+        }
+        Ok(())
+    }
+
+    /// Dispatches a previously [`Self::announce`]d call as `real`, once
+    /// its proxy's delay has actually elapsed. Real `pallet-proxy` lets
+    /// anyone relay it at that point (an anti-censorship measure in case
+    /// the proxy itself goes offline); this simulation doesn't model who
+    /// pays for relaying, so it takes no relayer argument.
+    pub fn proxy_announced(
+        &mut self,
+        real: AccountId,
+        proxy: AccountId,
+        call: RuntimeCall,
+        now: BlockNumber,
+    ) -> Result<(), ProxyError> {
+        let definition = self.definition_of(real, proxy).ok_or(ProxyError::NotProxy)?;
+        if !definition.proxy_type.filter(&call) {
+            return Err(ProxyError::CallFiltered);
+        }
+        let hash = call_hash(&call);
+        let position = self
+            .announcements
+            .iter()
+            .position(|announcement| announcement.proxy == proxy && announcement.real == real && announcement.call_hash == hash)
+            .ok_or(ProxyError::NoMatchingAnnouncement)?;
+        if now.saturating_sub(self.announcements[position].announced_at) < definition.delay {
+            return Err(ProxyError::AnnouncementStillDelayed);
+        }
+        self.announcements.remove(position);
+        call.dispatch(RuntimeOrigin::signed(real)).map(|_| ()).map_err(|error| ProxyError::Dispatch(error.error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: AccountId = 2;
+    const PROXY: AccountId = 3;
+
+    fn vote(candidate_id: u32) -> RuntimeCall {
+        pallet_voting::Call::vote { candidate_id }.into()
+    }
+
+    fn start_voting() -> RuntimeCall {
+        pallet_voting::Call::start_voting {}.into()
+    }
+
+    fn register() -> RuntimeCall {
+        pallet_voting::Call::register {}.into()
+    }
+
+    #[test]
+    fn an_any_proxy_can_vote_on_the_real_accounts_behalf() {
+        new_test_ext().execute_with(|| {
+            register().dispatch(RuntimeOrigin::signed(ALICE)).unwrap();
+            start_voting().dispatch(RuntimeOrigin::signed(ADMIN)).unwrap();
+
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ALICE, PROXY, ProxyType::Any, 0).unwrap();
+
+            registry.proxy(ALICE, PROXY, vote(0)).unwrap();
+            assert_eq!(pallet_voting::VoteCounts::<Runtime>::get(0), 1);
+        });
+    }
+
+    #[test]
+    fn a_governance_proxy_cannot_vote() {
+        new_test_ext().execute_with(|| {
+            register().dispatch(RuntimeOrigin::signed(ALICE)).unwrap();
+            start_voting().dispatch(RuntimeOrigin::signed(ADMIN)).unwrap();
+
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ALICE, PROXY, ProxyType::Governance, 0).unwrap();
+
+            assert_eq!(registry.proxy(ALICE, PROXY, vote(0)), Err(ProxyError::CallFiltered));
+        });
+    }
+
+    #[test]
+    fn a_governance_proxy_can_drive_the_voting_phase() {
+        new_test_ext().execute_with(|| {
+            register().dispatch(RuntimeOrigin::signed(ADMIN)).unwrap();
+
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ADMIN, PROXY, ProxyType::Governance, 0).unwrap();
+
+            registry.proxy(ADMIN, PROXY, start_voting()).unwrap();
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::VotingActive);
+        });
+    }
+
+    #[test]
+    fn using_proxy_directly_on_a_delayed_relationship_is_rejected() {
+        new_test_ext().execute_with(|| {
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ALICE, PROXY, ProxyType::Any, 10).unwrap();
+
+            assert_eq!(
+                registry.proxy(ALICE, PROXY, start_voting()),
+                Err(ProxyError::DelayRequiresAnnouncement)
+            );
+        });
+    }
+
+    #[test]
+    fn a_delayed_proxy_must_wait_out_the_announcement_before_it_can_be_relayed() {
+        new_test_ext().execute_with(|| {
+            register().dispatch(RuntimeOrigin::signed(ALICE)).unwrap();
+            start_voting().dispatch(RuntimeOrigin::signed(ADMIN)).unwrap();
+
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ALICE, PROXY, ProxyType::Any, 10).unwrap();
+            registry.announce(PROXY, ALICE, &vote(0), 1).unwrap();
+
+            assert_eq!(
+                registry.proxy_announced(ALICE, PROXY, vote(0), 5),
+                Err(ProxyError::AnnouncementStillDelayed)
+            );
+
+            registry.proxy_announced(ALICE, PROXY, vote(0), 11).unwrap();
+            assert_eq!(pallet_voting::VoteCounts::<Runtime>::get(0), 1);
+        });
+    }
+
+    #[test]
+    fn removing_an_announcement_stops_it_from_being_relayed() {
+        new_test_ext().execute_with(|| {
+            let mut registry = ProxyRegistry::new();
+            registry.add_proxy(ALICE, PROXY, ProxyType::Any, 10).unwrap();
+            registry.announce(PROXY, ALICE, &vote(0), 1).unwrap();
+            registry.remove_announcement(PROXY, ALICE, &vote(0)).unwrap();
+
+            assert_eq!(
+                registry.proxy_announced(ALICE, PROXY, vote(0), 20),
+                Err(ProxyError::NoMatchingAnnouncement)
+            );
+        });
+    }
+
+    #[test]
+    fn adding_the_same_proxy_twice_is_rejected() {
+        let mut registry = ProxyRegistry::new();
+        registry.add_proxy(ALICE, PROXY, ProxyType::Any, 0).unwrap();
+        assert_eq!(registry.add_proxy(ALICE, PROXY, ProxyType::Governance, 5), Err(ProxyError::AlreadyProxy));
+    }
+}
@@ -0,0 +1,333 @@
+//! Solution for Task 45: a `pallet-scheduler`-style scheduler - named and
+//! anonymous calls, priorities, and failure retry - wired to Task 5's
+//! `pallet-voting` so an [`ElectionRegistry`] can queue up a whole
+//! election's `start_voting`/`finalize` calls in advance and let
+//! [`Scheduler::run_to_block`] drive the phases forward as the clock
+//! ticks, instead of an admin calling them by hand.
+
+use std::collections::BTreeMap;
+
+use frame_support::{
+    construct_runtime, derive_impl,
+    sp_runtime::{traits::Dispatchable, BuildStorage, DispatchError},
+    traits::ConstU32,
+};
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Voting: pallet_voting,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_voting::Config for Runtime {
+    type WeightInfo = ();
+    type MaxVoters = ConstU32<1_000>;
+    type MaxCandidates = ConstU32<16>;
+}
+
+pub use common::AccountId;
+pub type BlockNumber = u64;
+
+pub const ADMIN: AccountId = 1;
+
+/// Builds a fresh externalities with `ADMIN` as the voting pallet's admin
+/// and one candidate seeded, ready for scheduled `pallet_voting::Call`s
+/// to run against.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_voting::GenesisConfig::<Runtime> { admin: Some(ADMIN), candidates: vec![b"Alice".to_vec()] }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// `pallet-scheduler`'s `(BlockNumber, u32)` address: the block a call is
+/// due at, plus its index within that block's agenda.
+pub type TaskAddress = (BlockNumber, u32);
+
+/// Matches `pallet-scheduler`'s convention: 0 is the highest priority,
+/// 255 runs last among calls due in the same block.
+pub const HIGHEST_PRIORITY: u8 = 0;
+pub const LOWEST_PRIORITY: u8 = 255;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SchedulerError {
+    NameAlreadyInUse,
+    NotFound,
+}
+
+struct ScheduledCall {
+    origin: AccountId,
+    call: RuntimeCall,
+    priority: u8,
+    retries_remaining: u32,
+}
+
+/// A block's agenda and the name-to-address lookup, the same two pieces
+/// of state `pallet-scheduler` keeps in its `Agenda`/`Lookup` storage
+/// items - kept here as plain Rust state rather than pallet storage, the
+/// same way Task 38's `batch`/`batch_all` sit above the runtime instead
+/// of inside a pallet of their own.
+#[derive(Default)]
+pub struct Scheduler {
+    agenda: BTreeMap<BlockNumber, Vec<Option<ScheduledCall>>>,
+    lookup: BTreeMap<Vec<u8>, TaskAddress>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `call` for `at`, dispatched as `origin`, without a name - it
+    /// can only be cancelled by [`TaskAddress`], the way `pallet-scheduler`'s
+    /// anonymous `schedule` works.
+    pub fn schedule(&mut self, at: BlockNumber, priority: u8, origin: AccountId, call: RuntimeCall, retries: u32) -> TaskAddress {
+        let slot = self.agenda.entry(at).or_default();
+        let index = slot.len() as u32;
+        slot.push(Some(ScheduledCall { origin, call, priority, retries_remaining: retries }));
+        (at, index)
+    }
+
+    /// Like [`Self::schedule`], but registers `name` so the task can also
+    /// be looked up and cancelled by name. Fails if `name` is already in
+    /// use, the same way `pallet-scheduler::schedule_named` does.
+    pub fn schedule_named(
+        &mut self,
+        name: Vec<u8>,
+        at: BlockNumber,
+        priority: u8,
+        origin: AccountId,
+        call: RuntimeCall,
+        retries: u32,
+    ) -> Result<TaskAddress, SchedulerError> {
+        if self.lookup.contains_key(&name) {
+            return Err(SchedulerError::NameAlreadyInUse);
+        }
+        let address = self.schedule(at, priority, origin, call, retries);
+        self.lookup.insert(name, address);
+        Ok(address)
+    }
+
+    pub fn cancel(&mut self, address: TaskAddress) -> Result<(), SchedulerError> {
+        let (at, index) = address;
+        let slot = self.agenda.get_mut(&at).ok_or(SchedulerError::NotFound)?;
+        let entry = slot.get_mut(index as usize).ok_or(SchedulerError::NotFound)?;
+        entry.take().ok_or(SchedulerError::NotFound)?;
+        Ok(())
+    }
+
+    pub fn cancel_named(&mut self, name: &[u8]) -> Result<(), SchedulerError> {
+        let address = self.lookup.remove(name).ok_or(SchedulerError::NotFound)?;
+        self.cancel(address)
+    }
+
+    /// Dispatches every call due at `now`, highest priority (lowest
+    /// number) first, each as its own origin. A call that fails is
+    /// rescheduled one block later if it still has retries left, and
+    /// dropped for good once they run out - `pallet-scheduler`'s retry
+    /// config, simplified to a flat per-task counter. A retried task is
+    /// re-queued anonymously even if the original was named, since
+    /// tracking a name across a new address on every retry isn't modeled
+    /// here.
+    pub fn run_to_block(&mut self, now: BlockNumber) -> Vec<(TaskAddress, Result<(), DispatchError>)> {
+        let Some(slot) = self.agenda.remove(&now) else {
+            return Vec::new();
+        };
+
+        let mut due: Vec<(u32, ScheduledCall)> =
+            slot.into_iter().enumerate().filter_map(|(index, maybe_call)| maybe_call.map(|call| (index as u32, call))).collect();
+        due.sort_by_key(|(_, call)| call.priority);
+
+        due.into_iter()
+            .map(|(index, scheduled)| {
+                let result = scheduled
+                    .call
+                    .clone()
+                    .dispatch(RuntimeOrigin::signed(scheduled.origin))
+                    .map(|_| ())
+                    .map_err(|error| error.error);
+                if result.is_err() && scheduled.retries_remaining > 0 {
+                    self.schedule(now + 1, scheduled.priority, scheduled.origin, scheduled.call, scheduled.retries_remaining - 1);
+                }
+                ((now, index), result)
+            })
+            .collect()
+    }
+}
+
+/// Identifies an election's pair of scheduled tasks so it can later be
+/// [`ElectionRegistry::cancel`]led as a unit.
+pub struct ElectionHandle {
+    pub index: u32,
+    start_name: Vec<u8>,
+    finalize_name: Vec<u8>,
+}
+
+fn election_task_name(index: u32, suffix: &[u8]) -> Vec<u8> {
+    let mut name = b"election-".to_vec();
+    name.extend_from_slice(&index.to_be_bytes());
+    name.push(b'-');
+    name.extend_from_slice(suffix);
+    name
+}
+
+/// Wraps a [`Scheduler`] with election-shaped convenience: creating an
+/// election schedules its `start_voting` and `finalize` calls up front,
+/// so the voting phase advances on its own as [`Self::run_to_block`]
+/// reaches each scheduled block, instead of an admin dispatching them by
+/// hand.
+#[derive(Default)]
+pub struct ElectionRegistry {
+    scheduler: Scheduler,
+    next_election: u32,
+}
+
+impl ElectionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedules `admin`'s `start_voting` for `starts_at` and `finalize`
+    /// for `finalizes_at`, each retried up to `retries` times if the
+    /// voting pallet isn't in the right phase yet when its turn comes.
+    pub fn create_election(
+        &mut self,
+        admin: AccountId,
+        starts_at: BlockNumber,
+        finalizes_at: BlockNumber,
+        retries: u32,
+    ) -> Result<ElectionHandle, SchedulerError> {
+        let index = self.next_election;
+        let start_name = election_task_name(index, b"start");
+        let finalize_name = election_task_name(index, b"finalize");
+
+        self.scheduler.schedule_named(
+            start_name.clone(),
+            starts_at,
+            HIGHEST_PRIORITY,
+            admin,
+            pallet_voting::Call::start_voting {}.into(),
+            retries,
+        )?;
+        self.scheduler.schedule_named(
+            finalize_name.clone(),
+            finalizes_at,
+            HIGHEST_PRIORITY,
+            admin,
+            pallet_voting::Call::finalize {}.into(),
+            retries,
+        )?;
+
+        self.next_election += 1;
+        Ok(ElectionHandle { index, start_name, finalize_name })
+    }
+
+    /// Cancels both of an election's scheduled calls. Fails if either has
+    /// already run (or was already cancelled) - partial cancellation
+    /// still leaves whichever call succeeded in place.
+    pub fn cancel(&mut self, handle: &ElectionHandle) -> Result<(), SchedulerError> {
+        self.scheduler.cancel_named(&handle.start_name)?;
+        self.scheduler.cancel_named(&handle.finalize_name)
+    }
+
+    pub fn run_to_block(&mut self, now: BlockNumber) -> Vec<(TaskAddress, Result<(), DispatchError>)> {
+        self.scheduler.run_to_block(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register() -> RuntimeCall {
+        pallet_voting::Call::register {}.into()
+    }
+
+    #[test]
+    fn anonymous_calls_run_in_priority_order_within_the_same_block() {
+        new_test_ext().execute_with(|| {
+            let mut scheduler = Scheduler::new();
+            scheduler.schedule(2, LOWEST_PRIORITY, 2, register(), 0);
+            scheduler.schedule(2, HIGHEST_PRIORITY, 3, register(), 0);
+
+            let outcomes = scheduler.run_to_block(2);
+            assert_eq!(outcomes.len(), 2);
+            // The highest-priority call (account 3, scheduled second) ran first.
+            assert!(pallet_voting::RegisteredVoters::<Runtime>::contains_key(3));
+            assert!(pallet_voting::RegisteredVoters::<Runtime>::contains_key(2));
+            assert_eq!(pallet_voting::VoterCount::<Runtime>::get(), 2);
+        });
+    }
+
+    #[test]
+    fn named_tasks_can_be_looked_up_and_cancelled() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule_named(b"alice-register".to_vec(), 5, HIGHEST_PRIORITY, 2, register(), 0).unwrap();
+
+        assert_eq!(
+            scheduler.schedule_named(b"alice-register".to_vec(), 6, HIGHEST_PRIORITY, 2, register(), 0),
+            Err(SchedulerError::NameAlreadyInUse)
+        );
+
+        scheduler.cancel_named(b"alice-register").unwrap();
+        assert_eq!(scheduler.cancel_named(b"alice-register"), Err(SchedulerError::NotFound));
+    }
+
+    #[test]
+    fn a_failing_call_is_retried_on_the_next_block_until_it_runs_out() {
+        new_test_ext().execute_with(|| {
+            // No candidate voted-for exists for account 2 to register twice,
+            // so the second schedule of the same register call always fails.
+            let mut scheduler = Scheduler::new();
+            register().dispatch(RuntimeOrigin::signed(2)).unwrap();
+            scheduler.schedule(1, HIGHEST_PRIORITY, 2, register(), 1);
+
+            let outcomes = scheduler.run_to_block(1);
+            assert!(outcomes[0].1.is_err());
+
+            // Retried once more at block 2, then dropped for good.
+            let outcomes = scheduler.run_to_block(2);
+            assert!(outcomes[0].1.is_err());
+            assert!(scheduler.run_to_block(3).is_empty());
+        });
+    }
+
+    #[test]
+    fn an_election_auto_starts_and_auto_finalizes_on_schedule() {
+        new_test_ext().execute_with(|| {
+            let mut elections = ElectionRegistry::new();
+            elections.create_election(ADMIN, 2, 4, 0).unwrap();
+
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::RegistrationOpen);
+
+            elections.run_to_block(2);
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::VotingActive);
+
+            elections.run_to_block(4);
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::ResultsFinalized);
+        });
+    }
+
+    #[test]
+    fn cancelling_an_election_stops_it_from_starting() {
+        new_test_ext().execute_with(|| {
+            let mut elections = ElectionRegistry::new();
+            let handle = elections.create_election(ADMIN, 2, 4, 0).unwrap();
+            elections.cancel(&handle).unwrap();
+
+            elections.run_to_block(2);
+            assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::RegistrationOpen);
+        });
+    }
+}
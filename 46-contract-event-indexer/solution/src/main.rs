@@ -0,0 +1,262 @@
+//! Long-running indexer for the Task 2 wishlist and Task 3 voting
+//! contracts: subscribes to finalized blocks over subxt, decodes every
+//! `Contracts.ContractEmitted` event it sees through whichever
+//! contract's `.contract` metadata matches the emitting address, and
+//! writes the decoded event into SQLite so it can be queried without
+//! re-reading the chain.
+//!
+//! Like Task 6's client, this talks to `pallet_contracts` through
+//! subxt's *dynamic* event API rather than macro-generated static
+//! types, so the binary works against any `substrate-contracts-node`
+//! without regenerating code from its metadata first.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use contract_transcode::ContractMessageTranscoder;
+use rusqlite::Connection;
+use std::path::PathBuf;
+use subxt::utils::AccountId32;
+use subxt::{OnlineClient, SubstrateConfig};
+
+#[derive(Parser)]
+#[command(about = "Indexes the wishlist and voting contracts' events into SQLite")]
+struct Cli {
+    /// WebSocket URL of a running `substrate-contracts-node`.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Path to the SQLite database to write events into; created if it
+    /// doesn't exist.
+    #[arg(long, default_value = "contract-events.db")]
+    db: PathBuf,
+
+    /// Address of the deployed voting contract (Task 3), SS58-encoded.
+    #[arg(long)]
+    voting_address: String,
+
+    /// Path to the voting contract's `.contract` bundle.
+    #[arg(long)]
+    voting_bundle: PathBuf,
+
+    /// Address of the deployed wishlist contract (Task 2), SS58-encoded.
+    #[arg(long)]
+    wishlist_address: String,
+
+    /// Path to the wishlist contract's `.contract` bundle.
+    #[arg(long)]
+    wishlist_bundle: PathBuf,
+}
+
+/// One of the contracts this indexer watches, paired with the
+/// transcoder that decodes its events.
+struct WatchedContract {
+    label: &'static str,
+    address: AccountId32,
+    transcoder: ContractMessageTranscoder,
+}
+
+fn load_watched_contract(label: &'static str, address: &str, bundle: &PathBuf) -> Result<WatchedContract> {
+    let address: AccountId32 = address.parse().map_err(|_| anyhow::anyhow!("invalid SS58 address for {label}: {address}"))?;
+    let transcoder = ContractMessageTranscoder::load(bundle)
+        .with_context(|| format!("failed to load ink metadata from the {label} bundle"))?;
+    Ok(WatchedContract { label, address, transcoder })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let watched = vec![
+        load_watched_contract("voting", &cli.voting_address, &cli.voting_bundle)?,
+        load_watched_contract("wishlist", &cli.wishlist_address, &cli.wishlist_bundle)?,
+    ];
+
+    let store = EventStore::open(&cli.db)?;
+
+    let api = OnlineClient::<SubstrateConfig>::from_url(&cli.url)
+        .await
+        .with_context(|| format!("failed to connect to {}", cli.url))?;
+
+    println!("indexing contract events into {}...", cli.db.display());
+    index_forever(api, &watched, &store).await
+}
+
+/// Subscribes to finalized blocks forever, recording every
+/// `Contracts.ContractEmitted` event whose contract address matches one
+/// of `watched` into `store`.
+async fn index_forever(api: OnlineClient<SubstrateConfig>, watched: &[WatchedContract], store: &EventStore) -> Result<()> {
+    let mut blocks = api.stream_blocks().await.context("failed to subscribe to finalized blocks")?;
+
+    while let Some(block) = blocks.next().await {
+        let block = match block {
+            Ok(block) => block,
+            Err(err) => {
+                eprintln!("skipping a block: {err}");
+                continue;
+            }
+        };
+        let block_number = block.number();
+        let block_hash = block.hash();
+        let Ok(at_block) = block.at().await else { continue };
+        let Ok(events) = at_block.events().fetch().await else { continue };
+
+        for event in events.iter() {
+            let Ok(event) = event else { continue };
+            if event.pallet_name() != "Contracts" || event.event_name() != "ContractEmitted" {
+                continue;
+            }
+            let Ok(emitted) = event.decode_as::<ContractEmitted>() else { continue };
+            let Some(contract) = watched.iter().find(|w| w.address == emitted.contract) else {
+                continue;
+            };
+            let Some(sig_topic) = event.topics().first() else {
+                continue;
+            };
+            let mut data = emitted.data.as_slice();
+            let Ok(decoded) = contract.transcoder.decode_contract_event(sig_topic, &mut data) else {
+                continue;
+            };
+            let event_name = match &decoded {
+                contract_transcode::Value::Map(map) => map.ident().unwrap_or_else(|| "unknown".to_string()),
+                _ => "unknown".to_string(),
+            };
+
+            store.record(IndexedEvent {
+                block_number,
+                block_hash: format!("{block_hash:?}"),
+                contract_label: contract.label,
+                contract_address: contract.address.to_string(),
+                event_name,
+                fields: decoded.to_string(),
+            })?;
+            println!("indexed {} event from {}", contract.label, contract.address);
+        }
+    }
+
+    Ok(())
+}
+
+use subxt::events::DecodeAsEvent;
+
+/// Mirrors `pallet_contracts::Event::ContractEmitted`'s fields.
+#[derive(scale_decode::DecodeAsType)]
+struct ContractEmitted {
+    contract: AccountId32,
+    data: Vec<u8>,
+}
+
+impl DecodeAsEvent for ContractEmitted {
+    fn is_event(pallet_name: &str, event_name: &str) -> bool {
+        pallet_name == "Contracts" && event_name == "ContractEmitted"
+    }
+}
+
+/// One decoded contract event, ready to write into or read back out of
+/// the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEvent {
+    pub block_number: u64,
+    pub block_hash: String,
+    pub contract_label: &'static str,
+    pub contract_address: String,
+    pub event_name: String,
+    pub fields: String,
+}
+
+/// SQLite-backed storage for indexed events, plus the small query API
+/// used both by this binary and by anything that wants to read the
+/// index back out afterward.
+pub struct EventStore {
+    conn: Connection,
+}
+
+impl EventStore {
+    pub fn open(path: &PathBuf) -> Result<Self> {
+        let conn = Connection::open(path).context("failed to open the event index database")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                block_number INTEGER NOT NULL,
+                block_hash TEXT NOT NULL,
+                contract_label TEXT NOT NULL,
+                contract_address TEXT NOT NULL,
+                event_name TEXT NOT NULL,
+                fields TEXT NOT NULL
+            )",
+            (),
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn record(&self, event: IndexedEvent) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO events (block_number, block_hash, contract_label, contract_address, event_name, fields)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (event.block_number, event.block_hash, event.contract_label, event.contract_address, event.event_name, event.fields),
+        )?;
+        Ok(())
+    }
+
+    /// All events recorded for a given contract label ("voting" or
+    /// "wishlist"), oldest first.
+    pub fn events_for_contract(&self, contract_label: &str) -> Result<Vec<IndexedEvent>> {
+        let mut statement = self.conn.prepare(
+            "SELECT block_number, block_hash, contract_label, contract_address, event_name, fields
+             FROM events WHERE contract_label = ?1 ORDER BY id ASC",
+        )?;
+        let rows = statement.query_map([contract_label], Self::row_to_event)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    /// All events with a given name (e.g. "VoteCast"), oldest first,
+    /// across every watched contract.
+    pub fn events_by_name(&self, event_name: &str) -> Result<Vec<IndexedEvent>> {
+        let mut statement = self.conn.prepare(
+            "SELECT block_number, block_hash, contract_label, contract_address, event_name, fields
+             FROM events WHERE event_name = ?1 ORDER BY id ASC",
+        )?;
+        let rows = statement.query_map([event_name], Self::row_to_event)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<IndexedEvent> {
+        Ok(IndexedEvent {
+            block_number: row.get(0)?,
+            block_hash: row.get(1)?,
+            contract_label: match row.get::<_, String>(2)?.as_str() {
+                "wishlist" => "wishlist",
+                _ => "voting",
+            },
+            contract_address: row.get(3)?,
+            event_name: row.get(4)?,
+            fields: row.get(5)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_event(contract_label: &'static str, event_name: &str) -> IndexedEvent {
+        IndexedEvent {
+            block_number: 1,
+            block_hash: "0xabc".to_string(),
+            contract_label,
+            contract_address: "5Grwva...".to_string(),
+            event_name: event_name.to_string(),
+            fields: "{ voter: 5Grwva... }".to_string(),
+        }
+    }
+
+    #[test]
+    fn recorded_events_can_be_queried_back_by_contract_and_by_name() {
+        let store = EventStore::open(&PathBuf::from(":memory:")).unwrap();
+        store.record(sample_event("voting", "VoteCast")).unwrap();
+        store.record(sample_event("wishlist", "ItemAdded")).unwrap();
+
+        assert_eq!(store.events_for_contract("voting").unwrap().len(), 1);
+        assert_eq!(store.events_by_name("ItemAdded").unwrap().len(), 1);
+        assert_eq!(store.events_by_name("VoteCast").unwrap()[0].contract_label, "voting");
+    }
+}
@@ -0,0 +1,183 @@
+//! Wallet CLI for Task 3's voting contract: the complete client-side
+//! pipeline in one binary - load a key the way Task 37 derives one,
+//! construct a `cast_vote`-shaped `Contracts.call` extrinsic, sign it,
+//! submit it over subxt, and watch it through inclusion and finalization
+//! instead of stopping at "submitted".
+//!
+//! Like Task 6's client, this talks to `pallet_contracts` through
+//! subxt's dynamic API so it works against any `substrate-contracts-node`
+//! without regenerating code from its metadata first. Signing goes
+//! through Task 37's `Keypair` rather than `subxt-signer`, so the same
+//! mnemonic/derivation-path machinery that generates a key is what signs
+//! with it.
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser};
+use contract_transcode::ContractMessageTranscoder;
+use mnemonic_and_hd_key_derivation::{Keypair, Scheme};
+use scale_value::Value;
+use std::path::PathBuf;
+use subxt::config::Config;
+use subxt::tx::Signer;
+use subxt::utils::{AccountId32, MultiSignature};
+use subxt::{dynamic, OnlineClient, SubstrateConfig};
+
+const GAS_LIMIT_REF_TIME: u64 = 10_000_000_000;
+const GAS_LIMIT_PROOF_SIZE: u64 = 1_000_000;
+
+#[derive(Parser)]
+#[command(about = "Signs and submits a vote on the Task 3 voting contract")]
+struct Cli {
+    /// WebSocket URL of a running `substrate-contracts-node`. Ignored with
+    /// `--spawn-node`, which picks its own.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Spawn a `substrate-contracts-node` for this run instead of
+    /// connecting to one already running at `--url`.
+    #[arg(long)]
+    spawn_node: bool,
+
+    /// Address of the deployed voting contract, SS58-encoded.
+    #[arg(long)]
+    contract_address: String,
+
+    /// Path to the voting contract's `.contract` bundle.
+    #[arg(long)]
+    bundle: PathBuf,
+
+    /// Candidate id to vote for.
+    #[arg(long)]
+    candidate_id: u32,
+
+    /// Conviction multiplier passed to the contract's `vote` message.
+    #[arg(long, default_value_t = 0)]
+    conviction: u8,
+
+    #[command(flatten)]
+    key: KeyArgs,
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct KeyArgs {
+    /// BIP-39 mnemonic phrase to sign with.
+    #[arg(long)]
+    phrase: Option<String>,
+
+    /// A well-known `--dev` account path (e.g. "//Alice") instead of a
+    /// real phrase.
+    #[arg(long)]
+    dev: Option<String>,
+
+    /// `//hard/soft` path to derive from `--phrase` before signing;
+    /// ignored with `--dev`, which already names its own path.
+    #[arg(long, default_value = "")]
+    derive: String,
+}
+
+fn load_keypair(key: &KeyArgs) -> Result<Keypair> {
+    if let Some(path) = &key.dev {
+        return Keypair::dev_account(Scheme::Sr25519, path).map_err(|err| anyhow::anyhow!("failed to derive dev account {path}: {err:?}"));
+    }
+    let phrase = key.phrase.as_ref().expect("clap enforces exactly one of --phrase/--dev");
+    let keypair = Keypair::from_phrase(Scheme::Sr25519, phrase, None).map_err(|err| anyhow::anyhow!("invalid mnemonic phrase: {err:?}"))?;
+    if key.derive.is_empty() {
+        Ok(keypair)
+    } else {
+        keypair.derive(&key.derive).map_err(|err| anyhow::anyhow!("failed to derive path {}: {err:?}", key.derive))
+    }
+}
+
+/// Adapts Task 37's [`Keypair`] to subxt's [`Signer`] trait, so the same
+/// wrapper that derives a key from a mnemonic is what signs the
+/// extrinsic - no `subxt-signer` keys involved.
+struct WalletSigner(Keypair);
+
+impl Signer<SubstrateConfig> for WalletSigner {
+    fn account_id(&self) -> <SubstrateConfig as Config>::AccountId {
+        AccountId32::from(self.0.public_bytes())
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> <SubstrateConfig as Config>::Signature {
+        match self.0 {
+            Keypair::Sr25519(_) => MultiSignature::Sr25519(self.0.sign(signer_payload)),
+            Keypair::Ed25519(_) => MultiSignature::Ed25519(self.0.sign(signer_payload)),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let contract: AccountId32 = cli
+        .contract_address
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid SS58 contract address: {}", cli.contract_address))?;
+    let transcoder = ContractMessageTranscoder::load(&cli.bundle).context("failed to load ink metadata from the bundle")?;
+    let signer = WalletSigner(load_keypair(&cli.key)?);
+
+    let _node;
+    let url = if cli.spawn_node {
+        println!("spawning a substrate-contracts-node...");
+        _node = contracts_node_harness::NodeHandle::spawn_default()
+            .context("failed to spawn substrate-contracts-node")?;
+        _node.ws_url().to_string()
+    } else {
+        cli.url.clone()
+    };
+
+    let api = OnlineClient::<SubstrateConfig>::from_url(&url)
+        .await
+        .with_context(|| format!("failed to connect to {url}"))?;
+
+    let message_data = transcoder.encode("vote", [cli.candidate_id.to_string(), cli.conviction.to_string()])?;
+    let tx = vote_tx(&contract, message_data);
+
+    println!("submitting vote for candidate {}...", cli.candidate_id);
+    submit_and_watch(&api, &signer, &tx).await
+}
+
+fn vote_tx(contract: &AccountId32, message_data: Vec<u8>) -> subxt::tx::DynamicPayload<Vec<Value>> {
+    let dest = Value::unnamed_variant("Id", [Value::from_bytes(contract.0)]);
+    let call_data: Vec<Value> = vec![
+        dest,
+        Value::u128(0),
+        Value::named_composite([
+            ("ref_time".to_string(), Value::u128(GAS_LIMIT_REF_TIME as u128)),
+            ("proof_size".to_string(), Value::u128(GAS_LIMIT_PROOF_SIZE as u128)),
+        ]),
+        Value::unnamed_variant("None", Vec::<Value>::new()),
+        Value::from_bytes(message_data),
+    ];
+    dynamic::tx("Contracts", "call", call_data)
+}
+
+/// Signs and submits `tx`, printing progress as it moves from broadcast
+/// to included-in-block to finalized, then confirms the extrinsic itself
+/// dispatched successfully rather than just making it into a block.
+async fn submit_and_watch(api: &OnlineClient<SubstrateConfig>, signer: &WalletSigner, tx: &subxt::tx::DynamicPayload<Vec<Value>>) -> Result<()> {
+    let mut progress = api
+        .transactions()
+        .await
+        .context("failed to build a transactions client")?
+        .sign_and_submit_then_watch_default(tx, signer)
+        .await
+        .context("failed to submit the vote extrinsic")?;
+
+    while let Some(status) = progress.next().await {
+        let status = status.context("error while watching the extrinsic's progress")?;
+        if let Some(in_block) = status.as_in_block() {
+            println!("included in block {:?}", in_block.block_hash());
+        }
+        if let Some(finalized) = status.as_finalized() {
+            println!("finalized in block {:?}", finalized.block_hash());
+            finalized.wait_for_success().await.context("vote extrinsic was included but failed to dispatch")?;
+            println!("vote dispatched successfully");
+            return Ok(());
+        }
+    }
+
+    bail!("extrinsic stream ended before the vote was finalized")
+}
@@ -0,0 +1,205 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Dispatches a real runtime call - a balance transfer or a `System::remark`
+/// - through `pallet-contracts`' `call_runtime` host function, bridging the
+/// contract and pallet layers the rest of this repo's tasks keep apart.
+///
+/// ## Runtime-side configuration notes
+///
+/// [`RuntimeCall`] mirrors the shape `call_runtime` SCALE-decodes its
+/// argument into on the target chain - but SCALE enums encode only a
+/// discriminant index, not a variant name, so the `#[codec(index = ..)]`
+/// on every variant here has to match the target runtime's actual pallet
+/// order (`construct_runtime!`) and call order exactly, or the host
+/// decodes a completely different call than the one this contract meant
+/// to send. None of that can be checked from this crate; whoever deploys
+/// this contract owns keeping these indices in sync with the runtime.
+/// `call_runtime` itself is also gated behind
+/// `pallet_contracts::Config::UnsafeUnstableInterface` and, in
+/// production, a `CallFilter` that allow-lists the exact calls a
+/// contract may dispatch - without both, every message below fails
+/// before the runtime call is even attempted.
+#[ink::contract]
+pub mod call_runtime_bridge {
+    use ink::prelude::vec::Vec;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum BridgeError {
+        NotOwner,
+        RuntimeCallFailed,
+    }
+
+    /// Mirrors the target runtime's own `RuntimeCall` enum, as far as the
+    /// two calls this contract dispatches. See the module doc comment for
+    /// why the `#[codec(index = ..)]` values matter.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RuntimeCall {
+        /// Matches `pallet_balances`' index in this worked example's
+        /// target runtime.
+        #[codec(index = 4)]
+        Balances(BalancesCall),
+        /// Matches `frame_system`'s index, always `0` by convention.
+        #[codec(index = 0)]
+        System(SystemCall),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum BalancesCall {
+        #[codec(index = 0)]
+        Transfer { dest: AccountId, value: Balance },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum SystemCall {
+        #[codec(index = 0)]
+        Remark { remark: Vec<u8> },
+    }
+
+    #[ink(storage)]
+    pub struct CallRuntimeBridge {
+        owner: AccountId,
+        last_outcome: Option<bool>,
+    }
+
+    impl CallRuntimeBridge {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { owner: Self::env().caller(), last_outcome: None }
+        }
+
+        /// Dispatches `Balances::transfer { dest, value }`.
+        #[ink(message)]
+        pub fn dispatch_balance_transfer(&mut self, dest: AccountId, value: Balance) -> Result<(), BridgeError> {
+            let call = self.prepare_transfer(dest, value)?;
+            self.dispatch(&call)
+        }
+
+        /// Dispatches `System::remark { remark }`.
+        #[ink(message)]
+        pub fn dispatch_remark(&mut self, remark: Vec<u8>) -> Result<(), BridgeError> {
+            let call = self.prepare_remark(remark)?;
+            self.dispatch(&call)
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn last_outcome(&self) -> Option<bool> {
+            self.last_outcome
+        }
+
+        /// Owner check plus call construction, kept apart from
+        /// [`Self::dispatch`] so it can be exercised by `#[ink::test]`
+        /// without touching `self.env().call_runtime`, which panics in
+        /// the off-chain test environment.
+        fn prepare_transfer(&self, dest: AccountId, value: Balance) -> Result<RuntimeCall, BridgeError> {
+            self.ensure_owner()?;
+            Ok(RuntimeCall::Balances(BalancesCall::Transfer { dest, value }))
+        }
+
+        fn prepare_remark(&self, remark: Vec<u8>) -> Result<RuntimeCall, BridgeError> {
+            self.ensure_owner()?;
+            Ok(RuntimeCall::System(SystemCall::Remark { remark }))
+        }
+
+        /// The only place this contract calls `self.env().call_runtime`;
+        /// records whether it succeeded so a caller can poll
+        /// [`Self::last_outcome`] afterwards.
+        fn dispatch(&mut self, call: &RuntimeCall) -> Result<(), BridgeError> {
+            let outcome = self.env().call_runtime(call);
+            self.last_outcome = Some(outcome.is_ok());
+            outcome.map_err(|_| BridgeError::RuntimeCallFailed)
+        }
+
+        fn ensure_owner(&self) -> Result<(), BridgeError> {
+            if self.env().caller() != self.owner {
+                return Err(BridgeError::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    impl Default for CallRuntimeBridge {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<Environment> {
+            ink::env::test::default_accounts::<Environment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<Environment>(caller);
+        }
+
+        #[ink::test]
+        fn prepare_transfer_rejects_a_non_owner() {
+            let accounts = accounts();
+            let bridge = CallRuntimeBridge::new();
+            set_caller(accounts.bob);
+            assert_eq!(bridge.prepare_transfer(accounts.charlie, 100), Err(BridgeError::NotOwner));
+        }
+
+        #[ink::test]
+        fn prepare_transfer_builds_the_balances_transfer_call() {
+            let accounts = accounts();
+            let bridge = CallRuntimeBridge::new();
+            let call = bridge.prepare_transfer(accounts.bob, 100).unwrap();
+            assert_eq!(call, RuntimeCall::Balances(BalancesCall::Transfer { dest: accounts.bob, value: 100 }));
+        }
+
+        #[ink::test]
+        fn prepare_transfer_call_encodes_with_the_expected_pallet_and_call_indices() {
+            use scale::Encode;
+
+            let accounts = accounts();
+            let bridge = CallRuntimeBridge::new();
+            let call = bridge.prepare_transfer(accounts.bob, 100).unwrap();
+
+            // Stands in for the pallet-contracts decode a live runtime would
+            // do: pallet index 4, call index 0, then the call's own fields.
+            let mut expected = Vec::from([4u8, 0u8]);
+            expected.extend(accounts.bob.encode());
+            expected.extend(100u128.encode());
+            assert_eq!(call.encode(), expected);
+        }
+
+        #[ink::test]
+        fn prepare_remark_rejects_a_non_owner() {
+            let accounts = accounts();
+            let bridge = CallRuntimeBridge::new();
+            set_caller(accounts.bob);
+            assert_eq!(bridge.prepare_remark(Vec::from(*b"hi")), Err(BridgeError::NotOwner));
+        }
+
+        #[ink::test]
+        fn prepare_remark_call_encodes_with_the_expected_pallet_and_call_indices() {
+            use scale::Encode;
+
+            let bridge = CallRuntimeBridge::new();
+            let call = bridge.prepare_remark(Vec::from(*b"hi")).unwrap();
+
+            let mut expected = Vec::from([0u8, 0u8]);
+            expected.extend(Vec::from(*b"hi").encode());
+            assert_eq!(call.encode(), expected);
+        }
+
+        #[ink::test]
+        fn last_outcome_starts_unset() {
+            let bridge = CallRuntimeBridge::new();
+            assert_eq!(bridge.last_outcome(), None);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{AccountId, Balance, Runtime, RuntimeCall, RuntimeOrigin, System, VotingApi};
+use frame_support::{assert_ok, sp_runtime::{traits::Dispatchable, BuildStorage}, traits::Hooks};
+
+const ADMIN: AccountId = 1;
+const ALICE: AccountId = 2;
+const BOB: AccountId = 3;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_voting::GenesisConfig::<Runtime> {
+        admin: Some(ADMIN),
+        candidates: alloc::vec![b"Alice".to_vec(), b"Bob".to_vec()],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn dispatch(call: RuntimeCall, who: AccountId) {
+    assert_ok!(call.dispatch(RuntimeOrigin::signed(who)));
+}
+
+#[test]
+fn a_full_election_runs_through_dispatched_runtime_calls() {
+    new_test_ext().execute_with(|| {
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::register {}), ALICE);
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::register {}), BOB);
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::start_voting {}), ADMIN);
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::vote { candidate_id: 0 }), ALICE);
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::vote { candidate_id: 0 }), BOB);
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::finalize {}), ADMIN);
+
+        assert_eq!(<Runtime as VotingApi>::tally(0), 2);
+        assert_eq!(<Runtime as VotingApi>::tally(1), 0);
+    });
+}
+
+#[test]
+fn a_non_admin_cannot_dispatch_start_voting() {
+    use frame_support::assert_noop;
+
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            RuntimeCall::Voting(pallet_voting::Call::start_voting {}).dispatch(RuntimeOrigin::signed(ALICE)),
+            pallet_voting::Error::<Runtime>::NotAdmin
+        );
+    });
+}
+
+#[test]
+fn balances_and_voting_coexist_in_the_same_runtime() {
+    use pallet_balances::Call as BalancesCall;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(RuntimeCall::Balances(BalancesCall::force_set_balance {
+            who: ALICE,
+            new_free: 1_000 as Balance
+        })
+        .dispatch(frame_system::RawOrigin::Root.into()));
+        assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(ALICE), 1_000);
+    });
+}
+
+#[test]
+fn a_scheduled_call_runs_on_its_target_block() {
+    new_test_ext().execute_with(|| {
+        // The admin schedules their own `start_voting` for block 2 instead
+        // of dispatching it directly - the scheduled call runs with
+        // whatever origin scheduled it, so it still has to pass
+        // `start_voting`'s own admin check once it fires.
+        pallet_scheduler::Pallet::<Runtime>::schedule(
+            RuntimeOrigin::signed(ADMIN),
+            2,
+            None,
+            0,
+            alloc::boxed::Box::new(RuntimeCall::Voting(pallet_voting::Call::start_voting {})),
+        )
+        .unwrap();
+        dispatch(RuntimeCall::Voting(pallet_voting::Call::register {}), ALICE);
+        assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::RegistrationOpen);
+
+        System::set_block_number(2);
+        pallet_scheduler::Pallet::<Runtime>::on_initialize(2);
+
+        assert_eq!(pallet_voting::CurrentPhase::<Runtime>::get(), pallet_voting::Phase::VotingActive);
+    });
+}
@@ -0,0 +1,83 @@
+//! Benchmarks for `pallet_voting`, run via `cargo test --features
+//! runtime-benchmarks` (through [`impl_benchmark_test_suite`]'s sanity
+//! checks) or, on a real chain, through `frame-benchmarking-cli`'s
+//! `benchmark pallet` to produce the numbers `weights.rs` stands in for.
+//! Each benchmark pre-fills storage to the worst case the pallet's
+//! `MaxVoters`/`MaxCandidates` bounds allow, since that's the case a
+//! weight has to cover - see the README for why "however many voters
+//! happen to be registered right now" isn't good enough.
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+    use frame_support::traits::{ConstU32, Get};
+
+    /// Worst case: `MaxVoters - 1` voters already registered, so this
+    /// call's `RegisteredVoters` write lands in an as-full-as-possible
+    /// map.
+    #[benchmark]
+    fn register() {
+        let max_voters = T::MaxVoters::get();
+        for i in 0..max_voters.saturating_sub(1) {
+            let voter: T::AccountId = account("voter", i, 0);
+            RegisteredVoters::<T>::insert(&voter, ());
+        }
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        register(RawOrigin::Signed(caller.clone()));
+
+        assert!(RegisteredVoters::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn start_voting() {
+        let admin: T::AccountId = whitelisted_caller();
+        Admin::<T>::put(&admin);
+
+        #[extrinsic_call]
+        start_voting(RawOrigin::Signed(admin));
+
+        assert_eq!(CurrentPhase::<T>::get(), Phase::VotingActive);
+    }
+
+    /// Worst case: `MaxCandidates` candidates on the ballot, voting for
+    /// the last one - the `candidate_id < CandidateCount` check and the
+    /// `VoteCounts` write both scale with how many candidates exist.
+    #[benchmark]
+    fn vote() {
+        let max_candidates = T::MaxCandidates::get();
+        for id in 0..max_candidates {
+            let name: BoundedVec<u8, ConstU32<128>> =
+                BoundedVec::try_from(b"candidate".to_vec()).expect("fits in 128 bytes");
+            Candidates::<T>::insert(id, name);
+        }
+        CandidateCount::<T>::put(max_candidates);
+        CurrentPhase::<T>::put(Phase::VotingActive);
+        let caller: T::AccountId = whitelisted_caller();
+        RegisteredVoters::<T>::insert(&caller, ());
+
+        #[extrinsic_call]
+        vote(RawOrigin::Signed(caller.clone()), max_candidates - 1);
+
+        assert!(Voted::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn finalize() {
+        let admin: T::AccountId = whitelisted_caller();
+        Admin::<T>::put(&admin);
+        CurrentPhase::<T>::put(Phase::VotingActive);
+
+        #[extrinsic_call]
+        finalize(RawOrigin::Signed(admin));
+
+        assert_eq!(CurrentPhase::<T>::get(), Phase::ResultsFinalized);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,213 @@
+//! The same election flow as Task 1 (plain Rust) and Task 3 (ink!), now as
+//! a FRAME pallet: storage items replace struct fields, dispatchables
+//! replace `&mut self` methods, and `DispatchResult`/`Error<T>` replace
+//! `Result<(), VoteError>`. `benchmarking.rs`/`weights.rs` add the
+//! `#[benchmarks]`/`WeightInfo` machinery described in the README.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use crate::weights::WeightInfo as _;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+        type WeightInfo: crate::weights::WeightInfo;
+
+        /// The most voters `register` is benchmarked to support; enforced
+        /// so `vote`/`register`'s weight never undercounts how full
+        /// `RegisteredVoters` actually is.
+        #[pallet::constant]
+        type MaxVoters: Get<u32>;
+
+        /// The most candidates a round's genesis config may seed; `vote`
+        /// is benchmarked against a full ballot of this size.
+        #[pallet::constant]
+        type MaxCandidates: Get<u32>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen, Default)]
+    pub enum Phase {
+        #[default]
+        RegistrationOpen,
+        VotingActive,
+        ResultsFinalized,
+    }
+
+    /// The account allowed to drive phase transitions; set at genesis.
+    #[pallet::storage]
+    pub type Admin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    #[pallet::storage]
+    pub type CurrentPhase<T: Config> = StorageValue<_, Phase, ValueQuery>;
+
+    #[pallet::storage]
+    pub type Round<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Candidate names for the current round, indexed by candidate id.
+    #[pallet::storage]
+    pub type Candidates<T: Config> =
+        StorageMap<_, Twox64Concat, u32, BoundedVec<u8, ConstU32<128>>, OptionQuery>;
+
+    #[pallet::storage]
+    pub type CandidateCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub type RegisteredVoters<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// How many voters are registered - tracked separately so `register`
+    /// can enforce `MaxVoters` without an O(n) count over the map.
+    #[pallet::storage]
+    pub type VoterCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub type Voted<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    #[pallet::storage]
+    pub type VoteCounts<T: Config> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        pub admin: Option<T::AccountId>,
+        pub candidates: Vec<Vec<u8>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            if let Some(admin) = &self.admin {
+                Admin::<T>::put(admin);
+            }
+            assert!(
+                self.candidates.len() as u32 <= T::MaxCandidates::get(),
+                "more candidates in genesis than MaxCandidates allows"
+            );
+            for (id, name) in self.candidates.iter().enumerate() {
+                let bounded: BoundedVec<u8, ConstU32<128>> = name
+                    .clone()
+                    .try_into()
+                    .expect("candidate name longer than 128 bytes");
+                Candidates::<T>::insert(id as u32, bounded);
+            }
+            CandidateCount::<T>::put(self.candidates.len() as u32);
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        VoterRegistered { voter: T::AccountId },
+        VoteCast { voter: T::AccountId, candidate_id: u32 },
+        RoundFinalized { round: u32 },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        NotAdmin,
+        WrongPhase,
+        AlreadyRegistered,
+        NotRegistered,
+        AlreadyVoted,
+        InvalidCandidate,
+        TooManyVoters,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::register())]
+        pub fn register(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                CurrentPhase::<T>::get() == Phase::RegistrationOpen,
+                Error::<T>::WrongPhase
+            );
+            ensure!(
+                !RegisteredVoters::<T>::contains_key(&who),
+                Error::<T>::AlreadyRegistered
+            );
+            let voter_count = VoterCount::<T>::get();
+            ensure!(voter_count < T::MaxVoters::get(), Error::<T>::TooManyVoters);
+            RegisteredVoters::<T>::insert(&who, ());
+            VoterCount::<T>::put(voter_count + 1);
+            Self::deposit_event(Event::VoterRegistered { voter: who });
+            Ok(())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::start_voting())]
+        pub fn start_voting(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Admin::<T>::get().as_ref() == Some(&who), Error::<T>::NotAdmin);
+            ensure!(
+                CurrentPhase::<T>::get() == Phase::RegistrationOpen,
+                Error::<T>::WrongPhase
+            );
+            CurrentPhase::<T>::put(Phase::VotingActive);
+            Ok(())
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::vote())]
+        pub fn vote(origin: OriginFor<T>, candidate_id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                CurrentPhase::<T>::get() == Phase::VotingActive,
+                Error::<T>::WrongPhase
+            );
+            ensure!(
+                RegisteredVoters::<T>::contains_key(&who),
+                Error::<T>::NotRegistered
+            );
+            ensure!(!Voted::<T>::contains_key(&who), Error::<T>::AlreadyVoted);
+            ensure!(
+                candidate_id < CandidateCount::<T>::get(),
+                Error::<T>::InvalidCandidate
+            );
+            Voted::<T>::insert(&who, ());
+            VoteCounts::<T>::mutate(candidate_id, |count| *count += 1);
+            Self::deposit_event(Event::VoteCast { voter: who, candidate_id });
+            Ok(())
+        }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::finalize())]
+        pub fn finalize(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Admin::<T>::get().as_ref() == Some(&who), Error::<T>::NotAdmin);
+            ensure!(
+                CurrentPhase::<T>::get() == Phase::VotingActive,
+                Error::<T>::WrongPhase
+            );
+            CurrentPhase::<T>::put(Phase::ResultsFinalized);
+            let round = Round::<T>::get();
+            Self::deposit_event(Event::RoundFinalized { round });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Read-only vote count for `candidate_id`, mirroring the ink!
+        /// contract's `tally` message.
+        pub fn tally(candidate_id: u32) -> u32 {
+            VoteCounts::<T>::get(candidate_id)
+        }
+    }
+}
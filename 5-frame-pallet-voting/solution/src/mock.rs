@@ -0,0 +1,43 @@
+//! Minimal runtime wiring `pallet-voting` up next to `frame_system` so the
+//! dispatchables in `tests.rs` can run against real `TestExternalities`.
+use crate as pallet_voting;
+use frame_support::{construct_runtime, derive_impl, sp_runtime::BuildStorage};
+
+construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Voting: pallet_voting,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = frame_system::mocking::MockBlock<Test>;
+}
+
+impl pallet_voting::Config for Test {
+    type WeightInfo = ();
+    type MaxVoters = frame_support::traits::ConstU32<1_000>;
+    type MaxCandidates = frame_support::traits::ConstU32<16>;
+}
+
+pub const ADMIN: u64 = 1;
+pub const ALICE: u64 = 2;
+pub const BOB: u64 = 3;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_voting::GenesisConfig::<Test> {
+        admin: Some(ADMIN),
+        candidates: vec![b"Alice".to_vec(), b"Bob".to_vec()],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
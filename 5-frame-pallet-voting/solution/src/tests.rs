@@ -0,0 +1,135 @@
+use crate::mock::*;
+use crate::pallet::{Error, Event, Phase};
+use crate::weights::WeightInfo as _;
+use frame_support::traits::Get;
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn registration_then_voting_happy_path() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::register(RuntimeOrigin::signed(ALICE)));
+        assert_ok!(Voting::start_voting(RuntimeOrigin::signed(ADMIN)));
+        assert_ok!(Voting::vote(RuntimeOrigin::signed(ALICE), 0));
+        assert_eq!(crate::Pallet::<Test>::tally(0), 1);
+
+        assert_ok!(Voting::finalize(RuntimeOrigin::signed(ADMIN)));
+        System::assert_has_event(Event::RoundFinalized { round: 0 }.into());
+    });
+}
+
+#[test]
+fn only_admin_can_start_voting() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Voting::start_voting(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::NotAdmin
+        );
+    });
+}
+
+#[test]
+fn register_rejects_once_voting_has_started() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::start_voting(RuntimeOrigin::signed(ADMIN)));
+        assert_noop!(
+            Voting::register(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::WrongPhase
+        );
+    });
+}
+
+#[test]
+fn cannot_register_twice() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::register(RuntimeOrigin::signed(ALICE)));
+        assert_noop!(
+            Voting::register(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::AlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn unregistered_accounts_cannot_vote() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::start_voting(RuntimeOrigin::signed(ADMIN)));
+        assert_noop!(
+            Voting::vote(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::NotRegistered
+        );
+    });
+}
+
+#[test]
+fn cannot_vote_twice() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::register(RuntimeOrigin::signed(ALICE)));
+        assert_ok!(Voting::start_voting(RuntimeOrigin::signed(ADMIN)));
+        assert_ok!(Voting::vote(RuntimeOrigin::signed(ALICE), 0));
+        assert_noop!(
+            Voting::vote(RuntimeOrigin::signed(ALICE), 1),
+            Error::<Test>::AlreadyVoted
+        );
+    });
+}
+
+#[test]
+fn vote_rejects_unknown_candidate() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Voting::register(RuntimeOrigin::signed(ALICE)));
+        assert_ok!(Voting::start_voting(RuntimeOrigin::signed(ADMIN)));
+        assert_noop!(
+            Voting::vote(RuntimeOrigin::signed(ALICE), 99),
+            Error::<Test>::InvalidCandidate
+        );
+    });
+}
+
+#[test]
+fn finalize_requires_voting_to_be_active() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Voting::finalize(RuntimeOrigin::signed(ADMIN)),
+            Error::<Test>::WrongPhase
+        );
+    });
+}
+
+#[test]
+fn genesis_config_seeds_admin_and_candidates() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(crate::pallet::CandidateCount::<Test>::get(), 2);
+        assert_eq!(crate::pallet::Admin::<Test>::get(), Some(ADMIN));
+        assert_eq!(crate::pallet::CurrentPhase::<Test>::get(), Phase::RegistrationOpen);
+    });
+}
+
+#[test]
+fn register_rejects_once_max_voters_reached() {
+    new_test_ext().execute_with(|| {
+        let max_voters: u32 = <Test as crate::Config>::MaxVoters::get();
+        for account in 0..max_voters as u64 {
+            assert_ok!(Voting::register(RuntimeOrigin::signed(1_000 + account)));
+        }
+        assert_eq!(crate::pallet::VoterCount::<Test>::get(), max_voters);
+        assert_noop!(
+            Voting::register(RuntimeOrigin::signed(ALICE)),
+            Error::<Test>::TooManyVoters
+        );
+    });
+}
+
+#[test]
+fn call_weights_fit_in_a_block() {
+    let block_weights: frame_system::limits::BlockWeights =
+        <Test as frame_system::Config>::BlockWeights::get();
+    let max_block = block_weights.max_block;
+    for weight in [
+        <Test as crate::Config>::WeightInfo::register(),
+        <Test as crate::Config>::WeightInfo::start_voting(),
+        <Test as crate::Config>::WeightInfo::vote(),
+        <Test as crate::Config>::WeightInfo::finalize(),
+    ] {
+        assert!(weight.all_lte(max_block));
+    }
+}
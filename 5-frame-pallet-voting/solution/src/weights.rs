@@ -0,0 +1,82 @@
+//! Weight functions for `pallet_voting`.
+//!
+//! Generated by running the benchmarks in `benchmarking.rs` through
+//! `frame-benchmarking-cli`'s `benchmark pallet` and reformatted by its
+//! `--template` output - the numbers below are hand-picked stand-ins for
+//! what that run would measure on real hardware, since this exercise has
+//! no node binary to run the benchmark CLI against. See the README for
+//! why the shape of this file (a `WeightInfo` trait, a `SubstrateWeight<T>`
+//! using `T::DbWeight`, and a `()` fallback using `RocksDbWeight` directly)
+//! matches what the real tool produces.
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_voting`.
+pub trait WeightInfo {
+    fn register() -> Weight;
+    fn start_voting() -> Weight;
+    fn vote() -> Weight;
+    fn finalize() -> Weight;
+}
+
+/// Weights for `pallet_voting` using the runtime's configured `DbWeight`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Worst case: `MaxVoters - 1` voters already registered.
+    fn register() -> Weight {
+        Weight::from_parts(12_345_000, 3_593)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    fn start_voting() -> Weight {
+        Weight::from_parts(8_210_000, 1_489)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    /// Worst case: `MaxCandidates` candidates on the ballot.
+    fn vote() -> Weight {
+        Weight::from_parts(14_980_000, 4_102)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    fn finalize() -> Weight {
+        Weight::from_parts(7_640_000, 1_489)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+}
+
+/// Fallback for runtimes that haven't generated their own weights yet -
+/// uses `RocksDbWeight` directly rather than a configured `DbWeight`.
+impl WeightInfo for () {
+    fn register() -> Weight {
+        Weight::from_parts(12_345_000, 3_593)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn start_voting() -> Weight {
+        Weight::from_parts(8_210_000, 1_489)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn vote() -> Weight {
+        Weight::from_parts(14_980_000, 4_102)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn finalize() -> Weight {
+        Weight::from_parts(7_640_000, 1_489)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+}
@@ -0,0 +1,261 @@
+//! Demo client for Task 3's voting contract: connects to a running
+//! `substrate-contracts-node`, uploads and instantiates the contract,
+//! registers two dev accounts as voters, runs a round of voting, and
+//! prints every contract event it sees along the way.
+//!
+//! This talks to `pallet_contracts` through subxt's *dynamic* API rather
+//! than macro-generated static types: there's no `Config` tied to a
+//! specific node build, so the binary works against any
+//! `substrate-contracts-node` without regenerating code from its metadata
+//! first. Message/constructor arguments are encoded with the same
+//! `contract-transcode` crate `cargo contract` itself uses, driven by the
+//! `.contract` bundle built from `3-voting-contract/solution`.
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use contract_transcode::ContractMessageTranscoder;
+use scale_decode::DecodeAsType;
+use scale_value::Value;
+use std::path::PathBuf;
+use subxt::dynamic;
+use subxt::events::DecodeAsEvent;
+use subxt::utils::AccountId32;
+use subxt::{OnlineClient, SubstrateConfig};
+use subxt_signer::sr25519::{dev, Keypair};
+
+/// A generous fixed weight limit for every call in this demo; a production
+/// client would dry-run each call first and use the returned weight instead.
+const GAS_LIMIT_REF_TIME: u64 = 10_000_000_000;
+const GAS_LIMIT_PROOF_SIZE: u64 = 1_000_000;
+
+#[derive(Parser)]
+#[command(about = "Drives the Task 3 voting contract over subxt")]
+struct Cli {
+    /// WebSocket URL of a running `substrate-contracts-node`. Ignored with
+    /// `--spawn-node`, which picks its own.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Spawn a `substrate-contracts-node` for this run instead of
+    /// connecting to one already running at `--url`.
+    #[arg(long)]
+    spawn_node: bool,
+
+    /// Path to the `.contract` bundle built by `cargo contract build` for
+    /// `3-voting-contract/solution`.
+    #[arg(long)]
+    bundle: PathBuf,
+}
+
+/// Mirrors `pallet_contracts::Event::Instantiated`'s fields we care about.
+#[derive(DecodeAsType)]
+struct Instantiated {
+    #[allow(dead_code)]
+    deployer: AccountId32,
+    contract: AccountId32,
+}
+
+impl DecodeAsEvent for Instantiated {
+    fn is_event(pallet_name: &str, event_name: &str) -> bool {
+        pallet_name == "Contracts" && event_name == "Instantiated"
+    }
+}
+
+/// Mirrors `pallet_contracts::Event::ContractEmitted`'s fields.
+#[derive(DecodeAsType)]
+struct ContractEmitted {
+    contract: AccountId32,
+    data: Vec<u8>,
+}
+
+impl DecodeAsEvent for ContractEmitted {
+    fn is_event(pallet_name: &str, event_name: &str) -> bool {
+        pallet_name == "Contracts" && event_name == "ContractEmitted"
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let metadata = contract_metadata::ContractMetadata::load(&cli.bundle)
+        .context("failed to load the .contract bundle")?;
+    let code = metadata
+        .source
+        .wasm
+        .ok_or_else(|| {
+            anyhow!("bundle has no embedded wasm - was it built with `cargo contract build`?")
+        })?
+        .0;
+    let transcoder = ContractMessageTranscoder::load(&cli.bundle)
+        .context("failed to load ink metadata from the .contract bundle")?;
+
+    let _node;
+    let url = if cli.spawn_node {
+        println!("spawning a substrate-contracts-node...");
+        _node = contracts_node_harness::NodeHandle::spawn_default()
+            .context("failed to spawn substrate-contracts-node")?;
+        _node.ws_url().to_string()
+    } else {
+        cli.url.clone()
+    };
+
+    let api = OnlineClient::<SubstrateConfig>::from_url(&url)
+        .await
+        .with_context(|| format!("failed to connect to {url}"))?;
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    println!("watching for contract events...");
+    let watcher = {
+        let api = api.clone();
+        tokio::spawn(async move { watch_events(api).await })
+    };
+
+    println!("instantiating the voting contract...");
+    let ctor_data = transcoder.encode(
+        "new",
+        ["[\"Alice the candidate\", \"Bob the candidate\"]"],
+    )?;
+    let contract = instantiate(&api, &alice, code, ctor_data).await?;
+    println!("contract instantiated at {contract}");
+
+    println!("registering alice and bob as voters...");
+    call(&api, &alice, &contract, transcoder.encode("register", Vec::<String>::new())?).await?;
+    call(&api, &bob, &contract, transcoder.encode("register", Vec::<String>::new())?).await?;
+
+    println!("starting voting...");
+    call(
+        &api,
+        &alice,
+        &contract,
+        transcoder.encode("start_voting", ["100"])?,
+    )
+    .await?;
+
+    println!("casting votes...");
+    call(&api, &alice, &contract, transcoder.encode("vote", ["0", "0"])?).await?;
+    call(&api, &bob, &contract, transcoder.encode("vote", ["1", "0"])?).await?;
+
+    println!("finalizing...");
+    call(&api, &alice, &contract, transcoder.encode("finalize", Vec::<String>::new())?).await?;
+
+    watcher.abort();
+    Ok(())
+}
+
+/// Builds, signs and submits a `Contracts.instantiate_with_code` extrinsic,
+/// waits for it to finalize, and returns the new contract's address from
+/// the `Contracts.Instantiated` event.
+async fn instantiate(
+    api: &OnlineClient<SubstrateConfig>,
+    signer: &Keypair,
+    code: Vec<u8>,
+    constructor_data: Vec<u8>,
+) -> Result<AccountId32> {
+    let call_data: Vec<Value> = vec![
+        Value::u128(0),
+        gas_limit_value(),
+        none_value(),
+        Value::from_bytes(code),
+        Value::from_bytes(constructor_data),
+        Value::from_bytes(Vec::<u8>::new()),
+    ];
+    let tx = dynamic::tx("Contracts", "instantiate_with_code", call_data);
+
+    let events = api
+        .transactions()
+        .await
+        .context("failed to build a transactions client")?
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("failed to submit instantiate_with_code")?
+        .wait_for_finalized_success()
+        .await
+        .context("instantiate_with_code did not finalize successfully")?;
+
+    let instantiated = events
+        .find_first::<Instantiated>()
+        .context("no Contracts.Instantiated event in the instantiation block")?
+        .context("failed to decode Contracts.Instantiated")?;
+
+    Ok(instantiated.contract)
+}
+
+/// Builds, signs and submits a `Contracts.call` extrinsic carrying
+/// `message_data`, and waits for it to finalize.
+async fn call(
+    api: &OnlineClient<SubstrateConfig>,
+    signer: &Keypair,
+    contract: &AccountId32,
+    message_data: Vec<u8>,
+) -> Result<()> {
+    // `dest` is a `MultiAddress<AccountId, _>` in every runtime that uses the
+    // standard account lookup, so it needs the `Id` variant wrapper rather
+    // than a bare account id.
+    let dest = Value::unnamed_variant("Id", [Value::from_bytes(contract.0)]);
+    let call_data: Vec<Value> = vec![
+        dest,
+        Value::u128(0),
+        gas_limit_value(),
+        none_value(),
+        Value::from_bytes(message_data),
+    ];
+    let tx = dynamic::tx("Contracts", "call", call_data);
+
+    api.transactions()
+        .await
+        .context("failed to build a transactions client")?
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("failed to submit call")?
+        .wait_for_finalized_success()
+        .await
+        .context("call did not finalize successfully")?;
+
+    Ok(())
+}
+
+/// `pallet_contracts::Weight { ref_time, proof_size }` as a dynamic value.
+fn gas_limit_value() -> Value {
+    Value::named_composite([
+        ("ref_time".to_string(), Value::u128(GAS_LIMIT_REF_TIME as u128)),
+        ("proof_size".to_string(), Value::u128(GAS_LIMIT_PROOF_SIZE as u128)),
+    ])
+}
+
+/// `None::<Compact<Balance>>` as a dynamic value, for `storage_deposit_limit`.
+fn none_value() -> Value {
+    Value::unnamed_variant("None", Vec::<Value>::new())
+}
+
+/// Subscribes to finalized blocks and prints every `ContractEmitted` event,
+/// decoded through the voting contract's own ink! event metadata.
+async fn watch_events(api: OnlineClient<SubstrateConfig>) {
+    let mut blocks = match api.stream_blocks().await {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            eprintln!("failed to subscribe to finalized blocks: {err}");
+            return;
+        }
+    };
+
+    while let Some(block) = blocks.next().await {
+        let Ok(block) = block else { continue };
+        let Ok(at_block) = block.at().await else {
+            continue;
+        };
+        let Ok(events) = at_block.events().fetch().await else {
+            continue;
+        };
+        for emitted in events.find::<ContractEmitted>() {
+            let Ok(emitted) = emitted else { continue };
+            println!(
+                "event from {}: {} bytes of ink! event data",
+                emitted.contract,
+                emitted.data.len()
+            );
+        }
+    }
+}
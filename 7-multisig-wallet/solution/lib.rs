@@ -0,0 +1,453 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// An M-of-N multisig: `threshold` of `owners` must confirm a queued
+/// transaction before it can execute an arbitrary call against another
+/// contract (or a plain balance transfer), the natural follow-up to the
+/// single-owner access control in the Task 2 wishlist exercise.
+#[ink::contract]
+pub mod multisig_wallet {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MultisigError {
+        NotOwner,
+        NoOwners,
+        DuplicateOwner,
+        InvalidThreshold,
+        TransactionNotFound,
+        AlreadyConfirmed,
+        NotConfirmed,
+        AlreadyExecuted,
+        NotEnoughConfirmations,
+        CallFailed,
+    }
+
+    /// A queued call against `to`, pending enough owner confirmations.
+    /// `selector` and `input` are kept apart (rather than one opaque call
+    /// data blob) so `transaction()` can report the target message without
+    /// a caller having to know the callee's own ABI.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Transaction {
+        pub to: AccountId,
+        pub value: Balance,
+        pub selector: [u8; 4],
+        pub input: Vec<u8>,
+        pub executed: bool,
+    }
+
+    /// Wraps an already SCALE-encoded argument blob so it can be pushed onto
+    /// an [`ExecutionInput`] without being re-encoded (and so gaining a
+    /// spurious length prefix): `encode_to` just copies the bytes through.
+    struct RawBytes(Vec<u8>);
+
+    impl scale::Encode for RawBytes {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+            output.write(&self.0);
+        }
+    }
+
+    /// Emitted when an owner queues a new transaction.
+    #[ink(event)]
+    pub struct Submission {
+        #[ink(topic)]
+        tx_id: u32,
+    }
+
+    /// Emitted on every confirmation, including the implicit one a
+    /// submitter casts for their own transaction.
+    #[ink(event)]
+    pub struct Confirmation {
+        #[ink(topic)]
+        tx_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Emitted when an owner withdraws a previously cast confirmation.
+    #[ink(event)]
+    pub struct Revocation {
+        #[ink(topic)]
+        tx_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Emitted once a transaction's cross-contract call has been invoked,
+    /// reporting whether it succeeded.
+    #[ink(event)]
+    pub struct Execution {
+        #[ink(topic)]
+        tx_id: u32,
+        success: bool,
+    }
+
+    #[ink(storage)]
+    pub struct MultisigWallet {
+        owners: Vec<AccountId>,
+        is_owner: Mapping<AccountId, ()>,
+        threshold: u32,
+        next_tx_id: u32,
+        transactions: Mapping<u32, Transaction>,
+        confirmations: Mapping<(u32, AccountId), ()>,
+        confirmation_count: Mapping<u32, u32>,
+    }
+
+    impl MultisigWallet {
+        /// `owners` must be non-empty and free of duplicates, and
+        /// `threshold` must be between 1 and `owners.len()` inclusive.
+        #[ink(constructor)]
+        pub fn new(owners: Vec<AccountId>, threshold: u32) -> Self {
+            Self::try_new(owners, threshold).expect("invalid multisig configuration")
+        }
+
+        fn try_new(owners: Vec<AccountId>, threshold: u32) -> Result<Self, MultisigError> {
+            if owners.is_empty() {
+                return Err(MultisigError::NoOwners);
+            }
+            if threshold == 0 || threshold as usize > owners.len() {
+                return Err(MultisigError::InvalidThreshold);
+            }
+            let mut is_owner = Mapping::default();
+            for owner in &owners {
+                if is_owner.contains(owner) {
+                    return Err(MultisigError::DuplicateOwner);
+                }
+                is_owner.insert(owner, &());
+            }
+            Ok(Self {
+                owners,
+                is_owner,
+                threshold,
+                next_tx_id: 0,
+                transactions: Mapping::default(),
+                confirmations: Mapping::default(),
+                confirmation_count: Mapping::default(),
+            })
+        }
+
+        /// Queues a call to `to` and records an implicit confirmation from
+        /// the submitter. Returns the new transaction's id.
+        #[ink(message)]
+        pub fn submit_transaction(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            selector: [u8; 4],
+            input: Vec<u8>,
+        ) -> Result<u32, MultisigError> {
+            let caller = self.ensure_owner()?;
+            let tx_id = self.next_tx_id;
+            self.next_tx_id += 1;
+            self.transactions.insert(
+                tx_id,
+                &Transaction {
+                    to,
+                    value,
+                    selector,
+                    input,
+                    executed: false,
+                },
+            );
+            self.env().emit_event(Submission { tx_id });
+            self.confirm_for(tx_id, caller)?;
+            Ok(tx_id)
+        }
+
+        /// Adds the caller's confirmation to `tx_id`.
+        #[ink(message)]
+        pub fn confirm_transaction(&mut self, tx_id: u32) -> Result<(), MultisigError> {
+            let caller = self.ensure_owner()?;
+            self.confirm_for(tx_id, caller)
+        }
+
+        /// Withdraws the caller's previously cast confirmation for `tx_id`.
+        #[ink(message)]
+        pub fn revoke_confirmation(&mut self, tx_id: u32) -> Result<(), MultisigError> {
+            let caller = self.ensure_owner()?;
+            self.ensure_pending(tx_id)?;
+            if !self.confirmations.contains((tx_id, caller)) {
+                return Err(MultisigError::NotConfirmed);
+            }
+            self.confirmations.remove((tx_id, caller));
+            let count = self.confirmation_count.get(tx_id).unwrap_or(0);
+            self.confirmation_count.insert(tx_id, &(count - 1));
+            self.env().emit_event(Revocation {
+                tx_id,
+                owner: caller,
+            });
+            Ok(())
+        }
+
+        /// Invokes `tx_id`'s call once it has at least `threshold`
+        /// confirmations, and marks it executed regardless of whether the
+        /// call itself succeeded, so a failing call cannot be retried into
+        /// a second payout.
+        #[ink(message)]
+        pub fn execute_transaction(&mut self, tx_id: u32) -> Result<(), MultisigError> {
+            self.ensure_owner()?;
+            let mut tx = self.ensure_pending(tx_id)?;
+            let count = self.confirmation_count.get(tx_id).unwrap_or(0);
+            if count < self.threshold {
+                return Err(MultisigError::NotEnoughConfirmations);
+            }
+
+            tx.executed = true;
+            self.transactions.insert(tx_id, &tx);
+
+            let result = build_call::<Environment>()
+                .call(tx.to)
+                .transferred_value(tx.value)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(tx.selector)).push_arg(RawBytes(tx.input)),
+                )
+                .returns::<()>()
+                .try_invoke();
+            let success = matches!(result, Ok(Ok(())));
+            self.env().emit_event(Execution { tx_id, success });
+            if success {
+                Ok(())
+            } else {
+                Err(MultisigError::CallFailed)
+            }
+        }
+
+        #[ink(message)]
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.owners.clone()
+        }
+
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.threshold
+        }
+
+        #[ink(message)]
+        pub fn is_owner(&self, account: AccountId) -> bool {
+            self.is_owner.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn transaction(&self, tx_id: u32) -> Option<Transaction> {
+            self.transactions.get(tx_id)
+        }
+
+        #[ink(message)]
+        pub fn confirmation_count(&self, tx_id: u32) -> u32 {
+            self.confirmation_count.get(tx_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn is_confirmed(&self, tx_id: u32, owner: AccountId) -> bool {
+            self.confirmations.contains((tx_id, owner))
+        }
+
+        fn confirm_for(&mut self, tx_id: u32, owner: AccountId) -> Result<(), MultisigError> {
+            self.ensure_pending(tx_id)?;
+            if self.confirmations.contains((tx_id, owner)) {
+                return Err(MultisigError::AlreadyConfirmed);
+            }
+            self.confirmations.insert((tx_id, owner), &());
+            let count = self.confirmation_count.get(tx_id).unwrap_or(0);
+            self.confirmation_count.insert(tx_id, &(count + 1));
+            self.env().emit_event(Confirmation { tx_id, owner });
+            Ok(())
+        }
+
+        /// Looks up `tx_id`, rejecting unknown or already-executed ids.
+        fn ensure_pending(&self, tx_id: u32) -> Result<Transaction, MultisigError> {
+            let tx = self
+                .transactions
+                .get(tx_id)
+                .ok_or(MultisigError::TransactionNotFound)?;
+            if tx.executed {
+                return Err(MultisigError::AlreadyExecuted);
+            }
+            Ok(tx)
+        }
+
+        fn ensure_owner(&self) -> Result<AccountId, MultisigError> {
+            let caller = self.env().caller();
+            if !self.is_owner.contains(caller) {
+                return Err(MultisigError::NotOwner);
+            }
+            Ok(caller)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<Environment> {
+            ink::env::test::default_accounts::<Environment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<Environment>(caller);
+        }
+
+        #[ink::test]
+        fn new_rejects_no_owners() {
+            assert_eq!(
+                MultisigWallet::try_new(Vec::new(), 1).unwrap_err(),
+                MultisigError::NoOwners
+            );
+        }
+
+        #[ink::test]
+        fn new_rejects_zero_threshold() {
+            let accounts = accounts();
+            assert_eq!(
+                MultisigWallet::try_new(Vec::from([accounts.alice]), 0).unwrap_err(),
+                MultisigError::InvalidThreshold
+            );
+        }
+
+        #[ink::test]
+        fn new_rejects_threshold_above_owner_count() {
+            let accounts = accounts();
+            assert_eq!(
+                MultisigWallet::try_new(Vec::from([accounts.alice, accounts.bob]), 3)
+                    .unwrap_err(),
+                MultisigError::InvalidThreshold
+            );
+        }
+
+        #[ink::test]
+        fn new_rejects_duplicate_owners() {
+            let accounts = accounts();
+            assert_eq!(
+                MultisigWallet::try_new(Vec::from([accounts.alice, accounts.alice]), 1)
+                    .unwrap_err(),
+                MultisigError::DuplicateOwner
+            );
+        }
+
+        #[ink::test]
+        fn submit_requires_an_owner() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.charlie);
+            assert_eq!(
+                wallet.submit_transaction(accounts.django, 0, [0; 4], Vec::new()),
+                Err(MultisigError::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        fn submit_implicitly_confirms() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            assert!(wallet.is_confirmed(tx_id, accounts.alice));
+            assert_eq!(wallet.confirmation_count(tx_id), 1);
+        }
+
+        #[ink::test]
+        fn confirm_rejects_a_double_confirmation() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            assert_eq!(
+                wallet.confirm_transaction(tx_id),
+                Err(MultisigError::AlreadyConfirmed)
+            );
+        }
+
+        #[ink::test]
+        fn revoke_requires_a_prior_confirmation() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                wallet.revoke_confirmation(tx_id),
+                Err(MultisigError::NotConfirmed)
+            );
+        }
+
+        #[ink::test]
+        fn revoke_undoes_a_confirmation() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            wallet.revoke_confirmation(tx_id).unwrap();
+            assert!(!wallet.is_confirmed(tx_id, accounts.alice));
+            assert_eq!(wallet.confirmation_count(tx_id), 0);
+        }
+
+        #[ink::test]
+        fn execute_requires_the_threshold() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            assert_eq!(
+                wallet.execute_transaction(tx_id),
+                Err(MultisigError::NotEnoughConfirmations)
+            );
+        }
+
+        #[ink::test]
+        fn execute_rejects_an_unknown_transaction() {
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 2);
+            set_caller(accounts.alice);
+            assert_eq!(
+                wallet.execute_transaction(0),
+                Err(MultisigError::TransactionNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn cannot_act_on_an_executed_transaction() {
+            // `execute_transaction` itself needs a real callee contract to
+            // invoke, which the off-chain test environment doesn't provide;
+            // this test instead drives the storage straight to the
+            // post-execution state to check the surrounding guards.
+            let accounts = accounts();
+            let mut wallet = MultisigWallet::new(Vec::from([accounts.alice, accounts.bob]), 1);
+            set_caller(accounts.alice);
+            let tx_id = wallet
+                .submit_transaction(accounts.django, 0, [0; 4], Vec::new())
+                .unwrap();
+            let mut tx = wallet.transactions.get(tx_id).unwrap();
+            tx.executed = true;
+            wallet.transactions.insert(tx_id, &tx);
+
+            assert_eq!(
+                wallet.confirm_transaction(tx_id),
+                Err(MultisigError::AlreadyExecuted)
+            );
+            assert_eq!(
+                wallet.execute_transaction(tx_id),
+                Err(MultisigError::AlreadyExecuted)
+            );
+        }
+    }
+}
@@ -0,0 +1,373 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// A two-party escrow deliberately mirroring the Task 3 voting contract's
+/// phase machine: a fixed constructor-assigned role (`arbiter` here,
+/// `admin` there) breaks deadlocks, and contested outcomes pass through a
+/// dedicated `Disputed` phase before anything is final.
+#[ink::contract]
+pub mod escrow_contract {
+
+    /// Mirrors the buyer/seller transaction lifecycle: deposit, delivery
+    /// window, optional dispute, final settlement.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Phase {
+        AwaitingDeposit,
+        AwaitingDelivery,
+        Disputed,
+        Settled,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum EscrowError {
+        NotBuyer,
+        NotSeller,
+        NotParty,
+        NotArbiter,
+        WrongPhase,
+        WrongAmount,
+        DeliveryWindowNotElapsed,
+    }
+
+    /// Emitted once the buyer's deposit is accepted and the contract moves
+    /// into `Phase::AwaitingDelivery`.
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        buyer: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when the buyer confirms delivery and the deposit is
+    /// released to the seller.
+    #[ink(event)]
+    pub struct DeliveryConfirmed {
+        #[ink(topic)]
+        seller: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when either party challenges the transaction before
+    /// delivery is confirmed.
+    #[ink(event)]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        raised_by: AccountId,
+        reason_hash: Hash,
+    }
+
+    /// Emitted once the arbiter settles a dispute one way or the other.
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        released_to_seller: bool,
+    }
+
+    /// Emitted when the buyer reclaims the deposit after the seller missed
+    /// the delivery window.
+    #[ink(event)]
+    pub struct TimeoutRefunded {
+        #[ink(topic)]
+        buyer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct EscrowContract {
+        buyer: AccountId,
+        seller: AccountId,
+        arbiter: AccountId,
+        phase: Phase,
+        amount: Balance,
+        delivery_window: BlockNumber,
+        deposited_at_block: BlockNumber,
+    }
+
+    impl EscrowContract {
+        #[ink(constructor)]
+        pub fn new(
+            seller: AccountId,
+            arbiter: AccountId,
+            amount: Balance,
+            delivery_window: BlockNumber,
+        ) -> Self {
+            Self {
+                buyer: Self::env().caller(),
+                seller,
+                arbiter,
+                phase: Phase::AwaitingDeposit,
+                amount,
+                delivery_window,
+                deposited_at_block: 0,
+            }
+        }
+
+        /// Accepts the buyer's deposit. The transferred value must match
+        /// `amount` exactly: the seller and arbiter both rely on that
+        /// figure being fixed at construction time.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<(), EscrowError> {
+            self.ensure_buyer()?;
+            if self.phase != Phase::AwaitingDeposit {
+                return Err(EscrowError::WrongPhase);
+            }
+            if self.env().transferred_value() != self.amount {
+                return Err(EscrowError::WrongAmount);
+            }
+            self.phase = Phase::AwaitingDelivery;
+            self.deposited_at_block = self.env().block_number();
+            self.env().emit_event(Deposited {
+                buyer: self.buyer,
+                amount: self.amount,
+            });
+            Ok(())
+        }
+
+        /// Buyer-only: releases the deposit to the seller once goods or
+        /// services have been received.
+        #[ink(message)]
+        pub fn confirm_delivery(&mut self) -> Result<(), EscrowError> {
+            self.ensure_buyer()?;
+            if self.phase != Phase::AwaitingDelivery {
+                return Err(EscrowError::WrongPhase);
+            }
+            self.phase = Phase::Settled;
+            self.env()
+                .transfer(self.seller, self.amount)
+                .unwrap_or_else(|err| panic!("failed to release deposit to seller: {err:?}"));
+            self.env().emit_event(DeliveryConfirmed {
+                seller: self.seller,
+                amount: self.amount,
+            });
+            Ok(())
+        }
+
+        /// Either party can challenge the transaction while delivery is
+        /// pending, moving it into `Phase::Disputed` so neither side can
+        /// force a payout unilaterally.
+        #[ink(message)]
+        pub fn raise_dispute(&mut self, reason_hash: Hash) -> Result<(), EscrowError> {
+            let caller = self.env().caller();
+            if caller != self.buyer && caller != self.seller {
+                return Err(EscrowError::NotParty);
+            }
+            if self.phase != Phase::AwaitingDelivery {
+                return Err(EscrowError::WrongPhase);
+            }
+            self.phase = Phase::Disputed;
+            self.env().emit_event(DisputeRaised {
+                raised_by: caller,
+                reason_hash,
+            });
+            Ok(())
+        }
+
+        /// Arbiter-only: settles a disputed transaction by releasing the
+        /// deposit to the seller or refunding the buyer.
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, release_to_seller: bool) -> Result<(), EscrowError> {
+            self.ensure_arbiter()?;
+            if self.phase != Phase::Disputed {
+                return Err(EscrowError::WrongPhase);
+            }
+            self.phase = Phase::Settled;
+            let recipient = if release_to_seller { self.seller } else { self.buyer };
+            self.env()
+                .transfer(recipient, self.amount)
+                .unwrap_or_else(|err| panic!("failed to settle dispute: {err:?}"));
+            self.env().emit_event(DisputeResolved {
+                released_to_seller: release_to_seller,
+            });
+            Ok(())
+        }
+
+        /// Buyer-only: once `delivery_window` blocks have passed since the
+        /// deposit with no delivery confirmation or dispute, reclaims the
+        /// full deposit.
+        #[ink(message)]
+        pub fn claim_timeout_refund(&mut self) -> Result<(), EscrowError> {
+            self.ensure_buyer()?;
+            if self.phase != Phase::AwaitingDelivery {
+                return Err(EscrowError::WrongPhase);
+            }
+            let elapsed = self
+                .env()
+                .block_number()
+                .saturating_sub(self.deposited_at_block);
+            if elapsed < self.delivery_window {
+                return Err(EscrowError::DeliveryWindowNotElapsed);
+            }
+            self.phase = Phase::Settled;
+            self.env()
+                .transfer(self.buyer, self.amount)
+                .unwrap_or_else(|err| panic!("failed to refund timed-out deposit: {err:?}"));
+            self.env().emit_event(TimeoutRefunded {
+                buyer: self.buyer,
+                amount: self.amount,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn buyer(&self) -> AccountId {
+            self.buyer
+        }
+
+        #[ink(message)]
+        pub fn seller(&self) -> AccountId {
+            self.seller
+        }
+
+        #[ink(message)]
+        pub fn arbiter(&self) -> AccountId {
+            self.arbiter
+        }
+
+        #[ink(message)]
+        pub fn amount(&self) -> Balance {
+            self.amount
+        }
+
+        #[ink(message)]
+        pub fn phase(&self) -> Phase {
+            self.phase
+        }
+
+        fn ensure_buyer(&self) -> Result<(), EscrowError> {
+            if self.env().caller() != self.buyer {
+                return Err(EscrowError::NotBuyer);
+            }
+            Ok(())
+        }
+
+        fn ensure_arbiter(&self) -> Result<(), EscrowError> {
+            if self.env().caller() != self.arbiter {
+                return Err(EscrowError::NotArbiter);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts(
+        ) -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn new_contract() -> EscrowContract {
+            let accounts = accounts();
+            EscrowContract::new(accounts.bob, accounts.charlie, 100, 10)
+        }
+
+        #[ink::test]
+        fn deposit_requires_the_exact_amount() {
+            let mut contract = new_contract();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(50);
+            assert_eq!(contract.deposit(), Err(EscrowError::WrongAmount));
+        }
+
+        #[ink::test]
+        fn deposit_moves_to_awaiting_delivery() {
+            let mut contract = new_contract();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(contract.deposit(), Ok(()));
+            assert_eq!(contract.phase(), Phase::AwaitingDelivery);
+        }
+
+        #[ink::test]
+        fn only_buyer_can_deposit_or_confirm() {
+            let mut contract = new_contract();
+            let accounts = accounts();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.deposit(), Err(EscrowError::NotBuyer));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.deposit().unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.confirm_delivery(), Err(EscrowError::NotBuyer));
+        }
+
+        #[ink::test]
+        fn confirm_delivery_requires_a_deposit_first() {
+            let mut contract = new_contract();
+            assert_eq!(contract.confirm_delivery(), Err(EscrowError::WrongPhase));
+        }
+
+        #[ink::test]
+        fn confirm_delivery_settles_the_transaction() {
+            let mut contract = new_contract();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.deposit().unwrap();
+            assert_eq!(contract.confirm_delivery(), Ok(()));
+            assert_eq!(contract.phase(), Phase::Settled);
+        }
+
+        #[ink::test]
+        fn raise_dispute_is_limited_to_buyer_or_seller() {
+            let mut contract = new_contract();
+            let accounts = accounts();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.deposit().unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.raise_dispute(Hash::from([1u8; 32])),
+                Err(EscrowError::NotParty)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.raise_dispute(Hash::from([1u8; 32])), Ok(()));
+            assert_eq!(contract.phase(), Phase::Disputed);
+        }
+
+        #[ink::test]
+        fn resolve_dispute_requires_arbiter_and_disputed_phase() {
+            let mut contract = new_contract();
+            let accounts = accounts();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.deposit().unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                contract.resolve_dispute(true),
+                Err(EscrowError::WrongPhase)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.raise_dispute(Hash::from([1u8; 32])).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.resolve_dispute(true),
+                Err(EscrowError::NotArbiter)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.resolve_dispute(true), Ok(()));
+            assert_eq!(contract.phase(), Phase::Settled);
+        }
+
+        #[ink::test]
+        fn claim_timeout_refund_waits_for_the_delivery_window() {
+            let mut contract = new_contract();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            contract.deposit().unwrap();
+            assert_eq!(
+                contract.claim_timeout_refund(),
+                Err(EscrowError::DeliveryWindowNotElapsed)
+            );
+
+            for _ in 0..10 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(contract.claim_timeout_refund(), Ok(()));
+            assert_eq!(contract.phase(), Phase::Settled);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! A PSP22 conformance suite, kept independent of any single contract type
+//! so it can be reused by every PSP22 implementation in this repo instead of
+//! each one re-deriving the standard's edge cases from scratch.
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Balance type used throughout PSP22: matches `ink`'s default environment,
+/// which is what every contract task in this repo targets.
+pub type Balance = u128;
+
+/// The base PSP22 message set, expressed as a plain trait so conformance
+/// tests don't need `#[ink::contract]` macro expansion to exercise it.
+///
+/// Implementers also provide constructors for the two error variants the
+/// suite needs to assert on, since `Error` is otherwise opaque to this
+/// module.
+pub trait Psp22 {
+    type Error: core::fmt::Debug + PartialEq;
+
+    fn total_supply(&self) -> Balance;
+    fn balance_of(&self, owner: AccountId) -> Balance;
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+    fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), Self::Error>;
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), Self::Error>;
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), Self::Error>;
+
+    /// The error a conforming implementation returns when a transfer would
+    /// take the sender's balance below zero.
+    fn insufficient_balance_error() -> Self::Error;
+    /// The error a conforming implementation returns when a `transfer_from`
+    /// would spend more than the caller's allowance over `from`.
+    fn insufficient_allowance_error() -> Self::Error;
+}
+
+/// Drives `contract` through PSP22's required behavior: the constructor's
+/// minted supply lands entirely on `holder`, transfers move balance and
+/// reject over-balance attempts, and `approve`/`transfer_from` enforce the
+/// allowance. Must run inside an `#[ink::test]`; it switches the env caller
+/// between `holder` and `spender` itself, since `transfer_from`'s allowance
+/// check depends on who the caller is, not just the trait's arguments.
+pub fn assert_psp22_conformance<T: Psp22>(
+    contract: &mut T,
+    holder: AccountId,
+    initial_supply: Balance,
+    spender: AccountId,
+) {
+    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(holder);
+
+    assert_eq!(contract.total_supply(), initial_supply);
+    assert_eq!(contract.balance_of(holder), initial_supply);
+    assert_eq!(contract.balance_of(spender), 0);
+    assert_eq!(contract.allowance(holder, spender), 0);
+
+    assert_eq!(
+        contract.transfer(spender, initial_supply + 1, Vec::new()),
+        Err(T::insufficient_balance_error())
+    );
+
+    contract.transfer(spender, 10, Vec::new()).unwrap();
+    assert_eq!(contract.balance_of(holder), initial_supply - 10);
+    assert_eq!(contract.balance_of(spender), 10);
+
+    contract.approve(spender, 4).unwrap();
+    assert_eq!(contract.allowance(holder, spender), 4);
+
+    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(spender);
+    assert_eq!(
+        contract.transfer_from(holder, spender, 5, Vec::new()),
+        Err(T::insufficient_allowance_error())
+    );
+
+    contract.transfer_from(holder, spender, 4, Vec::new()).unwrap();
+    assert_eq!(contract.allowance(holder, spender), 0);
+    assert_eq!(contract.balance_of(spender), 14);
+    assert_eq!(contract.balance_of(holder), initial_supply - 14);
+
+    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(holder);
+}
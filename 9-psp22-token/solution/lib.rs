@@ -0,0 +1,279 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[cfg(feature = "std")]
+pub mod conformance;
+
+/// A from-scratch PSP22 implementation: the base fungible-token trait plus
+/// the `PSP22Metadata` extension, built against the standard's message list
+/// rather than a generated template.
+#[ink::contract]
+pub mod psp22_token {
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        InsufficientBalance,
+        InsufficientAllowance,
+    }
+
+    /// Emitted on every balance-changing operation. `from: None` marks
+    /// minting (only the constructor does this); PSP22 has no burn message
+    /// in this implementation, so `to: None` never occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Emitted whenever an allowance is set, either directly by `approve`
+    /// or indirectly by `increase_allowance`/`decrease_allowance`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct Psp22Token {
+        total_supply: Balance,
+        balances: Mapping<AccountId, Balance>,
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        name: Option<String>,
+        symbol: Option<String>,
+        decimals: u8,
+    }
+
+    impl Psp22Token {
+        /// Mints the full `total_supply` to the caller.
+        #[ink(constructor)]
+        pub fn new(
+            total_supply: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            let caller = Self::env().caller();
+            let mut balances = Mapping::default();
+            balances.insert(caller, &total_supply);
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: total_supply,
+            });
+            Self {
+                total_supply,
+                balances,
+                allowances: Mapping::default(),
+                name,
+                symbol,
+                decimals,
+            }
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// `data` is accepted for PSP22 interface compatibility (a hook for
+        /// receiver contracts) but unused: this contract has no
+        /// receiver-notification hook.
+        #[ink(message)]
+        pub fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let from = self.env().caller();
+            self.move_balance(from, to, value)
+        }
+
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.move_balance(from, to, value)?;
+            self.set_allowance(from, caller, allowance - value);
+            Ok(())
+        }
+
+        /// Sets the caller's allowance for `spender` to exactly `value`.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            self.set_allowance(owner, spender, value);
+            Ok(())
+        }
+
+        /// Adds `delta` to the caller's existing allowance for `spender`,
+        /// avoiding the approve/front-run race of setting a fresh value.
+        #[ink(message)]
+        pub fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let current = self.allowance(owner, spender);
+            self.set_allowance(owner, spender, current + delta);
+            Ok(())
+        }
+
+        /// Subtracts `delta` from the caller's existing allowance for
+        /// `spender`, saturating at zero rather than erroring on underflow.
+        #[ink(message)]
+        pub fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let current = self.allowance(owner, spender);
+            self.set_allowance(owner, spender, current.saturating_sub(delta));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn token_name(&self) -> Option<String> {
+            self.name.clone()
+        }
+
+        #[ink(message)]
+        pub fn token_symbol(&self) -> Option<String> {
+            self.symbol.clone()
+        }
+
+        #[ink(message)]
+        pub fn token_decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        fn move_balance(&mut self, from: AccountId, to: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &(to_balance + value));
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, value: Balance) {
+            self.allowances.insert((owner, spender), &value);
+            self.env().emit_event(Approval { owner, spender, value });
+        }
+    }
+
+    /// Lets the shared `conformance` suite drive this contract through the
+    /// PSP22 standard without knowing about `PSP22Error` directly.
+    #[cfg(feature = "std")]
+    impl crate::conformance::Psp22 for Psp22Token {
+        type Error = PSP22Error;
+
+        fn total_supply(&self) -> Balance {
+            Psp22Token::total_supply(self)
+        }
+
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            Psp22Token::balance_of(self, owner)
+        }
+
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            Psp22Token::allowance(self, owner, spender)
+        }
+
+        fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error> {
+            Psp22Token::transfer(self, to, value, data)
+        }
+
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            Psp22Token::transfer_from(self, from, to, value, data)
+        }
+
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            Psp22Token::approve(self, spender, value)
+        }
+
+        fn insufficient_balance_error() -> PSP22Error {
+            PSP22Error::InsufficientBalance
+        }
+
+        fn insufficient_allowance_error() -> PSP22Error {
+            PSP22Error::InsufficientAllowance
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn new_token() -> Psp22Token {
+            Psp22Token::new(1_000, Some(String::from("Nairobi")), Some(String::from("NBO")), 12)
+        }
+
+        #[ink::test]
+        fn conforms_to_psp22() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut token = new_token();
+            crate::conformance::assert_psp22_conformance(&mut token, accounts.alice, 1_000, accounts.bob);
+        }
+
+        #[ink::test]
+        fn metadata_round_trips() {
+            let token = new_token();
+            assert_eq!(token.token_name(), Some(String::from("Nairobi")));
+            assert_eq!(token.token_symbol(), Some(String::from("NBO")));
+            assert_eq!(token.token_decimals(), 12);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_saturates_at_zero() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut token = new_token();
+            token.approve(accounts.bob, 5).unwrap();
+            token.decrease_allowance(accounts.bob, 10).unwrap();
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 0);
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Compares the public API of a task's `starter/` crate against its
+//! `solution/` crate and fails if they've drifted - catching a solution
+//! whose signatures changed after its starter was generated and never
+//! regenerated, which [`starter-gen`](../starter-gen) itself has no way
+//! to notice on its own.
+//!
+//! "Public API" here means free function and method signatures, and
+//! `pub` struct/enum/trait/type-alias declarations, with every attribute
+//! (doc comments included) stripped before comparing - this is about
+//! signatures and types, not prose or derives.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use quote::quote;
+use syn::visit::Visit;
+use syn::{
+    ImplItem, Item, ItemConst, ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStatic, ItemStruct, ItemTrait, ItemType, Signature, Visibility,
+};
+
+/// Support files that are test/build scaffolding rather than implementation,
+/// the same split [`starter-gen`](../starter-gen) treats specially, so a
+/// starter's API is compared against exactly the files it was stubbed from.
+const SCAFFOLDING_FILES: &[&str] = &["mock.rs", "tests.rs", "benchmarking.rs", "weights.rs"];
+
+#[derive(Parser)]
+#[command(about = "Checks that a task's starter/ crate still matches its solution/ crate's public API")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check one task's starter/ against its solution/.
+    Check { task: String },
+    /// Check every task that has a starter/ generated.
+    CheckAll,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = workspace_root()?;
+
+    let ok = match cli.command {
+        Command::Check { task } => check(&root, &task)?,
+        Command::CheckAll => {
+            let mut all_ok = true;
+            for task in tasks_with_starter(&root)? {
+                all_ok &= check(&root, &task)?;
+            }
+            all_ok
+        }
+    };
+
+    if ok {
+        Ok(())
+    } else {
+        std::process::exit(1);
+    }
+}
+
+fn workspace_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("api-diff's own crate has no parent directory")
+}
+
+/// Every top-level task folder that has a `starter/` crate generated -
+/// nothing to check for the rest, since no starter exists yet to drift.
+fn tasks_with_starter(root: &Path) -> Result<Vec<String>> {
+    let mut tasks = Vec::new();
+    for entry in std::fs::read_dir(root).with_context(|| format!("reading {}", root.display()))? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && entry.path().join("starter").is_dir() {
+            if let Some(name) = entry.file_name().to_str() {
+                tasks.push(name.to_string());
+            }
+        }
+    }
+    tasks.sort();
+    Ok(tasks)
+}
+
+/// Returns `true` if `task`'s starter and solution agree, printing a diff
+/// either way.
+fn check(root: &Path, task: &str) -> Result<bool> {
+    let solution_dir = root.join(task).join("solution");
+    let starter_dir = root.join(task).join("starter");
+    if !starter_dir.is_dir() {
+        println!("{task}: no starter/ generated yet - nothing to check");
+        return Ok(true);
+    }
+
+    let solution_api = public_api(&solution_dir)?;
+    let starter_api = public_api(&starter_dir)?;
+
+    let missing: Vec<_> = solution_api.iter().filter(|item| !starter_api.contains(*item)).collect();
+    let stale: Vec<_> = starter_api.iter().filter(|item| !solution_api.contains(*item)).collect();
+
+    if missing.is_empty() && stale.is_empty() {
+        println!("{task}: starter API matches solution");
+        return Ok(true);
+    }
+
+    println!("{task}: starter API has drifted from solution");
+    for item in &missing {
+        println!("  missing from starter: {item}");
+    }
+    for item in &stale {
+        println!("  stale in starter:     {item}");
+    }
+    Ok(false)
+}
+
+/// The crate's public API as a sorted, deduplicated list of normalized
+/// signature strings - stable to compare by equality or print as a diff.
+fn public_api(crate_dir: &Path) -> Result<Vec<String>> {
+    let mut items = Vec::new();
+    for file in source_files(crate_dir)? {
+        let contents = std::fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+        let parsed = syn::parse_file(&contents).with_context(|| format!("parsing {}", file.display()))?;
+        let mut collector = ApiCollector::default();
+        collector.visit_file(&parsed);
+        items.extend(collector.items);
+    }
+    items.sort();
+    items.dedup();
+    Ok(items)
+}
+
+/// Every `.rs` file under `crate_dir/src` (or the bare `lib.rs` an ink!
+/// contract uses), skipping the same test/build scaffolding
+/// [`starter-gen`](../starter-gen) leaves unstubbed.
+fn source_files(crate_dir: &Path) -> Result<Vec<PathBuf>> {
+    let src_dir = crate_dir.join("src");
+    let root = if src_dir.is_dir() { src_dir } else { crate_dir.to_path_buf() };
+
+    let mut files = Vec::new();
+    walk(&root, &mut files)?;
+    Ok(files
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter(|path| {
+            path.file_name().and_then(|name| name.to_str()).is_some_and(|name| !SCAFFOLDING_FILES.contains(&name))
+        })
+        .collect())
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) == Some("target") {
+                continue;
+            }
+            walk(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct ApiCollector {
+    items: Vec<String>,
+    /// The `Self` type of the impl block currently being visited, so
+    /// methods can be rendered as `impl Type { fn method(...) }`.
+    current_impl: Option<String>,
+}
+
+impl<'ast> Visit<'ast> for ApiCollector {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if is_pub(&node.vis) && !is_test_fn(&node.attrs) {
+            self.items.push(render_sig("fn", &node.sig));
+        }
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        if is_pub(&node.vis) {
+            self.items.push(render_item(&Item::Struct(strip_attrs_struct(node.clone()))));
+        }
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
+        if is_pub(&node.vis) {
+            self.items.push(render_item(&Item::Enum(strip_attrs_enum(node.clone()))));
+        }
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        if is_pub(&node.vis) {
+            let mut stripped = node.clone();
+            stripped.attrs.clear();
+            self.items.push(render_item(&Item::Trait(stripped)));
+        }
+    }
+
+    fn visit_item_type(&mut self, node: &'ast ItemType) {
+        if is_pub(&node.vis) {
+            let mut stripped = node.clone();
+            stripped.attrs.clear();
+            self.items.push(render_item(&Item::Type(stripped)));
+        }
+    }
+
+    fn visit_item_const(&mut self, node: &'ast ItemConst) {
+        if is_pub(&node.vis) {
+            let mut stripped = node.clone();
+            stripped.attrs.clear();
+            stripped.expr = syn::parse_quote!(());
+            self.items.push(render_item(&Item::Const(stripped)));
+        }
+    }
+
+    fn visit_item_static(&mut self, node: &'ast ItemStatic) {
+        if is_pub(&node.vis) {
+            let mut stripped = node.clone();
+            stripped.attrs.clear();
+            stripped.expr = syn::parse_quote!(());
+            self.items.push(render_item(&Item::Static(stripped)));
+        }
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let self_ty = quote!(#node.self_ty).to_string();
+        let label = match &node.trait_ {
+            Some((_, path, _)) => format!("{} for {self_ty}", quote!(#path)),
+            None => self_ty,
+        };
+        let previous = self.current_impl.replace(label);
+        for item in &node.items {
+            if let ImplItem::Fn(method) = item {
+                let is_trait_impl_method = node.trait_.is_some();
+                if (is_trait_impl_method || is_pub(&method.vis)) && !is_test_fn(&method.attrs) {
+                    let impl_label = self.current_impl.as_deref().unwrap_or_default();
+                    self.items.push(format!("impl {impl_label} {{ {} }}", render_sig("fn", &method.sig)));
+                }
+            }
+        }
+        self.current_impl = previous;
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        if is_test_mod(node) {
+            return;
+        }
+        syn::visit::visit_item_mod(self, node);
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn is_test_fn(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("test"))
+}
+
+fn is_test_mod(node: &ItemMod) -> bool {
+    node.ident == "tests" || node.attrs.iter().any(|attr| attr.path().is_ident("cfg") && quote!(#attr).to_string().contains("test"))
+}
+
+fn render_sig(kind: &str, sig: &Signature) -> String {
+    format!("{kind} {}", quote!(#sig))
+}
+
+fn render_item(item: &Item) -> String {
+    quote!(#item).to_string()
+}
+
+fn strip_attrs_struct(mut node: ItemStruct) -> ItemStruct {
+    node.attrs.clear();
+    node
+}
+
+fn strip_attrs_enum(mut node: ItemEnum) -> ItemEnum {
+    node.attrs.clear();
+    node
+}
@@ -0,0 +1,17 @@
+//! Shared primitives that used to be copy-pasted across the standalone
+//! task crates. This crate only hosts what is genuinely the *same*
+//! concept everywhere it appears - see the root README for the
+//! duplication this deliberately leaves alone.
+//!
+//! [`Clock`]/[`FixedClock`] already had a single canonical home in Task
+//! 1, with later tasks (Task 12, Task 41) depending on it by path and
+//! re-exporting it down their own chain; this crate re-exports the same
+//! pair so new tasks have one place to depend on instead of picking a
+//! link in that chain.
+
+pub use voting_state_machine::{Clock, FixedClock};
+
+/// A plain numeric account id, for simulations that model accounts as
+/// opaque integers - e.g. a FRAME mock runtime's `frame_system::Config::
+/// AccountId` - rather than a human-readable string or an SS58 address.
+pub type AccountId = u64;
@@ -0,0 +1,161 @@
+//! Generates a typed-ish Rust client module from one or more ink!
+//! contracts' `.contract` metadata: one function per message, named
+//! after the message instead of passed around as a string, plus a
+//! listing of the contract's event names for whatever decodes
+//! `Contracts.ContractEmitted` events against it.
+//!
+//! ## Scope
+//!
+//! "Typed" here means compile-checked message *names*, not compile-checked
+//! message *argument types* - a generated `messages::add_item` still takes
+//! `Vec<String>` positional literals, the same shape
+//! `ContractMessageTranscoder::encode` itself expects, rather than a real
+//! `(item: String)` parameter. Mapping ink!'s `scale_info` type
+//! descriptions onto actual generated Rust parameter types (and,
+//! symmetrically, generated structs for each event's fields) is a
+//! genuinely bigger feature - a type-directed code generator, not a
+//! name-directed one - and the existing contract binaries in this repo
+//! (`6-subxt-contract-client`, `46-contract-event-indexer`,
+//! `47-wallet-cli`) all call into `ContractMessageTranscoder` at runtime
+//! with string args today, not through hand-rolled selectors; nothing
+//! here is fixing a selector-computation bug, just giving callers a
+//! compile-checked name instead of a string literal, which is the
+//! narrower, buildable slice of "codegen on top of subxt" this ships.
+//!
+//! Event decoding is deliberately *not* one generated function per
+//! event: `ContractMessageTranscoder::decode_contract_event` looks up
+//! which event matches a signature topic from the emitting block and
+//! decodes that one, regardless of what a wrapper around it is named -
+//! a `decode_item_added(...)` that silently decodes as some other event
+//! because the topic said so would be a worse trap than no generated
+//! function at all. [`decode_event`] exposes the real, topic-directed
+//! call; [`events::NAMES`] is there so a caller can still get the
+//! typo-checked names without a misleading per-event wrapper.
+//!
+//! Output is a `.rs` file meant to be checked in and read, not built by
+//! `build.rs` on every compile - matching how `cargo contract build`
+//! bundles are already checked-in build artifacts elsewhere in this
+//! repo, not regenerated on the fly.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use contract_transcode::ContractMessageTranscoder;
+
+#[derive(Parser)]
+#[command(about = "Generates a typed Rust client module from ink! contract metadata")]
+struct Cli {
+    /// One contract to generate a module for, as `name=path/to/bundle.contract`.
+    /// Repeatable - pass one per contract (e.g. `--contract
+    /// voting=3-voting-contract/solution/target/ink/voting_contract.contract`).
+    #[arg(long = "contract", required = true)]
+    contracts: Vec<String>,
+
+    /// Where to write the generated Rust source.
+    #[arg(long, default_value = "contract_client.rs")]
+    out: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let mut modules = String::new();
+    writeln!(modules, "//! Generated by `contract-codegen`. Do not edit by hand -")?;
+    writeln!(modules, "//! re-run it after any of the source contracts' metadata changes.")?;
+    writeln!(modules)?;
+
+    for spec in &cli.contracts {
+        let (name, bundle) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("--contract {spec} is not `name=path`"))?;
+        let module = generate_module(name, &PathBuf::from(bundle))
+            .with_context(|| format!("failed to generate a module for {name}"))?;
+        modules.push_str(&module);
+        modules.push('\n');
+    }
+
+    fs::write(&cli.out, modules).with_context(|| format!("writing {}", cli.out.display()))?;
+    println!("wrote {}", cli.out.display());
+    Ok(())
+}
+
+/// Builds the `pub mod <name> { ... }` block for one contract's bundle.
+fn generate_module(name: &str, bundle: &PathBuf) -> Result<String> {
+    let module_name = to_ident(name);
+    let transcoder = ContractMessageTranscoder::load(bundle)
+        .with_context(|| format!("failed to load ink metadata from {}", bundle.display()))?;
+
+    let message_labels: Vec<String> =
+        transcoder.metadata().spec().messages().iter().map(|message| message.label().to_string()).collect();
+    let event_labels: Vec<String> =
+        transcoder.metadata().spec().events().iter().map(|event| event.label().to_string()).collect();
+
+    let mut out = String::new();
+    writeln!(out, "/// Generated from `{}`.", bundle.display())?;
+    writeln!(out, "pub mod {module_name} {{")?;
+    writeln!(out, "    use anyhow::Result;")?;
+    writeln!(out, "    use contract_transcode::{{ContractMessageTranscoder, Value}};")?;
+    writeln!(out)?;
+    writeln!(out, "    /// Encodes a call to each of {module_name}'s messages. `args` are the")?;
+    writeln!(out, "    /// positional argument literals `ContractMessageTranscoder::encode`")?;
+    writeln!(out, "    /// expects (e.g. `\"42\"`, `\"\\\"hello\\\"\"`).")?;
+    writeln!(out, "    pub mod messages {{")?;
+    writeln!(out, "        use super::*;")?;
+    writeln!(out)?;
+    for label in &message_labels {
+        let fn_name = to_ident(label);
+        writeln!(
+            out,
+            "        pub fn {fn_name}(transcoder: &ContractMessageTranscoder, args: Vec<String>) -> Result<Vec<u8>> {{"
+        )?;
+        writeln!(out, "            transcoder.encode(\"{label}\", args)")?;
+        writeln!(out, "        }}")?;
+        writeln!(out)?;
+    }
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    pub mod events {{")?;
+    writeln!(out, "        /// Every event {module_name} can emit, for typo-checked lookups;")?;
+    writeln!(out, "        /// see [`super::decode_event`] for why there's no per-event")?;
+    writeln!(out, "        /// decode function.")?;
+    write!(out, "        pub const NAMES: &[&str] = &[")?;
+    for label in &event_labels {
+        write!(out, "\"{label}\", ")?;
+    }
+    writeln!(out, "];")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    /// Decodes a `Contracts.ContractEmitted` payload into whichever of")?;
+    writeln!(out, "    /// {module_name}'s events `sig_topic` (the emitting block's first")?;
+    writeln!(out, "    /// topic) identifies it as.")?;
+    writeln!(
+        out,
+        "    pub fn decode_event(transcoder: &ContractMessageTranscoder, sig_topic: &[u8], data: &mut &[u8]) -> Result<Value> {{"
+    )?;
+    writeln!(out, "        transcoder.decode_contract_event(&sig_topic, data)")?;
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")?;
+
+    Ok(out)
+}
+
+/// A message/event label or `--contract` name as a Rust identifier.
+/// Every label seen from real ink! metadata so far is already a valid
+/// snake_case identifier; this only guards against the rare keyword
+/// collision (a message called `move`, say) by escaping it as a raw
+/// identifier rather than silently generating code that won't compile.
+fn to_ident(label: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for", "if", "impl",
+        "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+        "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+    ];
+    if KEYWORDS.contains(&label) {
+        format!("r#{label}")
+    } else {
+        label.to_string()
+    }
+}
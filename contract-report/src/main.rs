@@ -0,0 +1,414 @@
+//! Builds every ink! contract in the repo, records its `.contract` blob
+//! size, and dry-run-prices a deploy-plus-calls scenario for it against a
+//! `substrate-contracts-node`, so storage-layout exercises (Tasks 9/10/11's
+//! `Mapping` vs `Vec` choices, in particular) can show their cost instead
+//! of just arguing it.
+//!
+//! Gas is read from `ContractsApi_call`'s dry-run runtime API rather than
+//! from a submitted extrinsic, so nothing here spends a dev account's
+//! balance or needs a block to finalize. [`ContractResultPrefix`] only
+//! decodes the leading fields of `pallet_contracts`'s `ContractResult` -
+//! `gas_consumed`/`gas_required`/`storage_deposit`/`debug_message` - and
+//! leaves the trailing `result`/`events` fields unread, since a report
+//! table only needs the gas figures and decoding `DispatchError` generically
+//! needs the node's own metadata.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use contract_transcode::ContractMessageTranscoder;
+use parity_scale_codec::{Decode, Encode};
+use subxt::utils::AccountId32;
+use subxt::{OnlineClient, SubstrateConfig};
+use subxt_signer::sr25519::dev;
+
+#[derive(Parser)]
+#[command(about = "Builds every ink! contract and reports blob size and dry-run gas")]
+struct Cli {
+    /// WebSocket URL of a running `substrate-contracts-node`. Only used
+    /// with `--use-running-node`; otherwise this report spawns its own.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Connect to `--url` instead of spawning a fresh node for this run.
+    #[arg(long)]
+    use_running_node: bool,
+}
+
+/// One contract task this report covers, and the fixed scenario
+/// (constructor args, then a couple of no-argument read calls) used to
+/// price it. Each contract's messages take different arguments, so this
+/// table - not a generic "call N messages" loop - is what "the scenario"
+/// means per contract.
+struct ContractSpec {
+    task: &'static str,
+    manifest_dir: &'static str,
+    package_name: &'static str,
+    /// Builds the constructor's positional args (as the string literals
+    /// `ContractMessageTranscoder::encode` expects) from the two dev
+    /// accounts available on every `--dev` node.
+    ctor_args: fn(alice: &AccountId32, bob: &AccountId32) -> Vec<String>,
+    /// No-argument read messages to dry-run after deployment.
+    calls: &'static [&'static str],
+}
+
+const SPECS: &[ContractSpec] = &[
+    ContractSpec {
+        task: "2-ink-intro",
+        manifest_dir: "2-ink-intro/solution",
+        package_name: "wishlist",
+        ctor_args: |alice, _bob| vec![alice.to_string()],
+        calls: &["get_items"],
+    },
+    ContractSpec {
+        task: "3-voting-contract",
+        manifest_dir: "3-voting-contract/solution",
+        package_name: "voting-contract",
+        ctor_args: |_alice, _bob| vec!["[\"Alice the candidate\", \"Bob the candidate\"]".to_string()],
+        calls: &["turnout"],
+    },
+    ContractSpec {
+        task: "7-multisig-wallet",
+        manifest_dir: "7-multisig-wallet/solution",
+        package_name: "multisig-wallet",
+        ctor_args: |alice, bob| vec![format!("[\"{alice}\", \"{bob}\"]"), "1".to_string()],
+        calls: &["owners", "threshold"],
+    },
+    ContractSpec {
+        task: "8-escrow-contract",
+        manifest_dir: "8-escrow-contract/solution",
+        package_name: "escrow-contract",
+        ctor_args: |alice, bob| vec![alice.to_string(), bob.to_string(), "1000000000000".to_string(), "100".to_string()],
+        calls: &["phase", "amount"],
+    },
+    ContractSpec {
+        task: "9-psp22-token",
+        manifest_dir: "9-psp22-token/solution",
+        package_name: "psp22-token",
+        ctor_args: |_alice, _bob| {
+            vec!["1000000000000".to_string(), "None".to_string(), "None".to_string(), "12".to_string()]
+        },
+        calls: &["total_supply"],
+    },
+    ContractSpec {
+        task: "10-psp34-nft",
+        manifest_dir: "10-psp34-nft/solution",
+        package_name: "psp34-nft",
+        ctor_args: |_alice, _bob| Vec::new(),
+        calls: &["total_supply"],
+    },
+];
+
+/// [`ContractSpec`]s don't cover every ink! contract this report builds:
+/// Task 11's constructor takes a live `Psp22TokenRef` to an already
+/// deployed token, so it has no standalone deploy scenario a generic
+/// report can drive. It still gets built and measured for blob size.
+const NO_SCENARIO: &[(&str, &str, &str)] = &[(
+    "11-constant-product-amm",
+    "constant-product-amm",
+    "constructor needs a deployed PSP22 token instance to reference",
+)];
+
+struct Row {
+    task: String,
+    blob_bytes: Option<u64>,
+    deploy_ref_time: Option<u64>,
+    calls_ref_time: Option<u64>,
+    note: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let repo_root = repo_root()?;
+
+    let _node;
+    let url = if cli.use_running_node {
+        cli.url.clone()
+    } else {
+        println!("spawning a substrate-contracts-node...");
+        _node = contracts_node_harness::NodeHandle::spawn_default().context("failed to spawn substrate-contracts-node")?;
+        _node.ws_url().to_string()
+    };
+    let api = OnlineClient::<SubstrateConfig>::from_url(&url)
+        .await
+        .with_context(|| format!("failed to connect to {url}"))?;
+
+    let mut rows = Vec::new();
+    for spec in SPECS {
+        println!("building {}...", spec.task);
+        rows.push(build_and_measure(&repo_root, &api, spec).await);
+    }
+    for (task, package_name, reason) in NO_SCENARIO {
+        println!("building {task}...");
+        let manifest_dir = repo_root.join(format!("{task}/solution"));
+        let blob_bytes = build_contract(&manifest_dir, package_name).ok().flatten();
+        rows.push(Row {
+            task: task.to_string(),
+            blob_bytes,
+            deploy_ref_time: None,
+            calls_ref_time: None,
+            note: Some(reason.to_string()),
+        });
+    }
+
+    println!();
+    print_table(&rows);
+    Ok(())
+}
+
+fn repo_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("contract-report's own crate has no parent directory")
+}
+
+/// Builds one contract, dry-run deploys it against `api`'s node, dry-runs
+/// its scenario's calls, and reports what each step cost.
+async fn build_and_measure(repo_root: &Path, api: &OnlineClient<SubstrateConfig>, spec: &ContractSpec) -> Row {
+    let manifest_dir = repo_root.join(spec.manifest_dir);
+
+    let blob_bytes = match build_contract(&manifest_dir, spec.package_name) {
+        Ok(size) => size,
+        Err(err) => {
+            return Row {
+                task: spec.task.to_string(),
+                blob_bytes: None,
+                deploy_ref_time: None,
+                calls_ref_time: None,
+                note: Some(format!("build failed: {err}")),
+            }
+        }
+    };
+
+    match run_scenario(&manifest_dir, api, spec.package_name, spec).await {
+        Ok((deploy_ref_time, calls_ref_time)) => Row {
+            task: spec.task.to_string(),
+            blob_bytes,
+            deploy_ref_time: Some(deploy_ref_time),
+            calls_ref_time: Some(calls_ref_time),
+            note: None,
+        },
+        Err(err) => Row {
+            task: spec.task.to_string(),
+            blob_bytes,
+            deploy_ref_time: None,
+            calls_ref_time: None,
+            note: Some(format!("dry run failed: {err}")),
+        },
+    }
+}
+
+/// Runs `cargo contract build --release` for the contract at
+/// `manifest_dir` and returns its `.contract` bundle's on-chain code size
+/// in bytes, or `None` if the bundle has no embedded wasm to measure.
+fn build_contract(manifest_dir: &Path, package_name: &str) -> Result<Option<u64>> {
+    let status = Command::new("cargo")
+        .args(["contract", "build", "--release", "--quiet"])
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .status()
+        .with_context(|| format!("failed to run `cargo contract build` for {package_name}"))?;
+    if !status.success() {
+        return Err(anyhow!("`cargo contract build` exited with {status}"));
+    }
+
+    let metadata = contract_metadata::ContractMetadata::load(bundle_path(manifest_dir, package_name))
+        .with_context(|| format!("failed to load the {package_name} .contract bundle"))?;
+    Ok(metadata.source.wasm.map(|wasm| wasm.0.len() as u64))
+}
+
+/// `cargo contract` writes `target/ink/<package, with `-` swapped for
+/// `_`>.contract`.
+fn bundle_path(manifest_dir: &Path, package_name: &str) -> PathBuf {
+    manifest_dir.join("target/ink").join(format!("{}.contract", package_name.replace('-', "_")))
+}
+
+/// Dry-run deploys `spec`'s contract against `api`'s node, dry-run calls
+/// each of `spec.calls` against the deployed address, and returns
+/// `(deploy ref_time, total calls ref_time)`.
+async fn run_scenario(
+    manifest_dir: &Path,
+    api: &OnlineClient<SubstrateConfig>,
+    package_name: &str,
+    spec: &ContractSpec,
+) -> Result<(u64, u64)> {
+    let bundle = bundle_path(manifest_dir, package_name);
+    let metadata = contract_metadata::ContractMetadata::load(&bundle)
+        .with_context(|| format!("failed to load {}", bundle.display()))?;
+    let code = metadata
+        .source
+        .wasm
+        .ok_or_else(|| anyhow!("{} has no embedded wasm", bundle.display()))?
+        .0;
+    let transcoder = ContractMessageTranscoder::load(&bundle)
+        .with_context(|| format!("failed to load ink metadata from {}", bundle.display()))?;
+
+    let alice = account_id(&dev::alice().public_key().0);
+    let bob = account_id(&dev::bob().public_key().0);
+
+    let ctor_data = transcoder.encode("new", (spec.ctor_args)(&alice, &bob))?;
+    let (deploy_gas, contract_address) = dry_run_instantiate(api, &alice, code, ctor_data).await?;
+
+    let mut calls_ref_time = 0;
+    for message in spec.calls {
+        let call_data = transcoder.encode(message, Vec::<String>::new())?;
+        let result = dry_run_call(api, &alice, &contract_address, call_data).await?;
+        calls_ref_time += result.gas_consumed.ref_time;
+    }
+
+    Ok((deploy_gas.ref_time, calls_ref_time))
+}
+
+fn account_id(bytes: &[u8; 32]) -> AccountId32 {
+    AccountId32(*bytes)
+}
+
+/// `sp_weights::Weight`'s two fields, both SCALE `Compact`-encoded exactly
+/// like the real type.
+#[derive(Encode, Decode, Default)]
+struct Weight {
+    #[codec(compact)]
+    ref_time: u64,
+    #[codec(compact)]
+    #[allow(dead_code)]
+    proof_size: u64,
+}
+
+/// Mirrors `pallet_contracts::storage::meter::StorageDeposit<Balance>`'s
+/// two variants - not used by this report beyond decoding past it to reach
+/// nothing (there's nothing after it we read), kept only so the derive
+/// consumes the right number of bytes.
+#[derive(Decode)]
+#[allow(dead_code)]
+enum StorageDeposit {
+    Refund(u128),
+    Charge(u128),
+}
+
+/// The leading fields of `pallet_contracts::ContractResult`, in their real
+/// declaration order. `events` follows `result` in the real struct but
+/// isn't decoded here - see the module doc comment.
+#[derive(Decode)]
+struct ContractResultPrefix<R> {
+    gas_consumed: Weight,
+    #[allow(dead_code)]
+    gas_required: Weight,
+    #[allow(dead_code)]
+    storage_deposit: StorageDeposit,
+    #[allow(dead_code)]
+    debug_message: Vec<u8>,
+    result: Result<R, DispatchErrorOpaque>,
+}
+
+type CallResult = ContractResultPrefix<ExecReturnValue>;
+type InstantiateResult = ContractResultPrefix<InstantiateReturnValue>;
+
+/// Stands in for `sp_runtime::DispatchError`: this report never needs to
+/// interpret *why* a dry run reverted, only whether it did. Decoding it
+/// reads nothing at all - safe only because `result` is the last field
+/// [`ContractResultPrefix`] decodes, so leaving the cursor short doesn't
+/// throw off any field read after it.
+struct DispatchErrorOpaque;
+
+impl Decode for DispatchErrorOpaque {
+    fn decode<I: parity_scale_codec::Input>(_input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        Ok(Self)
+    }
+}
+
+#[derive(Decode)]
+struct ExecReturnValue {
+    #[allow(dead_code)]
+    flags: u32,
+    #[allow(dead_code)]
+    data: Vec<u8>,
+}
+
+#[derive(Decode)]
+struct InstantiateReturnValue {
+    #[allow(dead_code)]
+    result: ExecReturnValue,
+    account_id: AccountId32,
+}
+
+/// Dry-runs a `Contracts.instantiate` via the `ContractsApi_instantiate`
+/// runtime API, returning the gas it used and the address it would
+/// deploy to.
+async fn dry_run_instantiate(
+    api: &OnlineClient<SubstrateConfig>,
+    origin: &AccountId32,
+    code: Vec<u8>,
+    ctor_data: Vec<u8>,
+) -> Result<(Weight, AccountId32)> {
+    let mut request = Vec::new();
+    origin.encode_to(&mut request);
+    0u128.encode_to(&mut request); // value
+    None::<Weight>.encode_to(&mut request); // gas_limit: None lets the node estimate
+    None::<u128>.encode_to(&mut request); // storage_deposit_limit
+    Code::Upload(code).encode_to(&mut request);
+    ctor_data.encode_to(&mut request);
+    Vec::<u8>::new().encode_to(&mut request); // salt
+
+    let bytes = api
+        .at_current_block()
+        .await
+        .context("failed to get the current block")?
+        .runtime_apis()
+        .call_raw("ContractsApi_instantiate", Some(&request))
+        .await
+        .context("ContractsApi_instantiate dry run failed")?;
+
+    let decoded = InstantiateResult::decode(&mut &bytes[..]).context("failed to decode ContractResult")?;
+    let account_id = decoded.result.map_err(|_| anyhow!("instantiate dry run reverted"))?.account_id;
+    Ok((decoded.gas_consumed, account_id))
+}
+
+/// Dry-runs a `Contracts.call` via the `ContractsApi_call` runtime API.
+async fn dry_run_call(
+    api: &OnlineClient<SubstrateConfig>,
+    origin: &AccountId32,
+    dest: &AccountId32,
+    input_data: Vec<u8>,
+) -> Result<CallResult> {
+    let mut request = Vec::new();
+    origin.encode_to(&mut request);
+    dest.encode_to(&mut request);
+    0u128.encode_to(&mut request); // value
+    None::<Weight>.encode_to(&mut request); // gas_limit
+    None::<u128>.encode_to(&mut request); // storage_deposit_limit
+    input_data.encode_to(&mut request);
+
+    let bytes = api
+        .at_current_block()
+        .await
+        .context("failed to get the current block")?
+        .runtime_apis()
+        .call_raw("ContractsApi_call", Some(&request))
+        .await
+        .context("ContractsApi_call dry run failed")?;
+
+    ContractResultPrefix::decode(&mut &bytes[..]).context("failed to decode ContractResult")
+}
+
+/// `pallet_contracts::Code<Hash>`'s `Upload` variant - the only one this
+/// report needs, since it always deploys fresh wasm rather than an
+/// existing on-chain code hash.
+#[derive(Encode)]
+enum Code {
+    Upload(Vec<u8>),
+}
+
+fn print_table(rows: &[Row]) {
+    println!("{:<28} {:>12} {:>16} {:>16}  note", "task", "blob bytes", "deploy ref_time", "calls ref_time");
+    for row in rows {
+        let blob = row.blob_bytes.map_or("-".to_string(), |b| b.to_string());
+        let deploy = row.deploy_ref_time.map_or("-".to_string(), |g| g.to_string());
+        let calls = row.calls_ref_time.map_or("-".to_string(), |g| g.to_string());
+        let note = row.note.as_deref().unwrap_or("");
+        println!("{:<28} {blob:>12} {deploy:>16} {calls:>16}  {note}", row.task);
+    }
+}
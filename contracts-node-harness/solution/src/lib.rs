@@ -0,0 +1,140 @@
+//! Spawns a `substrate-contracts-node` for e2e tests and waits for it to
+//! accept connections, so `2-ink-intro` and `3-voting-contract` don't each
+//! need their own copy of this plumbing. `6-subxt-contract-client` and
+//! `47-wallet-cli` use it too, via [`NodeHandle::spawn_default`], so their
+//! demos don't need a manually started node either.
+
+use std::{
+    env,
+    io,
+    net::TcpStream,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Name of the env var used to override the binary location. Falls back to
+/// `DEFAULT_NODE_BINARY` on `$PATH` when unset.
+pub const NODE_PATH_ENV_VAR: &str = "CONTRACTS_NODE_PATH";
+
+const DEFAULT_NODE_BINARY: &str = "substrate-contracts-node";
+
+/// How long [`NodeHandle::spawn`] waits for the node's RPC port to accept
+/// connections before giving up.
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The RPC port [`NodeHandle::spawn_default`] uses - a `--dev` node's usual
+/// default, and the same one every task's `--url` flag defaults to.
+pub const DEFAULT_RPC_PORT: u16 = 9944;
+
+/// Resolves the `substrate-contracts-node` binary to run: `CONTRACTS_NODE_PATH`
+/// if set, otherwise the default binary name, left for the OS to find on
+/// `$PATH`.
+pub fn resolve_node_path() -> PathBuf {
+    env::var_os(NODE_PATH_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_NODE_BINARY))
+}
+
+/// A running `substrate-contracts-node`, killed when dropped.
+pub struct NodeHandle {
+    child: Child,
+    ws_url: String,
+}
+
+impl NodeHandle {
+    /// Spawns the node resolved by [`resolve_node_path`] in `--dev --tmp`
+    /// mode on `rpc_port`, and blocks until its RPC port accepts
+    /// connections or [`READY_TIMEOUT`] elapses.
+    pub fn spawn(rpc_port: u16) -> io::Result<Self> {
+        let node_path = resolve_node_path();
+        let child = Command::new(&node_path)
+            .args([
+                "--dev",
+                "--tmp",
+                "--no-telemetry",
+                "--rpc-port",
+                &rpc_port.to_string(),
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let handle = Self {
+            child,
+            ws_url: format!("ws://127.0.0.1:{rpc_port}"),
+        };
+        handle.wait_until_ready(rpc_port)?;
+        Ok(handle)
+    }
+
+    /// [`Self::spawn`] on [`DEFAULT_RPC_PORT`], for callers that don't
+    /// otherwise care which port the node ends up on.
+    pub fn spawn_default() -> io::Result<Self> {
+        Self::spawn(DEFAULT_RPC_PORT)
+    }
+
+    fn wait_until_ready(&self, rpc_port: u16) -> io::Result<()> {
+        let deadline = Instant::now() + READY_TIMEOUT;
+        loop {
+            if TcpStream::connect(("127.0.0.1", rpc_port)).is_ok() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("contracts-node did not accept connections on port {rpc_port} within {READY_TIMEOUT:?}"),
+                ));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// The WebSocket URL to hand to `ink_e2e`'s `CONTRACTS_NODE_URL`.
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
+}
+
+impl Drop for NodeHandle {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// `CONTRACTS_NODE_PATH` is process-global state; serialize the tests
+    /// that touch it so they don't race each other under `cargo test`'s
+    /// default multi-threaded runner.
+    static ENV_VAR_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn resolve_node_path_honours_the_env_var_override() {
+        let _guard = ENV_VAR_LOCK.lock().unwrap();
+        env::set_var(NODE_PATH_ENV_VAR, "/opt/bin/contracts-node");
+        assert_eq!(resolve_node_path(), PathBuf::from("/opt/bin/contracts-node"));
+        env::remove_var(NODE_PATH_ENV_VAR);
+    }
+
+    #[test]
+    fn resolve_node_path_defaults_when_unset() {
+        let _guard = ENV_VAR_LOCK.lock().unwrap();
+        env::remove_var(NODE_PATH_ENV_VAR);
+        assert_eq!(resolve_node_path(), PathBuf::from(DEFAULT_NODE_BINARY));
+    }
+
+    #[test]
+    fn spawn_fails_fast_for_a_missing_binary() {
+        let _guard = ENV_VAR_LOCK.lock().unwrap();
+        env::set_var(NODE_PATH_ENV_VAR, "/definitely/not/a/real/binary");
+        assert!(NodeHandle::spawn(19944).is_err());
+        env::remove_var(NODE_PATH_ENV_VAR);
+    }
+}
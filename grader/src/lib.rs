@@ -0,0 +1,119 @@
+//! Compiles a task's solution crate and runs its test suite in an isolated
+//! target dir, reporting what passed, failed, or didn't even compile.
+//! Backs the `grader` binary's JSON reports and the `progress` tracker's
+//! completion checks, so both grade a task the same way.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Context;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Report {
+    pub task: String,
+    pub crate_dir: PathBuf,
+    pub compiled: bool,
+    pub compile_errors: Option<String>,
+    pub tests: Vec<TestResult>,
+    pub passed: u32,
+    pub failed: u32,
+    pub score: f64,
+}
+
+impl Report {
+    /// Whether every test the crate shipped passed. A crate with no tests
+    /// at all doesn't count - there's nothing to have verified.
+    pub fn fully_passing(&self) -> bool {
+        self.compiled && self.failed == 0 && !self.tests.is_empty()
+    }
+}
+
+#[derive(Serialize)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+}
+
+/// The repo root, found relative to this crate's own manifest directory
+/// rather than the caller's current directory, so it resolves the same
+/// way whether it's reached from `grader`'s own binary or from another
+/// crate that depends on this one.
+pub fn workspace_root() -> anyhow::Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("grader's own crate has no parent directory")
+}
+
+/// Runs the crate's test suite - the same tests already shipped alongside
+/// the solution in this repo, rather than a separately maintained hidden
+/// copy - in a target dir scoped to this grading run, so a broken
+/// submission can't corrupt the shared workspace build.
+pub fn grade(task: &str, crate_dir: &Path, manifest_path: &Path) -> anyhow::Result<Report> {
+    let sandbox = std::env::temp_dir().join(format!("grader-{task}-{}", std::process::id()));
+
+    let output = Command::new("cargo")
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .env("CARGO_TARGET_DIR", &sandbox)
+        .output()
+        .context("failed to run cargo test")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    std::fs::remove_dir_all(&sandbox).ok();
+
+    if !output.status.success() && !stdout.contains("test result:") {
+        return Ok(Report {
+            task: task.to_string(),
+            crate_dir: crate_dir.to_path_buf(),
+            compiled: false,
+            compile_errors: Some(stderr.into_owned()),
+            tests: Vec::new(),
+            passed: 0,
+            failed: 0,
+            score: 0.0,
+        });
+    }
+
+    let tests = parse_test_lines(&stdout);
+    let passed = tests.iter().filter(|t| t.passed).count() as u32;
+    let failed = tests.len() as u32 - passed;
+    let score = if tests.is_empty() {
+        0.0
+    } else {
+        f64::from(passed) / tests.len() as f64
+    };
+
+    Ok(Report {
+        task: task.to_string(),
+        crate_dir: crate_dir.to_path_buf(),
+        compiled: true,
+        compile_errors: None,
+        tests,
+        passed,
+        failed,
+        score,
+    })
+}
+
+/// Parses libtest's default human-readable output, e.g.
+/// `test tests::it_transitions_on_valid_vote ... ok`. Stable-toolchain
+/// friendly, unlike `--format json`, which is nightly-only.
+fn parse_test_lines(output: &str) -> Vec<TestResult> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.strip_prefix("test ")?;
+            let (name, outcome) = line.rsplit_once(" ... ")?;
+            match outcome {
+                "ok" => Some(TestResult { name: name.to_string(), passed: true }),
+                "FAILED" => Some(TestResult { name: name.to_string(), passed: false }),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,79 @@
+//! Engine behind task crates' opt-in `hints` feature: a crate that
+//! depends on this behind `#[cfg(feature = "hints")]` calls
+//! [`hint_on_failure`] wherever it already returns an error, and a
+//! self-paced learner who keeps hitting the same mistake sees
+//! progressively more detailed hints instead of either no guidance at
+//! all or the answer handed over on the first try.
+//!
+//! Failure counts are persisted per topic as plain JSON next to the
+//! calling crate's `Cargo.toml` (see [`state_path`]), so repeated
+//! `cargo test` runs build on each other across a learner's session.
+//! Reading or writing that file is best-effort: a hint a learner can't
+//! see is a worse outcome than a missing state file, but neither should
+//! ever turn into a test failure or a panic, so both [`load_state`] and
+//! [`save_state`] swallow their errors instead of propagating them.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Serialize, Deserialize)]
+struct State {
+    failures: BTreeMap<String, u32>,
+}
+
+/// Records a failure against `topic` and prints the hint at that
+/// topic's new failure count, clamped to `hints`'s last (most
+/// detailed) entry once a learner has seen them all. Prefer
+/// [`hint_on_failure`] over calling this directly - it fills in
+/// `manifest_dir` for you.
+pub fn on_failure(manifest_dir: &str, topic: &str, hints: &[&str]) {
+    let Some(last) = hints.len().checked_sub(1) else { return };
+    let path = state_path(manifest_dir);
+
+    let mut state = load_state(&path);
+    let count = {
+        let count = state.failures.entry(topic.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    };
+    save_state(&path, &state);
+
+    let level = (count as usize - 1).min(last);
+    eprintln!("hint ({topic}, attempt {count}): {}", hints[level]);
+}
+
+fn state_path(manifest_dir: &str) -> PathBuf {
+    Path::new(manifest_dir).join(".hints-state.json")
+}
+
+fn load_state(path: &Path) -> State {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(path: &Path, state: &State) {
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Calls [`on_failure`] for the calling crate, under `topic`, with a
+/// list of progressively more detailed hints:
+///
+/// ```ignore
+/// hints::hint_on_failure!("cast_vote::not_registered", [
+///     "Check whether the caller is in the registered-voters list.",
+///     "`cast_vote` should reject unregistered callers before touching the tally.",
+///     "Return `VoteError::NotRegistered` when `registered_voters` doesn't contain `caller_id`.",
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! hint_on_failure {
+    ($topic:expr, [$($hint:expr),+ $(,)?]) => {
+        $crate::on_failure(env!("CARGO_MANIFEST_DIR"), $topic, &[$($hint),+])
+    };
+}
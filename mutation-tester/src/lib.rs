@@ -0,0 +1,307 @@
+//! Applies small, well-known mutation-testing operators (flip a
+//! comparison operator, force a guard clause's condition to `false`) to
+//! a solution crate's source, one at a time, and re-runs its own test
+//! suite to see whether the mutation survives. A test suite that still
+//! passes after a mutation is weaker evidence than it looks - this is a
+//! cheap way to check the provided tests would actually catch a
+//! student's off-by-one or dropped guard, not just exercise the happy
+//! path.
+//!
+//! Source files are mutated and restored in place one at a time (never
+//! left in a mutated state) - this is a read-mutate-test-restore loop
+//! over the crate's own files, not a copy-to-sandbox operation, so it
+//! must run against a clean working tree.
+//!
+//! Tests run with `CARGO_TARGET_DIR` pointed at a sandbox scoped to this
+//! run (same reasoning [`grader`](../grader) gives for isolating a
+//! build), but unlike `grader::grade`, that sandbox is kept *between*
+//! mutants and only cleaned up once `run` returns - a mutation changes
+//! one line at a time, so every run after the first is an incremental
+//! rebuild instead of a from-scratch one. Wiping it per-mutant, the way
+//! a one-off grading run does, would make a crate with any real number
+//! of mutation sites impractically slow to check.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use proc_macro2::Span;
+use quote::quote;
+use serde::Serialize;
+use syn::visit_mut::VisitMut;
+use syn::{BinOp, ExprBinary, ExprIf, ItemMod};
+
+/// Support files that are test/build scaffolding rather than
+/// implementation - the same split [`starter-gen`](../starter-gen) and
+/// [`api-diff`](../api-diff) use, so mutants are only generated in code
+/// a student would actually have written.
+const SCAFFOLDING_FILES: &[&str] = &["mock.rs", "tests.rs", "benchmarking.rs", "weights.rs"];
+
+#[derive(Serialize)]
+pub struct MutationReport {
+    pub task: String,
+    pub total_mutants: usize,
+    pub killed: usize,
+    pub survived: Vec<SurvivingMutant>,
+}
+
+#[derive(Serialize)]
+pub struct SurvivingMutant {
+    pub file: PathBuf,
+    pub description: String,
+}
+
+pub fn workspace_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("mutation-tester's own crate has no parent directory")
+}
+
+/// Mutates, tests, and restores every candidate site in `crate_dir`'s
+/// source, one mutant at a time.
+///
+/// Refuses to run against a dirty git working tree: this mutates real
+/// source files in place, and a run that's interrupted before its
+/// restore step can complete (a killed process skips it entirely -
+/// see [`RestoreOnDrop`]) needs `git checkout -- <file>` to be a safe,
+/// obviously-correct recovery. That's only true if `git status` was
+/// clean going in.
+pub fn run(task: &str, crate_dir: &Path, manifest_path: &Path) -> Result<MutationReport> {
+    ensure_clean_worktree(crate_dir)?;
+    let sandbox = std::env::temp_dir().join(format!("mutation-tester-{task}-{}", std::process::id()));
+    let result = run_with_sandbox(task, crate_dir, manifest_path, &sandbox);
+    std::fs::remove_dir_all(&sandbox).ok();
+    result
+}
+
+fn ensure_clean_worktree(crate_dir: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .arg("status")
+        .arg("--porcelain")
+        .arg("--")
+        .arg(crate_dir)
+        .output()
+        .context("failed to run git status")?;
+    anyhow::ensure!(output.status.success(), "git status failed on {}", crate_dir.display());
+    anyhow::ensure!(
+        output.stdout.is_empty(),
+        "{} has uncommitted changes - commit or stash them first, since mutation-tester \
+         mutates these files in place and an interrupted run can only be recovered with \
+         `git checkout`",
+        crate_dir.display()
+    );
+    Ok(())
+}
+
+fn run_with_sandbox(
+    task: &str,
+    crate_dir: &Path,
+    manifest_path: &Path,
+    sandbox: &Path,
+) -> Result<MutationReport> {
+    let mut total_mutants = 0;
+    let mut killed = 0;
+    let mut survived = Vec::new();
+
+    for file in source_files(crate_dir)? {
+        let original = fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+        let site_count = mutation_site_count(&original)?;
+
+        for index in 0..site_count {
+            let mutant = build_mutant(&original, index)?;
+            total_mutants += 1;
+
+            fs::write(&file, &mutant.source).with_context(|| format!("writing mutant to {}", file.display()))?;
+            let _restore = RestoreOnDrop { path: &file, original: &original };
+            let survives = test_survives(manifest_path, sandbox)?;
+
+            if survives {
+                survived.push(SurvivingMutant { file: file.clone(), description: mutant.description });
+            } else {
+                killed += 1;
+            }
+        }
+    }
+
+    Ok(MutationReport { task: task.to_string(), total_mutants, killed, survived })
+}
+
+/// Puts the mutated file back the moment this guard goes out of scope -
+/// including on an early return via `?` from [`test_survives`] failing,
+/// not just the happy path. Doesn't help against the process being
+/// killed outright (`SIGKILL`, a hard `timeout`); that's what
+/// [`ensure_clean_worktree`] is for.
+struct RestoreOnDrop<'a> {
+    path: &'a Path,
+    original: &'a str,
+}
+
+impl Drop for RestoreOnDrop<'_> {
+    fn drop(&mut self) {
+        let _ = fs::write(self.path, self.original);
+    }
+}
+
+/// Runs the crate's test suite with the mutation already written to
+/// disk. A mutant "survives" only if the crate still compiles, still
+/// has at least one test, and every test still passes - anything else
+/// (a compile error, a failing test) means the mutation was caught.
+fn test_survives(manifest_path: &Path, sandbox: &Path) -> Result<bool> {
+    let output = Command::new("cargo")
+        .arg("test")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .env("CARGO_TARGET_DIR", sandbox)
+        .output()
+        .context("failed to run cargo test")?;
+
+    if !output.status.success() {
+        return Ok(false);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_passed_count(&stdout) > 0)
+}
+
+/// Sums `N passed` across every `test result:` line in libtest's
+/// human-readable output - a workspace crate with both unit and
+/// integration test binaries prints one such line per binary.
+fn parse_passed_count(output: &str) -> u32 {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("test result: ok. ")?.split_whitespace().next())
+        .filter_map(|count| count.parse::<u32>().ok())
+        .sum()
+}
+
+struct Mutant {
+    source: String,
+    description: String,
+}
+
+/// Counts how many sites [`Mutator`] would visit in `source` - run with
+/// no target index, so it mutates nothing and just counts.
+fn mutation_site_count(source: &str) -> Result<usize> {
+    let mut file = syn::parse_file(source).context("parsing source for mutation site count")?;
+    let mut mutator = Mutator { target: None, current: 0, description: None };
+    mutator.visit_file_mut(&mut file);
+    Ok(mutator.current)
+}
+
+/// Re-parses `source` fresh and mutates only the `target`th site -
+/// re-parsing rather than mutating a shared AST keeps every mutant
+/// independent of the others.
+fn build_mutant(source: &str, target: usize) -> Result<Mutant> {
+    let mut file = syn::parse_file(source).context("parsing source to build a mutant")?;
+    let mut mutator = Mutator { target: Some(target), current: 0, description: None };
+    mutator.visit_file_mut(&mut file);
+    let description = mutator
+        .description
+        .unwrap_or_else(|| format!("mutation site {target} (description unavailable)"));
+    Ok(Mutant { source: prettyplease::unparse(&file), description })
+}
+
+/// Walks every expression in the file, flipping the `target`th
+/// comparison operator or guard condition it finds and recording what it
+/// did. Counts sites the same way whether or not `target` is set, so a
+/// counting pass and a mutating pass visit sites in the same order.
+struct Mutator {
+    target: Option<usize>,
+    current: usize,
+    description: Option<String>,
+}
+
+impl Mutator {
+    fn is_target(&self) -> bool {
+        self.target == Some(self.current)
+    }
+}
+
+impl VisitMut for Mutator {
+    fn visit_expr_binary_mut(&mut self, node: &mut ExprBinary) {
+        if let Some(flipped) = flip_comparison(&node.op) {
+            if self.is_target() {
+                self.description = Some(format!("flip `{}` to `{}`", quote!(#node), {
+                    let mut flipped_node = node.clone();
+                    flipped_node.op = flipped;
+                    quote!(#flipped_node)
+                }));
+                node.op = flipped;
+            }
+            self.current += 1;
+        }
+        syn::visit_mut::visit_expr_binary_mut(self, node);
+    }
+
+    fn visit_expr_if_mut(&mut self, node: &mut ExprIf) {
+        if self.is_target() {
+            let cond = &node.cond;
+            self.description = Some(format!("force guard `if {}` to never trigger", quote!(#cond)));
+            *node.cond = syn::parse_quote!(false);
+        }
+        self.current += 1;
+        syn::visit_mut::visit_expr_if_mut(self, node);
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut ItemMod) {
+        if is_test_mod(node) {
+            return;
+        }
+        syn::visit_mut::visit_item_mod_mut(self, node);
+    }
+}
+
+/// The standard mutation-testing comparison-operator table: every
+/// relational/equality operator maps to a different one that would
+/// change at least one input/output pair's result.
+fn flip_comparison(op: &BinOp) -> Option<BinOp> {
+    let span = Span::call_site();
+    match op {
+        BinOp::Eq(_) => Some(BinOp::Ne(syn::token::Ne(span))),
+        BinOp::Ne(_) => Some(BinOp::Eq(syn::token::EqEq(span))),
+        BinOp::Lt(_) => Some(BinOp::Ge(syn::token::Ge(span))),
+        BinOp::Ge(_) => Some(BinOp::Lt(syn::token::Lt(span))),
+        BinOp::Le(_) => Some(BinOp::Gt(syn::token::Gt(span))),
+        BinOp::Gt(_) => Some(BinOp::Le(syn::token::Le(span))),
+        _ => None,
+    }
+}
+
+fn is_test_mod(node: &ItemMod) -> bool {
+    node.ident == "tests" || node.attrs.iter().any(|attr| attr.path().is_ident("cfg") && quote!(#attr).to_string().contains("test"))
+}
+
+/// Every `.rs` file under `crate_dir/src` (or the bare crate root, for
+/// an ink! contract's `lib.rs`), skipping test/build scaffolding -
+/// mirrors [`api-diff`](../api-diff)'s `source_files`.
+fn source_files(crate_dir: &Path) -> Result<Vec<PathBuf>> {
+    let src_dir = crate_dir.join("src");
+    let root = if src_dir.is_dir() { src_dir } else { crate_dir.to_path_buf() };
+
+    let mut files = Vec::new();
+    walk(&root, &mut files)?;
+    Ok(files
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter(|path| {
+            path.file_name().and_then(|name| name.to_str()).is_some_and(|name| !SCAFFOLDING_FILES.contains(&name))
+        })
+        .collect())
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) == Some("target") {
+                continue;
+            }
+            walk(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
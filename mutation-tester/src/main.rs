@@ -0,0 +1,43 @@
+//! CLI front end for the `mutation-tester` library: prints a task's
+//! mutation report as JSON.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(about = "Mutation-test a task's solution crate against its own test suite")]
+struct Cli {
+    /// Task folder name, e.g. "1-low-level-intro-to-polkadot".
+    task: String,
+
+    /// Crate to mutation-test, relative to the repo root. Defaults to
+    /// "<task>/solution", i.e. this repo's own reference solution.
+    #[arg(long)]
+    crate_dir: Option<PathBuf>,
+
+    /// Write the JSON report here instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = mutation_tester::workspace_root()?;
+    let crate_dir = cli
+        .crate_dir
+        .unwrap_or_else(|| PathBuf::from(&cli.task).join("solution"));
+    let manifest_path = root.join(&crate_dir).join("Cargo.toml");
+    if !manifest_path.exists() {
+        bail!("no Cargo.toml at {}", manifest_path.display());
+    }
+
+    let report = mutation_tester::run(&cli.task, &crate_dir, &manifest_path)?;
+    let json = serde_json::to_string_pretty(&report)?;
+    match cli.out {
+        Some(path) => std::fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?,
+        None => println!("{json}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Thin `wasm-bindgen` wrappers around this repo's off-chain task crates -
+//! [Task 1](../../1-low-level-intro-to-polkadot)'s voting library,
+//! [Task 4](../../4-scale-codec-exercise)'s SCALE codec, and
+//! [Task 19](../../19-ss58-address-codec)'s SS58 codec - so a web page can
+//! run the exercises without a local Rust toolchain. Each wrapper exposes
+//! just enough of its task's API to drive that exercise from JS; see the
+//! task crates themselves for the real surface this leaves out.
+
+use wasm_bindgen::prelude::*;
+
+use scale_codec_exercise::{Compact, Decode, Encode};
+use voting_state_machine::{Timestamp, UserId, VoteOption, VotingState};
+
+/// JS-facing handle on a [`VotingState`], walking it through the same
+/// `register_voter`/`add_vote_option`/`cast_vote` calls Task 1's own tests
+/// use, with [`voting_state_machine::VoteError`] flattened to a message
+/// string since `wasm-bindgen` can't export a data-carrying Rust enum.
+#[wasm_bindgen]
+pub struct JsVotingState(VotingState);
+
+#[wasm_bindgen]
+impl JsVotingState {
+    #[wasm_bindgen(constructor)]
+    pub fn new(admin_id: UserId) -> Self {
+        Self(VotingState::new(admin_id))
+    }
+
+    #[wasm_bindgen(js_name = addVoteOption)]
+    pub fn add_vote_option(&mut self, option: VoteOption) {
+        self.0.add_vote_option(option);
+    }
+
+    #[wasm_bindgen(js_name = registerVoter)]
+    pub fn register_voter(&mut self, caller_id: UserId) -> Result<(), JsError> {
+        self.0.register_voter(caller_id).map_err(|err| JsError::new(&format!("{err:?}")))
+    }
+
+    #[wasm_bindgen(js_name = castVote)]
+    pub fn cast_vote(&mut self, caller_id: UserId, vote_option: VoteOption, now: Timestamp) -> Result<(), JsError> {
+        self.0
+            .cast_vote(caller_id, vote_option, now)
+            .map_err(|err| JsError::new(&format!("{err:?}")))
+    }
+
+    pub fn phase(&self) -> String {
+        format!("{:?}", self.0.current_voting_state)
+    }
+
+    #[wasm_bindgen(js_name = voteCount)]
+    pub fn vote_count(&self, option: VoteOption) -> Option<u64> {
+        self.0.vote_counts.get(&option).copied()
+    }
+}
+
+/// Encodes `value` as a SCALE [`Compact`] integer.
+#[wasm_bindgen(js_name = encodeCompact)]
+pub fn encode_compact(value: u64) -> Vec<u8> {
+    Compact(value).encode()
+}
+
+/// Decodes a SCALE [`Compact`] integer from the front of `bytes`, ignoring
+/// any trailing bytes - the same "decode what you need, leave the rest"
+/// contract [`Decode::decode`] has everywhere else in Task 4.
+#[wasm_bindgen(js_name = decodeCompact)]
+pub fn decode_compact(bytes: &[u8]) -> Result<u64, JsError> {
+    let mut input = bytes;
+    Compact::decode(&mut input).map(|compact| compact.0).map_err(|err| JsError::new(&format!("{err:?}")))
+}
+
+/// Encodes a 32-byte account id as an SS58 address under `prefix`.
+#[wasm_bindgen(js_name = ss58Encode)]
+pub fn ss58_encode(prefix: u16, account_id: &[u8]) -> Result<String, JsError> {
+    let account_id: [u8; 32] = account_id
+        .try_into()
+        .map_err(|_| JsError::new("account id must be 32 bytes"))?;
+    ss58_address_codec::encode(prefix, &account_id).map_err(|err| JsError::new(&format!("{err:?}")))
+}
+
+/// Decodes an SS58 address, returning its network prefix and 32-byte
+/// account id packed into one [`Ss58Address`].
+#[wasm_bindgen(js_name = ss58Decode)]
+pub fn ss58_decode(address: &str) -> Result<Ss58Address, JsError> {
+    let (prefix, account_id) = ss58_address_codec::decode(address).map_err(|err| JsError::new(&format!("{err:?}")))?;
+    Ok(Ss58Address { prefix, account_id: account_id.to_vec() })
+}
+
+/// The `(prefix, account_id)` pair [`ss58_decode`] returns - `wasm-bindgen`
+/// can't export a tuple directly, so this is its JS-facing stand-in.
+#[wasm_bindgen]
+pub struct Ss58Address {
+    prefix: u16,
+    account_id: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Ss58Address {
+    #[wasm_bindgen(getter)]
+    pub fn prefix(&self) -> u16 {
+        self.prefix
+    }
+
+    #[wasm_bindgen(getter, js_name = accountId)]
+    pub fn account_id(&self) -> Vec<u8> {
+        self.account_id.clone()
+    }
+}
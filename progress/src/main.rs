@@ -0,0 +1,133 @@
+//! Tracks which study-group tasks a learner has completed, by running
+//! each task's own test suite through the [`grader`] library rather than
+//! taking a learner's word for it.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+#[derive(Parser)]
+#[command(about = "Track which study-group tasks you've completed")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a task's own test suite and mark it complete if every test passes.
+    Complete {
+        /// Task folder name, e.g. "17-hashing-utilities".
+        task: String,
+
+        /// Crate to grade, relative to the repo root. Defaults to
+        /// "<task>/solution", the same default `grader` uses.
+        #[arg(long)]
+        crate_dir: Option<PathBuf>,
+    },
+    /// Print a course map with a completion percentage and the next task.
+    Status,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct State {
+    completed: BTreeSet<String>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = grader::workspace_root()?;
+    let state_path = root.join(".progress.json");
+
+    match cli.command {
+        Command::Complete { task, crate_dir } => complete(&root, &state_path, &task, crate_dir),
+        Command::Status => status(&root, &state_path),
+    }
+}
+
+fn load_state(state_path: &Path) -> Result<State> {
+    match std::fs::read_to_string(state_path) {
+        Ok(contents) => {
+            serde_json::from_str(&contents).with_context(|| format!("parsing {}", state_path.display()))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(State::default()),
+        Err(err) => Err(err).with_context(|| format!("reading {}", state_path.display())),
+    }
+}
+
+fn save_state(state_path: &Path, state: &State) -> Result<()> {
+    let json = serde_json::to_string_pretty(state)?;
+    std::fs::write(state_path, json).with_context(|| format!("writing {}", state_path.display()))
+}
+
+fn complete(root: &Path, state_path: &Path, task: &str, crate_dir: Option<PathBuf>) -> Result<()> {
+    let crate_dir = crate_dir.unwrap_or_else(|| PathBuf::from(task).join("solution"));
+    let manifest_path = root.join(&crate_dir).join("Cargo.toml");
+    if !manifest_path.exists() {
+        bail!("no Cargo.toml at {}", manifest_path.display());
+    }
+
+    println!("running {task}'s test suite...");
+    let report = grader::grade(task, &crate_dir, &manifest_path)?;
+    if !report.fully_passing() {
+        match &report.compile_errors {
+            Some(errors) => println!("{task} does not compile yet:\n{errors}"),
+            None => println!(
+                "{task}: {} of {} tests passing - not complete yet",
+                report.passed,
+                report.tests.len()
+            ),
+        }
+        return Ok(());
+    }
+
+    let mut state = load_state(state_path)?;
+    state.completed.insert(task.to_string());
+    save_state(state_path, &state)?;
+    println!("{task} complete! ({} tests passing)", report.passed);
+    Ok(())
+}
+
+fn status(root: &Path, state_path: &Path) -> Result<()> {
+    let state = load_state(state_path)?;
+    let tasks = list_tasks(root)?;
+
+    for (number, name) in &tasks {
+        let mark = if state.completed.contains(name) { "[x]" } else { "[ ]" };
+        println!("{mark} {number}: {name}");
+    }
+
+    let total = tasks.len();
+    let done = tasks.iter().filter(|(_, name)| state.completed.contains(name)).count();
+    let percent = if total == 0 { 0.0 } else { 100.0 * done as f64 / total as f64 };
+    println!("\n{done}/{total} tasks complete ({percent:.0}%)");
+
+    match tasks.iter().find(|(_, name)| !state.completed.contains(name)) {
+        Some((_, name)) => println!("next up: {name}"),
+        None => println!("all tasks complete!"),
+    }
+    Ok(())
+}
+
+/// Every `<N>-name` task folder at the repo root, sorted by number - the
+/// same numbering `xtask new-task` extends, skipping this repo's own
+/// unnumbered utility crates (`common`, `grader`, `xtask`, ...).
+fn list_tasks(root: &Path) -> Result<Vec<(u32, String)>> {
+    let mut tasks = Vec::new();
+    for entry in std::fs::read_dir(root).with_context(|| format!("reading {}", root.display()))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else { continue };
+        let Some(number) = name.split('-').next().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        tasks.push((number, name));
+    }
+    tasks.sort_by_key(|(number, _)| *number);
+    Ok(tasks)
+}
@@ -0,0 +1,254 @@
+//! Random multi-actor action sequences against [`voting_state_machine`],
+//! checking the invariants unit tests don't think to ask for after
+//! every single step instead of just at a few hand-picked points.
+//!
+//! [`run`] drives many short, independently-seeded trials rather than
+//! one long one, so a failure is reproducible from its own small seed
+//! and - since sequences are short to begin with - cheap to shrink.
+//! When a trial violates an invariant, [`minimize`] repeatedly drops
+//! actions from the front of the failing sequence and replays it,
+//! keeping a drop only if the exact same violation still reproduces,
+//! until no further action can be removed.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use voting_state_machine::{transition, Action, Timestamp, UserId, VoteOption, VotingState};
+
+/// One step a fuzzed actor can take. `Pause` and `Reset` are named for
+/// what an operator would call them; they drive the same
+/// [`Action::StartRegistration`] / [`Action::ResetApplication`] the
+/// library itself exposes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzAction {
+    Register { caller: UserId },
+    Vote { caller: UserId, option: VoteOption },
+    StartVoting { caller: UserId, duration: Timestamp },
+    Pause { caller: UserId },
+    EndVoting { caller: UserId },
+    Reset { caller: UserId },
+    TimeWarp { delta: Timestamp },
+}
+
+pub struct SimConfig {
+    pub admin: UserId,
+    pub actors: Vec<UserId>,
+    pub options: Vec<VoteOption>,
+    pub base_seed: u64,
+    pub trials: u64,
+    pub steps_per_trial: usize,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            admin: "admin".to_string(),
+            actors: vec!["alice".to_string(), "bob".to_string(), "carol".to_string(), "dave".to_string()],
+            options: vec!["yes".to_string(), "no".to_string(), "abstain".to_string()],
+            base_seed: 0,
+            trials: 2_000,
+            steps_per_trial: 40,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Failure {
+    pub trial: u64,
+    pub seed: u64,
+    pub violation: String,
+    pub sequence: Vec<FuzzAction>,
+    pub minimized: Vec<FuzzAction>,
+}
+
+pub struct Report {
+    pub trials_run: u64,
+    pub failure: Option<Failure>,
+}
+
+/// Runs `config.trials` independent trials of `config.steps_per_trial`
+/// random actions each, seeded from `config.base_seed + trial`, and
+/// stops at the first invariant violation.
+pub fn run(config: &SimConfig) -> Report {
+    for trial in 0..config.trials {
+        let seed = config.base_seed.wrapping_add(trial);
+        if let Some((violation, sequence)) = run_trial(config, seed) {
+            let minimized = minimize(config, &sequence, &violation);
+            return Report {
+                trials_run: trial + 1,
+                failure: Some(Failure { trial, seed, violation, sequence, minimized }),
+            };
+        }
+    }
+    Report { trials_run: config.trials, failure: None }
+}
+
+/// Plays one random sequence out and returns `(violation, sequence so
+/// far)` the first time [`check_invariants`] fails - the sequence is
+/// truncated at the failing step, since nothing after it matters.
+fn run_trial(config: &SimConfig, seed: u64) -> Option<(String, Vec<FuzzAction>)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut state = VotingState::new(config.admin.clone());
+    let mut now: Timestamp = 0;
+    let mut sequence = Vec::with_capacity(config.steps_per_trial);
+
+    for option in &config.options {
+        state.add_vote_option(option.clone());
+    }
+
+    for _ in 0..config.steps_per_trial {
+        let action = random_action(&mut rng, config);
+        apply(&mut state, &mut now, &action);
+        sequence.push(action);
+        if let Err(violation) = check_invariants(&state, now) {
+            return Some((violation, sequence));
+        }
+    }
+    None
+}
+
+fn random_action(rng: &mut StdRng, config: &SimConfig) -> FuzzAction {
+    let actor = |rng: &mut StdRng| config.actors[rng.gen_range(0..config.actors.len())].clone();
+    // Transitions are usually driven by the admin, but occasionally by a
+    // random actor too, so rejecting a non-admin caller is part of what
+    // gets exercised, not just the happy path.
+    let caller = |rng: &mut StdRng| {
+        if rng.gen_ratio(4, 5) {
+            config.admin.clone()
+        } else {
+            actor(rng)
+        }
+    };
+
+    match rng.gen_range(0..7) {
+        0 => FuzzAction::Register { caller: actor(rng) },
+        1 => FuzzAction::Vote { caller: actor(rng), option: config.options[rng.gen_range(0..config.options.len())].clone() },
+        2 => FuzzAction::StartVoting { caller: caller(rng), duration: rng.gen_range(0..20) },
+        3 => FuzzAction::Pause { caller: caller(rng) },
+        4 => FuzzAction::EndVoting { caller: caller(rng) },
+        5 => FuzzAction::Reset { caller: caller(rng) },
+        _ => FuzzAction::TimeWarp { delta: rng.gen_range(0..25) },
+    }
+}
+
+/// Applies one [`FuzzAction`] to `state`, advancing `now` for
+/// [`FuzzAction::TimeWarp`]. Every other outcome - success or a
+/// [`voting_state_machine::VoteError`] - is discarded: a rejected
+/// action is exactly as valid a thing for the fuzzer to have tried as
+/// an accepted one, and [`check_invariants`] is what decides whether
+/// anything actually went wrong.
+fn apply(state: &mut VotingState, now: &mut Timestamp, action: &FuzzAction) {
+    match action {
+        FuzzAction::Register { caller } => {
+            let _ = state.register_voter(caller.clone());
+        }
+        FuzzAction::Vote { caller, option } => {
+            let _ = state.cast_vote(caller.clone(), option.clone(), *now);
+        }
+        FuzzAction::StartVoting { caller, duration } => {
+            let _ = transition(state, caller.clone(), Action::StartVoting { duration: *duration }, *now);
+        }
+        FuzzAction::Pause { caller } => {
+            let _ = transition(state, caller.clone(), Action::StartRegistration, *now);
+        }
+        FuzzAction::EndVoting { caller } => {
+            let _ = transition(state, caller.clone(), Action::EndVoting { override_quorum: false }, *now);
+        }
+        FuzzAction::Reset { caller } => {
+            let _ = transition(state, caller.clone(), Action::ResetApplication, *now);
+        }
+        FuzzAction::TimeWarp { delta } => {
+            *now += delta;
+        }
+    }
+}
+
+/// Checks everything about `state` that should hold no matter which
+/// sequence of actions produced it, returning the first violation found
+/// as a human-readable description.
+fn check_invariants(state: &VotingState, now: Timestamp) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for voter in &state.registered_voters {
+        if !seen.insert(voter) {
+            return Err(format!("{voter} is registered more than once"));
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for voter in &state.votes_cast {
+        if !seen.insert(voter) {
+            return Err(format!("{voter} voted more than once"));
+        }
+        if !state.registered_voters.contains(voter) {
+            return Err(format!("{voter} voted without ever being registered"));
+        }
+    }
+
+    if state.votes_cast.len() > state.registered_voters.len() {
+        return Err("more votes were cast than there are registered voters".to_string());
+    }
+
+    let tallied: u64 = state.vote_counts.values().sum();
+    if tallied != state.votes_cast.len() as u64 {
+        return Err(format!(
+            "vote_counts sums to {tallied} but votes_cast has {} entries",
+            state.votes_cast.len()
+        ));
+    }
+
+    if state.voting_start_time > state.voting_end_time {
+        return Err(format!(
+            "voting_start_time {} is after voting_end_time {}",
+            state.voting_start_time, state.voting_end_time
+        ));
+    }
+
+    use voting_state_machine::VotingPhase;
+    if state.current_voting_state == VotingPhase::ResultsFinalized && now < state.voting_end_time {
+        return Err(format!("results were finalized at {now}, before voting_end_time {}", state.voting_end_time));
+    }
+
+    Ok(())
+}
+
+/// Re-runs `sequence` from a fresh state and returns the first
+/// invariant violation encountered, if any - used by [`minimize`] to
+/// check whether a shrunk sequence still reproduces the same failure.
+fn replay(config: &SimConfig, sequence: &[FuzzAction]) -> Option<String> {
+    let mut state = VotingState::new(config.admin.clone());
+    let mut now: Timestamp = 0;
+    for option in &config.options {
+        state.add_vote_option(option.clone());
+    }
+    for action in sequence {
+        apply(&mut state, &mut now, action);
+        if let Err(violation) = check_invariants(&state, now) {
+            return Some(violation);
+        }
+    }
+    None
+}
+
+/// Shrinks `sequence` to the smallest prefix-preserving subsequence
+/// that still reproduces `target` exactly, by repeatedly trying to
+/// drop one action at a time and keeping the drop only when
+/// [`replay`] still fails with the same message.
+fn minimize(config: &SimConfig, sequence: &[FuzzAction], target: &str) -> Vec<FuzzAction> {
+    let mut sequence = sequence.to_vec();
+    loop {
+        let mut shrunk = false;
+        let mut i = 0;
+        while i < sequence.len() {
+            let mut candidate = sequence.clone();
+            candidate.remove(i);
+            if replay(config, &candidate).as_deref() == Some(target) {
+                sequence = candidate;
+                shrunk = true;
+            } else {
+                i += 1;
+            }
+        }
+        if !shrunk {
+            return sequence;
+        }
+    }
+}
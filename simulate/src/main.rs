@@ -0,0 +1,49 @@
+//! CLI front end for the `simulate` library: runs the trials and
+//! prints either a clean bill of health or the minimized failing
+//! sequence.
+
+use anyhow::Result;
+use clap::Parser;
+use simulate::SimConfig;
+
+#[derive(Parser)]
+#[command(about = "Fuzz the voting state machine for invariant violations")]
+struct Cli {
+    /// How many independent random sequences to try.
+    #[arg(long, default_value_t = 2_000)]
+    trials: u64,
+
+    /// How many actions each sequence tries before giving up on it.
+    #[arg(long, default_value_t = 40)]
+    steps: usize,
+
+    /// Seed for the first trial; later trials use seed + trial index,
+    /// so re-running with the same seed reproduces the same run.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = SimConfig { base_seed: cli.seed, trials: cli.trials, steps_per_trial: cli.steps, ..SimConfig::default() };
+
+    let report = simulate::run(&config);
+    match report.failure {
+        None => {
+            println!("{} trials x {} steps each: no invariant violations", report.trials_run, config.steps_per_trial);
+            Ok(())
+        }
+        Some(failure) => {
+            println!("invariant violated on trial {} (seed {}): {}", failure.trial, failure.seed, failure.violation);
+            println!(
+                "minimized {} action(s) down to {}:",
+                failure.sequence.len(),
+                failure.minimized.len()
+            );
+            for action in &failure.minimized {
+                println!("  {action:?}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! A small golden-file snapshot helper for test output that's easier to
+//! eyeball as a diff than to encode as a pile of individual field
+//! assertions - a tally report, an event log, a rendered transcript.
+//! Deliberately minimal rather than pulling in `insta`: one file per
+//! snapshot, one environment variable to accept changes, no inline
+//! literal-rewriting machinery.
+//!
+//! Call [`assert_snapshot!`] with a name and the value to check. The
+//! first run creates `snapshots/<name>.snap` next to the calling crate's
+//! `Cargo.toml`; every run after that diffs against it and panics if the
+//! value changed. Re-run with `UPDATE_SNAPSHOTS=1` to accept new output
+//! as the new golden file.
+//!
+//! ```ignore
+//! snapshot::assert_snapshot!("phase_after_vote", format!("{:?}", state.current_voting_state));
+//! ```
+
+use std::path::{Path, PathBuf};
+
+/// The guts of [`assert_snapshot!`] - a macro so `manifest_dir` resolves
+/// to the *caller's* crate root via `env!("CARGO_MANIFEST_DIR")`, not
+/// this crate's.
+#[doc(hidden)]
+pub fn check(manifest_dir: &str, name: &str, actual: &str) {
+    let path = snapshot_path(manifest_dir, name);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().expect("snapshot path always has a parent"))
+            .expect("creating snapshots directory");
+        std::fs::write(&path, actual).expect("writing snapshot");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("no snapshot at {} - run with UPDATE_SNAPSHOTS=1 to create it", path.display())
+    });
+    assert_eq!(
+        expected,
+        actual,
+        "snapshot {} has changed - run with UPDATE_SNAPSHOTS=1 to accept the new output",
+        path.display()
+    );
+}
+
+fn snapshot_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir).join("snapshots").join(format!("{name}.snap"))
+}
+
+/// Asserts `$actual` (anything that implements `Display`, typically a
+/// `String`) matches the golden file `snapshots/$name.snap` in the
+/// calling crate.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::check(env!("CARGO_MANIFEST_DIR"), $name, &$actual.to_string())
+    };
+}
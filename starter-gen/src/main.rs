@@ -0,0 +1,172 @@
+//! Derives a `starter/` crate from a task's `solution/` crate by replacing
+//! every function body with a `todo!()` (carrying the function's doc
+//! comment as the panic message, where it has one). The two can never
+//! drift apart because the starter is regenerated from the solution
+//! rather than hand-maintained alongside it.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use quote::quote;
+use syn::visit_mut::VisitMut;
+use syn::{Attribute, Block, Expr, ImplItemFn, ItemFn, ItemMod, Lit, Meta};
+
+/// Support files that are test/build scaffolding rather than the logic a
+/// student is meant to implement, per the layout Task 5's pallet already
+/// established (`mock.rs`/`tests.rs`/`benchmarking.rs`/`weights.rs`).
+/// These are copied into the starter untouched.
+const SCAFFOLDING_FILES: &[&str] = &["mock.rs", "tests.rs", "benchmarking.rs", "weights.rs"];
+
+#[derive(Parser)]
+#[command(about = "Generate a starter/ crate from a task's solution/ crate")]
+struct Cli {
+    /// Task folder name, e.g. "17-hashing-utilities".
+    task: String,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = workspace_root()?;
+    let solution_dir = root.join(&cli.task).join("solution");
+    let starter_dir = root.join(&cli.task).join("starter");
+
+    generate(&solution_dir, &starter_dir)?;
+    println!("generated {}", starter_dir.display());
+    Ok(())
+}
+
+fn workspace_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("starter-gen's own crate has no parent directory")
+}
+
+fn generate(solution_dir: &Path, starter_dir: &Path) -> Result<()> {
+    for entry in walk(solution_dir)? {
+        let relative = entry.strip_prefix(solution_dir)?;
+        let dest = starter_dir.join(relative);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let is_rust_source = entry.extension().is_some_and(|ext| ext == "rs");
+        let is_scaffolding = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| SCAFFOLDING_FILES.contains(&name));
+
+        let file_name = entry.file_name().and_then(|n| n.to_str());
+        if is_rust_source && !is_scaffolding {
+            stub_file(&entry, &dest)?;
+        } else if file_name == Some("Cargo.toml") {
+            copy_manifest(&entry, &dest)?;
+        } else if file_name != Some("Cargo.lock") {
+            std::fs::copy(&entry, &dest)
+                .with_context(|| format!("copying {} to {}", entry.display(), dest.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn walk(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) == Some("target") {
+                continue;
+            }
+            files.extend(walk(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Copies `Cargo.toml` as-is, then marks the starter crate as the root of
+/// its own (empty) workspace. Its path dependencies stay at the same
+/// relative depth as the solution crate's, but without this, Cargo would
+/// otherwise try to fold the starter into this repo's own workspace the
+/// moment anyone runs a Cargo command inside it.
+fn copy_manifest(src: &Path, dest: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(src).with_context(|| format!("reading {}", src.display()))?;
+    std::fs::write(dest, format!("{contents}\n[workspace]\n"))
+        .with_context(|| format!("writing {}", dest.display()))
+}
+
+fn stub_file(src: &Path, dest: &Path) -> Result<()> {
+    let contents = std::fs::read_to_string(src).with_context(|| format!("reading {}", src.display()))?;
+    let mut file = syn::parse_file(&contents).with_context(|| format!("parsing {}", src.display()))?;
+    Stubber.visit_file_mut(&mut file);
+    std::fs::write(dest, prettyplease::unparse(&file))
+        .with_context(|| format!("writing {}", dest.display()))
+}
+
+struct Stubber;
+
+impl VisitMut for Stubber {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        if !is_test_fn(&node.attrs) {
+            *node.block = stub_block(&node.attrs);
+        }
+    }
+
+    fn visit_impl_item_fn_mut(&mut self, node: &mut ImplItemFn) {
+        if !is_test_fn(&node.attrs) {
+            node.block = stub_block(&node.attrs);
+        }
+    }
+
+    fn visit_item_mod_mut(&mut self, node: &mut ItemMod) {
+        if is_test_mod(node) {
+            return;
+        }
+        syn::visit_mut::visit_item_mod_mut(self, node);
+    }
+}
+
+fn is_test_fn(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("test"))
+}
+
+fn is_test_mod(node: &ItemMod) -> bool {
+    node.ident == "tests"
+        || node.attrs.iter().any(|attr| {
+            attr.path().is_ident("cfg") && quote!(#attr).to_string().contains("test")
+        })
+}
+
+/// Builds a `{ todo!(...) }` block, using the function's doc comment (if
+/// any) as the `todo!()` message so a student sees what's expected of
+/// them the moment the stub panics.
+fn stub_block(attrs: &[Attribute]) -> Block {
+    let doc = doc_comment(attrs);
+    let tokens = match doc {
+        Some(message) => quote! { { todo!(#message) } },
+        None => quote! { { todo!() } },
+    };
+    syn::parse2(tokens).expect("todo!() stub is always a valid block")
+}
+
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    Lit::Str(s) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
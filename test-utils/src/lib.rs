@@ -0,0 +1,102 @@
+//! Shared test fixtures for the Task 1 voting state machine and anything
+//! built on it: a clock fixture, named account ids, a builder that walks
+//! a [`VotingState`] through the same `register_voter`/`transition` calls
+//! a real caller would make, and a couple of assertion helpers for the
+//! checks tests make over and over.
+
+pub use voting_state_machine::{
+    Action, Clock, FixedClock, UserId, VoteError, VotingPhase, VotingState, VotingStateBuilder as ValidatingBuilder,
+};
+
+/// [`FixedClock`] under the name test code reaches for when it's standing
+/// in for "the current time" rather than being advanced through a real
+/// schedule.
+pub type MockClock = FixedClock;
+
+pub fn alice() -> UserId {
+    "alice".to_string()
+}
+
+pub fn bob() -> UserId {
+    "bob".to_string()
+}
+
+pub fn admin() -> UserId {
+    "admin".to_string()
+}
+
+/// Builds a [`VotingState`] through the same calls a caller would make,
+/// so a test can ask for "registration open with two voters" or "voting
+/// active with one candidate" without re-deriving the right sequence of
+/// `register_voter`/`transition` calls at every call site.
+///
+/// A thin wrapper around the library's own [`ValidatingBuilder`], filling
+/// in the `admin` fixture when a test doesn't care who the admin is and
+/// `.expect()`-ing validation instead of returning a `Result` - a
+/// misconfigured test fixture is a bug in the test, not something a test
+/// should have to handle.
+#[derive(Default)]
+pub struct VotingStateBuilder {
+    inner: ValidatingBuilder,
+    admin_id: Option<UserId>,
+}
+
+impl VotingStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_admin(mut self, admin_id: UserId) -> Self {
+        self.admin_id = Some(admin_id);
+        self
+    }
+
+    pub fn with_candidate(mut self, option: &str) -> Self {
+        self.inner = self.inner.with_option(option.to_string());
+        self
+    }
+
+    pub fn with_voter(mut self, voter: UserId) -> Self {
+        self.inner = self.inner.with_voter(voter);
+        self
+    }
+
+    /// Drives the state through `Action::StartVoting { duration }` at
+    /// time `at`, landing it in `VotingPhase::VotingActive`.
+    pub fn voting_started(mut self, duration: u64, at: u64) -> Self {
+        self.inner = self.inner.voting_active(duration, at);
+        self
+    }
+
+    pub fn build(self) -> VotingState {
+        let admin_id = self.admin_id.unwrap_or_else(admin);
+        self.inner
+            .with_admin(admin_id)
+            .build()
+            .expect("fixture state should be valid")
+    }
+}
+
+/// Asserts `state` is in `phase`, printing both phases on failure instead
+/// of just `false`.
+pub fn assert_phase(state: &VotingState, phase: VotingPhase) {
+    assert_eq!(state.current_voting_state, phase);
+}
+
+/// Asserts `option` has exactly `count` votes recorded.
+pub fn assert_vote_count(state: &VotingState, option: &str, count: u64) {
+    assert_eq!(state.vote_counts.get(option).copied().unwrap_or(0), count);
+}
+
+/// Renders `state`'s phase and tally as a deterministic multi-line
+/// string - `vote_counts` is already a `BTreeMap`, so iteration order
+/// needs no sorting of its own. Meant for snapshot tests, where the
+/// point is a stable, eyeball-able report rather than individual field
+/// assertions.
+pub fn render_report(state: &VotingState) -> String {
+    let mut report = format!("phase: {:?}\n", state.current_voting_state);
+    for (option, count) in &state.vote_counts {
+        report.push_str(&format!("{option}: {count}\n"));
+    }
+    report
+}
@@ -0,0 +1,336 @@
+//! Migrates a Task 2 [`Wishlist`](../../2-ink-intro) contract's items from
+//! an already-deployed instance to a freshly deployed one, reading the
+//! source through a `ContractsApi_call` dry-run (a read-only contract
+//! state query over RPC, not a submitted extrinsic) and re-submitting
+//! each item to the destination with real `add_item` calls.
+//!
+//! ## Scope
+//!
+//! The request this tool grew out of pictures a schema migration: an old
+//! `Wishlist` storing items in an `ink::storage::StorageVec`, a new one
+//! storing them in a `Mapping`, and this tool bridging the two by
+//! decoding the old contract's raw child-storage layout by hand. That
+//! schema split doesn't exist in this repo - `2-ink-intro`'s `Wishlist`
+//! has only ever had one shape, `items: Vec<String>`, and its `new`
+//! constructor is the only way anything here has ever deployed one. So
+//! rather than hand-decode a legacy layout that was never written,
+//! `--seed-demo-source` below deploys a source instance from the same
+//! bundle as the destination and seeds it with a few items, and the
+//! migration itself goes through `get_items`/`add_item` - the contract's
+//! own public interface - the same as a real migration would if an old
+//! deployment's storage changed shape but its getters didn't. Swapping
+//! `read_items`'s dry-run call for a raw child-storage read is the one
+//! change a genuine StorageVec-era contract would need here.
+//!
+//! See `6-subxt-contract-client` for why this reaches for subxt's
+//! *dynamic* API (no `Config` tied to one node build) and
+//! `contract-report` for why a dry run's `ContractResult` is decoded by
+//! hand rather than through subxt's generated types.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use contract_transcode::{ContractMessageTranscoder, Value as TranscodedValue};
+use parity_scale_codec::{Decode, Encode};
+use scale_decode::DecodeAsType;
+use scale_value::Value;
+use subxt::dynamic;
+use subxt::events::DecodeAsEvent;
+use subxt::utils::AccountId32;
+use subxt::{OnlineClient, SubstrateConfig};
+use subxt_signer::sr25519::{dev, Keypair};
+
+const GAS_LIMIT_REF_TIME: u64 = 10_000_000_000;
+const GAS_LIMIT_PROOF_SIZE: u64 = 1_000_000;
+
+#[derive(Parser)]
+#[command(about = "Migrates a Task 2 wishlist's items from one deployed instance to a fresh one")]
+struct Cli {
+    /// Path to the Task 2 `wishlist` `.contract` bundle, used to deploy
+    /// the destination (and, with `--seed-demo-source`, the source too).
+    #[arg(long)]
+    bundle: PathBuf,
+
+    /// Address of an already-deployed source `Wishlist` to migrate items
+    /// from. Required unless `--seed-demo-source` is passed.
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Deploy a source instance from `--bundle`, seed it with a few demo
+    /// items, and migrate those instead of reading `--from`. Lets the
+    /// tool be tried end to end without a real old deployment on hand.
+    #[arg(long)]
+    seed_demo_source: bool,
+
+    /// WebSocket URL of a running `substrate-contracts-node`. Ignored
+    /// with `--spawn-node`.
+    #[arg(long, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Spawn a `substrate-contracts-node` for this run instead of
+    /// connecting to one already running at `--url`.
+    #[arg(long)]
+    spawn_node: bool,
+}
+
+/// Mirrors `pallet_contracts::Event::Instantiated`'s fields we care
+/// about.
+#[derive(DecodeAsType)]
+struct Instantiated {
+    #[allow(dead_code)]
+    deployer: AccountId32,
+    contract: AccountId32,
+}
+
+impl DecodeAsEvent for Instantiated {
+    fn is_event(pallet_name: &str, event_name: &str) -> bool {
+        pallet_name == "Contracts" && event_name == "Instantiated"
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if cli.from.is_none() && !cli.seed_demo_source {
+        return Err(anyhow!("pass --from <address> or --seed-demo-source"));
+    }
+
+    let metadata =
+        contract_metadata::ContractMetadata::load(&cli.bundle).context("failed to load the .contract bundle")?;
+    let code = metadata
+        .source
+        .wasm
+        .ok_or_else(|| anyhow!("bundle has no embedded wasm - was it built with `cargo contract build`?"))?
+        .0;
+    let transcoder = ContractMessageTranscoder::load(&cli.bundle)
+        .context("failed to load ink metadata from the .contract bundle")?;
+
+    let _node;
+    let url = if cli.spawn_node {
+        println!("spawning a substrate-contracts-node...");
+        _node = contracts_node_harness::NodeHandle::spawn_default().context("failed to spawn substrate-contracts-node")?;
+        _node.ws_url().to_string()
+    } else {
+        cli.url.clone()
+    };
+    let api = OnlineClient::<SubstrateConfig>::from_url(&url)
+        .await
+        .with_context(|| format!("failed to connect to {url}"))?;
+
+    let alice = dev::alice();
+    let alice_id = account_id(&alice.public_key().0);
+
+    let source = match cli.from {
+        Some(address) => address.parse().context("--from is not a valid ss58/hex address")?,
+        None => {
+            println!("deploying a demo source instance...");
+            let source = instantiate(&api, &alice, code.clone(), transcoder.encode("new", [alice_id.to_string()])?).await?;
+            for item in ["telescope", "microscope", "barometer"] {
+                call(&api, &alice, &source, transcoder.encode("add_item", [format!("\"{item}\"")])?).await?;
+            }
+            source
+        }
+    };
+    println!("source wishlist: {source}");
+
+    println!("reading source items via a ContractsApi_call dry run...");
+    let items = read_items(&api, &transcoder, &alice_id, &source).await?;
+    println!("found {} item(s) to migrate", items.len());
+
+    println!("deploying the destination instance...");
+    let destination =
+        instantiate(&api, &alice, code, transcoder.encode("new", [alice_id.to_string()])?).await?;
+    println!("destination wishlist: {destination}");
+
+    for item in &items {
+        call(&api, &alice, &destination, transcoder.encode("add_item", [format!("\"{item}\"")])?).await?;
+        println!("migrated: {item}");
+    }
+
+    println!("\nmigration complete: {} item(s) moved from {source} to {destination}", items.len());
+    Ok(())
+}
+
+fn account_id(bytes: &[u8; 32]) -> AccountId32 {
+    AccountId32(*bytes)
+}
+
+/// Builds, signs and submits a `Contracts.instantiate_with_code`
+/// extrinsic, waits for it to finalize, and returns the new contract's
+/// address from the `Contracts.Instantiated` event.
+async fn instantiate(
+    api: &OnlineClient<SubstrateConfig>,
+    signer: &Keypair,
+    code: Vec<u8>,
+    constructor_data: Vec<u8>,
+) -> Result<AccountId32> {
+    let call_data: Vec<Value> = vec![
+        Value::u128(0),
+        gas_limit_value(),
+        none_value(),
+        Value::from_bytes(code),
+        Value::from_bytes(constructor_data),
+        Value::from_bytes(Vec::<u8>::new()),
+    ];
+    let tx = dynamic::tx("Contracts", "instantiate_with_code", call_data);
+
+    let events = api
+        .transactions()
+        .await
+        .context("failed to build a transactions client")?
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("failed to submit instantiate_with_code")?
+        .wait_for_finalized_success()
+        .await
+        .context("instantiate_with_code did not finalize successfully")?;
+
+    let instantiated = events
+        .find_first::<Instantiated>()
+        .context("no Contracts.Instantiated event in the instantiation block")?
+        .context("failed to decode Contracts.Instantiated")?;
+
+    Ok(instantiated.contract)
+}
+
+/// Builds, signs and submits a `Contracts.call` extrinsic carrying
+/// `message_data`, and waits for it to finalize.
+async fn call(
+    api: &OnlineClient<SubstrateConfig>,
+    signer: &Keypair,
+    contract: &AccountId32,
+    message_data: Vec<u8>,
+) -> Result<()> {
+    let dest = Value::unnamed_variant("Id", [Value::from_bytes(contract.0)]);
+    let call_data: Vec<Value> = vec![dest, Value::u128(0), gas_limit_value(), none_value(), Value::from_bytes(message_data)];
+    let tx = dynamic::tx("Contracts", "call", call_data);
+
+    api.transactions()
+        .await
+        .context("failed to build a transactions client")?
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("failed to submit call")?
+        .wait_for_finalized_success()
+        .await
+        .context("call did not finalize successfully")?;
+
+    Ok(())
+}
+
+/// Dry-runs `source.get_items()` via the `ContractsApi_call` runtime API
+/// and decodes the returned `Vec<String>` - the "read via RPC contract
+/// state query" half of the migration.
+async fn read_items(
+    api: &OnlineClient<SubstrateConfig>,
+    transcoder: &ContractMessageTranscoder,
+    origin: &AccountId32,
+    source: &AccountId32,
+) -> Result<Vec<String>> {
+    let input_data = transcoder.encode("get_items", Vec::<String>::new())?;
+
+    let mut request = Vec::new();
+    origin.encode_to(&mut request);
+    source.encode_to(&mut request);
+    0u128.encode_to(&mut request); // value
+    None::<Weight>.encode_to(&mut request); // gas_limit: None lets the node estimate
+    None::<u128>.encode_to(&mut request); // storage_deposit_limit
+    input_data.encode_to(&mut request);
+
+    let bytes = api
+        .at_current_block()
+        .await
+        .context("failed to get the current block")?
+        .runtime_apis()
+        .call_raw("ContractsApi_call", Some(&request))
+        .await
+        .context("ContractsApi_call dry run failed")?;
+
+    let decoded = CallResult::decode(&mut &bytes[..]).context("failed to decode ContractResult")?;
+    let exec = decoded.result.map_err(|_| anyhow!("get_items dry run reverted"))?;
+
+    let value = transcoder
+        .decode_message_return("get_items", &mut &exec.data[..])
+        .context("failed to decode get_items' return value")?;
+    let TranscodedValue::Seq(items) = value else {
+        return Err(anyhow!("expected get_items to return a sequence, got {value:?}"));
+    };
+    items
+        .elems()
+        .iter()
+        .map(|item| match item {
+            TranscodedValue::String(s) => Ok(s.clone()),
+            other => Err(anyhow!("expected a string item, got {other:?}")),
+        })
+        .collect()
+}
+
+/// `sp_weights::Weight`'s two fields, both SCALE `Compact`-encoded
+/// exactly like the real type - see `contract-report` for the full
+/// `ContractResult` decoding this is adapted from.
+#[derive(Encode, Decode, Default)]
+struct Weight {
+    #[codec(compact)]
+    ref_time: u64,
+    #[codec(compact)]
+    #[allow(dead_code)]
+    proof_size: u64,
+}
+
+/// Mirrors `pallet_contracts::storage::meter::StorageDeposit<Balance>`'s
+/// two variants, only so the derive consumes the right number of bytes.
+#[derive(Decode)]
+#[allow(dead_code)]
+enum StorageDeposit {
+    Refund(u128),
+    Charge(u128),
+}
+
+/// The leading fields of `pallet_contracts::ContractResult`, in their
+/// real declaration order.
+#[derive(Decode)]
+struct CallResult {
+    #[allow(dead_code)]
+    gas_consumed: Weight,
+    #[allow(dead_code)]
+    gas_required: Weight,
+    #[allow(dead_code)]
+    storage_deposit: StorageDeposit,
+    #[allow(dead_code)]
+    debug_message: Vec<u8>,
+    result: Result<ExecReturnValue, DispatchErrorOpaque>,
+}
+
+/// Stands in for `sp_runtime::DispatchError`: this tool never needs to
+/// interpret *why* a dry run reverted, only whether it did. Decoding it
+/// reads nothing at all - safe only because `result` is the last field
+/// [`CallResult`] decodes.
+struct DispatchErrorOpaque;
+
+impl Decode for DispatchErrorOpaque {
+    fn decode<I: parity_scale_codec::Input>(_input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        Ok(Self)
+    }
+}
+
+#[derive(Decode)]
+struct ExecReturnValue {
+    #[allow(dead_code)]
+    flags: u32,
+    data: Vec<u8>,
+}
+
+/// `pallet_contracts::storage::meter::Weight { ref_time, proof_size }`
+/// as a dynamic value.
+fn gas_limit_value() -> Value {
+    Value::named_composite([
+        ("ref_time".to_string(), Value::u128(GAS_LIMIT_REF_TIME as u128)),
+        ("proof_size".to_string(), Value::u128(GAS_LIMIT_PROOF_SIZE as u128)),
+    ])
+}
+
+/// `None::<Compact<Balance>>` as a dynamic value, for `storage_deposit_limit`.
+fn none_value() -> Value {
+    Value::unnamed_variant("None", Vec::<Value>::new())
+}
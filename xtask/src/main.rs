@@ -0,0 +1,182 @@
+//! Repo maintenance tasks that don't belong in any one exercise's own
+//! `solution` crate, run as `cargo xtask <command>` via the alias in
+//! `.cargo/config.toml` - the usual way a Cargo workspace gets its own
+//! "run this repo-wide thing" commands without reaching for a shell
+//! script that only some contributors remember exists.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(about = "Repo maintenance tasks for the Polkadot Nairobi Dev Study Group repo")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scaffold a new numbered task folder in the standard layout.
+    NewTask {
+        /// Short, kebab-case name for the task, e.g. "zk-snark-verifier".
+        name: String,
+        #[arg(long, value_enum, default_value_t = Kind::Lib)]
+        kind: Kind,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    /// A plain off-chain simulation crate: `solution/src/lib.rs`.
+    Lib,
+    /// An ink! smart contract crate: `solution/lib.rs`.
+    Ink,
+    /// A FRAME pallet crate, split the way Task 5 is: `lib.rs`/`mock.rs`/`tests.rs`.
+    Pallet,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::NewTask { name, kind } => new_task(&workspace_root()?, &name, kind),
+    }
+}
+
+/// The repo root, found relative to this crate's own manifest rather than
+/// the caller's current directory, so `cargo xtask` works the same
+/// whether it's run from the repo root or from inside a task folder.
+fn workspace_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .context("xtask's own crate has no parent directory")
+}
+
+/// The next free task number: one past the highest `<N>-...` folder
+/// already at the repo root, so new tasks never need to be renumbered as
+/// the backlog grows around them.
+fn next_task_number(root: &Path) -> Result<u32> {
+    let mut highest = 0;
+    for entry in fs::read_dir(root).with_context(|| format!("failed to read {}", root.display()))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else { continue };
+        let Some(number) = file_name.split('-').next().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        highest = highest.max(number);
+    }
+    Ok(highest + 1)
+}
+
+fn title_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn new_task(root: &Path, name: &str, kind: Kind) -> Result<()> {
+    let number = next_task_number(root)?;
+    let task_dir = root.join(format!("{number}-{name}"));
+    if task_dir.exists() {
+        bail!("{} already exists", task_dir.display());
+    }
+    let solution_dir = task_dir.join("solution");
+    fs::create_dir_all(&solution_dir)?;
+
+    fs::write(task_dir.join("README.md"), readme_stub(number, name))?;
+
+    match kind {
+        Kind::Lib => {
+            let crate_name = name.to_string();
+            fs::write(solution_dir.join("Cargo.toml"), lib_cargo_toml(&crate_name))?;
+            fs::create_dir_all(solution_dir.join("src"))?;
+            fs::write(solution_dir.join("src/lib.rs"), lib_rs_stub(&title_case(name)))?;
+        }
+        Kind::Ink => {
+            let crate_name = name.to_string();
+            fs::write(solution_dir.join("Cargo.toml"), ink_cargo_toml(&crate_name))?;
+            fs::write(solution_dir.join("lib.rs"), ink_lib_rs_stub(name, &title_case(name)))?;
+        }
+        Kind::Pallet => {
+            let crate_name = format!("pallet-{name}");
+            fs::write(solution_dir.join("Cargo.toml"), pallet_cargo_toml(&crate_name))?;
+            fs::create_dir_all(solution_dir.join("src"))?;
+            fs::write(solution_dir.join("src/lib.rs"), pallet_lib_rs_stub(&title_case(name)))?;
+            fs::write(solution_dir.join("src/mock.rs"), pallet_mock_rs_stub(&crate_name.replace('-', "_")))?;
+            fs::write(solution_dir.join("src/tests.rs"), pallet_tests_rs_stub())?;
+        }
+    }
+
+    println!("scaffolded {}", task_dir.display());
+    Ok(())
+}
+
+fn readme_stub(number: u32, name: &str) -> String {
+    format!(
+        "# Task {number}: {title}\n\nTODO: one or two sentences on what this task asks for and which earlier\ntask (if any) it builds on.\n\n## 1. TODO\n\nTODO: describe the approach.\n\n## 2. Scope cuts\n\nTODO: what this solution deliberately doesn't handle, and why.\n",
+        title = title_case(name),
+    )
+}
+
+fn lib_cargo_toml(crate_name: &str) -> String {
+    format!(
+        "[package]\nname = \"{crate_name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\nauthors = [\"Polkadot Nairobi Dev Study Group\"]\n\n[dependencies]\n",
+    )
+}
+
+fn lib_rs_stub(title: &str) -> String {
+    format!(
+        "//! Solution for Task TODO: {title}.\n\n#[cfg(test)]\nmod tests {{\n    #[test]\n    fn todo() {{\n        todo!(\"replace with a real test once the solution is written\");\n    }}\n}}\n",
+    )
+}
+
+fn ink_cargo_toml(crate_name: &str) -> String {
+    format!(
+        "[package]\nname = \"{crate_name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\nauthors = [\"Polkadot Nairobi Dev Study Group\"]\n\n[dependencies]\nink = {{ version = \"5\", default-features = false }}\nscale = {{ package = \"parity-scale-codec\", version = \"3\", default-features = false, features = [\"derive\"] }}\nscale-info = {{ version = \"2.6\", default-features = false, features = [\"derive\"], optional = true }}\n\n[lib]\npath = \"lib.rs\"\n\n[features]\ndefault = [\"std\"]\nstd = [\"ink/std\", \"scale/std\", \"scale-info/std\", \"scale-info\"]\nink-as-dependency = []\n",
+    )
+}
+
+fn ink_lib_rs_stub(module_name: &str, title: &str) -> String {
+    let module_ident = module_name.replace('-', "_");
+    format!(
+        "#![cfg_attr(not(feature = \"std\"), no_std, no_main)]\n\n/// Solution for Task TODO: {title}.\n#[ink::contract]\npub mod {module_ident} {{\n    #[ink(storage)]\n    pub struct {struct_name} {{}}\n\n    impl {struct_name} {{\n        #[ink(constructor)]\n        pub fn new() -> Self {{\n            Self {{}}\n        }}\n    }}\n\n    #[cfg(test)]\n    mod tests {{\n        use super::*;\n\n        #[ink::test]\n        fn todo() {{\n            todo!(\"replace with a real test once the solution is written\");\n        }}\n    }}\n}}\n",
+        struct_name = title.replace(' ', ""),
+    )
+}
+
+fn pallet_cargo_toml(crate_name: &str) -> String {
+    format!(
+        "[package]\nname = \"{crate_name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\nauthors = [\"Polkadot Nairobi Dev Study Group\"]\n\n[dependencies]\ncodec = {{ package = \"parity-scale-codec\", version = \"3\", default-features = false, features = [\"derive\"] }}\nscale-info = {{ version = \"2.6\", default-features = false, features = [\"derive\"] }}\nframe-support = {{ version = \"47\", default-features = false }}\nframe-system = {{ version = \"47\", default-features = false }}\nsp-runtime = {{ version = \"47\", default-features = false }}\n\n[dev-dependencies]\nsp-io = {{ version = \"46\", default-features = false }}\nsp-core = {{ version = \"41\", default-features = false }}\n\n[features]\ndefault = [\"std\"]\nstd = [\n    \"codec/std\",\n    \"scale-info/std\",\n    \"frame-support/std\",\n    \"frame-system/std\",\n    \"sp-runtime/std\",\n]\n",
+    )
+}
+
+fn pallet_lib_rs_stub(title: &str) -> String {
+    format!(
+        "//! Solution for Task TODO: {title}.\n#![cfg_attr(not(feature = \"std\"), no_std)]\n\npub use pallet::*;\n\n#[cfg(test)]\nmod mock;\n#[cfg(test)]\nmod tests;\n\n#[frame_support::pallet]\npub mod pallet {{\n    use frame_support::pallet_prelude::*;\n    use frame_system::pallet_prelude::*;\n\n    #[pallet::config]\n    pub trait Config: frame_system::Config {{}}\n\n    #[pallet::pallet]\n    pub struct Pallet<T>(_);\n\n    #[pallet::error]\n    pub enum Error<T> {{}}\n\n    #[pallet::call]\n    impl<T: Config> Pallet<T> {{}}\n}}\n",
+    )
+}
+
+fn pallet_mock_rs_stub(crate_ident: &str) -> String {
+    format!(
+        "use frame_support::{{construct_runtime, derive_impl, sp_runtime::BuildStorage}};\n\nconstruct_runtime!(\n    pub enum Runtime {{\n        System: frame_system,\n        Todo: {crate_ident},\n    }}\n);\n\n#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]\nimpl frame_system::Config for Runtime {{\n    type Block = frame_system::mocking::MockBlock<Runtime>;\n}}\n\nimpl crate::Config for Runtime {{}}\n\npub fn new_test_ext() -> sp_io::TestExternalities {{\n    let storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();\n    storage.into()\n}}\n",
+    )
+}
+
+fn pallet_tests_rs_stub() -> String {
+    "use crate::mock::*;\n\n#[test]\nfn todo() {\n    new_test_ext().execute_with(|| {\n        todo!(\"replace with a real test once the solution is written\");\n    });\n}\n"
+        .to_string()
+}